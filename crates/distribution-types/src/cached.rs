@@ -4,6 +4,7 @@ use anyhow::Result;
 
 use distribution_filename::WheelFilename;
 use pep508_rs::VerbatimUrl;
+use pypi_types::Hashes;
 use uv_normalize::PackageName;
 
 use crate::direct_url::{DirectUrl, LocalFileUrl};
@@ -25,6 +26,8 @@ pub enum CachedDist {
 pub struct CachedRegistryDist {
     pub filename: WheelFilename,
     pub path: PathBuf,
+    /// The hashes of the archive from which the wheel was unzipped, if known.
+    pub hashes: Hashes,
 }
 
 #[derive(Debug, Clone)]
@@ -39,9 +42,11 @@ impl CachedDist {
     /// Initialize a [`CachedDist`] from a [`Dist`].
     pub fn from_remote(remote: Dist, filename: WheelFilename, path: PathBuf) -> Self {
         match remote {
-            Dist::Built(BuiltDist::Registry(_dist)) => {
-                Self::Registry(CachedRegistryDist { filename, path })
-            }
+            Dist::Built(BuiltDist::Registry(dist)) => Self::Registry(CachedRegistryDist {
+                filename,
+                path,
+                hashes: dist.file.hashes,
+            }),
             Dist::Built(BuiltDist::DirectUrl(dist)) => Self::Url(CachedDirectUrlDist {
                 filename,
                 url: dist.url,
@@ -54,9 +59,11 @@ impl CachedDist {
                 path,
                 editable: false,
             }),
-            Dist::Source(SourceDist::Registry(_dist)) => {
-                Self::Registry(CachedRegistryDist { filename, path })
-            }
+            Dist::Source(SourceDist::Registry(_dist)) => Self::Registry(CachedRegistryDist {
+                filename,
+                path,
+                hashes: Hashes::default(),
+            }),
             Dist::Source(SourceDist::DirectUrl(dist)) => Self::Url(CachedDirectUrlDist {
                 filename,
                 url: dist.url,
@@ -15,7 +15,7 @@ pub enum DirectUrl {
     Archive(DirectArchiveUrl),
 }
 
-/// A local path url
+/// A local path url, pointing to a directory
 ///
 /// Examples:
 /// * `file:///home/ferris/my_project`
@@ -105,6 +105,11 @@ impl TryFrom<&Url> for DirectUrl {
                 ))),
             }
         } else if url.scheme().eq_ignore_ascii_case("file") {
+            // Per PEP 610, local archives (wheels and source distributions) are recorded with
+            // `archive_info`, while local directories are recorded with `dir_info`.
+            if url.to_file_path().is_ok_and(|path| path.is_file()) {
+                return Ok(Self::Archive(DirectArchiveUrl::from(url)));
+            }
             Ok(Self::LocalFile(LocalFileUrl {
                 url: url.clone(),
                 editable: false,
@@ -52,7 +52,16 @@ impl File {
                 .map_err(|err| FileConversionError::RequiresPython(err.line().clone(), err))?,
             size: file.size,
             upload_time_utc_ms: file.upload_time.map(|dt| dt.timestamp_millis()),
-            url: if file.url.contains("://") {
+            url: if base.scheme() == "file" || file.url.starts_with("file://") {
+                // Files from a local (`file://`) index are read directly from disk.
+                let url = base
+                    .join(&file.url)
+                    .map_err(|err| FileConversionError::Url(file.url.clone(), err))?;
+                match url.to_file_path() {
+                    Ok(path) => FileLocation::Path(path),
+                    Err(()) => FileLocation::AbsoluteUrl(url.to_string()),
+                }
+            } else if file.url.contains("://") {
                 let url = safe_copy_url_auth_to_str(base, &file.url)
                     .map_err(|err| FileConversionError::Url(file.url.clone(), err))?
                     .map(|url| url.to_string())
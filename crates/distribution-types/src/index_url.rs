@@ -230,6 +230,11 @@ impl<'a> IndexLocations {
         self.index().into_iter().chain(self.extra_index())
     }
 
+    /// Return `true` if index lookups are disabled (i.e., `--no-index` is set).
+    pub fn no_index(&self) -> bool {
+        self.no_index
+    }
+
     /// Return an iterator over the [`FlatIndexLocation`] entries.
     pub fn flat_index(&'a self) -> impl Iterator<Item = &'a FlatIndexLocation> + 'a {
         self.flat_index.iter()
@@ -19,6 +19,12 @@ pub enum InstalledDist {
     Registry(InstalledRegistryDist),
     /// The distribution was derived from an arbitrary URL.
     Url(InstalledDirectUrlDist),
+    /// The distribution was installed in the legacy `.egg-info` format (e.g., by `distutils` or
+    /// `setup.py install`).
+    EggInfo(InstalledEggInfo),
+    /// The distribution was installed as a legacy editable (e.g., by `setup.py develop`), and is
+    /// represented by an `.egg-link` file pointing to the project.
+    LegacyEditable(InstalledLegacyEditable),
 }
 
 #[derive(Debug, Clone)]
@@ -37,8 +43,29 @@ pub struct InstalledDirectUrlDist {
     pub path: PathBuf,
 }
 
+#[derive(Debug, Clone)]
+pub struct InstalledEggInfo {
+    pub name: PackageName,
+    pub version: Version,
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Clone)]
+pub struct InstalledLegacyEditable {
+    pub name: PackageName,
+    pub version: Version,
+    /// The path to the `.egg-link` file.
+    pub egg_link: PathBuf,
+    /// The path to the `.egg-info` directory within the project.
+    pub egg_info: PathBuf,
+    /// The URL of the project directory.
+    pub target_url: Url,
+}
+
 impl InstalledDist {
-    /// Try to parse a distribution from a `.dist-info` directory name (like `django-5.0a1.dist-info`).
+    /// Try to parse a distribution from a `.dist-info` directory name (like `django-5.0a1.dist-info`),
+    /// from a legacy `.egg-info` directory or file name (like `django-5.0a1-py3.12.egg-info`), or
+    /// from a legacy `.egg-link` file (like `django.egg-link`).
     ///
     /// See: <https://packaging.python.org/en/latest/specifications/recording-installed-packages/#recording-installed-packages>
     pub fn try_from_path(path: &Path) -> Result<Option<Self>> {
@@ -81,6 +108,85 @@ impl InstalledDist {
                 })))
             };
         }
+
+        if path.extension().is_some_and(|ext| ext == "egg-info") {
+            let Some(file_stem) = path.file_stem() else {
+                return Ok(None);
+            };
+            let Some(file_stem) = file_stem.to_str() else {
+                return Ok(None);
+            };
+
+            // The file stem is `{name}-{version}`, optionally followed by `-py{X.Y}`. The version
+            // may be omitted (e.g., for `setup.py develop`), in which case, we read it from the
+            // metadata.
+            let mut parts = file_stem.split('-');
+            let Some(name) = parts.next() else {
+                return Ok(None);
+            };
+            let name = PackageName::from_str(name)?;
+            let version = if let Some(version) = parts.next() {
+                Version::from_str(version).map_err(|err| anyhow!(err))?
+            } else {
+                let metadata = Self::read_pkg_info(path)?;
+                metadata.version
+            };
+
+            return Ok(Some(Self::EggInfo(InstalledEggInfo {
+                name,
+                version,
+                path: path.to_path_buf(),
+            })));
+        }
+
+        if path.extension().is_some_and(|ext| ext == "egg-link") {
+            let Some(file_stem) = path.file_stem() else {
+                return Ok(None);
+            };
+            let Some(file_stem) = file_stem.to_str() else {
+                return Ok(None);
+            };
+
+            // The first line of the `.egg-link` file is the path to the project (or its `src`
+            // directory), which contains the `.egg-info` directory.
+            let contents = fs::read_to_string(path)?;
+            let Some(target) = contents
+                .lines()
+                .map(str::trim)
+                .find(|line| !line.is_empty())
+            else {
+                warn!("Empty .egg-link file: {}", path.normalized_display());
+                return Ok(None);
+            };
+            let target = path
+                .parent()
+                .map_or_else(|| PathBuf::from(target), |parent| parent.join(target));
+
+            let egg_info = target.join(format!("{file_stem}.egg-info"));
+            if !egg_info.is_dir() {
+                warn!(
+                    "Missing .egg-info directory for legacy editable: {}",
+                    egg_info.normalized_display()
+                );
+                return Ok(None);
+            }
+
+            let Ok(target_url) = Url::from_file_path(&target) else {
+                warn!("Invalid .egg-link target: {}", target.normalized_display());
+                return Ok(None);
+            };
+
+            let name = PackageName::from_str(file_stem)?;
+            let version = Self::read_pkg_info(&egg_info)?.version;
+            return Ok(Some(Self::LegacyEditable(InstalledLegacyEditable {
+                name,
+                version,
+                egg_link: path.to_path_buf(),
+                egg_info,
+                target_url,
+            })));
+        }
+
         Ok(None)
     }
 
@@ -89,6 +195,8 @@ impl InstalledDist {
         match self {
             Self::Registry(dist) => &dist.path,
             Self::Url(dist) => &dist.path,
+            Self::EggInfo(dist) => &dist.path,
+            Self::LegacyEditable(dist) => &dist.egg_link,
         }
     }
 
@@ -97,6 +205,8 @@ impl InstalledDist {
         match self {
             Self::Registry(dist) => &dist.version,
             Self::Url(dist) => &dist.version,
+            Self::EggInfo(dist) => &dist.version,
+            Self::LegacyEditable(dist) => &dist.version,
         }
     }
 
@@ -110,8 +220,14 @@ impl InstalledDist {
         Ok(Some(direct_url))
     }
 
-    /// Read the `METADATA` file from a `.dist-info` directory.
+    /// Read the `METADATA` file from a `.dist-info` directory, or the `PKG-INFO` file from an
+    /// `.egg-info` directory (or the `.egg-info` file itself).
     pub fn metadata(&self) -> Result<pypi_types::Metadata21> {
+        match self {
+            Self::EggInfo(dist) => return Self::read_pkg_info(&dist.path),
+            Self::LegacyEditable(dist) => return Self::read_pkg_info(&dist.egg_info),
+            Self::Registry(_) | Self::Url(_) => {}
+        }
         let path = self.path().join("METADATA");
         let contents = fs::read(&path)?;
         pypi_types::Metadata21::parse(&contents).with_context(|| {
@@ -122,8 +238,27 @@ impl InstalledDist {
         })
     }
 
+    /// Read the `PKG-INFO` file from an `.egg-info` directory, or the `.egg-info` file itself.
+    fn read_pkg_info(path: &Path) -> Result<pypi_types::Metadata21> {
+        let path = if path.is_dir() {
+            path.join("PKG-INFO")
+        } else {
+            path.to_path_buf()
+        };
+        let contents = fs::read(&path)?;
+        pypi_types::Metadata21::parse(&contents).with_context(|| {
+            format!(
+                "Failed to parse PKG-INFO file at: {}",
+                path.normalized_display()
+            )
+        })
+    }
+
     /// Return the `INSTALLER` of the distribution.
     pub fn installer(&self) -> Result<Option<String>> {
+        if matches!(self, Self::EggInfo(_) | Self::LegacyEditable(_)) {
+            return Ok(None);
+        }
         let path = self.path().join("INSTALLER");
         match fs::read_to_string(path) {
             Ok(installer) => Ok(Some(installer)),
@@ -135,16 +270,18 @@ impl InstalledDist {
     /// Return true if the distribution is editable.
     pub fn is_editable(&self) -> bool {
         match self {
-            Self::Registry(_) => false,
+            Self::Registry(_) | Self::EggInfo(_) => false,
             Self::Url(dist) => dist.editable,
+            Self::LegacyEditable(_) => true,
         }
     }
 
     /// Return the [`Url`] of the distribution, if it is editable.
     pub fn as_editable(&self) -> Option<&Url> {
         match self {
-            Self::Registry(_) => None,
+            Self::Registry(_) | Self::EggInfo(_) => None,
             Self::Url(dist) => dist.editable.then_some(&dist.url),
+            Self::LegacyEditable(dist) => Some(&dist.target_url),
         }
     }
 }
@@ -161,11 +298,25 @@ impl Name for InstalledDirectUrlDist {
     }
 }
 
+impl Name for InstalledEggInfo {
+    fn name(&self) -> &PackageName {
+        &self.name
+    }
+}
+
+impl Name for InstalledLegacyEditable {
+    fn name(&self) -> &PackageName {
+        &self.name
+    }
+}
+
 impl Name for InstalledDist {
     fn name(&self) -> &PackageName {
         match self {
             Self::Registry(dist) => dist.name(),
             Self::Url(dist) => dist.name(),
+            Self::EggInfo(dist) => dist.name(),
+            Self::LegacyEditable(dist) => dist.name(),
         }
     }
 }
@@ -182,11 +333,25 @@ impl InstalledMetadata for InstalledDirectUrlDist {
     }
 }
 
+impl InstalledMetadata for InstalledEggInfo {
+    fn installed_version(&self) -> InstalledVersion {
+        InstalledVersion::Version(&self.version)
+    }
+}
+
+impl InstalledMetadata for InstalledLegacyEditable {
+    fn installed_version(&self) -> InstalledVersion {
+        InstalledVersion::Url(&self.target_url, &self.version)
+    }
+}
+
 impl InstalledMetadata for InstalledDist {
     fn installed_version(&self) -> InstalledVersion {
         match self {
             Self::Registry(dist) => dist.installed_version(),
             Self::Url(dist) => dist.installed_version(),
+            Self::EggInfo(dist) => dist.installed_version(),
+            Self::LegacyEditable(dist) => dist.installed_version(),
         }
     }
 }
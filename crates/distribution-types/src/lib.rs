@@ -31,6 +31,9 @@
 //! * [`InstalledRegistryDist`]
 //! * [`InstalledDirectUrlDist`]
 //!
+//! Distributions installed in the legacy `.egg-info` format are tracked as [`InstalledEggInfo`],
+//! and legacy editable installs (`.egg-link` files) as [`InstalledLegacyEditable`].
+//!
 //! Since we read this information from [`direct_url.json`](https://packaging.python.org/en/latest/specifications/direct-url-data-structure/), it doesn't match the information [`Dist`] exactly.
 use std::borrow::Cow;
 use std::path::{Path, PathBuf};
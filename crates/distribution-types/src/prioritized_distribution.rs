@@ -13,6 +13,8 @@ pub struct PrioritizedDist(Box<PrioritizedDistInner>);
 struct PrioritizedDistInner {
     /// An arbitrary source distribution for the package version.
     source: Option<DistMetadata>,
+    /// An arbitrary, incompatible source distribution for the package version.
+    incompatible_source: Option<(DistMetadata, IncompatibleSource)>,
     /// The highest-priority, installable wheel for the package version.
     compatible_wheel: Option<(DistMetadata, TagPriority)>,
     /// The most-relevant, incompatible wheel for the package version.
@@ -44,6 +46,12 @@ pub enum WheelCompatibility {
     Compatible(TagPriority),
 }
 
+#[derive(Debug, PartialEq, Eq)]
+pub enum SourceDistCompatibility {
+    Incompatible(IncompatibleSource),
+    Compatible,
+}
+
 #[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
 pub enum IncompatibleWheel {
     Tag(IncompatibleTag),
@@ -51,6 +59,19 @@ pub enum IncompatibleWheel {
     NoBinary,
 }
 
+#[derive(Debug, PartialEq, Eq, Ord, PartialOrd, Clone)]
+pub enum IncompatibleSource {
+    NoBuild,
+}
+
+/// The reason that no distribution for a package version can be used.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum IncompatibleDist {
+    Wheel(IncompatibleWheel),
+    Source(IncompatibleSource),
+    Unavailable,
+}
+
 /// A [`Dist`] and metadata about it required for downstream filtering.
 #[derive(Debug, Clone)]
 pub struct DistMetadata {
@@ -74,6 +95,7 @@ impl PrioritizedDist {
         match compatibility {
             WheelCompatibility::Compatible(priority) => Self(Box::new(PrioritizedDistInner {
                 source: None,
+                incompatible_source: None,
                 compatible_wheel: Some((
                     DistMetadata {
                         dist,
@@ -89,6 +111,7 @@ impl PrioritizedDist {
             WheelCompatibility::Incompatible(incompatibility) => {
                 Self(Box::new(PrioritizedDistInner {
                     source: None,
+                    incompatible_source: None,
                     compatible_wheel: None,
                     incompatible_wheel: Some((
                         DistMetadata {
@@ -111,18 +134,11 @@ impl PrioritizedDist {
         requires_python: Option<VersionSpecifiers>,
         yanked: Yanked,
         hash: Option<Hashes>,
+        compatibility: SourceDistCompatibility,
     ) -> Self {
-        Self(Box::new(PrioritizedDistInner {
-            source: Some(DistMetadata {
-                dist,
-                requires_python,
-                yanked,
-            }),
-            compatible_wheel: None,
-            incompatible_wheel: None,
-            hashes: hash.map(|hash| vec![hash]).unwrap_or_default(),
-            exclude_newer: false,
-        }))
+        let mut prioritized = Self::default();
+        prioritized.insert_source(dist, requires_python, yanked, hash, compatibility);
+        prioritized
     }
 
     /// Insert the given built distribution into the [`PrioritizedDist`].
@@ -197,13 +213,30 @@ impl PrioritizedDist {
         requires_python: Option<VersionSpecifiers>,
         yanked: Yanked,
         hash: Option<Hashes>,
+        compatibility: SourceDistCompatibility,
     ) {
-        if self.0.source.is_none() {
-            self.0.source = Some(DistMetadata {
-                dist,
-                requires_python,
-                yanked,
-            });
+        match compatibility {
+            SourceDistCompatibility::Compatible => {
+                if self.0.source.is_none() {
+                    self.0.source = Some(DistMetadata {
+                        dist,
+                        requires_python,
+                        yanked,
+                    });
+                }
+            }
+            SourceDistCompatibility::Incompatible(incompatibility) => {
+                if self.0.incompatible_source.is_none() {
+                    self.0.incompatible_source = Some((
+                        DistMetadata {
+                            dist,
+                            requires_python,
+                            yanked,
+                        },
+                        incompatibility,
+                    ));
+                }
+            }
         }
 
         if let Some(hash) = hash {
@@ -250,6 +283,25 @@ impl PrioritizedDist {
         self.0.incompatible_wheel.as_ref()
     }
 
+    /// Return the incompatible source distribution, if any.
+    pub fn incompatible_source(&self) -> Option<&(DistMetadata, IncompatibleSource)> {
+        self.0.incompatible_source.as_ref()
+    }
+
+    /// Return the reason that no distribution for the package version can be used.
+    ///
+    /// An incompatible source distribution takes precedence over an incompatible wheel, since
+    /// the source distribution would otherwise be used in place of the wheel.
+    pub fn incompatibility(&self) -> IncompatibleDist {
+        if let Some((_, incompatibility)) = &self.0.incompatible_source {
+            IncompatibleDist::Source(incompatibility.clone())
+        } else if let Some((_, incompatibility)) = &self.0.incompatible_wheel {
+            IncompatibleDist::Wheel(incompatibility.clone())
+        } else {
+            IncompatibleDist::Unavailable
+        }
+    }
+
     /// Set the `exclude_newer` flag
     pub fn set_exclude_newer(&mut self) {
         self.0.exclude_newer = true;
@@ -269,6 +321,7 @@ impl PrioritizedDist {
     /// source distributions or wheels.
     pub fn is_empty(&self) -> bool {
         self.0.source.is_none()
+            && self.0.incompatible_source.is_none()
             && self.0.compatible_wheel.is_none()
             && self.0.incompatible_wheel.is_none()
     }
@@ -11,9 +11,9 @@ use fs_err::File;
 use tracing::info;
 use uv_fs::Normalized;
 
-use uv_interpreter::Interpreter;
+use uv_interpreter::{Interpreter, PyVenvConfiguration};
 
-use crate::{Error, Prompt};
+use crate::{Error, InterpreterLink, OnExisting, Prompt};
 
 /// The bash activate scripts with the venv dependent paths patches out
 const ACTIVATE_TEMPLATES: &[(&str, &str)] = &[
@@ -66,6 +66,9 @@ pub fn create_bare_venv(
     location: &Utf8Path,
     interpreter: &Interpreter,
     prompt: Prompt,
+    system_site_packages: bool,
+    on_existing: OnExisting,
+    interpreter_link: InterpreterLink,
     extra_cfg: Vec<(String, String)>,
 ) -> Result<VenvPaths, Error> {
     // We have to canonicalize the interpreter path, otherwise the home is set to the venv dir instead of the real root.
@@ -74,6 +77,10 @@ pub fn create_bare_venv(
         .try_into()
         .map_err(|err: FromPathBufError| err.into_io_error())?;
 
+    if cfg!(windows) && interpreter_link == InterpreterLink::Symlink {
+        return Err(Error::SymlinksUnsupported);
+    }
+
     // Validate the existing location.
     match location.metadata() {
         Ok(metadata) => {
@@ -83,16 +90,26 @@ pub fn create_bare_venv(
                     format!("File exists at `{location}`"),
                 )));
             } else if metadata.is_dir() {
-                if location.join("pyvenv.cfg").is_file() {
-                    info!("Removing existing directory");
-                    fs::remove_dir_all(location)?;
-                    fs::create_dir_all(location)?;
+                if on_existing == OnExisting::Upgrade {
+                    validate_upgrade(location, interpreter)?;
+                    info!("Upgrading existing virtualenv");
                 } else if location
                     .read_dir()
                     .is_ok_and(|mut dir| dir.next().is_none())
                 {
                     info!("Ignoring empty directory");
+                } else if on_existing == OnExisting::Allow {
+                    info!("Creating virtualenv in existing directory");
+                } else if location.join("pyvenv.cfg").is_file() {
+                    if on_existing == OnExisting::Clear {
+                        info!("Removing existing virtualenv");
+                        fs::remove_dir_all(location)?;
+                        fs::create_dir_all(location)?;
+                    } else {
+                        return Err(Error::ExistingVenv(location.to_string()));
+                    }
                 } else {
+                    // Never remove a directory that isn't a virtualenv, even with `--clear`.
                     return Err(Error::IO(io::Error::new(
                         io::ErrorKind::AlreadyExists,
                         format!("The directory `{location}` exists, but it's not a virtualenv"),
@@ -101,6 +118,9 @@ pub fn create_bare_venv(
             }
         }
         Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            if on_existing == OnExisting::Upgrade {
+                return Err(Error::MissingVenv(location.to_string()));
+            }
             fs::create_dir_all(location)?;
         }
         Err(err) => return Err(Error::IO(err)),
@@ -127,30 +147,52 @@ pub fn create_bare_venv(
     // Add the CACHEDIR.TAG.
     cachedir::ensure_tag(&location)?;
 
-    // Create a `.gitignore` file to ignore all files in the venv.
-    fs::write(location.join(".gitignore"), "*")?;
+    // Create a `.gitignore` file to ignore all files in the venv, unless the directory already
+    // contains one.
+    let gitignore = location.join(".gitignore");
+    if !gitignore.exists() {
+        fs::write(gitignore, "*")?;
+    }
 
     // Different names for the python interpreter
-    fs::create_dir(&bin_dir)?;
+    fs::create_dir_all(&bin_dir)?;
     let venv_python = bin_dir.join(format!("python{EXE_SUFFIX}"));
     // No symlinking on Windows, at least not on a regular non-dev non-admin Windows install.
     #[cfg(unix)]
     {
         use fs_err::os::unix::fs::symlink;
 
-        symlink(&base_python, &venv_python)?;
-        symlink(
-            "python",
+        let aliases = [
             bin_dir.join(format!("python{}", interpreter.python_major())),
-        )?;
-        symlink(
-            "python",
             bin_dir.join(format!(
                 "python{}.{}",
                 interpreter.python_major(),
                 interpreter.python_minor(),
             )),
-        )?;
+        ];
+
+        // Remove any interpreter links left behind by an existing virtualenv (copying onto a
+        // symlink would overwrite the base interpreter).
+        for link in std::iter::once(&venv_python).chain(&aliases) {
+            if link.symlink_metadata().is_ok() {
+                fs::remove_file(link)?;
+            }
+        }
+
+        match interpreter_link {
+            InterpreterLink::Symlink => {
+                symlink(&base_python, &venv_python)?;
+                for alias in aliases {
+                    symlink("python", alias)?;
+                }
+            }
+            InterpreterLink::Copy => {
+                fs::copy(&base_python, &venv_python)?;
+                for alias in aliases {
+                    fs::copy(&venv_python, alias)?;
+                }
+            }
+        }
     }
     #[cfg(windows)]
     {
@@ -250,7 +292,11 @@ pub fn create_bare_venv(
         ),
         (
             "include-system-site-packages".to_string(),
-            "false".to_string(),
+            if system_site_packages {
+                "true".to_string()
+            } else {
+                "false".to_string()
+            },
         ),
         (
             "base-prefix".to_string(),
@@ -295,6 +341,12 @@ pub fn create_bare_venv(
     fs::write(site_packages.join("_virtualenv.py"), VIRTUALENV_PATCH)?;
     fs::write(site_packages.join("_virtualenv.pth"), "import _virtualenv")?;
 
+    // Point any scripts that referenced the previous interpreter at the upgraded one.
+    #[cfg(unix)]
+    if on_existing == OnExisting::Upgrade {
+        fix_shebangs(&bin_dir, &venv_python)?;
+    }
+
     Ok(VenvPaths {
         root: location.to_path_buf(),
         interpreter: venv_python,
@@ -302,3 +354,62 @@ pub fn create_bare_venv(
         site_packages,
     })
 }
+
+/// Validate that the virtualenv at the given location can be upgraded in place to the given
+/// interpreter, i.e., that it only differs by patch version.
+fn validate_upgrade(location: &Utf8Path, interpreter: &Interpreter) -> Result<(), Error> {
+    let pyvenv_cfg = location.join("pyvenv.cfg");
+    if !pyvenv_cfg.is_file() {
+        return Err(Error::MissingVenv(location.to_string()));
+    }
+    let cfg = PyVenvConfiguration::parse(&pyvenv_cfg).map_err(uv_interpreter::Error::from)?;
+
+    let requested = &interpreter.markers().python_full_version.string;
+    if let Some(existing) = cfg.version_info() {
+        let minor = |version: &str| version.split('.').take(2).collect::<Vec<_>>();
+        if minor(existing) != minor(requested) {
+            return Err(Error::IncompatibleUpgrade {
+                path: location.to_string(),
+                existing: existing.to_string(),
+                requested: requested.to_string(),
+            });
+        }
+    }
+
+    Ok(())
+}
+
+/// Rewrite the shebangs of any scripts in the `bin` directory that point to a Python interpreter
+/// outside the virtualenv, such that they use the virtualenv's interpreter instead.
+#[cfg(unix)]
+fn fix_shebangs(bin_dir: &Utf8Path, venv_python: &Utf8Path) -> Result<(), Error> {
+    for entry in fs::read_dir(bin_dir)? {
+        let path = entry?.path();
+        if !path.symlink_metadata()?.is_file() {
+            continue;
+        }
+        let contents = fs::read(&path)?;
+        let Some(rest) = contents.strip_prefix(b"#!") else {
+            continue;
+        };
+        let end = rest
+            .iter()
+            .position(|&byte| byte == b'\n')
+            .unwrap_or(rest.len());
+        let Ok(shebang) = std::str::from_utf8(&rest[..end]) else {
+            continue;
+        };
+        let shebang = shebang.trim();
+        let is_python = Utf8Path::new(shebang)
+            .file_name()
+            .is_some_and(|name| name.starts_with("python"));
+        if !is_python || shebang == venv_python.as_str() {
+            continue;
+        }
+        info!("Updating shebang of {}", path.normalized_display());
+        let mut updated = format!("#!{venv_python}").into_bytes();
+        updated.extend_from_slice(&rest[end..]);
+        fs::write(&path, updated)?;
+    }
+    Ok(())
+}
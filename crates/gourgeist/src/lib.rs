@@ -21,6 +21,53 @@ pub enum Error {
     Platform(#[from] PlatformError),
     #[error("Reserved key used for pyvenv.cfg: {0}")]
     ReservedConfigKey(String),
+    #[error("A virtualenv already exists at `{0}` (use `--clear` to replace it, or `--allow-existing` to update it in place)")]
+    ExistingVenv(String),
+    #[error("Symlinking the Python interpreter is not supported on Windows")]
+    SymlinksUnsupported,
+    #[error("No virtualenv exists at `{0}` to upgrade")]
+    MissingVenv(String),
+    #[error("The virtualenv at `{path}` uses Python {existing}, which can't be upgraded in place to Python {requested} (only patch upgrades are supported)")]
+    IncompatibleUpgrade {
+        path: String,
+        existing: String,
+        requested: String,
+    },
+}
+
+/// The behavior to use when the target location already exists.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum OnExisting {
+    /// Fail if the location contains a virtualenv or a non-empty directory.
+    #[default]
+    Fail,
+    /// Remove an existing virtualenv and create a new one in its place.
+    Clear,
+    /// Create the virtualenv in the existing directory, overwriting any conflicting files.
+    Allow,
+    /// Upgrade an existing virtualenv to a new patch release of its base interpreter, preserving
+    /// the installed packages.
+    Upgrade,
+}
+
+/// How the base Python interpreter is placed into the virtualenv.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InterpreterLink {
+    /// Symlink the base interpreter into the virtualenv.
+    Symlink,
+    /// Copy the base interpreter into the virtualenv.
+    Copy,
+}
+
+impl Default for InterpreterLink {
+    /// Symlink on Unix; copy on Windows, where symlinks require elevated permissions.
+    fn default() -> Self {
+        if cfg!(windows) {
+            Self::Copy
+        } else {
+            Self::Symlink
+        }
+    }
 }
 
 /// The value to use for the shell prompt when inside a virtual environment.
@@ -51,12 +98,23 @@ pub fn create_venv(
     location: &Path,
     interpreter: Interpreter,
     prompt: Prompt,
+    system_site_packages: bool,
+    on_existing: OnExisting,
+    interpreter_link: InterpreterLink,
     extra_cfg: Vec<(String, String)>,
 ) -> Result<Virtualenv, Error> {
     let location: &Utf8Path = location
         .try_into()
         .map_err(|err: FromPathError| err.into_io_error())?;
-    let paths = create_bare_venv(location, &interpreter, prompt, extra_cfg)?;
+    let paths = create_bare_venv(
+        location,
+        &interpreter,
+        prompt,
+        system_site_packages,
+        on_existing,
+        interpreter_link,
+        extra_cfg,
+    )?;
     Ok(Virtualenv::from_interpreter(
         interpreter,
         paths.root.as_std_path(),
@@ -11,7 +11,7 @@ use tracing_subscriber::layer::SubscriberExt;
 use tracing_subscriber::util::SubscriberInitExt;
 use tracing_subscriber::{fmt, EnvFilter};
 
-use gourgeist::{create_bare_venv, Prompt};
+use gourgeist::{create_bare_venv, InterpreterLink, OnExisting, Prompt};
 use platform_host::Platform;
 use uv_cache::Cache;
 use uv_interpreter::{find_default_python, find_requested_python};
@@ -23,6 +23,18 @@ struct Cli {
     python: Option<String>,
     #[clap(long)]
     prompt: Option<String>,
+    #[clap(long)]
+    system_site_packages: bool,
+    #[clap(long, conflicts_with = "allow_existing")]
+    clear: bool,
+    #[clap(long)]
+    allow_existing: bool,
+    #[clap(long, conflicts_with_all = ["clear", "allow_existing"])]
+    upgrade: bool,
+    #[clap(long, conflicts_with = "symlinks")]
+    copies: bool,
+    #[clap(long)]
+    symlinks: bool,
 }
 
 fn run() -> Result<(), gourgeist::Error> {
@@ -45,6 +57,23 @@ fn run() -> Result<(), gourgeist::Error> {
         &location,
         &interpreter,
         Prompt::from_args(cli.prompt),
+        cli.system_site_packages,
+        if cli.clear {
+            OnExisting::Clear
+        } else if cli.allow_existing {
+            OnExisting::Allow
+        } else if cli.upgrade {
+            OnExisting::Upgrade
+        } else {
+            OnExisting::Fail
+        },
+        if cli.copies {
+            InterpreterLink::Copy
+        } else if cli.symlinks {
+            InterpreterLink::Symlink
+        } else {
+            InterpreterLink::default()
+        },
         Vec::new(),
     )?;
     Ok(())
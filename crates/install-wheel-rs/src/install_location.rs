@@ -79,6 +79,9 @@ pub struct InstallLocation<T> {
     /// absolute path
     venv_root: T,
     python_version: (u8, u8),
+    /// The interpreter to reference from generated scripts, if it doesn't live in the
+    /// environment itself (as when installing into a `--prefix`).
+    python: Option<PathBuf>,
 }
 
 impl<T: AsRef<Path>> InstallLocation<T> {
@@ -86,12 +89,25 @@ impl<T: AsRef<Path>> InstallLocation<T> {
         Self {
             venv_root: venv_base,
             python_version,
+            python: None,
+        }
+    }
+
+    /// Use the given interpreter in script shebangs and launchers, rather than the one in the
+    /// environment's `bin` directory.
+    #[must_use]
+    pub fn with_python(self, python: PathBuf) -> Self {
+        Self {
+            python: Some(python),
+            ..self
         }
     }
 
     /// Returns the location of the `python` interpreter.
     pub fn python(&self) -> PathBuf {
-        if cfg!(unix) {
+        if let Some(python) = &self.python {
+            python.clone()
+        } else if cfg!(unix) {
             // canonicalize on python would resolve the symlink
             self.venv_root.as_ref().join("bin").join("python")
         } else if cfg!(windows) {
@@ -125,6 +141,7 @@ impl InstallLocation<PathBuf> {
         Ok(InstallLocation {
             venv_root: locked_dir,
             python_version: self.python_version,
+            python: self.python.clone(),
         })
     }
 }
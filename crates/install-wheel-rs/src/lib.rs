@@ -16,11 +16,12 @@ use pep440_rs::Version;
 use platform_host::{Arch, Os};
 pub use record::RecordEntry;
 pub use script::Script;
-pub use uninstall::{uninstall_wheel, Uninstall};
+pub use uninstall::{uninstall_egg, uninstall_legacy_editable, uninstall_wheel, Uninstall};
 use uv_fs::Normalized;
 use uv_normalize::PackageName;
 pub use wheel::{
-    install_wheel, parse_key_value_file, read_record_file, relative_to, SHEBANG_PYTHON,
+    install_wheel, parse_key_value_file, read_record_file, relative_to, verify_installed_dist,
+    verify_unpacked_wheel, verify_wheel_archive, SHEBANG_PYTHON,
 };
 
 mod install_location;
@@ -81,6 +82,10 @@ pub enum Error {
     MissingDistInfo,
     #[error("Cannot uninstall package; RECORD file not found at: {}", _0.normalized_display())]
     MissingRecord(PathBuf),
+    #[error("Cannot uninstall package; it was installed by distutils, which doesn't record the installed files: {}", _0.normalized_display())]
+    MissingInstalledFiles(PathBuf),
+    #[error("Cannot uninstall package; invalid .egg-link file at: {}", _0.normalized_display())]
+    InvalidEggLink(PathBuf),
     #[error("Multiple .dist-info directories found: {0}")]
     MultipleDistInfo(String),
     #[error("Invalid wheel size")]
@@ -1,7 +1,7 @@
 //! Like `wheel.rs`, but for installing wheels that have already been unzipped, rather than
 //! reading from a zip file.
 
-use std::path::Path;
+use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
 use fs_err as fs;
@@ -18,7 +18,8 @@ use uv_normalize::PackageName;
 use crate::install_location::InstallLocation;
 use crate::script::scripts_from_ini;
 use crate::wheel::{
-    extra_dist_info, install_data, parse_metadata, parse_wheel_version, write_script_entrypoints,
+    bytecode_compile, extra_dist_info, install_data, parse_metadata, parse_wheel_version,
+    write_script_entrypoints,
 };
 use crate::{read_record_file, Error, Script};
 
@@ -29,6 +30,9 @@ use crate::{read_record_file, Error, Script};
 /// <https://packaging.python.org/en/latest/specifications/binary-distribution-format/#installing-a-wheel-distribution-1-0-py32-none-any-whl>
 ///
 /// Wheel 1.0: <https://www.python.org/dev/peps/pep-0427/>
+///
+/// If `compile` is set, the installed `.py` files are compiled to bytecode with the environment's
+/// interpreter, and the `.pyc` files are added to the `RECORD`.
 #[instrument(skip_all, fields(wheel = % wheel.as_ref().display()))]
 pub fn install_wheel(
     location: &InstallLocation<impl AsRef<Path>>,
@@ -37,6 +41,7 @@ pub fn install_wheel(
     direct_url: Option<&DirectUrl>,
     installer: Option<&str>,
     link_mode: LinkMode,
+    compile: bool,
 ) -> Result<(), Error> {
     let root = location.venv_root();
 
@@ -132,6 +137,24 @@ pub fn install_wheel(
         debug!(name, "No data");
     }
 
+    // 2.f Compile any installed .py to .pyc. (Uninstallers should be smart enough to remove .pyc
+    // even if it is not mentioned in RECORD.)
+    if compile {
+        debug!(name, "Bytecode compiling");
+        let installed_paths = record
+            .iter()
+            .map(|entry| PathBuf::from(&entry.path))
+            .collect();
+        bytecode_compile(
+            &site_packages,
+            installed_paths,
+            location.python_version(),
+            &location.python(),
+            &name,
+            &mut record,
+        )?;
+    }
+
     debug!(name, "Writing extra metadata");
     extra_dist_info(
         &site_packages,
@@ -228,6 +251,11 @@ pub enum LinkMode {
     Copy,
     /// Hard link packages from the wheel into the site packages.
     Hardlink,
+    /// Symbolically link packages from the wheel into the site packages.
+    ///
+    /// Installed packages will break if the corresponding cache entries are removed (e.g., via
+    /// `uv cache clean`).
+    Symlink,
 }
 
 impl Default for LinkMode {
@@ -252,6 +280,7 @@ impl LinkMode {
             Self::Clone => clone_wheel_files(site_packages, wheel),
             Self::Copy => copy_wheel_files(site_packages, wheel),
             Self::Hardlink => hardlink_wheel_files(site_packages, wheel),
+            Self::Symlink => symlink_wheel_files(site_packages, wheel),
         }
     }
 }
@@ -310,9 +339,14 @@ fn clone_recursive(
 
     debug!("Cloning {} to {}", from.display(), to.display());
 
+    if entry.file_type()?.is_symlink() {
+        copy_symlink(&from, &to)?;
+        return Ok(());
+    }
+
     match attempt {
         Attempt::Initial => {
-            if let Err(err) = reflink::reflink(&from, &to) {
+            if let Err(err) = reflink_file(&from, &to) {
                 if matches!(err.kind(), std::io::ErrorKind::AlreadyExists) {
                     // If cloning/copying fails and the directory exists already, it must be merged recursively.
                     if entry.file_type()?.is_dir() {
@@ -323,7 +357,7 @@ fn clone_recursive(
                         // If file already exists, overwrite it.
                         let tempdir = tempdir_in(site_packages)?;
                         let tempfile = tempdir.path().join(from.file_name().unwrap());
-                        if reflink::reflink(&from, &tempfile).is_ok() {
+                        if reflink_file(&from, &tempfile).is_ok() {
                             fs::rename(&tempfile, to)?;
                         } else {
                             debug!("Failed to clone {} to temporary location {} - attempting to copy files as a fallback", from.display(), tempfile.display());
@@ -344,7 +378,7 @@ fn clone_recursive(
             }
         }
         Attempt::Subsequent => {
-            if let Err(err) = reflink::reflink(&from, &to) {
+            if let Err(err) = reflink_file(&from, &to) {
                 if matches!(err.kind(), std::io::ErrorKind::AlreadyExists) {
                     // If cloning/copying fails and the directory exists already, it must be merged recursively.
                     if entry.file_type()?.is_dir() {
@@ -355,7 +389,7 @@ fn clone_recursive(
                         // If file already exists, overwrite it.
                         let tempdir = tempdir_in(site_packages)?;
                         let tempfile = tempdir.path().join(from.file_name().unwrap());
-                        reflink::reflink(&from, &tempfile)?;
+                        reflink_file(&from, &tempfile)?;
                         fs::rename(&tempfile, to)?;
                     }
                 } else {
@@ -381,6 +415,45 @@ fn clone_recursive(
     Ok(())
 }
 
+/// Clone the file at `from` to `to` via copy-on-write, preserving its permissions.
+fn reflink_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    reflink::reflink(from, to)?;
+
+    // On Linux, the clone is created with the default permissions, which drops the executable bit.
+    #[cfg(target_os = "linux")]
+    if to.is_file() {
+        fs::set_permissions(to, fs::metadata(from)?.permissions())?;
+    }
+
+    Ok(())
+}
+
+/// Recreate the symbolic link at `from` at `to`, replacing any existing file.
+///
+/// Links within a wheel are relative (e.g., `libfoo.so -> libfoo.so.1`), so the recreated link
+/// resolves to the corresponding file in site packages.
+#[cfg(unix)]
+fn copy_symlink(from: &Path, to: &Path) -> Result<(), Error> {
+    let target = fs::read_link(from)?;
+    match fs::symlink_metadata(to) {
+        Ok(metadata) if metadata.is_dir() => fs::remove_dir_all(to)?,
+        Ok(_) => fs::remove_file(to)?,
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+    std::os::unix::fs::symlink(target, to)?;
+    Ok(())
+}
+
+/// Recreate the symbolic link at `from` at `to`, replacing any existing file.
+///
+/// On Windows, the link target is copied instead.
+#[cfg(windows)]
+fn copy_symlink(from: &Path, to: &Path) -> Result<(), Error> {
+    fs::copy(from, to)?;
+    Ok(())
+}
+
 /// Extract a wheel by copying all of its files into site packages.
 fn copy_wheel_files(
     site_packages: impl AsRef<Path>,
@@ -401,6 +474,12 @@ fn copy_wheel_files(
             continue;
         }
 
+        if entry.file_type().is_symlink() {
+            copy_symlink(path, &out_path)?;
+            count += 1;
+            continue;
+        }
+
         // Copy the file, which will also set its permissions.
         fs::copy(path, &out_path)?;
 
@@ -414,6 +493,39 @@ fn copy_wheel_files(
 fn hardlink_wheel_files(
     site_packages: impl AsRef<Path>,
     wheel: impl AsRef<Path>,
+) -> Result<usize, Error> {
+    link_wheel_files_with(site_packages, wheel, |from, to| fs::hard_link(from, to))
+}
+
+/// Extract a wheel by symlinking all of its files into site packages.
+fn symlink_wheel_files(
+    site_packages: impl AsRef<Path>,
+    wheel: impl AsRef<Path>,
+) -> Result<usize, Error> {
+    // Resolve the wheel to its location in the archive store, such that the links remain valid if
+    // the cache entry that pointed to it is replaced.
+    let wheel = fs::canonicalize(wheel.as_ref())?;
+    link_wheel_files_with(site_packages, wheel, symlink_file)
+}
+
+/// Create a symbolic link to the file at `from`, at `to`.
+#[cfg(unix)]
+fn symlink_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::os::unix::fs::symlink(from, to)
+}
+
+/// Create a symbolic link to the file at `from`, at `to`.
+#[cfg(windows)]
+fn symlink_file(from: &Path, to: &Path) -> std::io::Result<()> {
+    std::os::windows::fs::symlink_file(from, to)
+}
+
+/// Extract a wheel by linking all of its files into site packages with `link`, falling back to
+/// copying if linking isn't supported (e.g., across filesystems).
+fn link_wheel_files_with(
+    site_packages: impl AsRef<Path>,
+    wheel: impl AsRef<Path>,
+    link: impl Fn(&Path, &Path) -> std::io::Result<()>,
 ) -> Result<usize, Error> {
     let mut attempt = Attempt::default();
     let mut count = 0usize;
@@ -431,19 +543,27 @@ fn hardlink_wheel_files(
             continue;
         }
 
-        // The `RECORD` file is modified during installation, so we copy it instead of hard-linking.
+        // Symbolic links within the wheel (e.g., to versioned shared libraries) are recreated as
+        // links, rather than linking to (or copying) the link itself.
+        if entry.file_type().is_symlink() {
+            copy_symlink(path, &out_path)?;
+            count += 1;
+            continue;
+        }
+
+        // The `RECORD` file is modified during installation, so we copy it instead of linking.
         if path.ends_with("RECORD") {
             fs::copy(path, &out_path)?;
             count += 1;
             continue;
         }
 
-        // Fallback to copying if hardlinks aren't supported for this installation.
+        // Fallback to copying if links aren't supported for this installation.
         match attempt {
             Attempt::Initial => {
                 // Once https://github.com/rust-lang/rust/issues/86442 is stable, use that.
                 attempt = Attempt::Subsequent;
-                if let Err(err) = fs::hard_link(path, &out_path) {
+                if let Err(err) = link(path, &out_path) {
                     // If the file already exists, remove it and try again.
                     if err.kind() == std::io::ErrorKind::AlreadyExists {
                         debug!(
@@ -453,7 +573,7 @@ fn hardlink_wheel_files(
                         // Removing and recreating would lead to race conditions.
                         let tempdir = tempdir_in(&site_packages)?;
                         let tempfile = tempdir.path().join(entry.file_name());
-                        if fs::hard_link(path, &tempfile).is_ok() {
+                        if link(path, &tempfile).is_ok() {
                             fs_err::rename(&tempfile, &out_path)?;
                         } else {
                             fs::copy(path, &out_path)?;
@@ -466,7 +586,7 @@ fn hardlink_wheel_files(
                 }
             }
             Attempt::Subsequent => {
-                if let Err(err) = fs::hard_link(path, &out_path) {
+                if let Err(err) = link(path, &out_path) {
                     // If the file already exists, remove it and try again.
                     if err.kind() == std::io::ErrorKind::AlreadyExists {
                         debug!(
@@ -476,7 +596,7 @@ fn hardlink_wheel_files(
                         // Removing and recreating would lead to race conditions.
                         let tempdir = tempdir_in(&site_packages)?;
                         let tempfile = tempdir.path().join(entry.file_name());
-                        fs::hard_link(path, &tempfile)?;
+                        link(path, &tempfile)?;
                         fs_err::rename(&tempfile, &out_path)?;
                     } else {
                         return Err(err.into());
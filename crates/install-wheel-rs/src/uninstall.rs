@@ -52,8 +52,212 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
         }
     }
 
-    // If any directories were left empty, remove them. Iterate in reverse order such that we visit
-    // the deepest directories first.
+    // If any directories were left empty, remove them.
+    dir_count += remove_empty_dirs(site_packages, &visited)?;
+
+    Ok(Uninstall {
+        file_count,
+        dir_count,
+    })
+}
+
+/// Uninstall the egg represented by the given `.egg-info` directory, as created by
+/// `setup.py install`.
+///
+/// If the installer recorded the installed files (as in `installed-files.txt`), those files are
+/// removed. Otherwise, the top-level modules and packages listed in `top_level.txt` are removed.
+///
+/// See: <https://github.com/pypa/pip/blob/41587f5e0017bcd849f42b314dc8a34a7db75621/src/pip/_internal/req/req_uninstall.py#L483-L553>
+pub fn uninstall_egg(egg_info: &Path) -> Result<Uninstall, Error> {
+    let Some(site_packages) = egg_info.parent() else {
+        return Err(Error::BrokenVenv(
+            "egg-info directory is not in a site-packages directory".to_string(),
+        ));
+    };
+
+    // An `.egg-info` file, as written by `distutils`, doesn't record the installed files.
+    if !egg_info.is_dir() {
+        return Err(Error::MissingInstalledFiles(egg_info.to_path_buf()));
+    }
+
+    let mut file_count = 0usize;
+    let mut dir_count = 0usize;
+
+    let installed_files = egg_info.join("installed-files.txt");
+    if installed_files.is_file() {
+        // Remove the recorded files, which are relative to the `.egg-info` directory, keeping
+        // track of any directories that are left empty.
+        let mut visited = BTreeSet::new();
+        for entry in read_lines(&installed_files)? {
+            let path = normalize_path(&egg_info.join(entry));
+            match fs::remove_file(&path) {
+                Ok(()) => {
+                    debug!("Removed file: {}", path.display());
+                    file_count += 1;
+                    if let Some(parent) = path.parent() {
+                        visited.insert(parent.to_path_buf());
+                    }
+                }
+                Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+                Err(err) => return Err(err.into()),
+            }
+        }
+
+        // If any directories were left empty, remove them.
+        dir_count += remove_empty_dirs(site_packages, &visited)?;
+    } else {
+        // Namespace packages may be shared with other distributions, so leave them in place.
+        let namespace_packages = read_lines(&egg_info.join("namespace_packages.txt"))?;
+        let top_level = read_lines(&egg_info.join("top_level.txt"))?
+            .into_iter()
+            .filter(|name| !namespace_packages.contains(name))
+            .collect::<Vec<_>>();
+
+        for entry in fs::read_dir(site_packages)? {
+            let entry = entry?;
+            let Some(file_name) = entry.file_name().to_str().map(ToString::to_string) else {
+                continue;
+            };
+            let path = entry.path();
+
+            if entry.file_type()?.is_dir() {
+                // Remove top-level packages (e.g., `foo/`).
+                if top_level.contains(&file_name) {
+                    fs::remove_dir_all(&path)?;
+                    debug!("Removed directory: {}", path.display());
+                    dir_count += 1;
+                }
+            } else {
+                // Remove top-level modules, including extension modules (e.g., `foo.py` or
+                // `foo.cpython-312-x86_64-linux-gnu.so`).
+                let Some((module, _)) = file_name.split_once('.') else {
+                    continue;
+                };
+                let is_module = path.extension().is_some_and(|ext| {
+                    ext == "py" || ext == "pyc" || ext == "pyo" || ext == "so" || ext == "pyd"
+                });
+                if is_module && top_level.iter().any(|name| name == module) {
+                    fs::remove_file(&path)?;
+                    debug!("Removed file: {}", path.display());
+                    file_count += 1;
+                }
+            }
+        }
+    }
+
+    // Remove the `.egg-info` directory itself.
+    match fs::remove_dir_all(egg_info) {
+        Ok(()) => {
+            debug!("Removed directory: {}", egg_info.display());
+            dir_count += 1;
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    Ok(Uninstall {
+        file_count,
+        dir_count,
+    })
+}
+
+/// Uninstall the legacy editable install (as created by `setup.py develop`) represented by the
+/// given `.egg-link` file.
+///
+/// The project itself is left in place; only the `.egg-link` file and the corresponding entry in
+/// `easy-install.pth` are removed.
+///
+/// See: <https://github.com/pypa/pip/blob/41587f5e0017bcd849f42b314dc8a34a7db75621/src/pip/_internal/req/req_uninstall.py#L555-L570>
+pub fn uninstall_legacy_editable(egg_link: &Path) -> Result<Uninstall, Error> {
+    let Some(site_packages) = egg_link.parent() else {
+        return Err(Error::BrokenVenv(
+            "egg-link file is not in a site-packages directory".to_string(),
+        ));
+    };
+
+    // The first line of the `.egg-link` file is the path to the project.
+    let Some(target) = read_lines(egg_link)?.into_iter().next() else {
+        return Err(Error::InvalidEggLink(egg_link.to_path_buf()));
+    };
+    let target = normalize_path(&site_packages.join(target));
+
+    fs::remove_file(egg_link)?;
+    debug!("Removed file: {}", egg_link.display());
+    let file_count = 1;
+
+    // Remove the project from `easy-install.pth`, preserving the file's line endings.
+    let easy_install = site_packages.join("easy-install.pth");
+    match fs::read_to_string(&easy_install) {
+        Ok(contents) => {
+            let newline = if contents.contains("\r\n") {
+                "\r\n"
+            } else {
+                "\n"
+            };
+            let mut removed = false;
+            let lines = contents
+                .lines()
+                .filter(|line| {
+                    let entry = line.trim();
+                    let matches = !entry.is_empty()
+                        && !entry.starts_with('#')
+                        && !entry.starts_with("import ")
+                        && normalize_path(&site_packages.join(entry)) == target;
+                    removed |= matches;
+                    !matches
+                })
+                .collect::<Vec<_>>();
+            if removed {
+                let mut contents = lines.join(newline);
+                if !contents.is_empty() {
+                    contents.push_str(newline);
+                }
+                fs::write(&easy_install, contents)?;
+                debug!(
+                    "Removed {} from: {}",
+                    target.display(),
+                    easy_install.display()
+                );
+            }
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(err.into()),
+    }
+
+    Ok(Uninstall {
+        file_count,
+        dir_count: 0,
+    })
+}
+
+/// Read the non-empty lines of a metadata file, returning an empty list if the file is missing.
+fn read_lines(path: &Path) -> Result<Vec<String>, Error> {
+    match fs::read_to_string(path) {
+        Ok(contents) => Ok(contents
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(ToString::to_string)
+            .collect()),
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(err) => Err(err.into()),
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct Uninstall {
+    /// The number of files that were removed during the uninstallation.
+    pub file_count: usize,
+    /// The number of directories that were removed during the uninstallation.
+    pub dir_count: usize,
+}
+
+/// Remove any directories within `site-packages` that were left empty after removing files from
+/// the `visited` directories, returning the number of directories removed.
+fn remove_empty_dirs(site_packages: &Path, visited: &BTreeSet<PathBuf>) -> Result<usize, Error> {
+    let mut dir_count = 0usize;
+
+    // Iterate in reverse order such that we visit the deepest directories first.
     for path in visited.iter().rev() {
         // No need to look at directories outside of `site-packages` (like `bin`).
         if !path.starts_with(site_packages) {
@@ -109,18 +313,7 @@ pub fn uninstall_wheel(dist_info: &Path) -> Result<Uninstall, Error> {
         }
     }
 
-    Ok(Uninstall {
-        file_count,
-        dir_count,
-    })
-}
-
-#[derive(Debug, Default)]
-pub struct Uninstall {
-    /// The number of files that were removed during the uninstallation.
-    pub file_count: usize,
-    /// The number of directories that were removed during the uninstallation.
-    pub dir_count: usize,
+    Ok(dir_count)
 }
 
 /// Normalize a path, removing things like `.` and `..`.
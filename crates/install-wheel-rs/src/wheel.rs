@@ -266,7 +266,32 @@ fn unpack_wheel_files<R: Read + Seek>(
 }
 
 fn get_shebang(location: &InstallLocation<impl AsRef<Path>>) -> String {
-    format!("#!{}", location.python().normalized().display())
+    let executable = location.python().normalized();
+    if cfg!(unix) {
+        format_shebang(&executable.to_string_lossy())
+    } else {
+        // On Windows, the shebang is ignored by the launcher, which locates the interpreter itself.
+        format!("#!{}", executable.display())
+    }
+}
+
+/// Format the shebang for a given Python executable.
+///
+/// Like pip, if a shebang is non-simple (too long or contains spaces), we use `/bin/sh` as the
+/// executable, and `exec` into the interpreter from a line that Python parses as a string.
+///
+/// See: <https://github.com/pypa/pip/blob/0ad4c94be74cc24874c6feb5bb3c2152c398a18e/src/pip/_vendor/distlib/scripts.py#L136-L165>
+fn format_shebang(executable: &str) -> String {
+    // The maximum length of a shebang on Linux, including the leading `#!` and trailing newline.
+    const MAX_SHEBANG_LENGTH: usize = 127;
+
+    let shebang_length = 2 + executable.len() + 1;
+    if shebang_length > MAX_SHEBANG_LENGTH || executable.contains(' ') {
+        let executable = executable.replace('\'', r#"'"'"'"#);
+        return format!("#!/bin/sh\n'''exec' '{executable}' \"$0\" \"$@\"\n' '''");
+    }
+
+    format!("#!{executable}")
 }
 
 /// A Windows script is a minimal .exe launcher binary with the python entrypoint script appended as
@@ -343,6 +368,16 @@ pub(crate) fn windows_script_launcher(
     Ok(launcher)
 }
 
+/// The name of the `.exe` launcher for a script on Windows.
+fn windows_launcher_name(script_name: &str) -> String {
+    // FIXME: What are the in-reality rules here for names?
+    script_name
+        .strip_suffix(".py")
+        .unwrap_or(script_name)
+        .to_string()
+        + ".exe"
+}
+
 /// Create the wrapper scripts in the bin folder of the venv for launching console scripts
 ///
 /// We also pass `venv_base` so we can write the same path as pip does
@@ -358,14 +393,7 @@ pub(crate) fn write_script_entrypoints(
     for entrypoint in entrypoints {
         let entrypoint_relative = if cfg!(windows) {
             // On windows we actually build an .exe wrapper
-            let script_name = entrypoint
-                .script_name
-                // FIXME: What are the in-reality rules here for names?
-                .strip_suffix(".py")
-                .unwrap_or(&entrypoint.script_name)
-                .to_string()
-                + ".exe";
-            bin_rel().join(script_name)
+            bin_rel().join(windows_launcher_name(&entrypoint.script_name))
         } else {
             bin_rel().join(&entrypoint.script_name)
         };
@@ -461,7 +489,7 @@ pub(crate) fn parse_wheel_version(wheel_text: &str) -> Result<(), Error> {
 /// 2.f Compile any installed .py to .pyc. (Uninstallers should be smart enough to remove .pyc
 /// even if it is not mentioned in RECORD.)
 #[instrument(skip_all)]
-fn bytecode_compile(
+pub(crate) fn bytecode_compile(
     site_packages: &Path,
     unpacked_paths: Vec<PathBuf>,
     python_version: (u8, u8),
@@ -688,7 +716,7 @@ fn install_script(
         )));
     }
 
-    let target_path = bin_rel().join(file.file_name());
+    let mut target_path = bin_rel().join(file.file_name());
 
     let path = file.path();
     let mut script = File::open(&path)?;
@@ -706,9 +734,34 @@ fn install_script(
     let mut start = vec![0; placeholder_python.len()];
     script.read_exact(&mut start)?;
     let size_and_encoded_hash = if start == placeholder_python {
-        let start = get_shebang(location).as_bytes().to_vec();
+        let mut contents = Vec::new();
+        script.read_to_end(&mut contents)?;
+        drop(script);
+
+        // Replace the entire placeholder line, including the `w` in `#!pythonw`.
+        let is_gui = contents.first() == Some(&b'w');
+        let body = contents
+            .iter()
+            .position(|byte| *byte == b'\n')
+            .map_or(&[][..], |newline| &contents[newline..]);
+
+        let mut launcher_python_script = get_shebang(location).into_bytes();
+        launcher_python_script.extend_from_slice(body);
+
+        // On Windows, wrap the script in a launcher binary, as for entrypoints.
+        if cfg!(windows) {
+            let script_name = file.file_name().to_string_lossy().to_string();
+            target_path = bin_rel().join(windows_launcher_name(&script_name));
+            launcher_python_script = windows_script_launcher(
+                &String::from_utf8_lossy(&launcher_python_script),
+                is_gui,
+                location,
+            )?;
+        }
+
         let mut target = File::create(site_packages.join(&target_path))?;
-        let size_and_encoded_hash = copy_and_hash(&mut start.chain(script), &mut target)?;
+        let size_and_encoded_hash =
+            copy_and_hash(&mut launcher_python_script.as_slice(), &mut target)?;
         fs::remove_file(&path)?;
         Some(size_and_encoded_hash)
     } else {
@@ -901,6 +954,136 @@ pub fn read_record_file(record: &mut impl Read) -> Result<Vec<RecordEntry>, Erro
         .collect()
 }
 
+/// Verify the files of an unzipped wheel against the SHA-256 hashes in its `RECORD` file.
+///
+/// Returns the paths (relative to the wheel root) of any files that are missing or whose contents
+/// don't match their recorded hash. Entries without a SHA-256 hash are skipped.
+pub fn verify_unpacked_wheel(wheel: &Path) -> Result<Vec<String>, Error> {
+    let dist_info = fs::read_dir(wheel)?
+        .filter_map(Result::ok)
+        .map(|entry| entry.path())
+        .find(|path| path.extension().is_some_and(|ext| ext == "dist-info") && path.is_dir())
+        .ok_or(Error::MissingDistInfo)?;
+    verify_record(wheel, &dist_info)
+}
+
+/// Verify the files of an installed distribution against the SHA-256 hashes in the `RECORD` file
+/// of the given `.dist-info` directory.
+///
+/// Returns the paths (relative to `site-packages`) of any files that are missing or whose contents
+/// don't match their recorded hash. Entries without a SHA-256 hash, and entries outside of
+/// `site-packages` (like scripts), are skipped.
+pub fn verify_installed_dist(dist_info: &Path) -> Result<Vec<String>, Error> {
+    let Some(site_packages) = dist_info.parent() else {
+        return Err(Error::BrokenVenv(
+            "dist-info directory is not in a site-packages directory".to_string(),
+        ));
+    };
+    verify_record(site_packages, dist_info)
+}
+
+/// Verify the files under `root` against the SHA-256 hashes in the `RECORD` file of the given
+/// `.dist-info` directory.
+fn verify_record(root: &Path, dist_info: &Path) -> Result<Vec<String>, Error> {
+    let record_path = dist_info.join("RECORD");
+    let record = match File::open(&record_path) {
+        Ok(record) => read_record_file(&mut BufReader::new(record))?,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => {
+            return Err(Error::MissingRecord(record_path));
+        }
+        Err(err) => return Err(err.into()),
+    };
+
+    let mut mismatched = Vec::new();
+    for entry in record {
+        let Some(expected) = entry
+            .hash
+            .as_deref()
+            .filter(|hash| hash.starts_with("sha256="))
+        else {
+            continue;
+        };
+        if entry.path.split('/').any(|component| component == "..") {
+            continue;
+        }
+        let path = root.join(&entry.path);
+
+        // Symbolic links are stored in the archive as files containing the link target, so the
+        // recorded hash may be that of the link target rather than of the linked file.
+        if let Ok(link) = fs::read_link(&path) {
+            let link = link.to_string_lossy();
+            let (_, actual) = copy_and_hash(&mut link.as_bytes(), &mut io::sink())?;
+            if actual == expected {
+                continue;
+            }
+        }
+
+        match File::open(&path) {
+            Ok(mut file) => {
+                let (_, actual) = copy_and_hash(&mut file, &mut io::sink())?;
+                if actual != expected {
+                    mismatched.push(entry.path);
+                }
+            }
+            Err(err) if err.kind() == io::ErrorKind::NotFound => mismatched.push(entry.path),
+            Err(err) => return Err(err.into()),
+        }
+    }
+    Ok(mismatched)
+}
+
+/// Verify the files of a wheel archive against their CRCs and the SHA-256 hashes in its `RECORD`
+/// file.
+///
+/// Returns the paths of any files that are missing, unreadable, or whose contents don't match
+/// their recorded hash. Entries without a SHA-256 hash are only checked against their CRC.
+pub fn verify_wheel_archive(wheel: &Path) -> Result<Vec<String>, Error> {
+    let mut archive = ZipArchive::new(BufReader::new(File::open(wheel)?))
+        .map_err(|err| Error::Zip(wheel.normalized_display().to_string(), err))?;
+    let record_path = archive
+        .file_names()
+        .find(|name| name.ends_with(".dist-info/RECORD") && name.matches('/').count() == 1)
+        .ok_or(Error::MissingDistInfo)?
+        .to_string();
+    let record = read_record_file(
+        &mut archive
+            .by_name(&record_path)
+            .map_err(|err| Error::Zip(record_path.clone(), err))?,
+    )?;
+    let expected = record
+        .into_iter()
+        .filter_map(|entry| Some((entry.path, entry.hash?)))
+        .filter(|(_, hash)| hash.starts_with("sha256="))
+        .collect::<FxHashMap<_, _>>();
+
+    let mut mismatched = Vec::new();
+    let mut seen = FxHashSet::default();
+    for index in 0..archive.len() {
+        let mut file = archive
+            .by_index(index)
+            .map_err(|err| Error::Zip(wheel.normalized_display().to_string(), err))?;
+        if file.is_dir() {
+            continue;
+        }
+        let name = file.name().to_string();
+        // Reading the entry to the end validates its CRC.
+        match copy_and_hash(&mut file, &mut io::sink()) {
+            Ok((_, actual)) => {
+                if expected
+                    .get(&name)
+                    .is_some_and(|expected| *expected != actual)
+                {
+                    mismatched.push(name.clone());
+                }
+            }
+            Err(_) => mismatched.push(name.clone()),
+        }
+        seen.insert(name);
+    }
+    mismatched.extend(expected.into_keys().filter(|path| !seen.contains(path)));
+    Ok(mismatched)
+}
+
 /// Parse a file with `Key: value` entries such as WHEEL and METADATA
 pub fn parse_key_value_file(
     file: &mut impl Read,
@@ -1166,7 +1349,10 @@ mod test {
 
     use indoc::{formatdoc, indoc};
 
-    use super::{parse_key_value_file, parse_wheel_version, read_record_file, relative_to, Script};
+    use super::{
+        format_shebang, parse_key_value_file, parse_wheel_version, read_record_file, relative_to,
+        verify_unpacked_wheel, Script,
+    };
 
     #[test]
     fn test_parse_key_value_file() {
@@ -1251,6 +1437,81 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_verify_unpacked_wheel() {
+        let wheel = tempfile::tempdir().unwrap();
+        fs_err::create_dir_all(wheel.path().join("foo")).unwrap();
+        fs_err::create_dir_all(wheel.path().join("foo-1.0.dist-info")).unwrap();
+        fs_err::write(wheel.path().join("foo/intact.py"), "hello").unwrap();
+        fs_err::write(wheel.path().join("foo/corrupted.py"), "goodbye").unwrap();
+        fs_err::write(
+            wheel.path().join("foo-1.0.dist-info/RECORD"),
+            indoc! {"
+                foo/intact.py,sha256=LPJNul-wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ,5
+                foo/corrupted.py,sha256=LPJNul-wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ,5
+                foo/missing.py,sha256=LPJNul-wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ,5
+                foo-1.0.dist-info/RECORD,,
+            "},
+        )
+        .unwrap();
+
+        let mismatched = verify_unpacked_wheel(wheel.path()).unwrap();
+        assert_eq!(mismatched, vec!["foo/corrupted.py", "foo/missing.py"]);
+    }
+
+    #[test]
+    #[cfg(unix)]
+    fn test_verify_unpacked_wheel_symlink() {
+        let wheel = tempfile::tempdir().unwrap();
+        fs_err::create_dir_all(wheel.path().join("foo")).unwrap();
+        fs_err::create_dir_all(wheel.path().join("foo-1.0.dist-info")).unwrap();
+        fs_err::write(wheel.path().join("foo/libfoo.so.1"), "hello").unwrap();
+        // One link recorded with the hash of its target path, one with the hash of the linked file.
+        fs_err::os::unix::fs::symlink("libfoo.so.1", wheel.path().join("foo/libfoo.so")).unwrap();
+        fs_err::os::unix::fs::symlink("libfoo.so.1", wheel.path().join("foo/libbar.so")).unwrap();
+        fs_err::write(
+            wheel.path().join("foo-1.0.dist-info/RECORD"),
+            indoc! {"
+                foo/libfoo.so.1,sha256=LPJNul-wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ,5
+                foo/libfoo.so,sha256=oRcLDDOqDiTfQQOc9JQDPBit1xtxJdmEUeTYnnEBaNE,11
+                foo/libbar.so,sha256=LPJNul-wow4m6DsqxbninhsWHlwfp0JecwQzYpOLmCQ,5
+                foo-1.0.dist-info/RECORD,,
+            "},
+        )
+        .unwrap();
+
+        let mismatched = verify_unpacked_wheel(wheel.path()).unwrap();
+        assert!(mismatched.is_empty(), "{mismatched:?}");
+    }
+
+    #[test]
+    fn test_shebang() {
+        // By default, use a simple shebang.
+        let executable = "/usr/bin/python3";
+        assert_eq!(format_shebang(executable), "#!/usr/bin/python3");
+
+        // If the path contains spaces, we should use the `exec` trick.
+        let executable = "/usr/bin/path to python3";
+        assert_eq!(
+            format_shebang(executable),
+            "#!/bin/sh\n'''exec' '/usr/bin/path to python3' \"$0\" \"$@\"\n' '''"
+        );
+
+        // Quotes in the path should be escaped.
+        let executable = "/usr/bin/path to 'python3'";
+        assert_eq!(
+            format_shebang(executable),
+            "#!/bin/sh\n'''exec' '/usr/bin/path to '\"'\"'python3'\"'\"'' \"$0\" \"$@\"\n' '''"
+        );
+
+        // If the path is too long, we should use the `exec` trick.
+        let executable = format!("/usr/bin/{}/python3", "a".repeat(120));
+        assert_eq!(
+            format_shebang(&executable),
+            format!("#!/bin/sh\n'''exec' '{executable}' \"$0\" \"$@\"\n' '''")
+        );
+    }
+
     #[test]
     fn test_script_from_value() {
         assert_eq!(
@@ -996,6 +996,55 @@ impl MarkerTree {
         }
     }
 
+    /// Remove any `extra` expressions that are satisfied by the given extras, returning `None` if
+    /// the marker is always satisfied as a result.
+    ///
+    /// Any `extra` expressions that aren't satisfied by the given extras are left unchanged, such
+    /// that the marker continues to evaluate to `false` when no extras are active.
+    #[must_use]
+    pub fn simplify_extras(self, extras: &[ExtraName]) -> Option<Self> {
+        match self {
+            Self::Expression(expression) => {
+                let name = match (&expression.l_value, &expression.r_value) {
+                    (MarkerValue::Extra, MarkerValue::QuotedString(name))
+                    | (MarkerValue::QuotedString(name), MarkerValue::Extra) => Some(name),
+                    _ => None,
+                };
+                if expression.operator == MarkerOperator::Equal
+                    && name
+                        .and_then(|name| ExtraName::from_str(name).ok())
+                        .is_some_and(|name| extras.contains(&name))
+                {
+                    None
+                } else {
+                    Some(Self::Expression(expression))
+                }
+            }
+            Self::And(expressions) => {
+                let mut expressions = expressions
+                    .into_iter()
+                    .filter_map(|marker| marker.simplify_extras(extras))
+                    .collect::<Vec<_>>();
+                match expressions.len() {
+                    0 => None,
+                    1 => expressions.pop(),
+                    _ => Some(Self::And(expressions)),
+                }
+            }
+            Self::Or(expressions) => {
+                let mut simplified = Vec::with_capacity(expressions.len());
+                for marker in expressions {
+                    simplified.push(marker.simplify_extras(extras)?);
+                }
+                if simplified.len() == 1 {
+                    simplified.pop()
+                } else {
+                    Some(Self::Or(simplified))
+                }
+            }
+        }
+    }
+
     /// Checks if the requirement should be activated with the given set of active extras and a set
     /// of possible python versions (from `requires-python`) without evaluating the remaining
     /// environment markers, i.e. if there is potentially an environment that could activate this
@@ -1365,6 +1414,7 @@ mod test {
     use crate::{MarkerExpression, MarkerOperator, MarkerTree, MarkerValue, MarkerValueString};
     use indoc::indoc;
     use std::str::FromStr;
+    use uv_normalize::ExtraName;
 
     fn assert_err(input: &str, error: &str) {
         assert_eq!(MarkerTree::from_str(input).unwrap_err().to_string(), error);
@@ -1578,6 +1628,26 @@ mod test {
         );
     }
 
+    #[test]
+    fn test_simplify_extras() {
+        let extras = [ExtraName::from_str("dev").unwrap()];
+
+        let marker = MarkerTree::from_str(r#"extra == "dev""#).unwrap();
+        assert_eq!(marker.simplify_extras(&extras), None);
+
+        let marker = MarkerTree::from_str(r#"os_name == "nt" and extra == "dev""#).unwrap();
+        assert_eq!(
+            marker.simplify_extras(&extras),
+            Some(MarkerTree::from_str(r#"os_name == "nt""#).unwrap())
+        );
+
+        let marker = MarkerTree::from_str(r#"os_name == "nt" or extra == "dev""#).unwrap();
+        assert_eq!(marker.simplify_extras(&extras), None);
+
+        let marker = MarkerTree::from_str(r#"os_name == "nt" and extra == "test""#).unwrap();
+        assert_eq!(marker.clone().simplify_extras(&extras), Some(marker));
+    }
+
     #[test]
     fn test_marker_expression_to_long() {
         assert_eq!(
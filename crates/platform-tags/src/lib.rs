@@ -86,11 +86,15 @@ impl Tags {
 
     /// Returns the compatible tags for the given Python implementation (e.g., `cpython`), version,
     /// and platform.
+    ///
+    /// If `gil_disabled` is set, the tags are generated for a free-threaded build (e.g., `cp313t`),
+    /// which is incompatible with the stable ABI.
     pub fn from_env(
         platform: &Platform,
         python_version: (u8, u8),
         implementation_name: &str,
         implementation_version: (u8, u8),
+        gil_disabled: bool,
     ) -> Result<Self, TagsError> {
         let implementation = Implementation::from_str(implementation_name)?;
         let platform_tags = compatible_tags(platform)?;
@@ -101,7 +105,7 @@ impl Tags {
         for platform_tag in &platform_tags {
             tags.push((
                 implementation.language_tag(python_version),
-                implementation.abi_tag(python_version, implementation_version),
+                implementation.abi_tag(python_version, implementation_version, gil_disabled),
                 platform_tag.clone(),
             ));
             tags.push((
@@ -111,7 +115,8 @@ impl Tags {
             ));
         }
         // 2. abi3 and no abi (e.g. executable binary)
-        if matches!(implementation, Implementation::CPython) {
+        // Free-threaded builds don't support the stable ABI.
+        if matches!(implementation, Implementation::CPython) && !gil_disabled {
             // For some reason 3.2 is the minimum python for the cp abi
             for minor in 2..=python_version.1 {
                 for platform_tag in &platform_tags {
@@ -256,6 +261,7 @@ impl TryFrom<usize> for TagPriority {
 pub enum Implementation {
     CPython,
     PyPy,
+    GraalPy,
 }
 
 impl Implementation {
@@ -267,14 +273,26 @@ impl Implementation {
             Self::CPython => format!("cp{}{}", python_version.0, python_version.1),
             // Ex) `pp39`
             Self::PyPy => format!("pp{}{}", python_version.0, python_version.1),
+            // Ex) `graalpy311`
+            Self::GraalPy => format!("graalpy{}{}", python_version.0, python_version.1),
         }
     }
 
-    pub fn abi_tag(&self, python_version: (u8, u8), implementation_version: (u8, u8)) -> String {
+    /// Returns the ABI tag for the current implementation and Python version (e.g., `cp39`,
+    /// `cp313t`, or `pypy39_pp73`).
+    pub fn abi_tag(
+        &self,
+        python_version: (u8, u8),
+        implementation_version: (u8, u8),
+        gil_disabled: bool,
+    ) -> String {
         match self {
             // Ex) `cp39`
             Self::CPython => {
-                if python_version.1 <= 7 {
+                if gil_disabled {
+                    // Ex) `cp313t`
+                    format!("cp{}{}t", python_version.0, python_version.1)
+                } else if python_version.1 <= 7 {
                     format!("cp{}{}m", python_version.0, python_version.1)
                 } else {
                     format!("cp{}{}", python_version.0, python_version.1)
@@ -288,6 +306,14 @@ impl Implementation {
                 implementation_version.0,
                 implementation_version.1
             ),
+            // Ex) `graalpy240_311_native`
+            Self::GraalPy => format!(
+                "graalpy{}{}_{}{}_native",
+                implementation_version.0,
+                implementation_version.1,
+                python_version.0,
+                python_version.1
+            ),
         }
     }
 }
@@ -300,6 +326,7 @@ impl FromStr for Implementation {
             // Known and supported implementations.
             "cpython" => Ok(Self::CPython),
             "pypy" => Ok(Self::PyPy),
+            "graalpy" => Ok(Self::GraalPy),
             // Known but unsupported implementations.
             "python" => Err(TagsError::UnsupportedImplementation(s.to_string())),
             "ironpython" => Err(TagsError::UnsupportedImplementation(s.to_string())),
@@ -484,3 +511,98 @@ fn get_mac_binary_formats(major: u16, minor: u16, arch: Arch) -> Vec<String> {
 
     formats
 }
+
+#[cfg(test)]
+mod tests {
+    use platform_host::{Arch, Os, Platform};
+
+    use super::{Implementation, Tags};
+
+    fn manylinux() -> Platform {
+        Platform::new(
+            Os::Manylinux {
+                major: 2,
+                minor: 17,
+            },
+            Arch::X86_64,
+        )
+    }
+
+    #[test]
+    fn cpython_tags() {
+        let implementation = Implementation::CPython;
+        assert_eq!(implementation.language_tag((3, 12)), "cp312");
+        assert_eq!(implementation.abi_tag((3, 12), (3, 12), false), "cp312");
+        assert_eq!(implementation.abi_tag((3, 7), (3, 7), false), "cp37m");
+
+        let tags = Tags::from_env(&manylinux(), (3, 12), "cpython", (3, 12), false).unwrap();
+        assert!(tags.is_compatible(
+            &["cp312".to_string()],
+            &["cp312".to_string()],
+            &["manylinux_2_17_x86_64".to_string()],
+        ));
+        assert!(tags.is_compatible(
+            &["cp38".to_string()],
+            &["abi3".to_string()],
+            &["manylinux_2_17_x86_64".to_string()],
+        ));
+    }
+
+    #[test]
+    fn pypy_tags() {
+        let implementation = Implementation::PyPy;
+        assert_eq!(implementation.language_tag((3, 9)), "pp39");
+        assert_eq!(implementation.abi_tag((3, 9), (7, 3), false), "pypy39_pp73");
+
+        let tags = Tags::from_env(&manylinux(), (3, 9), "pypy", (7, 3), false).unwrap();
+        assert!(tags.is_compatible(
+            &["pp39".to_string()],
+            &["pypy39_pp73".to_string()],
+            &["manylinux_2_17_x86_64".to_string()],
+        ));
+        assert!(!tags.is_compatible(
+            &["pp39".to_string()],
+            &["abi3".to_string()],
+            &["manylinux_2_17_x86_64".to_string()],
+        ));
+    }
+
+    #[test]
+    fn graalpy_tags() {
+        let implementation = Implementation::GraalPy;
+        assert_eq!(implementation.language_tag((3, 11)), "graalpy311");
+        assert_eq!(
+            implementation.abi_tag((3, 11), (24, 0), false),
+            "graalpy240_311_native"
+        );
+
+        let tags = Tags::from_env(&manylinux(), (3, 11), "graalpy", (24, 0), false).unwrap();
+        assert!(tags.is_compatible(
+            &["graalpy311".to_string()],
+            &["graalpy240_311_native".to_string()],
+            &["manylinux_2_17_x86_64".to_string()],
+        ));
+        assert!(tags.is_compatible(
+            &["graalpy311".to_string()],
+            &["none".to_string()],
+            &["manylinux_2_17_x86_64".to_string()],
+        ));
+        assert!(tags.is_compatible(
+            &["py3".to_string()],
+            &["none".to_string()],
+            &["any".to_string()],
+        ));
+
+        // GraalPy can't load CPython extension modules, including those built for the stable ABI.
+        assert!(!tags.is_compatible(
+            &["cp311".to_string()],
+            &["cp311".to_string()],
+            &["manylinux_2_17_x86_64".to_string()],
+        ));
+        assert!(!tags.is_compatible(
+            &["graalpy311".to_string()],
+            &["abi3".to_string()],
+            &["manylinux_2_17_x86_64".to_string()],
+        ));
+    }
+}
@@ -35,17 +35,16 @@ fn sorted_simple_json_files<'de, D: Deserializer<'de>>(d: D) -> Result<Vec<File>
 ///
 /// <https://peps.python.org/pep-0691/#project-detail>
 #[derive(Debug, Clone, Deserialize)]
-#[serde(rename_all = "kebab-case")]
+#[serde(from = "FileWire")]
 pub struct File {
-    // Non-PEP 691-compliant alias used by PyPI.
-    #[serde(alias = "data-dist-info-metadata")]
+    /// Whether the file's core metadata is available at `{url}.metadata` (PEP 658), and if so,
+    /// its hashes.
     pub dist_info_metadata: Option<DistInfoMetadata>,
     pub filename: String,
     pub hashes: Hashes,
     /// There are a number of invalid specifiers on pypi, so we first try to parse it into a [`VersionSpecifiers`]
     /// according to spec (PEP 440), then a [`LenientVersionSpecifiers`] with fixup for some common problems and if this
     /// still fails, we skip the file when creating a version map.
-    #[serde(default, deserialize_with = "deserialize_version_specifiers_lenient")]
     pub requires_python: Option<Result<VersionSpecifiers, VersionSpecifiersParseError>>,
     pub size: Option<u64>,
     pub upload_time: Option<DateTime<Utc>>,
@@ -53,6 +52,43 @@ pub struct File {
     pub yanked: Option<Yanked>,
 }
 
+/// The wire format of a [`File`].
+///
+/// [PEP 714](https://peps.python.org/pep-0714/) renamed `dist-info-metadata` to `core-metadata`,
+/// but indexes (including `PyPI`) may serve either or both keys, so we accept all of them, with
+/// `core-metadata` taking precedence.
+#[derive(Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct FileWire {
+    core_metadata: Option<DistInfoMetadata>,
+    // Non-PEP 691-compliant alias used by PyPI.
+    #[serde(alias = "data-dist-info-metadata")]
+    dist_info_metadata: Option<DistInfoMetadata>,
+    filename: String,
+    hashes: Hashes,
+    #[serde(default, deserialize_with = "deserialize_version_specifiers_lenient")]
+    requires_python: Option<Result<VersionSpecifiers, VersionSpecifiersParseError>>,
+    size: Option<u64>,
+    upload_time: Option<DateTime<Utc>>,
+    url: String,
+    yanked: Option<Yanked>,
+}
+
+impl From<FileWire> for File {
+    fn from(file: FileWire) -> Self {
+        Self {
+            dist_info_metadata: file.core_metadata.or(file.dist_info_metadata),
+            filename: file.filename,
+            hashes: file.hashes,
+            requires_python: file.requires_python,
+            size: file.size,
+            upload_time: file.upload_time,
+            url: file.url,
+            yanked: file.yanked,
+        }
+    }
+}
+
 fn deserialize_version_specifiers_lenient<'de, D>(
     deserializer: D,
 ) -> Result<Option<Result<VersionSpecifiers, VersionSpecifiersParseError>>, D::Error>
@@ -116,8 +152,8 @@ impl Default for Yanked {
 
 /// A dictionary mapping a hash name to a hex encoded digest of the file.
 ///
-/// PEP 691 says multiple hashes can be included and the interpretation is left to the client, we
-/// only support SHA 256 atm.
+/// PEP 691 says multiple hashes can be included and the interpretation is left to the client. We
+/// prefer SHA 256, but retain SHA 384 and SHA 512 digests when the index provides them.
 #[derive(
     Debug,
     Clone,
@@ -138,6 +174,8 @@ impl Default for Yanked {
 pub struct Hashes {
     pub md5: Option<String>,
     pub sha256: Option<String>,
+    pub sha384: Option<String>,
+    pub sha512: Option<String>,
 }
 
 impl Hashes {
@@ -146,11 +184,79 @@ impl Hashes {
         self.sha256
             .as_ref()
             .map(|sha256| format!("sha256:{sha256}"))
+            .or_else(|| {
+                self.sha384
+                    .as_ref()
+                    .map(|sha384| format!("sha384:{sha384}"))
+            })
+            .or_else(|| {
+                self.sha512
+                    .as_ref()
+                    .map(|sha512| format!("sha512:{sha512}"))
+            })
             .or_else(|| self.md5.as_ref().map(|md5| format!("md5:{md5}")))
     }
 
     /// Return the hash digest.
     pub fn as_str(&self) -> Option<&str> {
-        self.sha256.as_deref().or(self.md5.as_deref())
+        self.sha256
+            .as_deref()
+            .or(self.sha384.as_deref())
+            .or(self.sha512.as_deref())
+            .or(self.md5.as_deref())
+    }
+
+    /// Returns `true` if the given `<algorithm>:<hash>` digest (as in `--hash=sha256:...`)
+    /// matches the digest for the same algorithm.
+    pub fn matches(&self, digest: &str) -> bool {
+        let Some((algorithm, digest)) = digest.split_once(':') else {
+            return false;
+        };
+        let expected = match algorithm {
+            "md5" => self.md5.as_deref(),
+            "sha256" => self.sha256.as_deref(),
+            "sha384" => self.sha384.as_deref(),
+            "sha512" => self.sha512.as_deref(),
+            _ => None,
+        };
+        expected.is_some_and(|expected| expected.eq_ignore_ascii_case(digest))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DistInfoMetadata, File};
+
+    #[test]
+    fn core_metadata() {
+        // PyPI serves both the PEP 714 key and the legacy key.
+        let file: File = serde_json::from_str(
+            r#"{
+                "core-metadata": false,
+                "data-dist-info-metadata": {"sha256": "6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61"},
+                "filename": "Jinja2-3.1.2-py3-none-any.whl",
+                "hashes": {},
+                "url": "https://files.pythonhosted.org/Jinja2-3.1.2-py3-none-any.whl"
+            }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            file.dist_info_metadata,
+            Some(DistInfoMetadata::Bool(false))
+        ));
+
+        let file: File = serde_json::from_str(
+            r#"{
+                "dist-info-metadata": true,
+                "filename": "Jinja2-3.1.2-py3-none-any.whl",
+                "hashes": {},
+                "url": "https://files.pythonhosted.org/Jinja2-3.1.2-py3-none-any.whl"
+            }"#,
+        )
+        .unwrap();
+        assert!(matches!(
+            file.dist_info_metadata,
+            Some(DistInfoMetadata::Bool(true))
+        ));
     }
 }
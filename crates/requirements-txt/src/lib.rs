@@ -393,6 +393,15 @@ impl RequirementsTxt {
                             end,
                         }
                     })?;
+                    // Editable requirements don't constrain anything, so reject them rather than
+                    // silently dropping them.
+                    if !sub_constraints.editables.is_empty() {
+                        return Err(RequirementsTxtParserError::Parser {
+                            message: "Editable requirements are not supported in constraints files"
+                                .to_string(),
+                            location: start,
+                        });
+                    }
                     // Treat any nested requirements or constraints as constraints. This differs
                     // from `pip`, which seems to treat `-r` requirements in constraints files as
                     // _requirements_, but we don't want to support that.
@@ -1144,6 +1153,37 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn editable_constraint() -> Result<()> {
+        let temp_dir = assert_fs::TempDir::new()?;
+        let constraints_txt = temp_dir.child("constraints.txt");
+        constraints_txt.write_str(indoc! {"
+            -e ./black
+        "})?;
+        let requirements_txt = temp_dir.child("requirements.txt");
+        requirements_txt.write_str(indoc! {"
+            -c constraints.txt
+            black
+        "})?;
+
+        let error = RequirementsTxt::parse(requirements_txt.path(), temp_dir.path()).unwrap_err();
+        let errors = anyhow::Error::new(error).chain().join("\n");
+
+        let requirement_txt =
+            regex::escape(&requirements_txt.path().normalized_display().to_string());
+        let filters = vec![
+            (requirement_txt.as_str(), "<REQUIREMENTS_TXT>"),
+            (r"\\", "/"),
+        ];
+        insta::with_settings!({
+            filters => filters
+        }, {
+            insta::assert_display_snapshot!(errors, @"Editable requirements are not supported in constraints files in `<REQUIREMENTS_TXT>` at position 0");
+        });
+
+        Ok(())
+    }
+
     #[test]
     fn invalid_index_url() -> Result<()> {
         let temp_dir = assert_fs::TempDir::new()?;
@@ -23,14 +23,18 @@ use serde::{de, Deserialize, Deserializer, Serialize};
 use tempfile::{tempdir_in, TempDir};
 use thiserror::Error;
 use tokio::process::Command;
-use tokio::sync::Mutex;
+use tokio::sync::{Mutex, Semaphore};
 use tracing::{debug, info_span, instrument, Instrument};
 
 use distribution_types::Resolution;
 use pep508_rs::Requirement;
 use uv_fs::Normalized;
 use uv_interpreter::{Interpreter, Virtualenv};
-use uv_traits::{BuildContext, BuildKind, ConfigSettings, SetupPyStrategy, SourceBuildTrait};
+use uv_traits::{
+    BuildContext, BuildIsolation, BuildKind, Concurrency, ConfigSettings, SetupPyStrategy,
+    SourceBuildTrait,
+};
+use uv_warnings::warn_user;
 
 /// e.g. `pygraphviz/graphviz_wrap.c:3020:10: fatal error: graphviz/cgraph.h: No such file or directory`
 static MISSING_HEADER_RE: Lazy<Regex> = Lazy::new(|| {
@@ -297,11 +301,32 @@ impl Pep517Backend {
 }
 
 /// Uses an [`Arc`] internally, clone freely
-#[derive(Debug, Default, Clone)]
+#[derive(Debug, Clone)]
 pub struct SourceBuildContext {
     /// Cache the first resolution of `pip`, `setuptools` and `wheel` we made for setup.py (and
     /// some PEP 517) builds so we can reuse it.
     setup_py_resolution: Arc<Mutex<Option<Resolution>>>,
+    /// Limits the number of build backend invocations running at once.
+    ///
+    /// A permit is only held while a build backend hook runs, not while the build environment is
+    /// set up, since installing the build requirements may itself require (nested) builds.
+    build_permits: Arc<Semaphore>,
+}
+
+impl SourceBuildContext {
+    /// Create a context that runs at most `concurrent_builds` build backend hooks at once.
+    pub fn new(concurrent_builds: usize) -> Self {
+        Self {
+            setup_py_resolution: Arc::default(),
+            build_permits: Arc::new(Semaphore::new(concurrent_builds.max(1))),
+        }
+    }
+}
+
+impl Default for SourceBuildContext {
+    fn default() -> Self {
+        Self::new(Concurrency::default_builds())
+    }
 }
 
 /// Holds the state through a series of PEP 517 frontend to backend calls or a single setup.py
@@ -331,6 +356,8 @@ pub struct SourceBuild {
     package_id: String,
     /// Whether we do a regular PEP 517 build or an PEP 660 editable build
     build_kind: BuildKind,
+    /// Limits the number of build backend invocations running at once.
+    build_permits: Arc<Semaphore>,
 }
 
 impl SourceBuild {
@@ -389,41 +416,73 @@ impl SourceBuild {
         let default_backend: Pep517Backend = DEFAULT_BACKEND.clone();
 
         // Check if we have a PEP 517 build backend.
-        let pep517_backend = Self::get_pep517_backend(setup_py, &source_tree, &default_backend)
-            .map_err(|err| *err)?;
-
-        let venv = gourgeist::create_venv(
-            &temp_dir.path().join(".venv"),
-            interpreter.clone(),
-            gourgeist::Prompt::None,
-            Vec::new(),
-        )?;
-
-        // Setup the build environment.
-        let resolved_requirements = Self::get_resolved_requirements(
-            build_context,
-            source_build_context,
-            &default_backend,
-            pep517_backend.as_ref(),
-        )
-        .await?;
+        let pep517_backend =
+            Self::get_pep517_backend(setup_py, &source_tree, &default_backend, build_kind)
+                .map_err(|err| *err)?;
+
+        // Report the build strategy, since a legacy `setup.py` build may have been selected
+        // automatically.
+        match &pep517_backend {
+            Some(pep517_backend) => debug!(
+                "Building {package_id} with PEP 517 backend `{}`",
+                pep517_backend.backend
+            ),
+            None if setup_py == SetupPyStrategy::Pep517 => {
+                warn_user!(
+                    "`{package_id}` uses `distutils` without `setuptools`; building with `setup.py bdist_wheel` instead of a PEP 517 backend"
+                );
+            }
+            None => debug!("Building {package_id} with `setup.py bdist_wheel`"),
+        }
 
-        build_context
-            .install(&resolved_requirements, &venv)
-            .await
-            .map_err(|err| Error::RequirementsInstall("build-system.requires (install)", err))?;
+        // Create a virtual environment, or use the shared environment if build isolation is
+        // disabled.
+        let venv = match build_context.build_isolation() {
+            BuildIsolation::Isolated => gourgeist::create_venv(
+                &temp_dir.path().join(".venv"),
+                interpreter.clone(),
+                gourgeist::Prompt::None,
+                false,
+                gourgeist::OnExisting::Fail,
+                gourgeist::InterpreterLink::default(),
+                Vec::new(),
+            )?,
+            BuildIsolation::Shared(venv) => venv.clone(),
+        };
+
+        let build_permits = source_build_context.build_permits.clone();
 
-        if let Some(pep517_backend) = &pep517_backend {
-            create_pep517_build_environment(
-                &source_tree,
-                &venv,
-                pep517_backend,
+        // Setup the build environment. If build isolation is disabled, we assume the build
+        // requirements are already installed.
+        if build_context.build_isolation().is_isolated() {
+            let resolved_requirements = Self::get_resolved_requirements(
                 build_context,
-                &package_id,
-                build_kind,
-                &config_settings,
+                source_build_context,
+                &default_backend,
+                pep517_backend.as_ref(),
             )
             .await?;
+
+            build_context
+                .install(&resolved_requirements, &venv)
+                .await
+                .map_err(|err| {
+                    Error::RequirementsInstall("build-system.requires (install)", err)
+                })?;
+
+            if let Some(pep517_backend) = &pep517_backend {
+                create_pep517_build_environment(
+                    &source_tree,
+                    &venv,
+                    pep517_backend,
+                    build_context,
+                    &package_id,
+                    build_kind,
+                    &config_settings,
+                    &build_permits,
+                )
+                .await?;
+            }
         }
 
         Ok(Self {
@@ -435,6 +494,7 @@ impl SourceBuild {
             config_settings,
             metadata_directory: None,
             package_id,
+            build_permits,
         })
     }
 
@@ -487,6 +547,7 @@ impl SourceBuild {
         setup_py: SetupPyStrategy,
         source_tree: &Path,
         default_backend: &Pep517Backend,
+        build_kind: BuildKind,
     ) -> Result<Option<Pep517Backend>, Box<Error>> {
         match fs::read_to_string(source_tree.join("pyproject.toml")) {
             Ok(toml) => {
@@ -527,8 +588,21 @@ impl SourceBuild {
                 // case (which we allow via `SetupPyStrategy::Setuptools`), but plans to make PEP
                 // 517 builds the default in the future.
                 // See: https://github.com/pypa/pip/issues/9175.
+                //
+                // Projects that rely on `distutils` alone predate setuptools' PEP 517 support, so
+                // we fall back to invoking `setup.py` directly for those (except for editable
+                // builds, which require PEP 660).
                 match setup_py {
-                    SetupPyStrategy::Pep517 => Ok(Some(default_backend.clone())),
+                    SetupPyStrategy::Pep517 => {
+                        if build_kind == BuildKind::Wheel {
+                            let setup_py = fs::read_to_string(source_tree.join("setup.py"))
+                                .map_err(Error::from)?;
+                            if requires_legacy_setup_py(&setup_py) {
+                                return Ok(None);
+                            }
+                        }
+                        Ok(Some(default_backend.clone()))
+                    }
                     SetupPyStrategy::Setuptools => Ok(None),
                 }
             }
@@ -551,36 +625,43 @@ impl SourceBuild {
         let metadata_directory = self.temp_dir.path().join("metadata_directory");
         fs::create_dir(&metadata_directory)?;
 
+        // For editable builds, the metadata must come from the PEP 660 hook, since the backend
+        // may produce different metadata (e.g., additional dependencies) for editable wheels.
         debug!(
-            "Calling `{}.prepare_metadata_for_build_wheel()`",
-            pep517_backend.backend
+            "Calling `{}.prepare_metadata_for_build_{}()`",
+            pep517_backend.backend, self.build_kind
         );
         let script = formatdoc! {
             r#"
             {}
             import json
 
-            prepare_metadata_for_build_wheel = getattr(backend, "prepare_metadata_for_build_wheel", None)
-            if prepare_metadata_for_build_wheel:
-                print(prepare_metadata_for_build_wheel("{}", config_settings={}))
+            prepare_metadata_for_build = getattr(backend, "prepare_metadata_for_build_{}", None)
+            if prepare_metadata_for_build:
+                print(prepare_metadata_for_build("{}", config_settings={}))
             else:
                 print()
             "#,
             pep517_backend.backend_import(),
+            self.build_kind,
             escape_path_for_python(&metadata_directory),
             self.config_settings.escape_for_python(),
         };
         let span = info_span!(
             "run_python_script",
-            script="prepare_metadata_for_build_wheel",
+            script=format!("prepare_metadata_for_build_{}", self.build_kind),
             python_version = %self.venv.interpreter().python_version()
         );
+        let _permit = self.acquire_build_permit().await;
         let output = run_python_script(&self.venv, &script, &self.source_tree)
             .instrument(span)
             .await?;
         if !output.status.success() {
             return Err(Error::from_command_output(
-                "Build backend failed to determine metadata through `prepare_metadata_for_build_wheel`".to_string(),
+                format!(
+                    "Build backend failed to determine metadata through `prepare_metadata_for_build_{}`",
+                    self.build_kind
+                ),
                 &output,
                 &self.package_id,
             ));
@@ -595,7 +676,8 @@ impl SourceBuild {
             .map_err(|err| {
                 Error::from_command_output(
                     format!(
-                        "Build backend failed to return metadata directory with `prepare_metadata_for_build_wheel`: {err}"
+                        "Build backend failed to return metadata directory with `prepare_metadata_for_build_{}`: {err}",
+                        self.build_kind
                     ),
                     &output,
                     &self.package_id,
@@ -620,6 +702,8 @@ impl SourceBuild {
         // The build scripts run with the extracted root as cwd, so they need the absolute path.
         let wheel_dir = fs::canonicalize(wheel_dir)?;
 
+        let _permit = self.acquire_build_permit().await;
+
         if let Some(pep517_backend) = &self.pep517_backend {
             // Prevent clashes from two uv processes building wheels in parallel.
             let tmp_dir = tempdir_in(&wheel_dir)?;
@@ -674,6 +758,14 @@ impl SourceBuild {
         }
     }
 
+    /// Wait until fewer than the maximum number of concurrent build backend hooks are running.
+    async fn acquire_build_permit(&self) -> tokio::sync::SemaphorePermit<'_> {
+        self.build_permits
+            .acquire()
+            .await
+            .expect("the build semaphore is never closed")
+    }
+
     async fn pep517_build(
         &self,
         wheel_dir: &Path,
@@ -690,14 +782,21 @@ impl SourceBuild {
             pep517_backend.backend, self.build_kind, metadata_directory
         );
         let escaped_wheel_dir = escape_path_for_python(wheel_dir);
+        // The `build_editable` hook is optional (PEP 660), so fail with a clear message if the
+        // backend doesn't support editable installs.
         let script = formatdoc! {
             r#"
             {}
 
-            print(backend.build_{}("{}", metadata_directory={}, config_settings={}))
+            build = getattr(backend, "build_{}", None)
+            if build is None:
+                raise SystemExit("Build backend `{}` does not define a `build_{}` hook")
+            print(build("{}", metadata_directory={}, config_settings={}))
             "#,
             pep517_backend.backend_import(),
             self.build_kind,
+            pep517_backend.backend,
+            self.build_kind,
             escaped_wheel_dir,
             metadata_directory,
             self.config_settings.escape_for_python()
@@ -755,6 +854,7 @@ fn escape_path_for_python(path: &Path) -> String {
 }
 
 /// Not a method because we call it before the builder is completely initialized
+#[allow(clippy::too_many_arguments)]
 async fn create_pep517_build_environment(
     source_tree: &Path,
     venv: &Virtualenv,
@@ -763,6 +863,7 @@ async fn create_pep517_build_environment(
     package_id: &str,
     build_kind: BuildKind,
     config_settings: &ConfigSettings,
+    build_permits: &Semaphore,
 ) -> Result<(), Error> {
     debug!(
         "Calling `{}.get_requires_for_build_{}()`",
@@ -786,9 +887,15 @@ async fn create_pep517_build_environment(
         script=format!("get_requires_for_build_{}", build_kind),
         python_version = %venv.interpreter().python_version()
     );
-    let output = run_python_script(venv, &script, source_tree)
-        .instrument(span)
-        .await?;
+    let output = {
+        let _permit = build_permits
+            .acquire()
+            .await
+            .expect("the build semaphore is never closed");
+        run_python_script(venv, &script, source_tree)
+            .instrument(span)
+            .await?
+    };
     if !output.status.success() {
         return Err(Error::from_command_output(
             format!("Build backend failed to determine extra requires with `build_{build_kind}()`"),
@@ -868,13 +975,31 @@ async fn run_python_script(
         .map_err(|err| Error::CommandFailed(venv.python_executable(), err))
 }
 
+/// Returns `true` if a `setup.py` imports `distutils` without ever importing `setuptools`, in which
+/// case it should be invoked directly rather than through the setuptools PEP 517 backend.
+fn requires_legacy_setup_py(contents: &str) -> bool {
+    let imports = |module: &str| {
+        contents.lines().any(|line| {
+            let line = line.trim_start();
+            line.strip_prefix("import ")
+                .or_else(|| line.strip_prefix("from "))
+                .is_some_and(|rest| {
+                    rest.trim_start()
+                        .strip_prefix(module)
+                        .is_some_and(|rest| rest.is_empty() || rest.starts_with(['.', ' ', ',']))
+                })
+        })
+    };
+    imports("distutils") && !imports("setuptools")
+}
+
 #[cfg(test)]
 mod test {
     use std::process::{ExitStatus, Output};
 
     use indoc::indoc;
 
-    use crate::Error;
+    use crate::{requires_legacy_setup_py, Error};
 
     #[test]
     fn missing_header() {
@@ -975,4 +1100,34 @@ mod test {
             @"This error likely indicates that you need to install the library that provides a shared library for ncurses for pygraphviz-1.11 (e.g. libncurses-dev)"
         );
     }
+
+    #[test]
+    fn legacy_setup_py() {
+        assert!(requires_legacy_setup_py(indoc! {r"
+            from distutils.core import setup
+
+            setup(name='example', version='0.1.0')
+        "}));
+        assert!(requires_legacy_setup_py(indoc! {r"
+            import distutils.core
+
+            distutils.core.setup(name='example', version='0.1.0')
+        "}));
+        assert!(!requires_legacy_setup_py(indoc! {r"
+            from setuptools import setup
+            from distutils.command.build import build
+
+            setup(name='example', version='0.1.0', cmdclass={'build': build})
+        "}));
+        assert!(!requires_legacy_setup_py(indoc! {r"
+            from setuptools import setup
+
+            setup(name='example', version='0.1.0')
+        "}));
+        assert!(!requires_legacy_setup_py(indoc! {r"
+            import distutils_extra
+
+            distutils_extra.setup(name='example', version='0.1.0')
+        "}));
+    }
 }
@@ -0,0 +1,84 @@
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use fs_err as fs;
+
+use uv_fs::LockedFile;
+use uv_normalize::PackageName;
+
+/// A manifest recording the last time each package in the cache was referenced by a resolution
+/// (or installation), used to evict least-recently-used entries when pruning the cache.
+///
+/// The manifest is stored at the root of the cache, with one `<package> <seconds>` line per
+/// package, where `<seconds>` is the access time as seconds since the Unix epoch.
+#[derive(Debug, Default)]
+pub(crate) struct AccessManifest(BTreeMap<PackageName, SystemTime>);
+
+impl AccessManifest {
+    /// Read the manifest from the cache at `root`, returning an empty manifest if none exists.
+    ///
+    /// Malformed lines are ignored.
+    pub(crate) fn read(root: &Path) -> io::Result<Self> {
+        let contents = match fs::read_to_string(Self::path(root)) {
+            Ok(contents) => contents,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Self::default()),
+            Err(err) => return Err(err),
+        };
+
+        let mut accessed = BTreeMap::new();
+        for line in contents.lines() {
+            let Some((name, seconds)) = line.split_once(' ') else {
+                continue;
+            };
+            let Ok(name) = PackageName::from_str(name) else {
+                continue;
+            };
+            let Ok(seconds) = seconds.parse::<u64>() else {
+                continue;
+            };
+            accessed.insert(name, UNIX_EPOCH + Duration::from_secs(seconds));
+        }
+        Ok(Self(accessed))
+    }
+
+    /// Write the manifest to the cache at `root`.
+    pub(crate) fn write(&self, root: &Path) -> io::Result<()> {
+        let mut contents = String::new();
+        for (name, accessed) in &self.0 {
+            let seconds = accessed
+                .duration_since(UNIX_EPOCH)
+                .map(|duration| duration.as_secs())
+                .unwrap_or_default();
+            writeln!(contents, "{name} {seconds}").expect("writing to a string to succeed");
+        }
+        uv_fs::write_atomic_sync(Self::path(root), contents)
+    }
+
+    /// Lock the manifest in the cache at `root`, to serialize concurrent read-modify-write cycles.
+    pub(crate) fn lock(root: &Path) -> io::Result<LockedFile> {
+        LockedFile::acquire(root.join(".access.lock"), "cache access manifest")
+    }
+
+    /// Return the last time the given package was accessed, if known.
+    pub(crate) fn get(&self, name: &PackageName) -> Option<SystemTime> {
+        self.0.get(name).copied()
+    }
+
+    /// Record an access of the given package at the given time.
+    pub(crate) fn insert(&mut self, name: PackageName, accessed: SystemTime) {
+        self.0.insert(name, accessed);
+    }
+
+    /// Forget the given package, e.g., after it was evicted from the cache.
+    pub(crate) fn remove(&mut self, name: &PackageName) {
+        self.0.remove(name);
+    }
+
+    fn path(root: &Path) -> PathBuf {
+        root.join("access-v0.txt")
+    }
+}
@@ -0,0 +1,117 @@
+use std::hash::Hasher;
+use std::io;
+use std::path::Path;
+
+use cache_key::{CacheKey, CacheKeyHasher};
+use walkdir::DirEntry;
+
+/// Directories that are excluded when computing the digest of a source tree, at any depth, since
+/// they contain version control metadata, environments, or caches.
+const EXCLUDED_DIRECTORIES: &[&str] = &[
+    ".git",
+    ".hg",
+    ".svn",
+    ".nox",
+    ".tox",
+    ".venv",
+    ".mypy_cache",
+    ".pytest_cache",
+    ".ruff_cache",
+    "__pycache__",
+];
+
+/// Directories that are excluded when computing the digest of a source tree, but only at the top
+/// level, since they contain the artifacts of a previous build. Elsewhere, a directory named, e.g.,
+/// `build` may well be part of the package itself.
+const EXCLUDED_TOP_LEVEL_DIRECTORIES: &[&str] = &["build", "dist"];
+
+/// Compute a digest of the contents of an archive, which could be a file (like a source
+/// distribution archive) or a directory containing a Python package.
+///
+/// For directories, the digest covers the relative path and contents of every file in the tree,
+/// excluding top-level build artifacts (like `build/` and `*.egg-info/`), version control metadata,
+/// and virtual environments, such that building a package in-place doesn't change its digest.
+pub fn archive_digest(path: impl AsRef<Path>) -> Result<String, io::Error> {
+    let path = path.as_ref();
+    let mut hasher = CacheKeyHasher::new();
+
+    if path.is_file() {
+        hasher.write(&fs_err::read(path)?);
+    } else {
+        for entry in walkdir::WalkDir::new(path)
+            .sort_by_file_name()
+            .into_iter()
+            .filter_entry(|entry| entry.depth() == 0 || !is_excluded(entry))
+        {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+
+            let relative = entry
+                .path()
+                .strip_prefix(path)
+                .expect("walkdir entries are within the root");
+            relative
+                .components()
+                .map(|component| component.as_os_str().to_string_lossy().into_owned())
+                .collect::<Vec<_>>()
+                .cache_key(&mut hasher);
+
+            let contents = fs_err::read(entry.path())?;
+            hasher.write_usize(contents.len());
+            hasher.write(&contents);
+        }
+    }
+
+    Ok(cache_key::digest(&hasher.finish()))
+}
+
+/// Returns `true` if the directory entry should be excluded from the digest.
+fn is_excluded(entry: &DirEntry) -> bool {
+    if !entry.file_type().is_dir() {
+        return false;
+    }
+    let name = entry.file_name().to_string_lossy();
+    if entry.depth() == 1
+        && (EXCLUDED_TOP_LEVEL_DIRECTORIES.contains(&name.as_ref()) || name.ends_with(".egg-info"))
+    {
+        return true;
+    }
+    EXCLUDED_DIRECTORIES.contains(&name.as_ref()) || entry.path().join("pyvenv.cfg").is_file()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+
+    use super::archive_digest;
+
+    #[test]
+    fn exclude_top_level_build_artifacts() -> io::Result<()> {
+        let root = tempfile::tempdir()?;
+        fs_err::write(root.path().join("setup.py"), "")?;
+        fs_err::create_dir_all(root.path().join("src").join("build"))?;
+        fs_err::write(
+            root.path().join("src").join("build").join("__init__.py"),
+            "",
+        )?;
+        let digest = archive_digest(root.path())?;
+
+        // Build artifacts at the top level don't affect the digest.
+        fs_err::create_dir_all(root.path().join("build").join("lib"))?;
+        fs_err::write(root.path().join("build").join("lib").join("module.py"), "")?;
+        fs_err::create_dir_all(root.path().join("example.egg-info"))?;
+        fs_err::write(root.path().join("example.egg-info").join("PKG-INFO"), "")?;
+        assert_eq!(archive_digest(root.path())?, digest);
+
+        // But a nested directory with the same name is part of the package.
+        fs_err::write(
+            root.path().join("src").join("build").join("__init__.py"),
+            "x = 1",
+        )?;
+        assert_ne!(archive_digest(root.path())?, digest);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,193 @@
+use std::collections::HashSet;
+use std::io;
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use fs_err as fs;
+use tracing::debug;
+
+use uv_normalize::PackageName;
+
+use crate::{Cache, CacheBucket};
+
+/// A summary of the entries written by [`Cache::export`].
+#[derive(Debug, Default)]
+pub struct Export {
+    /// The number of files written to the archive.
+    pub num_files: u64,
+    /// The requested packages for which the cache contained no entries.
+    pub missing: Vec<PackageName>,
+}
+
+impl Cache {
+    /// Write the cache entries for the given packages to a tar archive.
+    ///
+    /// The archive includes the package's wheels, built wheels, and index responses, along with
+    /// the unzipped archives that its wheels link to. Paths are stored relative to the cache root,
+    /// such that the archive can be imported into a cache at any location via [`Cache::import`].
+    pub fn export<'a, W: Write>(
+        &self,
+        packages: impl IntoIterator<Item = &'a PackageName>,
+        writer: W,
+    ) -> io::Result<Export> {
+        let mut builder = tar::Builder::new(writer);
+        builder.mode(tar::HeaderMode::Deterministic);
+        builder.follow_symlinks(false);
+
+        let mut summary = Export::default();
+        let mut archives = HashSet::new();
+        for package in packages {
+            let entries = self.package_entries(package);
+            if entries.is_empty() {
+                summary.missing.push(package.clone());
+                continue;
+            }
+            for entry in entries {
+                summary.num_files += self.append(&mut builder, &entry, &mut archives)?;
+            }
+        }
+
+        builder.into_inner()?.flush()?;
+        Ok(summary)
+    }
+
+    /// Read cache entries from a tar archive produced by [`Cache::export`], replacing any
+    /// existing entries at the same paths.
+    ///
+    /// Returns the number of files imported.
+    pub fn import<R: Read>(&self, reader: R) -> io::Result<u64> {
+        let mut archive = tar::Archive::new(reader);
+        let mut num_files = 0;
+        for entry in archive.entries()? {
+            let mut entry = entry?;
+            let path = entry.path()?.into_owned();
+            if !is_normal_relative(&path) {
+                return Err(invalid_archive(format!(
+                    "unexpected path: {}",
+                    path.display()
+                )));
+            }
+
+            match entry.header().entry_type() {
+                tar::EntryType::Symlink => {
+                    // Symlinks point into the archive bucket, relative to the cache root.
+                    let target = entry
+                        .link_name()?
+                        .ok_or_else(|| {
+                            invalid_archive(format!("missing link target for {}", path.display()))
+                        })?
+                        .into_owned();
+                    if !is_normal_relative(&target)
+                        || !target.starts_with(CacheBucket::Archive.to_str())
+                    {
+                        return Err(invalid_archive(format!(
+                            "unexpected link target: {}",
+                            target.display()
+                        )));
+                    }
+                    let dst = self.root.join(&path);
+                    fs::create_dir_all(dst.parent().expect("Cache entry to have parent"))?;
+                    uv_fs::replace_symlink(self.root.join(target), dst)?;
+                }
+                tar::EntryType::Directory => {
+                    entry.unpack_in(&self.root)?;
+                }
+                tar::EntryType::Regular => {
+                    entry.unpack_in(&self.root)?;
+                    num_files += 1;
+                }
+                entry_type => {
+                    debug!(
+                        "Skipping unsupported entry type in cache archive ({entry_type:?}): {}",
+                        path.display()
+                    );
+                }
+            }
+        }
+        Ok(num_files)
+    }
+
+    /// Return the paths of the existing cache entries for the given package.
+    fn package_entries(&self, name: &PackageName) -> Vec<PathBuf> {
+        let mut entries = Vec::new();
+        for bucket in [
+            CacheBucket::Wheels,
+            CacheBucket::BuiltWheels,
+            CacheBucket::Simple,
+        ] {
+            for directory in bucket.package_directories(self) {
+                let path = match bucket {
+                    CacheBucket::Simple => directory.join(format!("{name}.rkyv")),
+                    _ => directory.join(name.to_string()),
+                };
+                if path.exists() {
+                    entries.push(path);
+                }
+            }
+        }
+        entries
+    }
+
+    /// Append the cache entry at `path` to the archive, along with any unzipped archives that the
+    /// symlinks within it point to.
+    ///
+    /// Returns the number of files appended.
+    fn append<W: Write>(
+        &self,
+        builder: &mut tar::Builder<W>,
+        path: &Path,
+        archives: &mut HashSet<PathBuf>,
+    ) -> io::Result<u64> {
+        let archive_bucket = self.bucket(CacheBucket::Archive);
+        let mut num_files = 0;
+        for entry in walkdir::WalkDir::new(path).sort_by_file_name() {
+            let entry = entry?;
+            let relative = entry
+                .path()
+                .strip_prefix(&self.root)
+                .expect("Cache entry to be within the cache root");
+
+            if entry.path_is_symlink() {
+                let Ok(target) = fs::canonicalize(entry.path()) else {
+                    continue;
+                };
+                let Ok(target_relative) = target.strip_prefix(&self.root) else {
+                    continue;
+                };
+                if !target.starts_with(&archive_bucket) {
+                    continue;
+                }
+
+                // Write the archive before the link, such that the link target exists on import.
+                if archives.insert(target.clone()) {
+                    num_files += self.append(builder, &target, archives)?;
+                }
+
+                let mut header = tar::Header::new_gnu();
+                header.set_entry_type(tar::EntryType::Symlink);
+                header.set_size(0);
+                header.set_mode(0o777);
+                builder.append_link(&mut header, relative, target_relative)?;
+            } else if entry.file_type().is_dir() {
+                builder.append_dir(relative, entry.path())?;
+            } else if entry.file_type().is_file() {
+                builder.append_path_with_name(entry.path(), relative)?;
+                num_files += 1;
+            }
+        }
+        Ok(num_files)
+    }
+}
+
+/// Returns `true` if the path is relative and contains no `..`, `.`, or root components.
+fn is_normal_relative(path: &Path) -> bool {
+    path.components()
+        .all(|component| matches!(component, Component::Normal(_)))
+}
+
+fn invalid_archive(message: String) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("Invalid cache archive: {message}"),
+    )
+}
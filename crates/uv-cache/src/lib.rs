@@ -1,31 +1,52 @@
 use std::cmp::max;
+use std::collections::BTreeMap;
 use std::fmt::{Display, Formatter};
 use std::io;
 use std::io::Write;
 use std::ops::Deref;
 use std::path::{Path, PathBuf};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
 use fs_err as fs;
 use tempfile::{tempdir, TempDir};
+use tracing::{debug, warn};
 
-use uv_fs::directories;
+use uv_fs::{directories, Normalized};
 use uv_normalize::PackageName;
 
+use crate::access::AccessManifest;
 pub use crate::by_timestamp::CachedByTimestamp;
 #[cfg(feature = "clap")]
 pub use crate::cli::CacheArgs;
-use crate::removal::{rm_rf, Removal};
+pub use crate::digest::archive_digest;
+pub use crate::export::Export;
+pub use crate::removal::Removal;
+use crate::removal::{rm_rf, rm_rf_dry_run};
 pub use crate::timestamp::Timestamp;
 pub use crate::wheel::WheelCache;
 use crate::wheel::WheelCacheKind;
 
+mod access;
 mod by_timestamp;
 mod cli;
+mod digest;
+mod export;
 mod removal;
 mod timestamp;
 mod wheel;
 
+/// The version of the cache layout as a whole.
+///
+/// Increment this whenever the version of any [`CacheBucket`] changes, such that caches written by
+/// older versions of uv are migrated (see [`Cache::migrate`]) when they're next opened.
+const CACHE_VERSION: u32 = 1;
+
+/// The window within which a package is considered to be in use, such that it's preserved by
+/// [`Cache::prune`] regardless of the size of the cache.
+pub const RECENTLY_USED: Duration = Duration::from_secs(24 * 60 * 60);
+
 /// A [`CacheEntry`] which may or may not exist yet.
 #[derive(Debug, Clone)]
 pub struct CacheEntry(PathBuf);
@@ -114,11 +135,15 @@ pub struct Cache {
 impl Cache {
     /// A persistent cache directory at `root`.
     pub fn from_path(root: impl Into<PathBuf>) -> Result<Self, io::Error> {
-        Ok(Self {
+        let cache = Self {
             root: Self::init(root)?,
             refresh: Refresh::None,
             _temp_dir_drop: None,
-        })
+        };
+        if let Err(err) = cache.migrate() {
+            warn!("Failed to migrate cache: {err}");
+        }
+        Ok(cache)
     }
 
     /// Create a temporary cache directory.
@@ -203,20 +228,42 @@ impl Cache {
         }
     }
 
-    /// Persist a temporary directory to the artifact store.
+    /// Persist a temporary directory to the artifact store, and link it into the cache at `path`.
+    ///
+    /// If the SHA-256 digest of the artifact from which the directory was unpacked is known, the
+    /// directory is stored under that digest, such that an artifact that's referenced from multiple
+    /// locations (e.g., the same wheel on multiple indexes) is only stored once. If the store
+    /// already contains the artifact, the temporary directory is discarded in favor of the existing
+    /// copy.
     pub fn persist(
         &self,
         temp_dir: impl AsRef<Path>,
         path: impl AsRef<Path>,
+        sha256: Option<&str>,
     ) -> io::Result<PathBuf> {
-        // Create a unique ID for the artifact.
-        // TODO(charlie): Support content-addressed persistence via SHAs.
-        let id = nanoid::nanoid!();
+        // Identify the artifact by its digest or, if unknown, a unique ID.
+        let id = match sha256 {
+            Some(sha256) => sha256.to_ascii_lowercase(),
+            None => nanoid::nanoid!(),
+        };
 
         // Move the temporary directory into the directory store.
         let archive_entry = self.entry(CacheBucket::Archive, "", id);
         fs_err::create_dir_all(archive_entry.dir())?;
-        fs_err::rename(temp_dir.as_ref(), archive_entry.path())?;
+        if sha256.is_some() && archive_entry.path().is_dir() {
+            debug!(
+                "Reusing stored artifact: {}",
+                archive_entry.path().normalized_display()
+            );
+            fs_err::remove_dir_all(temp_dir.as_ref())?;
+        } else if let Err(err) = fs_err::rename(temp_dir.as_ref(), archive_entry.path()) {
+            // Another process may have stored the same artifact in the interim.
+            if sha256.is_some() && archive_entry.path().is_dir() {
+                fs_err::remove_dir_all(temp_dir.as_ref())?;
+            } else {
+                return Err(err);
+            }
+        }
 
         // Create a symlink to the directory store.
         fs_err::create_dir_all(path.as_ref().parent().expect("Cache entry to have parent"))?;
@@ -258,15 +305,197 @@ impl Cache {
         fs::canonicalize(root)
     }
 
+    /// Migrate a cache written by an older version of uv, removing any buckets that have since
+    /// been superseded by a newer version (e.g., `simple-v3` once `simple-v4` is in use), along
+    /// with the unzipped archives they link to.
+    ///
+    /// Buckets from newer versions are left untouched, so that downgrading uv doesn't discard
+    /// entries that the newer version can still use.
+    fn migrate(&self) -> io::Result<()> {
+        let version_file = self.root.join(".version");
+        let version = match fs::read_to_string(&version_file) {
+            Ok(version) => version.trim().parse::<u32>().unwrap_or_default(),
+            Err(err) if err.kind() == io::ErrorKind::NotFound => 0,
+            Err(err) => return Err(err),
+        };
+        if version >= CACHE_VERSION {
+            return Ok(());
+        }
+
+        for entry in fs::read_dir(&self.root)? {
+            let entry = entry?;
+            if !entry.file_type()?.is_dir() {
+                continue;
+            }
+            let file_name = entry.file_name();
+            let Some((prefix, version)) = file_name.to_str().and_then(split_version) else {
+                continue;
+            };
+            let Some(current) = CacheBucket::iter().find_map(|bucket| {
+                split_version(bucket.to_str())
+                    .filter(|(name, _)| *name == prefix)
+                    .map(|(_, version)| version)
+            }) else {
+                continue;
+            };
+            if version < current {
+                debug!(
+                    "Removing superseded cache bucket: {}",
+                    entry.path().normalized_display()
+                );
+                rm_rf_with_archives(self, &entry.path(), false)?;
+            }
+        }
+
+        uv_fs::write_atomic_sync(version_file, CACHE_VERSION.to_string())
+    }
+
     /// Clear the cache, removing all entries.
     pub fn clear(&self) -> Result<Removal, io::Error> {
         rm_rf(&self.root)
     }
 
+    /// Summarize the entries that [`Cache::clear`] would remove, without removing them.
+    pub fn clear_dry_run(&self) -> Result<Removal, io::Error> {
+        rm_rf_dry_run(&self.root)
+    }
+
+    /// Summarize the files in the given cache bucket, i.e., those that removing it would free.
+    pub fn usage(&self, bucket: CacheBucket) -> Result<Removal, io::Error> {
+        rm_rf_dry_run(self.bucket(bucket))
+    }
+
+    /// Record that the given packages were referenced by a resolution or installation, such that
+    /// [`Cache::prune`] treats their entries as recently used.
+    ///
+    /// Failures are logged rather than surfaced, since access tracking is best-effort.
+    pub fn touch<'a>(&self, packages: impl IntoIterator<Item = &'a PackageName>) {
+        let touch = || -> io::Result<()> {
+            let _lock = AccessManifest::lock(&self.root)?;
+            let mut manifest = AccessManifest::read(&self.root)?;
+            let now = SystemTime::now();
+            for package in packages {
+                manifest.insert(package.clone(), now);
+            }
+            manifest.write(&self.root)
+        };
+        if let Err(err) = touch() {
+            warn!("Failed to record cache access times: {err}");
+        }
+    }
+
+    /// Remove least-recently-used packages from the cache.
+    ///
+    /// Packages that haven't been used for longer than `older_than` are removed. Then, while the
+    /// cache exceeds `max_size` bytes, packages are removed in least-recently-used order, though
+    /// packages used within [`RECENTLY_USED`] are always preserved.
+    ///
+    /// A package's last use is read from the access manifest maintained by [`Cache::touch`],
+    /// falling back to the modification time of its cache entries.
+    ///
+    /// Returns the removed packages, along with a summary of the entries removed for each. If
+    /// `dry_run` is set, nothing is removed.
+    pub fn prune(
+        &self,
+        older_than: Option<Duration>,
+        max_size: Option<u64>,
+        dry_run: bool,
+    ) -> Result<Vec<(PackageName, Removal)>, io::Error> {
+        let _lock = AccessManifest::lock(&self.root)?;
+        let mut manifest = AccessManifest::read(&self.root)?;
+
+        // Order the packages in the cache from least- to most-recently used.
+        let mut packages = self
+            .packages()?
+            .into_iter()
+            .map(|(name, modified)| {
+                let accessed = manifest.get(&name).unwrap_or(modified);
+                (name, accessed)
+            })
+            .collect::<Vec<_>>();
+        packages.sort_by_key(|(_, accessed)| *accessed);
+
+        let mut size = match max_size {
+            Some(_) => rm_rf_dry_run(&self.root)?.total_bytes,
+            None => 0,
+        };
+
+        let now = SystemTime::now();
+        let mut pruned = Vec::new();
+        for (name, accessed) in packages {
+            let age = now.duration_since(accessed).unwrap_or_default();
+            let expired = older_than.is_some_and(|older_than| age > older_than);
+            let oversized = max_size.is_some_and(|max_size| size > max_size) && age > RECENTLY_USED;
+
+            // Since packages are ordered by age, no remaining package can be eligible either.
+            if !expired && !oversized {
+                break;
+            }
+
+            let summary = self.remove_impl(&name, dry_run)?;
+            size = size.saturating_sub(summary.total_bytes);
+            pruned.push((name, summary));
+        }
+
+        if !dry_run && !pruned.is_empty() {
+            for (name, _) in &pruned {
+                manifest.remove(name);
+            }
+            manifest.write(&self.root)?;
+        }
+
+        Ok(pruned)
+    }
+
+    /// Return the packages with entries in the cache, along with the most recent modification
+    /// time across those entries.
+    fn packages(&self) -> Result<BTreeMap<PackageName, SystemTime>, io::Error> {
+        let mut packages = BTreeMap::new();
+        for bucket in [
+            CacheBucket::Wheels,
+            CacheBucket::BuiltWheels,
+            CacheBucket::Simple,
+        ] {
+            for directory in bucket.package_directories(self) {
+                let entries = match fs::read_dir(&directory) {
+                    Ok(entries) => entries,
+                    Err(err) if err.kind() == io::ErrorKind::NotFound => continue,
+                    Err(err) => return Err(err),
+                };
+                for entry in entries {
+                    let entry = entry?;
+                    let file_name = entry.file_name();
+                    let Some(file_name) = file_name.to_str() else {
+                        continue;
+                    };
+                    let name = file_name.strip_suffix(".rkyv").unwrap_or(file_name);
+                    let Ok(name) = PackageName::from_str(name) else {
+                        continue;
+                    };
+                    let modified = entry.metadata()?.modified()?;
+                    packages
+                        .entry(name)
+                        .and_modify(|accessed| *accessed = max(*accessed, modified))
+                        .or_insert(modified);
+                }
+            }
+        }
+        Ok(packages)
+    }
+
     /// Remove a package from the cache.
     ///
     /// Returns the number of entries removed from the cache.
     pub fn remove(&self, name: &PackageName) -> Result<Removal, io::Error> {
+        self.remove_impl(name, false)
+    }
+
+    /// Summarize the entries that [`Cache::remove`] would remove, without removing them.
+    pub fn remove_dry_run(&self, name: &PackageName) -> Result<Removal, io::Error> {
+        self.remove_impl(name, true)
+    }
+
+    fn remove_impl(&self, name: &PackageName, dry_run: bool) -> Result<Removal, io::Error> {
         let mut summary = Removal::default();
         for bucket in [
             CacheBucket::Wheels,
@@ -275,7 +504,7 @@ impl Cache {
             CacheBucket::Interpreter,
             CacheBucket::Simple,
         ] {
-            summary += bucket.remove(self, name)?;
+            summary += bucket.remove(self, name, dry_run)?;
         }
         Ok(summary)
     }
@@ -454,7 +683,7 @@ pub enum CacheBucket {
     /// Flat index responses, a format very similar to the simple metadata API.
     ///
     /// Cache structure:
-    ///  * `flat-index-v0/index/<digest(flat_index_url)>.msgpack`
+    ///  * `flat-index-v1/index/<digest(flat_index_url)>.msgpack`
     ///
     /// The response is stored as `Vec<File>`.
     FlatIndex,
@@ -466,16 +695,18 @@ pub enum CacheBucket {
     /// without the shim itself changing, we only cache when the path equals `sys.executable`, i.e.
     /// the path we're running is the python executable itself and not a shim.
     ///
-    /// Cache structure: `interpreter-v0/<digest(path)>.msgpack`
+    /// Cache structure: `interpreter-v1/<digest(path)>.msgpack`
     ///
     /// # Example
     ///
-    /// The contents of each of the MsgPack files has a timestamp field in unix time, the [PEP 508]
-    /// markers and some information from the `sys`/`sysconfig` modules.
+    /// The contents of each of the MsgPack files has a timestamp field in unix time and the size of
+    /// the executable in bytes (both used to invalidate the entry when the executable changes), the
+    /// [PEP 508] markers and some information from the `sys`/`sysconfig` modules.
     ///
     /// ```json
     /// {
     ///   "timestamp": 1698047994491,
+    ///   "size": 17240,
     ///   "data": {
     ///     "markers": {
     ///       "implementation_name": "cpython",
@@ -492,7 +723,9 @@ pub enum CacheBucket {
     ///     },
     ///     "base_exec_prefix": "/home/ferris/.pyenv/versions/3.12.0",
     ///     "base_prefix": "/home/ferris/.pyenv/versions/3.12.0",
-    ///     "sys_executable": "/home/ferris/projects/uv/.venv/bin/python"
+    ///     "stdlib": "/home/ferris/.pyenv/versions/3.12.0/lib/python3.12",
+    ///     "sys_executable": "/home/ferris/projects/uv/.venv/bin/python",
+    ///     "gil_disabled": false
     ///   }
     /// }
     /// ```
@@ -513,98 +746,81 @@ pub enum CacheBucket {
     /// that cache entries can be atomically replaced and removed, as storing directories in the
     /// other buckets directly would make atomic operations impossible.
     Archive,
+    /// Ephemeral virtual environments, e.g., for running scripts with inline metadata (PEP 723).
+    ///
+    /// Cache structure:
+    ///  * `environments-v0/<digest(interpreter, requirements)>/`
+    ///
+    /// Each environment is keyed by the interpreter it was created from and the requirements
+    /// installed into it, such that it can be reused across invocations.
+    Environments,
 }
 
 impl CacheBucket {
+    /// Return an iterator over all cache buckets.
+    pub fn iter() -> impl Iterator<Item = Self> {
+        [
+            Self::Wheels,
+            Self::BuiltWheels,
+            Self::FlatIndex,
+            Self::Git,
+            Self::Interpreter,
+            Self::Simple,
+            Self::Archive,
+            Self::Environments,
+        ]
+        .into_iter()
+    }
+
     fn to_str(self) -> &'static str {
         match self {
             Self::BuiltWheels => "built-wheels-v0",
-            Self::FlatIndex => "flat-index-v0",
+            Self::FlatIndex => "flat-index-v1",
             Self::Git => "git-v0",
-            Self::Interpreter => "interpreter-v0",
-            Self::Simple => "simple-v3",
+            Self::Interpreter => "interpreter-v1",
+            Self::Simple => "simple-v4",
             Self::Wheels => "wheels-v0",
             Self::Archive => "archive-v0",
+            Self::Environments => "environments-v0",
         }
     }
 
     /// Remove a package from the cache bucket.
     ///
     /// Returns the number of entries removed from the cache.
-    fn remove(self, cache: &Cache, name: &PackageName) -> Result<Removal, io::Error> {
+    ///
+    /// For wheels, the unzipped archives that the removed entries link to are removed as well.
+    fn remove(
+        self,
+        cache: &Cache,
+        name: &PackageName,
+        dry_run: bool,
+    ) -> Result<Removal, io::Error> {
+        let rm = |path: PathBuf| {
+            if dry_run {
+                rm_rf_dry_run(path)
+            } else {
+                rm_rf(path)
+            }
+        };
+        let rm_linked = |path: PathBuf| rm_rf_with_archives(cache, &path, dry_run);
         let mut summary = Removal::default();
         match self {
-            Self::Wheels => {
-                // For `pypi` wheels, we expect a directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Pypi);
-                summary += rm_rf(root.join(name.to_string()))?;
-
-                // For alternate indices, we expect a directory for every index, followed by a
-                // directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Index);
-                for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
-                }
-
-                // For direct URLs, we expect a directory for every URL, followed by a
-                // directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Url);
-                for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
-                }
-            }
-            Self::BuiltWheels => {
-                // For `pypi` wheels, we expect a directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Pypi);
-                summary += rm_rf(root.join(name.to_string()))?;
-
-                // For alternate indices, we expect a directory for every index, followed by a
-                // directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Index);
-                for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
-                }
-
-                // For direct URLs, we expect a directory for every index, followed by a
-                // directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Url);
-                for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
-                }
-
-                // For local dependencies, we expect a directory for every path, followed by a
-                // directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Path);
-                for directory in directories(root) {
-                    summary += rm_rf(directory.join(name.to_string()))?;
-                }
-
-                // For Git dependencies, we expect a directory for every repository, followed by a
-                // directory for every SHA, followed by a directory per package (indexed by name).
-                let root = cache.bucket(self).join(WheelCacheKind::Git);
-                for directory in directories(root) {
-                    for directory in directories(directory) {
-                        summary += rm_rf(directory.join(name.to_string()))?;
-                    }
+            Self::Wheels | Self::BuiltWheels => {
+                for directory in self.package_directories(cache) {
+                    summary += rm_linked(directory.join(name.to_string()))?;
                 }
             }
             Self::Simple => {
-                // For `pypi` wheels, we expect a rkyv file per package, indexed by name.
-                let root = cache.bucket(self).join(WheelCacheKind::Pypi);
-                summary += rm_rf(root.join(format!("{name}.rkyv")))?;
-
-                // For alternate indices, we expect a directory for every index, followed by a
-                // MsgPack file per package, indexed by name.
-                let root = cache.bucket(self).join(WheelCacheKind::Url);
-                for directory in directories(root) {
-                    summary += rm_rf(directory.join(format!("{name}.rkyv")))?;
+                for directory in self.package_directories(cache) {
+                    summary += rm(directory.join(format!("{name}.rkyv")))?;
                 }
             }
             Self::FlatIndex => {
                 // We can't know if the flat index includes a package, so we just remove the entire
                 // cache entry.
                 let root = cache.bucket(self);
-                summary += rm_rf(root)?;
+                summary += rm(root)?;
             }
             Self::Git => {
                 // Nothing to do.
@@ -615,11 +831,103 @@ impl CacheBucket {
             Self::Archive => {
                 // Nothing to do.
             }
+            Self::Environments => {
+                // Nothing to do.
+            }
         }
         Ok(summary)
     }
 }
 
+impl CacheBucket {
+    /// Return the directories in this bucket that contain an entry per package, indexed by name.
+    ///
+    /// Buckets that aren't organized by package return no directories.
+    fn package_directories(self, cache: &Cache) -> Vec<PathBuf> {
+        let bucket = cache.bucket(self);
+        match self {
+            Self::Wheels => {
+                // For `pypi` wheels, we expect a directory per package (indexed by name).
+                std::iter::once(bucket.join(WheelCacheKind::Pypi))
+                    // For alternate indices, we expect a directory for every index, followed by a
+                    // directory per package (indexed by name).
+                    .chain(directories(bucket.join(WheelCacheKind::Index)))
+                    // For direct URLs, we expect a directory for every URL, followed by a
+                    // directory per package (indexed by name).
+                    .chain(directories(bucket.join(WheelCacheKind::Url)))
+                    .collect()
+            }
+            Self::BuiltWheels => {
+                // For `pypi` wheels, we expect a directory per package (indexed by name).
+                std::iter::once(bucket.join(WheelCacheKind::Pypi))
+                    // For alternate indices, we expect a directory for every index, followed by a
+                    // directory per package (indexed by name).
+                    .chain(directories(bucket.join(WheelCacheKind::Index)))
+                    // For direct URLs, we expect a directory for every URL, followed by a
+                    // directory per package (indexed by name).
+                    .chain(directories(bucket.join(WheelCacheKind::Url)))
+                    // For local dependencies, we expect a directory for every path, followed by a
+                    // directory per package (indexed by name).
+                    .chain(directories(bucket.join(WheelCacheKind::Path)))
+                    // For Git dependencies, we expect a directory for every repository, followed
+                    // by a directory for every SHA, followed by a directory per package (indexed
+                    // by name).
+                    .chain(directories(bucket.join(WheelCacheKind::Git)).flat_map(directories))
+                    .collect()
+            }
+            Self::Simple => {
+                // For `pypi` wheels, we expect a rkyv file per package, indexed by name.
+                std::iter::once(bucket.join(WheelCacheKind::Pypi))
+                    // For alternate indices, we expect a directory for every index, followed by a
+                    // rkyv file per package, indexed by name.
+                    .chain(directories(bucket.join(WheelCacheKind::Url)))
+                    .collect()
+            }
+            Self::FlatIndex
+            | Self::Git
+            | Self::Interpreter
+            | Self::Archive
+            | Self::Environments => Vec::new(),
+        }
+    }
+}
+
+/// Remove the given cache entry, along with any archives that the symlinks within it point to.
+fn rm_rf_with_archives(cache: &Cache, path: &Path, dry_run: bool) -> io::Result<Removal> {
+    let archives = cache.bucket(CacheBucket::Archive);
+    let mut summary = Removal::default();
+    for entry in walkdir::WalkDir::new(path)
+        .into_iter()
+        .filter_map(Result::ok)
+    {
+        if !entry.path_is_symlink() {
+            continue;
+        }
+        let Ok(target) = fs::canonicalize(entry.path()) else {
+            continue;
+        };
+        if target.starts_with(&archives) {
+            summary += if dry_run {
+                rm_rf_dry_run(target)?
+            } else {
+                rm_rf(target)?
+            };
+        }
+    }
+    summary += if dry_run {
+        rm_rf_dry_run(path)?
+    } else {
+        rm_rf(path)?
+    };
+    Ok(summary)
+}
+
+/// Split a versioned bucket name (like `simple-v4`) into its name and version.
+fn split_version(bucket: &str) -> Option<(&str, u32)> {
+    let (name, version) = bucket.rsplit_once("-v")?;
+    Some((name, version.parse().ok()?))
+}
+
 impl Display for CacheBucket {
     fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
         f.write_str(self.to_str())
@@ -752,4 +1060,116 @@ impl Refresh {
     pub fn is_none(&self) -> bool {
         matches!(self, Self::None)
     }
+    /// Combine two [`Refresh`] policies, refreshing any entry that either policy would refresh.
+    #[must_use]
+    pub fn combine(self, other: Refresh) -> Self {
+        match (self, other) {
+            (Self::None, refresh) | (refresh, Self::None) => refresh,
+            (Self::All(a), Self::All(b)) => Self::All(a.max(b)),
+            (Self::All(a), Self::Packages(_, b)) | (Self::Packages(_, a), Self::All(b)) => {
+                Self::All(a.max(b))
+            }
+            (Self::Packages(mut packages, a), Self::Packages(other, b)) => {
+                for package in other {
+                    if !packages.contains(&package) {
+                        packages.push(package);
+                    }
+                }
+                Self::Packages(packages, a.max(b))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io;
+    use std::path::PathBuf;
+    use std::str::FromStr;
+
+    use distribution_types::IndexUrl;
+
+    use crate::{Cache, CacheBucket, WheelCache};
+
+    const SHA256: &str = "b8ffb5c8e8a4d6b2bdc2e8c3e3a5b8b3a9e6a1f4e5c6e2a1f0c4f9b4d1e2e1a8";
+
+    /// Unpack a fake wheel into a temporary directory within the cache, with a marker file.
+    fn unpack(cache: &Cache, marker: &str) -> io::Result<tempfile::TempDir> {
+        let temp_dir = tempfile::tempdir_in(cache.root())?;
+        fs_err::write(temp_dir.path().join(marker), "")?;
+        Ok(temp_dir)
+    }
+
+    /// Return the path at which the unzipped wheel from the given index is linked into the cache.
+    fn wheel_entry(cache: &Cache, index: &str) -> PathBuf {
+        let index = IndexUrl::from_str(index).unwrap();
+        cache
+            .entry(
+                CacheBucket::Wheels,
+                WheelCache::Index(&index).remote_wheel_dir("foo"),
+                "foo-1.0.0-py3-none-any",
+            )
+            .into_path_buf()
+    }
+
+    /// The same wheel, fetched from two indexes, is stored in a single archive directory.
+    #[test]
+    fn persist_same_wheel_from_two_indexes() -> io::Result<()> {
+        let cache = Cache::temp()?;
+
+        let first = wheel_entry(&cache, "https://example.com/simple");
+        let second = wheel_entry(&cache, "https://example.org/simple");
+        assert_ne!(first, second);
+
+        let first_archive =
+            cache.persist(unpack(&cache, "a.py")?.into_path(), &first, Some(SHA256))?;
+        let second_archive =
+            cache.persist(unpack(&cache, "b.py")?.into_path(), &second, Some(SHA256))?;
+
+        assert_eq!(first_archive, second_archive);
+        assert_eq!(
+            fs_err::read_dir(cache.bucket(CacheBucket::Archive))?.count(),
+            1
+        );
+        assert_eq!(
+            fs_err::canonicalize(&first)?,
+            fs_err::canonicalize(&second)?
+        );
+
+        Ok(())
+    }
+
+    /// If the archive already exists, the fresh unpack is discarded in favor of the existing copy.
+    #[test]
+    fn persist_existing_archive() -> io::Result<()> {
+        let cache = Cache::temp()?;
+        let entry = wheel_entry(&cache, "https://example.com/simple");
+
+        let archive = cache.persist(unpack(&cache, "a.py")?.into_path(), &entry, Some(SHA256))?;
+
+        let temp_dir = unpack(&cache, "b.py")?.into_path();
+        let reused = cache.persist(&temp_dir, &entry, Some(SHA256))?;
+
+        assert_eq!(archive, reused);
+        assert!(!temp_dir.exists());
+        assert!(entry.join("a.py").is_file());
+        assert!(!entry.join("b.py").exists());
+
+        Ok(())
+    }
+
+    /// Without a digest, each unpack is stored separately.
+    #[test]
+    fn persist_without_digest() -> io::Result<()> {
+        let cache = Cache::temp()?;
+        let entry = wheel_entry(&cache, "https://example.com/simple");
+
+        let first = cache.persist(unpack(&cache, "a.py")?.into_path(), &entry, None)?;
+        let second = cache.persist(unpack(&cache, "b.py")?.into_path(), &entry, None)?;
+
+        assert_ne!(first, second);
+        assert!(entry.join("b.py").is_file());
+
+        Ok(())
+    }
 }
@@ -9,7 +9,15 @@ use std::path::Path;
 /// the number of files and directories removed, along with a total byte count.
 pub(crate) fn rm_rf(path: impl AsRef<Path>) -> io::Result<Removal> {
     let mut removal = Removal::default();
-    removal.rm_rf(path.as_ref())?;
+    removal.rm_rf(path.as_ref(), false)?;
+    Ok(removal)
+}
+
+/// Like [`rm_rf`], but only tally the files and directories that would be removed, without
+/// removing them.
+pub(crate) fn rm_rf_dry_run(path: impl AsRef<Path>) -> io::Result<Removal> {
+    let mut removal = Removal::default();
+    removal.rm_rf(path.as_ref(), true)?;
     Ok(removal)
 }
 
@@ -28,7 +36,9 @@ pub struct Removal {
 
 impl Removal {
     /// Recursively remove a file or directory and all its contents.
-    fn rm_rf(&mut self, path: &Path) -> io::Result<()> {
+    ///
+    /// If `dry_run` is set, the contents are tallied, but not removed.
+    fn rm_rf(&mut self, path: &Path, dry_run: bool) -> io::Result<()> {
         let metadata = match fs_err::symlink_metadata(path) {
             Ok(metadata) => metadata,
             Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(()),
@@ -39,7 +49,9 @@ impl Removal {
             if let Ok(meta) = meta {
                 self.total_bytes += meta.len();
             }
-            remove_file(path)?;
+            if !dry_run {
+                remove_file(path)?;
+            }
 
             Ok(())
         };
@@ -54,14 +66,18 @@ impl Removal {
             if cfg!(windows) && entry.file_type().is_symlink() {
                 // In this branch, we try to handle junction removal.
                 self.num_files += 1;
-                fs_err::remove_dir(entry.path())?;
+                if !dry_run {
+                    fs_err::remove_dir(entry.path())?;
+                }
             } else if entry.file_type().is_dir() {
                 self.num_dirs += 1;
 
                 // The contents should have been removed by now, but sometimes a race condition is
                 // hit where other files have been added by the OS. Fall back to `remove_dir_all`,
                 // which will remove the directory robustly across platforms.
-                fs_err::remove_dir_all(entry.path())?;
+                if !dry_run {
+                    fs_err::remove_dir_all(entry.path())?;
+                }
             } else {
                 self.num_files += 1;
                 rm_file(entry.path(), entry.metadata())?;
@@ -57,6 +57,10 @@ pub enum ErrorKind {
     #[error("{0} isn't available locally, but making network requests to registries was banned.")]
     NoIndex(String),
 
+    /// A local index was provided as a URL that can't be converted to a path.
+    #[error("Expected a file URL for a local index, but received: {0}")]
+    NonFileUrl(Url),
+
     /// The package was not found in the registry.
     ///
     /// Make sure the package name is spelled correctly and that you've
@@ -10,8 +10,9 @@ use url::Url;
 
 use distribution_filename::DistFilename;
 use distribution_types::{
-    BuiltDist, Dist, File, FileLocation, FlatIndexLocation, IndexUrl, PrioritizedDist,
-    RegistryBuiltDist, RegistrySourceDist, SourceDist,
+    BuiltDist, Dist, File, FileLocation, FlatIndexLocation, IncompatibleSource, IncompatibleWheel,
+    IndexUrl, PrioritizedDist, RegistryBuiltDist, RegistrySourceDist, SourceDist,
+    SourceDistCompatibility, WheelCompatibility,
 };
 use pep440_rs::Version;
 use platform_tags::Tags;
@@ -19,6 +20,8 @@ use pypi_types::{Hashes, Yanked};
 use uv_auth::safe_copy_url_auth;
 use uv_cache::{Cache, CacheBucket};
 use uv_normalize::PackageName;
+use uv_traits::{NoBinary, NoBuild};
+use walkdir::WalkDir;
 
 use crate::cached_client::{CacheControl, CachedClientError};
 use crate::html::SimpleHtml;
@@ -211,19 +214,21 @@ impl<'a> FlatIndexClient<'a> {
     }
 
     /// Read a flat remote index from a `--find-links` directory.
+    ///
+    /// Subdirectories are searched recursively, such that (e.g.) a mirror that groups its
+    /// distributions into a directory per package can be used as-is.
     fn read_from_directory(path: &PathBuf) -> Result<FlatIndexEntries, std::io::Error> {
         // Absolute paths are required for the URL conversion.
         let path = fs_err::canonicalize(path)?;
 
         let mut dists = Vec::new();
-        for entry in fs_err::read_dir(path)? {
+        for entry in WalkDir::new(path).follow_links(true) {
             let entry = entry?;
-            let metadata = entry.metadata()?;
-            if !metadata.is_file() {
+            if !entry.file_type().is_file() {
                 continue;
             }
 
-            let Ok(filename) = entry.file_name().into_string() else {
+            let Some(filename) = entry.file_name().to_str() else {
                 warn!(
                     "Skipping non-UTF-8 filename in `--find-links` directory: {}",
                     entry.file_name().to_string_lossy()
@@ -238,11 +243,11 @@ impl<'a> FlatIndexClient<'a> {
                 requires_python: None,
                 size: None,
                 upload_time_utc_ms: None,
-                url: FileLocation::Path(entry.path().clone()),
+                url: FileLocation::Path(entry.path().to_path_buf()),
                 yanked: None,
             };
 
-            let Some(filename) = DistFilename::try_from_normalized_filename(&filename) else {
+            let Some(filename) = DistFilename::try_from_normalized_filename(filename) else {
                 debug!(
                     "Ignoring `--find-links` entry (expected a wheel or source distribution filename): {}",
                     entry.path().display()
@@ -269,12 +274,25 @@ pub struct FlatIndex {
 impl FlatIndex {
     /// Collect all files from a `--find-links` target into a [`FlatIndex`].
     #[instrument(skip_all)]
-    pub fn from_entries(entries: FlatIndexEntries, tags: &Tags) -> Self {
+    pub fn from_entries(
+        entries: FlatIndexEntries,
+        tags: &Tags,
+        no_build: &NoBuild,
+        no_binary: &NoBinary,
+    ) -> Self {
         // Collect compatible distributions.
         let mut index = FxHashMap::default();
         for (filename, file, url) in entries.entries {
             let distributions = index.entry(filename.name().clone()).or_default();
-            Self::add_file(distributions, file, filename, tags, url);
+            Self::add_file(
+                distributions,
+                file,
+                filename,
+                tags,
+                no_build,
+                no_binary,
+                url,
+            );
         }
 
         // Collect offline entries.
@@ -288,13 +306,26 @@ impl FlatIndex {
         file: File,
         filename: DistFilename,
         tags: &Tags,
+        no_build: &NoBuild,
+        no_binary: &NoBinary,
         index: IndexUrl,
     ) {
         // No `requires-python` here: for source distributions, we don't have that information;
         // for wheels, we read it lazily only when selected.
         match filename {
             DistFilename::WheelFilename(filename) => {
-                let compatibility = filename.compatibility(tags);
+                let mut compatibility = WheelCompatibility::from(filename.compatibility(tags));
+
+                // Mark all wheels as incompatible when binaries are disabled
+                let no_binary = match no_binary {
+                    NoBinary::None => false,
+                    NoBinary::All => true,
+                    NoBinary::Packages(packages) => packages.contains(&filename.name),
+                };
+                if no_binary && compatibility.is_compatible() {
+                    compatibility = WheelCompatibility::Incompatible(IncompatibleWheel::NoBinary);
+                }
+
                 let version = filename.version.clone();
 
                 let dist = Dist::Built(BuiltDist::Registry(RegistryBuiltDist {
@@ -309,7 +340,7 @@ impl FlatIndex {
                             None,
                             Yanked::default(),
                             None,
-                            compatibility.into(),
+                            compatibility,
                         );
                     }
                     Entry::Vacant(entry) => {
@@ -318,12 +349,24 @@ impl FlatIndex {
                             None,
                             Yanked::default(),
                             None,
-                            compatibility.into(),
+                            compatibility,
                         ));
                     }
                 }
             }
             DistFilename::SourceDistFilename(filename) => {
+                // Mark all source distributions as incompatible when builds are disabled
+                let no_build = match no_build {
+                    NoBuild::None => false,
+                    NoBuild::All => true,
+                    NoBuild::Packages(packages) => packages.contains(&filename.name),
+                };
+                let compatibility = if no_build {
+                    SourceDistCompatibility::Incompatible(IncompatibleSource::NoBuild)
+                } else {
+                    SourceDistCompatibility::Compatible
+                };
+
                 let dist = Dist::Source(SourceDist::Registry(RegistrySourceDist {
                     filename: filename.clone(),
                     file: Box::new(file),
@@ -331,9 +374,13 @@ impl FlatIndex {
                 }));
                 match distributions.0.entry(filename.version) {
                     Entry::Occupied(mut entry) => {
-                        entry
-                            .get_mut()
-                            .insert_source(dist, None, Yanked::default(), None);
+                        entry.get_mut().insert_source(
+                            dist,
+                            None,
+                            Yanked::default(),
+                            None,
+                            compatibility,
+                        );
                     }
                     Entry::Vacant(entry) => {
                         entry.insert(PrioritizedDist::from_source(
@@ -341,6 +388,7 @@ impl FlatIndex {
                             None,
                             Yanked::default(),
                             None,
+                            compatibility,
                         ));
                     }
                 }
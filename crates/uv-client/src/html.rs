@@ -93,6 +93,8 @@ impl SimpleHtml {
                 Ok(Hashes {
                     md5: Some(md5),
                     sha256: None,
+                    sha384: None,
+                    sha512: None,
                 })
             }
             "sha256" => {
@@ -101,6 +103,28 @@ impl SimpleHtml {
                 Ok(Hashes {
                     md5: None,
                     sha256: Some(sha256),
+                    sha384: None,
+                    sha512: None,
+                })
+            }
+            "sha384" => {
+                let sha384 = std::str::from_utf8(value.as_bytes())?;
+                let sha384 = sha384.to_string();
+                Ok(Hashes {
+                    md5: None,
+                    sha256: None,
+                    sha384: Some(sha384),
+                    sha512: None,
+                })
+            }
+            "sha512" => {
+                let sha512 = std::str::from_utf8(value.as_bytes())?;
+                let sha512 = sha512.to_string();
+                Ok(Hashes {
+                    md5: None,
+                    sha256: None,
+                    sha384: None,
+                    sha512: Some(sha512),
                 })
             }
             _ => Err(Error::UnsupportedHashAlgorithm(fragment.to_string())),
@@ -156,10 +180,14 @@ impl SimpleHtml {
             None
         };
 
-        // Extract the `data-dist-info-metadata` field, which should be set on
-        // the `data-dist-info-metadata` attribute.
-        let dist_info_metadata = if let Some(dist_info_metadata) =
-            link.attributes().get("data-dist-info-metadata").flatten()
+        // Extract the `data-core-metadata` field, which should be set on the
+        // `data-core-metadata` attribute (PEP 714), falling back to the legacy
+        // `data-dist-info-metadata` attribute (PEP 658).
+        let dist_info_metadata = if let Some(dist_info_metadata) = link
+            .attributes()
+            .get("data-core-metadata")
+            .flatten()
+            .or_else(|| link.attributes().get("data-dist-info-metadata").flatten())
         {
             let dist_info_metadata = std::str::from_utf8(dist_info_metadata.as_bytes())?;
             let dist_info_metadata = html_escape::decode_html_entities(dist_info_metadata);
@@ -221,7 +249,7 @@ pub enum Error {
     #[error("Unexpected fragment (expected `#sha256=...`) on URL: {0}")]
     FragmentParse(String),
 
-    #[error("Unsupported hash algorithm (expected `sha256`) on: {0}")]
+    #[error("Unsupported hash algorithm (expected one of: `md5`, `sha256`, `sha384`, or `sha512`) on: {0}")]
     UnsupportedHashAlgorithm(String),
 
     #[error("Invalid `requires-python` specifier: {0}")]
@@ -274,6 +302,8 @@ mod tests {
                         sha256: Some(
                             "6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61",
                         ),
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -328,6 +358,8 @@ mod tests {
                             "6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61",
                         ),
                         sha256: None,
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -385,6 +417,8 @@ mod tests {
                         sha256: Some(
                             "6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61",
                         ),
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -439,6 +473,8 @@ mod tests {
                         sha256: Some(
                             "6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61",
                         ),
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -491,6 +527,8 @@ mod tests {
                     hashes: Hashes {
                         md5: None,
                         sha256: None,
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -543,6 +581,8 @@ mod tests {
                     hashes: Hashes {
                         md5: None,
                         sha256: None,
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -629,6 +669,8 @@ mod tests {
                     hashes: Hashes {
                         md5: None,
                         sha256: None,
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -665,14 +707,14 @@ mod tests {
 <html>
   <body>
     <h1>Links for jinja2</h1>
-    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl#sha512=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61">Jinja2-3.1.2-py3-none-any.whl</a><br/>
+    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl#blake2b=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61">Jinja2-3.1.2-py3-none-any.whl</a><br/>
   </body>
 </html>
 <!--TIMESTAMP 1703347410-->
         "#;
         let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
         let result = SimpleHtml::parse(text, &base).unwrap_err();
-        insta::assert_display_snapshot!(result, @"Unsupported hash algorithm (expected `sha256`) on: sha512=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61");
+        insta::assert_display_snapshot!(result, @"Unsupported hash algorithm (expected one of: `md5`, `sha256`, `sha384`, or `sha512`) on: blake2b=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61");
     }
 
     #[test]
@@ -716,6 +758,8 @@ mod tests {
                     hashes: Hashes {
                         md5: None,
                         sha256: None,
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -729,6 +773,8 @@ mod tests {
                     hashes: Hashes {
                         md5: None,
                         sha256: None,
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -793,6 +839,8 @@ mod tests {
                         sha256: Some(
                             "9da884457e910bf0847d396cb4b778ad9f3c3d17db1c5997cb861937bd284237",
                         ),
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -808,6 +856,8 @@ mod tests {
                         sha256: Some(
                             "4c83829ff83d408b5e1d4995472265411d2c414112298f2eb4b359d9e4563373",
                         ),
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: None,
                     size: None,
@@ -823,6 +873,8 @@ mod tests {
                         sha256: Some(
                             "6489f51bb3666def6f314e15f19d50a1869a19ae0e8c9a3641ffe66c77d42403",
                         ),
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: Some(
                         Ok(
@@ -887,6 +939,8 @@ mod tests {
                         sha256: Some(
                             "6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61",
                         ),
+                        sha384: None,
+                        sha512: None,
                     },
                     requires_python: Some(
                         Ok(
@@ -909,4 +963,33 @@ mod tests {
         }
         "###);
     }
+
+    #[test]
+    fn parse_core_metadata() {
+        let text = r#"
+<!DOCTYPE html>
+<html>
+  <body>
+    <h1>Links for jinja2</h1>
+    <a href="/whl/Jinja2-3.1.2-py3-none-any.whl" data-core-metadata="sha256=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61" data-dist-info-metadata="true">Jinja2-3.1.2-py3-none-any.whl</a><br/>
+  </body>
+</html>
+        "#;
+        let base = Url::parse("https://download.pytorch.org/whl/jinja2/").unwrap();
+        let result = SimpleHtml::parse(text, &base).unwrap();
+        insta::assert_debug_snapshot!(result.files[0].dist_info_metadata, @r###"
+        Some(
+            Hashes(
+                Hashes {
+                    md5: None,
+                    sha256: Some(
+                        "6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61",
+                    ),
+                    sha384: None,
+                    sha512: None,
+                },
+            ),
+        )
+        "###);
+    }
 }
@@ -6,6 +6,8 @@ pub use registry_client::{
     VersionFiles,
 };
 pub use rkyvutil::OwnedArchive;
+pub use timeout::Timeouts;
+pub use tls::{ClientCert, InsecureHost, TlsError, TlsRoots, TlsSettings};
 
 mod cached_client;
 mod error;
@@ -15,4 +17,7 @@ mod httpcache;
 mod middleware;
 mod registry_client;
 mod remote_metadata;
+mod resumable;
 mod rkyvutil;
+mod timeout;
+mod tls;
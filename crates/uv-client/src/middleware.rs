@@ -1,10 +1,19 @@
 use std::fmt::Debug;
+use std::time::Duration;
 
-use reqwest::{Request, Response};
+use base64::prelude::BASE64_STANDARD;
+use base64::Engine;
+use rand::Rng;
+use reqwest::{header, Client, Request, Response, StatusCode};
 use reqwest_middleware::{Middleware, Next};
 use task_local_extensions::Extensions;
+use tracing::debug;
 use url::Url;
 
+use uv_auth::IndexCredentials;
+
+use crate::tls::url_host;
+
 /// A custom error type for the offline middleware.
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub(crate) struct OfflineError {
@@ -45,3 +54,203 @@ impl Middleware for OfflineMiddleware {
         ))
     }
 }
+
+/// A middleware that sends requests for specific hosts through a dedicated client, e.g., one that
+/// presents a client certificate for that host.
+pub(crate) struct HostClientMiddleware {
+    clients: Vec<(String, Client)>,
+}
+
+impl HostClientMiddleware {
+    pub(crate) fn new(clients: Vec<(String, Client)>) -> Self {
+        Self { clients }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for HostClientMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        if let Some(host) = url_host(req.url()) {
+            if let Some((_, client)) = self.clients.iter().find(|(existing, _)| *existing == host) {
+                return Ok(client.execute(req).await?);
+            }
+        }
+        next.run(req, extensions).await
+    }
+}
+
+/// A middleware that attaches index credentials to requests for the index's host, e.g., to fetch
+/// artifacts that are served from the same host as the index.
+///
+/// Requests that already carry credentials (in the URL or an `Authorization` header) are left
+/// untouched.
+pub(crate) struct AuthMiddleware {
+    credentials: Vec<IndexCredentials>,
+}
+
+impl AuthMiddleware {
+    pub(crate) fn new(credentials: Vec<IndexCredentials>) -> Self {
+        Self { credentials }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for AuthMiddleware {
+    async fn handle(
+        &self,
+        mut req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        if !req.headers().contains_key(header::AUTHORIZATION) {
+            if let Some(value) = authorization(&self.credentials, req.url()) {
+                req.headers_mut().insert(header::AUTHORIZATION, value);
+            }
+        }
+        next.run(req, extensions).await
+    }
+}
+
+/// Return the `Authorization` header to send with a request to the given URL, if the URL doesn't
+/// carry its own credentials and any of the index credentials apply to it.
+pub(crate) fn authorization(
+    credentials: &[IndexCredentials],
+    url: &Url,
+) -> Option<header::HeaderValue> {
+    if !url.username().is_empty() || url.password().is_some() {
+        return None;
+    }
+    let credentials = credentials
+        .iter()
+        .find(|credentials| credentials.applies_to(url))?;
+    let token = BASE64_STANDARD.encode(format!(
+        "{}:{}",
+        credentials.username(),
+        credentials.password().unwrap_or_default()
+    ));
+    let mut value = header::HeaderValue::from_str(&format!("Basic {token}"))
+        .expect("base64 is a valid header value");
+    value.set_sensitive(true);
+    Some(value)
+}
+
+/// The delay before the first retry; doubled for each subsequent retry.
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(500);
+
+/// The maximum delay between retries, including delays requested via `Retry-After`.
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(60);
+
+/// A middleware that retries requests that failed due to a transient error (i.e., a connection
+/// error, a timeout, or a `408`, `429`, or `5xx` response).
+///
+/// Retries are delayed with exponential backoff and jitter, unless the server provides a
+/// `Retry-After` header, in which case the requested delay is used (up to a limit).
+pub(crate) struct RetryMiddleware {
+    max_retries: u32,
+}
+
+impl RetryMiddleware {
+    pub(crate) fn new(max_retries: u32) -> Self {
+        Self { max_retries }
+    }
+}
+
+#[async_trait::async_trait]
+impl Middleware for RetryMiddleware {
+    async fn handle(
+        &self,
+        req: Request,
+        extensions: &mut Extensions,
+        next: Next<'_>,
+    ) -> reqwest_middleware::Result<Response> {
+        let mut past_retries = 0;
+        loop {
+            // Requests with streaming bodies can't be cloned, and so can't be retried.
+            let Some(attempt) = req.try_clone() else {
+                return next.run(req, extensions).await;
+            };
+
+            let result = next.clone().run(attempt, extensions).await;
+            if past_retries >= self.max_retries || !is_transient(&result) {
+                return result;
+            }
+
+            let delay = result
+                .as_ref()
+                .ok()
+                .and_then(retry_after)
+                .unwrap_or_else(|| backoff(past_retries));
+            past_retries += 1;
+            debug!(
+                "Transient failure for {}, retrying in {:.1}s (attempt {past_retries}/{})",
+                req.url(),
+                delay.as_secs_f32(),
+                self.max_retries
+            );
+            tokio::time::sleep(delay).await;
+        }
+    }
+}
+
+/// Returns `true` if the result of a request indicates a transient failure that's worth retrying.
+fn is_transient(result: &reqwest_middleware::Result<Response>) -> bool {
+    match result {
+        Ok(response) => {
+            let status = response.status();
+            status == StatusCode::REQUEST_TIMEOUT
+                || status == StatusCode::TOO_MANY_REQUESTS
+                || status.is_server_error()
+        }
+        Err(reqwest_middleware::Error::Reqwest(err)) => {
+            err.is_timeout() || err.is_connect() || err.is_request()
+        }
+        Err(reqwest_middleware::Error::Middleware(_)) => false,
+    }
+}
+
+/// Returns the delay requested by the server via the `Retry-After` header, if any.
+///
+/// Both forms are supported: a number of seconds, and an HTTP date.
+fn retry_after(response: &Response) -> Option<Duration> {
+    let value = response.headers().get(header::RETRY_AFTER)?.to_str().ok()?;
+    let delay = if let Ok(seconds) = value.trim().parse::<u64>() {
+        Duration::from_secs(seconds)
+    } else {
+        let date = chrono::DateTime::parse_from_rfc2822(value.trim()).ok()?;
+        (date.with_timezone(&chrono::Utc) - chrono::Utc::now())
+            .to_std()
+            .unwrap_or(Duration::ZERO)
+    };
+    Some(delay.min(RETRY_MAX_DELAY))
+}
+
+/// Returns the delay before the given retry, using exponential backoff with full jitter.
+fn backoff(past_retries: u32) -> Duration {
+    let ceiling = RETRY_BASE_DELAY
+        .saturating_mul(2u32.saturating_pow(past_retries))
+        .min(RETRY_MAX_DELAY);
+    ceiling.mul_f64(rand::thread_rng().gen_range(0.5..=1.0))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use super::{backoff, RETRY_BASE_DELAY, RETRY_MAX_DELAY};
+
+    #[test]
+    fn exponential_backoff() {
+        for past_retries in 0..4 {
+            let ceiling = RETRY_BASE_DELAY * 2u32.pow(past_retries);
+            let delay = backoff(past_retries);
+            assert!(delay >= ceiling / 2 && delay <= ceiling, "{delay:?}");
+        }
+        assert!(backoff(100) <= RETRY_MAX_DELAY);
+        assert!(backoff(100) >= Duration::from_secs(30));
+    }
+}
@@ -0,0 +1,283 @@
+use std::io;
+use std::time::Duration;
+
+use bytes::Bytes;
+use futures::stream::BoxStream;
+use futures::{Stream, StreamExt};
+use reqwest::header::{self, HeaderMap, HeaderValue};
+use reqwest::{Response, StatusCode};
+use reqwest_middleware::ClientWithMiddleware;
+use sha2::{Digest, Sha256};
+use tracing::debug;
+use url::Url;
+
+use crate::timeout::DOWNLOAD_TIMEOUT;
+
+/// The maximum number of times an interrupted download is resumed before giving up.
+const MAX_RESUMES: u32 = 3;
+
+/// Stream the body of the given response, resuming the download with a `Range` request if the
+/// connection is interrupted partway through.
+///
+/// A download is only resumed if the server advertises support for byte ranges and the body is
+/// served without a content encoding, such that the number of bytes received so far corresponds
+/// to an offset into the file. The resumed response must be a `206 Partial Content` for exactly
+/// the missing range; the original `ETag` (or `Last-Modified`) is sent as `If-Range`, so a
+/// server whose file has since changed responds with the full file, and we bail.
+///
+/// If no data is received for `read_timeout`, the connection is considered interrupted (and the
+/// download resumed, if possible), such that large downloads aren't bounded as a whole.
+///
+/// If the expected SHA-256 digest is known, the received bytes are hashed as they're streamed and
+/// verified once the stream completes, such that a mismatch surfaces as an error from the final
+/// read, before the caller persists anything. (For a resumed download, this also guarantees that
+/// the pieces belong to the same file.)
+pub(crate) fn resumable_stream(
+    client: ClientWithMiddleware,
+    response: Response,
+    sha256: Option<String>,
+    read_timeout: Duration,
+) -> impl Stream<Item = io::Result<Bytes>> + Unpin + Send {
+    let state = Resumable {
+        read_timeout,
+        url: response.url().clone(),
+        validator: validator(response.headers()),
+        length: resumable_length(response.headers()),
+        client,
+        hasher: sha256.map(|expected| (Sha256::new(), expected)),
+        offset: 0,
+        resumes: 0,
+        stream: body_stream(response),
+        done: false,
+    };
+    Box::pin(futures::stream::unfold(state, Resumable::next))
+}
+
+struct Resumable {
+    client: ClientWithMiddleware,
+    url: Url,
+    /// The maximum time to wait for the next chunk of the response.
+    read_timeout: Duration,
+    /// The `ETag` or `Last-Modified` header of the original response, sent as `If-Range`.
+    validator: Option<HeaderValue>,
+    /// The total length of the file, if the download can be resumed.
+    length: Option<u64>,
+    /// The hasher for the received bytes, along with the expected digest.
+    hasher: Option<(Sha256, String)>,
+    /// The number of bytes received so far.
+    offset: u64,
+    /// The number of times the download has been resumed.
+    resumes: u32,
+    stream: BoxStream<'static, io::Result<Bytes>>,
+    done: bool,
+}
+
+impl Resumable {
+    async fn next(mut self) -> Option<(io::Result<Bytes>, Self)> {
+        if self.done {
+            return None;
+        }
+        loop {
+            let next = tokio::time::timeout(self.read_timeout, self.stream.next())
+                .await
+                .unwrap_or_else(|_| {
+                    Some(Err(io::Error::new(
+                        io::ErrorKind::TimedOut,
+                        format!(
+                            "No data received for {}s (the read timeout)",
+                            self.read_timeout.as_secs()
+                        ),
+                    )))
+                });
+            match next {
+                Some(Ok(chunk)) => {
+                    self.offset += chunk.len() as u64;
+                    if let Some((hasher, _)) = self.hasher.as_mut() {
+                        hasher.update(&chunk);
+                    }
+                    return Some((Ok(chunk), self));
+                }
+                Some(Err(err)) => {
+                    let Some(length) = self.length.filter(|_| self.resumes < MAX_RESUMES) else {
+                        self.done = true;
+                        return Some((Err(err), self));
+                    };
+                    self.resumes += 1;
+                    debug!(
+                        "Download of {} was interrupted after {} of {length} bytes; resuming (attempt {}/{MAX_RESUMES}): {err}",
+                        self.url, self.offset, self.resumes
+                    );
+                    match self.resume(length).await {
+                        Ok(stream) => self.stream = stream,
+                        Err(reason) => {
+                            debug!("Failed to resume download of {}: {reason}", self.url);
+                            self.done = true;
+                            return Some((Err(err), self));
+                        }
+                    }
+                }
+                None => {
+                    self.done = true;
+                    let (hasher, expected) = self.hasher.take()?;
+                    let actual = format!("{:x}", hasher.finalize());
+                    if actual.eq_ignore_ascii_case(&expected) {
+                        return None;
+                    }
+                    let err = io::Error::new(
+                        io::ErrorKind::InvalidData,
+                        format!(
+                            "Hash mismatch for `{}`: expected sha256 `{expected}`, got `{actual}`",
+                            self.url
+                        ),
+                    );
+                    return Some((Err(err), self));
+                }
+            }
+        }
+    }
+
+    /// Request the remainder of the file, starting at the current offset.
+    async fn resume(&self, length: u64) -> Result<BoxStream<'static, io::Result<Bytes>>, String> {
+        let mut request = self
+            .client
+            .get(self.url.clone())
+            .timeout(DOWNLOAD_TIMEOUT)
+            .header(header::RANGE, format!("bytes={}-", self.offset));
+        if let Some(validator) = &self.validator {
+            request = request.header(header::IF_RANGE, validator.clone());
+        }
+        let response = request.send().await.map_err(|err| err.to_string())?;
+
+        if response.status() != StatusCode::PARTIAL_CONTENT {
+            return Err(format!(
+                "expected `206 Partial Content`, got `{}`",
+                response.status()
+            ));
+        }
+        let content_range = response
+            .headers()
+            .get(header::CONTENT_RANGE)
+            .and_then(|value| value.to_str().ok())
+            .ok_or_else(|| "missing `Content-Range` header".to_string())?;
+        let expected = format!("bytes {}-{}/{length}", self.offset, length - 1);
+        if content_range != expected {
+            return Err(format!(
+                "expected `Content-Range: {expected}`, got `{content_range}`"
+            ));
+        }
+
+        Ok(body_stream(response))
+    }
+}
+
+/// Stream the body of the given response.
+fn body_stream(response: Response) -> BoxStream<'static, io::Result<Bytes>> {
+    response
+        .bytes_stream()
+        .map(|result| result.map_err(|err| io::Error::new(io::ErrorKind::Other, err)))
+        .boxed()
+}
+
+/// Return the length of the response body, if the download can be resumed with a byte range.
+fn resumable_length(headers: &HeaderMap) -> Option<u64> {
+    let accepts_ranges = headers
+        .get(header::ACCEPT_RANGES)
+        .is_some_and(|value| value.as_bytes().eq_ignore_ascii_case(b"bytes"));
+    if !accepts_ranges || headers.contains_key(header::CONTENT_ENCODING) {
+        return None;
+    }
+    headers
+        .get(header::CONTENT_LENGTH)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.parse().ok())
+        .filter(|length| *length > 0)
+}
+
+/// Return the validator to send as `If-Range`: a strong `ETag`, or else `Last-Modified`.
+fn validator(headers: &HeaderMap) -> Option<HeaderValue> {
+    headers
+        .get(header::ETAG)
+        .filter(|etag| !etag.as_bytes().starts_with(b"W/"))
+        .or_else(|| headers.get(header::LAST_MODIFIED))
+        .cloned()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::time::Duration;
+
+    use futures::TryStreamExt;
+    use reqwest::header::{HeaderMap, HeaderValue};
+    use reqwest::Response;
+    use reqwest_middleware::ClientWithMiddleware;
+
+    use super::{resumable_length, resumable_stream, validator};
+
+    async fn stream(body: &'static str, sha256: &str) -> std::io::Result<Vec<u8>> {
+        let client = ClientWithMiddleware::from(reqwest::Client::new());
+        let response = Response::from(http::Response::new(body));
+        resumable_stream(
+            client,
+            response,
+            Some(sha256.to_string()),
+            Duration::from_secs(5),
+        )
+        .map_ok(|chunk| chunk.to_vec())
+        .try_concat()
+        .await
+    }
+
+    #[tokio::test]
+    async fn sha256() {
+        let body = stream(
+            "hello world",
+            "B94D27B9934D3E08A52E52D7DA7DABFAC484EFE37A5380EE9088F7ACE2EFCDE9",
+        )
+        .await
+        .unwrap();
+        assert_eq!(body, b"hello world");
+
+        let err = stream(
+            "hello world",
+            "0000000000000000000000000000000000000000000000000000000000000000",
+        )
+        .await
+        .unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn length() {
+        let mut headers = HeaderMap::new();
+        headers.insert("content-length", HeaderValue::from_static("1024"));
+        assert_eq!(resumable_length(&headers), None);
+
+        headers.insert("accept-ranges", HeaderValue::from_static("bytes"));
+        assert_eq!(resumable_length(&headers), Some(1024));
+
+        headers.insert("content-encoding", HeaderValue::from_static("gzip"));
+        assert_eq!(resumable_length(&headers), None);
+    }
+
+    #[test]
+    fn if_range() {
+        let mut headers = HeaderMap::new();
+        assert_eq!(validator(&headers), None);
+
+        headers.insert(
+            "last-modified",
+            HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"),
+        );
+        headers.insert("etag", HeaderValue::from_static("W/\"weak\""));
+        assert_eq!(
+            validator(&headers),
+            Some(HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"))
+        );
+
+        headers.insert("etag", HeaderValue::from_static("\"strong\""));
+        assert_eq!(
+            validator(&headers),
+            Some(HeaderValue::from_static("\"strong\""))
+        );
+    }
+}
@@ -0,0 +1,82 @@
+use std::env;
+use std::time::Duration;
+
+use uv_warnings::warn_user_once;
+
+/// An upper bound on the duration of a distribution download.
+///
+/// Downloads aren't subject to the read timeout as a whole, since large artifacts on slow mirrors
+/// can take arbitrarily long; instead, they're aborted if no data is received for the duration of
+/// the read timeout.
+pub(crate) const DOWNLOAD_TIMEOUT: Duration = Duration::from_secs(24 * 60 * 60);
+
+/// The timeouts to apply to HTTP requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Timeouts {
+    /// The maximum time to wait when establishing a connection, in seconds.
+    pub connect: u64,
+    /// The maximum time to wait for a response, in seconds.
+    ///
+    /// For requests to the simple index and for package metadata, this bounds the entire request.
+    /// For distribution downloads, it bounds the time between consecutive chunks of the response.
+    pub read: u64,
+}
+
+impl Default for Timeouts {
+    fn default() -> Self {
+        Self {
+            connect: 30,
+            read: 5 * 60,
+        }
+    }
+}
+
+impl Timeouts {
+    /// Read the timeouts from the environment, falling back to the defaults.
+    ///
+    /// The read timeout is read from `UV_HTTP_TIMEOUT` (or, for backwards compatibility,
+    /// `UV_REQUEST_TIMEOUT` or `HTTP_TIMEOUT`), matching
+    /// <https://doc.rust-lang.org/nightly/cargo/reference/config.html#httptimeout>; the connect
+    /// timeout is read from `UV_HTTP_CONNECT_TIMEOUT`.
+    pub fn from_env() -> Self {
+        let default = Self::default();
+        let read = env::var("UV_HTTP_TIMEOUT")
+            .or_else(|_| env::var("UV_REQUEST_TIMEOUT"))
+            .or_else(|_| env::var("HTTP_TIMEOUT"))
+            .map_or(default.read, |value| {
+                parse("UV_HTTP_TIMEOUT", &value, default.read)
+            });
+        let connect = env::var("UV_HTTP_CONNECT_TIMEOUT").map_or(default.connect, |value| {
+            parse("UV_HTTP_CONNECT_TIMEOUT", &value, default.connect)
+        });
+        Self { connect, read }
+    }
+
+    /// Override the read timeout, if provided.
+    #[must_use]
+    pub fn with_read(self, read: Option<u64>) -> Self {
+        Self {
+            read: read.unwrap_or(self.read),
+            ..self
+        }
+    }
+
+    /// Override the connect timeout, if provided.
+    #[must_use]
+    pub fn with_connect(self, connect: Option<u64>) -> Self {
+        Self {
+            connect: connect.unwrap_or(self.connect),
+            ..self
+        }
+    }
+}
+
+/// Parse a timeout from the environment, warning and using the default on a parse error.
+fn parse(var: &str, value: &str, default: u64) -> u64 {
+    value.parse::<u64>().unwrap_or_else(|_| {
+        warn_user_once!(
+            "Ignoring invalid value from environment for {var}. Expected integer number of seconds, got \"{value}\"."
+        );
+        default
+    })
+}
@@ -0,0 +1,382 @@
+use std::io::BufReader;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use std::sync::Arc;
+use std::time::SystemTime;
+
+use rustls::client::{ServerCertVerified, ServerCertVerifier};
+use rustls::{Certificate, ClientConfig, OwnedTrustAnchor, PrivateKey, RootCertStore, ServerName};
+use rustls_pemfile::Item;
+use tracing::debug;
+use url::Url;
+
+/// The TLS configuration for the registry client.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TlsSettings {
+    /// The root certificates used to verify servers.
+    pub roots: TlsRoots,
+    /// The client certificates used to authenticate with servers that require mutual TLS.
+    pub client_certs: Vec<ClientCert>,
+    /// The hosts for which TLS certificate verification is disabled.
+    pub insecure_hosts: Vec<InsecureHost>,
+}
+
+impl TlsSettings {
+    /// Return the client certificate to use for all requests, if any.
+    ///
+    /// If multiple global certificates are provided, the last one wins.
+    pub(crate) fn global_client_cert(&self) -> Option<&Path> {
+        self.client_certs
+            .iter()
+            .rev()
+            .find(|cert| cert.host.is_none())
+            .map(|cert| cert.path.as_path())
+    }
+
+    /// Return the client certificates to use for specific hosts, as `(host, path)` pairs.
+    ///
+    /// If multiple certificates are provided for the same host, the last one wins.
+    pub(crate) fn host_client_certs(&self) -> Vec<(&str, &Path)> {
+        let mut certs: Vec<(&str, &Path)> = Vec::new();
+        for cert in &self.client_certs {
+            let Some(host) = cert.host.as_deref() else {
+                continue;
+            };
+            certs.retain(|(existing, _)| *existing != host);
+            certs.push((host, cert.path.as_path()));
+        }
+        certs
+    }
+
+    /// Return the hosts that require a dedicated client, along with the client certificate to
+    /// present to each (if any) and whether to skip certificate verification.
+    pub(crate) fn host_settings(&self) -> Vec<(&str, Option<&Path>, bool)> {
+        let mut hosts = self
+            .host_client_certs()
+            .into_iter()
+            .map(|(host, path)| (host, Some(path), false))
+            .collect::<Vec<_>>();
+        for insecure in &self.insecure_hosts {
+            if let Some(existing) = hosts.iter_mut().find(|(host, ..)| *host == insecure.0) {
+                existing.2 = true;
+            } else {
+                hosts.push((&insecure.0, self.global_client_cert(), true));
+            }
+        }
+        hosts
+    }
+}
+
+/// A host for which TLS certificate verification is disabled, parsed from either a host (with an
+/// optional port, as in `example.com:8080`) or a URL on that host.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InsecureHost(String);
+
+impl FromStr for InsecureHost {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Ok(url) = Url::parse(s) {
+            if matches!(url.scheme(), "http" | "https") {
+                let host = url_host(&url).ok_or_else(|| format!("URL has no host: `{s}`"))?;
+                return Ok(Self(host));
+            }
+        }
+        let host = s.trim_end_matches('/');
+        if host.is_empty() || host.contains(['/', '@']) {
+            return Err(format!(
+                "Expected a host (e.g., `example.com:8080`), got: `{s}`"
+            ));
+        }
+        Ok(Self(host.to_ascii_lowercase()))
+    }
+}
+
+/// The source of the root certificates used to verify TLS connections.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub enum TlsRoots {
+    /// Use the Mozilla root certificates bundled with uv (via `webpki-roots`).
+    #[default]
+    Webpki,
+    /// Use the platform's native certificate store (e.g., the macOS keychain or the Windows
+    /// certificate store), which also includes any certificates installed by the system
+    /// administrator.
+    Native,
+    /// Use the certificates in the given PEM-encoded CA bundle (e.g., from `--cert` or
+    /// `SSL_CERT_FILE`), instead of any built-in roots.
+    Bundle(PathBuf),
+}
+
+/// A client certificate used to authenticate with an index that requires mutual TLS, parsed from
+/// either `<PATH>` (used for all requests) or `<INDEX_URL>=<PATH>` (used for requests to the
+/// index's host).
+///
+/// The file must be PEM-encoded and contain both the certificate (chain) and the private key.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClientCert {
+    /// The host (and port, if non-default) to use the certificate for, or `None` to use it for all
+    /// requests.
+    host: Option<String>,
+    /// The path to the PEM file containing the certificate and private key.
+    path: PathBuf,
+}
+
+impl FromStr for ClientCert {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if let Some((index, path)) = s.split_once('=') {
+            if let Ok(url) = Url::parse(index) {
+                if matches!(url.scheme(), "http" | "https") {
+                    let host = url_host(&url)
+                        .ok_or_else(|| format!("Index URL has no host: `{index}`"))?;
+                    return Ok(Self {
+                        host: Some(host),
+                        path: PathBuf::from(path),
+                    });
+                }
+            }
+        }
+        Ok(Self {
+            host: None,
+            path: PathBuf::from(s),
+        })
+    }
+}
+
+/// Return the host of the URL, including the port if it's not the default for the scheme.
+pub(crate) fn url_host(url: &Url) -> Option<String> {
+    let host = url.host_str()?;
+    Some(match url.port() {
+        Some(port) => format!("{host}:{port}"),
+        None => host.to_string(),
+    })
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum TlsError {
+    #[error("Failed to load the platform's native certificate store")]
+    Native(#[source] std::io::Error),
+    #[error("Failed to read certificates from: {}", _0.display())]
+    Bundle(PathBuf, #[source] std::io::Error),
+    #[error("No valid certificates found in: {}", _0.display())]
+    EmptyBundle(PathBuf),
+    #[error("Failed to read client certificate from: {}", _0.display())]
+    ClientCert(PathBuf, #[source] std::io::Error),
+    #[error("Client certificate file must contain a certificate and a private key: {}", _0.display())]
+    IncompleteClientCert(PathBuf),
+    #[error("Invalid client certificate: {}", _0.display())]
+    InvalidClientCert(PathBuf, #[source] rustls::Error),
+}
+
+/// Build the [`ClientConfig`] for the given root certificates and, optionally, the client
+/// certificate to present to servers.
+pub(crate) fn load(roots: &TlsRoots, client_cert: Option<&Path>) -> Result<ClientConfig, TlsError> {
+    let builder = ClientConfig::builder()
+        .with_safe_defaults()
+        .with_root_certificates(root_store(roots)?);
+    if let Some(path) = client_cert {
+        let (certs, key) = read_client_cert(path)?;
+        builder
+            .with_client_auth_cert(certs, key)
+            .map_err(|err| TlsError::InvalidClientCert(path.to_path_buf(), err))
+    } else {
+        Ok(builder.with_no_client_auth())
+    }
+}
+
+/// Disable certificate verification for the given [`ClientConfig`], such that any certificate
+/// presented by the server is accepted (e.g., a self-signed certificate).
+pub(crate) fn disable_verification(config: &mut ClientConfig) {
+    config
+        .dangerous()
+        .set_certificate_verifier(Arc::new(NoVerification));
+}
+
+/// A [`ServerCertVerifier`] that accepts any server certificate.
+struct NoVerification;
+
+impl ServerCertVerifier for NoVerification {
+    fn verify_server_cert(
+        &self,
+        _end_entity: &Certificate,
+        _intermediates: &[Certificate],
+        _server_name: &ServerName,
+        _scts: &mut dyn Iterator<Item = &[u8]>,
+        _ocsp_response: &[u8],
+        _now: SystemTime,
+    ) -> Result<ServerCertVerified, rustls::Error> {
+        Ok(ServerCertVerified::assertion())
+    }
+}
+
+/// Build the [`RootCertStore`] for the given root certificates.
+fn root_store(roots: &TlsRoots) -> Result<RootCertStore, TlsError> {
+    let mut store = RootCertStore::empty();
+    match roots {
+        TlsRoots::Webpki => {
+            store.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+                OwnedTrustAnchor::from_subject_spki_name_constraints(
+                    anchor.subject,
+                    anchor.spki,
+                    anchor.name_constraints,
+                )
+            }));
+        }
+        TlsRoots::Native => {
+            let certs = rustls_native_certs::load_native_certs().map_err(TlsError::Native)?;
+            let certs = certs.into_iter().map(|cert| cert.0).collect::<Vec<_>>();
+            let (valid, invalid) = store.add_parsable_certificates(&certs);
+            debug!("Loaded {valid} native root certificates (skipped {invalid} invalid)");
+        }
+        TlsRoots::Bundle(path) => {
+            let certs = read_bundle(path)?;
+            let (valid, invalid) = store.add_parsable_certificates(&certs);
+            debug!(
+                "Loaded {valid} root certificates from {} (skipped {invalid} invalid)",
+                path.display()
+            );
+            if valid == 0 {
+                return Err(TlsError::EmptyBundle(path.clone()));
+            }
+        }
+    }
+    Ok(store)
+}
+
+/// Read the DER-encoded certificates from a PEM-encoded CA bundle.
+fn read_bundle(path: &Path) -> Result<Vec<Vec<u8>>, TlsError> {
+    let file = fs_err::File::open(path).map_err(|err| TlsError::Bundle(path.to_path_buf(), err))?;
+    rustls_pemfile::certs(&mut BufReader::new(file))
+        .map_err(|err| TlsError::Bundle(path.to_path_buf(), err))
+}
+
+/// Read the certificate chain and private key from a PEM-encoded client certificate file.
+fn read_client_cert(path: &Path) -> Result<(Vec<Certificate>, PrivateKey), TlsError> {
+    let file =
+        fs_err::File::open(path).map_err(|err| TlsError::ClientCert(path.to_path_buf(), err))?;
+    let items = rustls_pemfile::read_all(&mut BufReader::new(file))
+        .map_err(|err| TlsError::ClientCert(path.to_path_buf(), err))?;
+
+    let mut certs = Vec::new();
+    let mut key = None;
+    for item in items {
+        match item {
+            Item::X509Certificate(cert) => certs.push(Certificate(cert)),
+            Item::PKCS8Key(der) | Item::RSAKey(der) | Item::ECKey(der) => {
+                key.get_or_insert(PrivateKey(der));
+            }
+            _ => {}
+        }
+    }
+
+    match key {
+        Some(key) if !certs.is_empty() => Ok((certs, key)),
+        _ => Err(TlsError::IncompleteClientCert(path.to_path_buf())),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::io::Write;
+
+    use super::{load, ClientCert, InsecureHost, TlsError, TlsRoots, TlsSettings};
+
+    #[test]
+    fn webpki() {
+        assert!(load(&TlsRoots::Webpki, None).is_ok());
+    }
+
+    #[test]
+    fn missing_bundle() {
+        let path = tempfile::tempdir().unwrap().path().join("missing.pem");
+        assert!(matches!(
+            load(&TlsRoots::Bundle(path), None),
+            Err(TlsError::Bundle(..))
+        ));
+    }
+
+    #[test]
+    fn empty_bundle() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "not a certificate").unwrap();
+        assert!(matches!(
+            load(&TlsRoots::Bundle(file.path().to_path_buf()), None),
+            Err(TlsError::EmptyBundle(..))
+        ));
+    }
+
+    #[test]
+    fn incomplete_client_cert() {
+        let mut file = tempfile::NamedTempFile::new().unwrap();
+        writeln!(file, "not a certificate").unwrap();
+        assert!(matches!(
+            load(&TlsRoots::Webpki, Some(file.path())),
+            Err(TlsError::IncompleteClientCert(..))
+        ));
+    }
+
+    #[test]
+    fn client_certs() {
+        let settings = TlsSettings {
+            roots: TlsRoots::Webpki,
+            client_certs: vec![
+                "global.pem".parse::<ClientCert>().unwrap(),
+                "https://pypi.example.com/simple=first.pem".parse().unwrap(),
+                "https://pypi.example.com:8443/simple=port.pem"
+                    .parse()
+                    .unwrap(),
+                "https://pypi.example.com/other=second.pem".parse().unwrap(),
+                "C:\\certs\\a=b.pem".parse().unwrap(),
+            ],
+            insecure_hosts: vec![
+                "https://pypi.example.com/simple"
+                    .parse::<InsecureHost>()
+                    .unwrap(),
+                "Mirror.internal:8080".parse().unwrap(),
+            ],
+        };
+        assert_eq!(
+            settings.global_client_cert(),
+            Some(std::path::Path::new("C:\\certs\\a=b.pem"))
+        );
+        assert_eq!(
+            settings
+                .host_client_certs()
+                .into_iter()
+                .map(|(host, path)| (host, path.to_str().unwrap()))
+                .collect::<Vec<_>>(),
+            vec![
+                ("pypi.example.com:8443", "port.pem"),
+                ("pypi.example.com", "second.pem"),
+            ]
+        );
+        assert_eq!(
+            settings
+                .host_settings()
+                .into_iter()
+                .map(|(host, path, insecure)| (host, path.and_then(|path| path.to_str()), insecure))
+                .collect::<Vec<_>>(),
+            vec![
+                ("pypi.example.com:8443", Some("port.pem"), false),
+                ("pypi.example.com", Some("second.pem"), true),
+                ("mirror.internal:8080", Some("C:\\certs\\a=b.pem"), true),
+            ]
+        );
+    }
+
+    #[test]
+    fn insecure_host() {
+        assert_eq!(
+            "https://user@mirror.internal:8443/simple"
+                .parse::<InsecureHost>()
+                .unwrap(),
+            InsecureHost("mirror.internal:8443".to_string())
+        );
+        assert_eq!(
+            "mirror.internal".parse::<InsecureHost>().unwrap(),
+            InsecureHost("mirror.internal".to_string())
+        );
+        assert!("user@mirror.internal".parse::<InsecureHost>().is_err());
+    }
+}
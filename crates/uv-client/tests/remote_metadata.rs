@@ -11,7 +11,7 @@ use uv_client::RegistryClientBuilder;
 #[tokio::test]
 async fn remote_metadata_with_and_without_cache() -> Result<()> {
     let cache = Cache::temp()?;
-    let client = RegistryClientBuilder::new(cache).build();
+    let client = RegistryClientBuilder::new(cache).build()?;
 
     // The first run is without cache (the tempdir is empty), the second has the cache from the
     // first run.
@@ -14,7 +14,9 @@ use uv_dispatch::BuildDispatch;
 use uv_installer::NoBinary;
 use uv_interpreter::Virtualenv;
 use uv_resolver::InMemoryIndex;
-use uv_traits::{BuildContext, BuildKind, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{
+    BuildContext, BuildKind, Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy,
+};
 
 #[derive(Parser)]
 pub(crate) struct BuildArgs {
@@ -55,7 +57,7 @@ pub(crate) async fn build(args: BuildArgs) -> Result<PathBuf> {
 
     let platform = Platform::current()?;
     let venv = Virtualenv::from_env(platform, &cache)?;
-    let client = RegistryClientBuilder::new(cache.clone()).build();
+    let client = RegistryClientBuilder::new(cache.clone()).build()?;
     let index_urls = IndexLocations::default();
     let flat_index = FlatIndex::default();
     let index = InMemoryIndex::default();
@@ -76,6 +78,7 @@ pub(crate) async fn build(args: BuildArgs) -> Result<PathBuf> {
         &config_settings,
         &NoBuild::None,
         &NoBinary::None,
+        Concurrency::default(),
     );
 
     let builder = SourceBuild::setup(
@@ -24,7 +24,7 @@ use uv_installer::{Downloader, NoBinary};
 use uv_interpreter::Virtualenv;
 use uv_normalize::PackageName;
 use uv_resolver::{DistFinder, InMemoryIndex};
-use uv_traits::{BuildContext, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{BuildContext, Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
 
 #[derive(Parser)]
 pub(crate) struct InstallManyArgs {
@@ -57,7 +57,7 @@ pub(crate) async fn install_many(args: InstallManyArgs) -> Result<()> {
     let cache = Cache::try_from(args.cache_args)?;
     let platform = Platform::current()?;
     let venv = Virtualenv::from_env(platform, &cache)?;
-    let client = RegistryClientBuilder::new(cache.clone()).build();
+    let client = RegistryClientBuilder::new(cache.clone()).build()?;
     let index_locations = IndexLocations::default();
     let flat_index = FlatIndex::default();
     let index = InMemoryIndex::default();
@@ -84,6 +84,7 @@ pub(crate) async fn install_many(args: InstallManyArgs) -> Result<()> {
         &config_settings,
         &no_build,
         &NoBinary::None,
+        Concurrency::default(),
     );
 
     for (idx, requirements) in requirements.chunks(100).enumerate() {
@@ -122,6 +123,7 @@ async fn install_chunk(
         venv.interpreter(),
         &FlatIndex::default(),
         &NoBinary::None,
+        build_dispatch.no_build(),
     )
     .resolve_stream(requirements)
     .collect()
@@ -18,7 +18,7 @@ use uv_dispatch::BuildDispatch;
 use uv_installer::NoBinary;
 use uv_interpreter::Virtualenv;
 use uv_resolver::{InMemoryIndex, Manifest, Options, Resolver};
-use uv_traits::{ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
 
 #[derive(ValueEnum, Default, Clone)]
 pub(crate) enum ResolveCliFormat {
@@ -64,19 +64,24 @@ pub(crate) async fn resolve_cli(args: ResolveCliArgs) -> Result<()> {
     );
     let client = RegistryClientBuilder::new(cache.clone())
         .index_urls(index_locations.index_urls())
-        .build();
+        .build()?;
+    let no_build = if args.no_build {
+        NoBuild::All
+    } else {
+        NoBuild::None
+    };
     let flat_index = {
         let client = FlatIndexClient::new(&client, &cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, venv.interpreter().tags()?)
+        FlatIndex::from_entries(
+            entries,
+            venv.interpreter().tags()?,
+            &no_build,
+            &NoBinary::None,
+        )
     };
     let index = InMemoryIndex::default();
     let in_flight = InFlight::default();
-    let no_build = if args.no_build {
-        NoBuild::All
-    } else {
-        NoBuild::None
-    };
     let config_settings = ConfigSettings::default();
 
     let build_dispatch = BuildDispatch::new(
@@ -92,6 +97,7 @@ pub(crate) async fn resolve_cli(args: ResolveCliArgs) -> Result<()> {
         &config_settings,
         &no_build,
         &NoBinary::None,
+        Concurrency::default(),
     );
 
     // Copied from `BuildDispatch`
@@ -21,7 +21,7 @@ use uv_installer::NoBinary;
 use uv_interpreter::Virtualenv;
 use uv_normalize::PackageName;
 use uv_resolver::InMemoryIndex;
-use uv_traits::{BuildContext, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{BuildContext, Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
 
 #[derive(Parser)]
 pub(crate) struct ResolveManyArgs {
@@ -75,7 +75,7 @@ pub(crate) async fn resolve_many(args: ResolveManyArgs) -> Result<()> {
     let platform = Platform::current()?;
     let venv = Virtualenv::from_env(platform, &cache)?;
     let in_flight = InFlight::default();
-    let client = RegistryClientBuilder::new(cache.clone()).build();
+    let client = RegistryClientBuilder::new(cache.clone()).build()?;
 
     let header_span = info_span!("resolve many");
     header_span.pb_set_style(&ProgressStyle::default_bar());
@@ -112,6 +112,7 @@ pub(crate) async fn resolve_many(args: ResolveManyArgs) -> Result<()> {
                     &config_settings,
                     &no_build,
                     &NoBinary::None,
+                    Concurrency::default(),
                 );
 
                 let start = Instant::now();
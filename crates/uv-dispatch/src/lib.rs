@@ -17,8 +17,12 @@ use uv_cache::Cache;
 use uv_client::{FlatIndex, RegistryClient};
 use uv_installer::{Downloader, Installer, NoBinary, Plan, Planner, Reinstall, SitePackages};
 use uv_interpreter::{Interpreter, Virtualenv};
+use uv_normalize::PackageName;
 use uv_resolver::{InMemoryIndex, Manifest, Options, Resolver};
-use uv_traits::{BuildContext, BuildKind, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{
+    BuildContext, BuildIsolation, BuildKind, Concurrency, ConfigSettings, InFlight, NoBuild,
+    PackageConfigSettings, SetupPyStrategy,
+};
 
 /// The main implementation of [`BuildContext`], used by the CLI, see [`BuildContext`]
 /// documentation.
@@ -35,8 +39,12 @@ pub struct BuildDispatch<'a> {
     no_build: &'a NoBuild,
     no_binary: &'a NoBinary,
     config_settings: &'a ConfigSettings,
+    package_config_settings: Option<&'a PackageConfigSettings>,
+    concurrency: Concurrency,
     source_build_context: SourceBuildContext,
     options: Options,
+    build_constraints: &'a [Requirement],
+    build_isolation: BuildIsolation<'a>,
 }
 
 impl<'a> BuildDispatch<'a> {
@@ -54,6 +62,7 @@ impl<'a> BuildDispatch<'a> {
         config_settings: &'a ConfigSettings,
         no_build: &'a NoBuild,
         no_binary: &'a NoBinary,
+        concurrency: Concurrency,
     ) -> Self {
         Self {
             client,
@@ -68,8 +77,12 @@ impl<'a> BuildDispatch<'a> {
             config_settings,
             no_build,
             no_binary,
-            source_build_context: SourceBuildContext::default(),
+            concurrency,
+            source_build_context: SourceBuildContext::new(concurrency.builds),
             options: Options::default(),
+            build_constraints: &[],
+            build_isolation: BuildIsolation::default(),
+            package_config_settings: None,
         }
     }
 
@@ -78,6 +91,33 @@ impl<'a> BuildDispatch<'a> {
         self.options = options;
         self
     }
+
+    /// Set the constraints to apply when resolving build requirements (e.g., the build backend
+    /// declared in `build-system.requires`).
+    #[must_use]
+    pub fn with_build_constraints(mut self, build_constraints: &'a [Requirement]) -> Self {
+        self.build_constraints = build_constraints;
+        self
+    }
+
+    /// Set whether source distributions should be built in isolated environments, or against the
+    /// packages installed in a shared environment.
+    #[must_use]
+    pub fn with_build_isolation(mut self, build_isolation: BuildIsolation<'a>) -> Self {
+        self.build_isolation = build_isolation;
+        self
+    }
+
+    /// Set the settings to pass to the build backends of specific packages, in addition to the
+    /// global config settings.
+    #[must_use]
+    pub fn with_package_config_settings(
+        mut self,
+        package_config_settings: &'a PackageConfigSettings,
+    ) -> Self {
+        self.package_config_settings = Some(package_config_settings);
+        self
+    }
 }
 
 impl<'a> BuildContext for BuildDispatch<'a> {
@@ -103,10 +143,29 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         self.no_binary
     }
 
+    fn build_isolation(&self) -> BuildIsolation {
+        self.build_isolation
+    }
+
     fn setup_py_strategy(&self) -> SetupPyStrategy {
         self.setup_py
     }
 
+    fn config_settings(&self, package: Option<&PackageName>) -> ConfigSettings {
+        // Apply any package-specific settings on top of the global settings.
+        match package
+            .zip(self.package_config_settings)
+            .and_then(|(package, settings)| settings.get(package))
+        {
+            Some(package_settings) => self.config_settings.clone().merge(package_settings.clone()),
+            None => self.config_settings.clone(),
+        }
+    }
+
+    fn concurrency(&self) -> Concurrency {
+        self.concurrency
+    }
+
     fn index_locations(&self) -> &IndexLocations {
         self.index_locations
     }
@@ -115,8 +174,15 @@ impl<'a> BuildContext for BuildDispatch<'a> {
         let markers = self.interpreter.markers();
         let tags = self.interpreter.tags()?;
         let resolver = Resolver::new(
-            Manifest::simple(requirements.to_vec()),
-            self.options,
+            Manifest::new(
+                requirements.to_vec(),
+                self.build_constraints.to_vec(),
+                Vec::new(),
+                Vec::new(),
+                None,
+                Vec::new(),
+            ),
+            self.options.clone(),
             markers,
             self.interpreter,
             tags,
@@ -274,6 +340,8 @@ impl<'a> BuildContext for BuildDispatch<'a> {
             }
         }
 
+        let config_settings = self.config_settings(dist.map(Name::name));
+
         let builder = SourceBuild::setup(
             source,
             subdirectory,
@@ -282,7 +350,7 @@ impl<'a> BuildContext for BuildDispatch<'a> {
             self.source_build_context.clone(),
             package_id.to_string(),
             self.setup_py,
-            self.config_settings.clone(),
+            config_settings,
             build_kind,
         )
         .boxed()
@@ -4,8 +4,9 @@ use std::path::Path;
 use std::sync::Arc;
 
 use futures::{FutureExt, TryStreamExt};
+use sha2::{Digest, Sha256};
 use tokio_util::compat::FuturesAsyncReadCompatExt;
-use tracing::{info_span, instrument, Instrument};
+use tracing::{debug, info_span, instrument, Instrument};
 use url::Url;
 
 use distribution_types::{
@@ -15,13 +16,14 @@ use platform_tags::Tags;
 use pypi_types::Metadata21;
 use uv_cache::{Cache, CacheBucket, Timestamp, WheelCache};
 use uv_client::{CacheControl, CachedClientError, Connectivity, RegistryClient};
-use uv_fs::metadata_if_exists;
+use uv_fs::{metadata_if_exists, write_atomic};
 use uv_git::GitSource;
 use uv_traits::{BuildContext, NoBinary, NoBuild};
 
 use crate::download::{BuiltWheel, UnzippedWheel};
+use crate::index::read_sha256;
 use crate::locks::Locks;
-use crate::reporter::Facade;
+use crate::reporter::{Facade, ProgressStream};
 use crate::{DiskWheel, Error, LocalWheel, Reporter, SourceDistCachedBuilder};
 
 /// A cached high-level interface to convert distributions (a requirement resolved to a location)
@@ -73,17 +75,22 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         }
     }
 
-    /// Handle a specific `reqwest` error, and convert it to [`io::Error`].
-    fn handle_response_errors(&self, err: reqwest::Error) -> io::Error {
-        if err.is_timeout() {
+    /// Handle a specific `reqwest` error surfaced while streaming a download.
+    fn handle_response_errors(&self, err: io::Error) -> io::Error {
+        let is_timeout = err.kind() == io::ErrorKind::TimedOut
+            || err
+                .get_ref()
+                .and_then(|err| err.downcast_ref::<reqwest::Error>())
+                .is_some_and(reqwest::Error::is_timeout);
+        if is_timeout {
             io::Error::new(
                 io::ErrorKind::TimedOut,
                 format!(
-                    "Failed to download distribution due to network timeout. Try increasing UV_HTTP_TIMEOUT (current value: {}s).",  self.client.timeout()
+                    "Failed to download distribution due to network timeout. Try increasing the timeout with `--timeout` or UV_HTTP_TIMEOUT (current value: {}s).",  self.client.timeout()
                 ),
             )
         } else {
-            io::Error::new(io::ErrorKind::Other, err)
+            err
         }
     }
 
@@ -159,30 +166,69 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                     wheel.filename.stem(),
                 );
                 let http_entry = wheel_entry.with_file(format!("{}.http", wheel.filename.stem()));
+                let sha256_entry =
+                    wheel_entry.with_file(format!("{}.sha256", wheel.filename.stem()));
+                let sha256 = wheel.file.hashes.sha256.as_deref();
+
+                // If the cached wheel wasn't verified against the expected digest, discard the
+                // cached response, such that the wheel is downloaded (and verified) again.
+                if let Some(sha256) = sha256 {
+                    if http_entry.path().exists()
+                        && !read_sha256(sha256_entry.path())
+                            .is_some_and(|recorded| recorded.eq_ignore_ascii_case(sha256))
+                    {
+                        debug!("Cached wheel has no matching digest; downloading again: {wheel}");
+                        fs_err::remove_file(http_entry.path()).map_err(Error::CacheWrite)?;
+                    }
+                }
 
                 let download = |response: reqwest::Response| {
                     async {
-                        let reader = response
-                            .bytes_stream()
-                            .map_err(|err| self.handle_response_errors(err))
-                            .into_async_read();
-
-                        // Download and unzip the wheel to a temporary directory.
+                        let size = response.content_length();
+                        let mut hasher = Sha256::new();
+                        let reader = ProgressStream::new(
+                            self.client.stream_download(response, sha256),
+                            wheel.name(),
+                            size,
+                            self.reporter.as_deref(),
+                        )
+                        .inspect_ok(|chunk| hasher.update(chunk))
+                        .map_err(|err| self.handle_response_errors(err))
+                        .into_async_read();
+
+                        // Download and unzip the wheel to a temporary directory. The digest is
+                        // verified as the wheel is streamed, so a mismatch fails before the wheel
+                        // is persisted.
                         let temp_dir =
                             tempfile::tempdir_in(self.cache.root()).map_err(Error::CacheWrite)?;
                         uv_extract::stream::unzip(reader.compat(), temp_dir.path()).await?;
 
-                        // Persist the temporary directory to the directory store.
+                        // Persist the temporary directory to the directory store, addressed by the
+                        // digest of the downloaded wheel.
+                        let digest = format!("{:x}", hasher.finalize());
                         let archive = self
                             .cache
-                            .persist(temp_dir.into_path(), wheel_entry.path())
+                            .persist(temp_dir.into_path(), wheel_entry.path(), Some(&digest))
                             .map_err(Error::CacheRead)?;
+
+                        // Record the verified digest, to check against on subsequent reads.
+                        match sha256 {
+                            Some(sha256) => write_atomic(sha256_entry.path(), sha256)
+                                .await
+                                .map_err(Error::CacheWrite)?,
+                            None => match fs_err::remove_file(sha256_entry.path()) {
+                                Ok(()) => {}
+                                Err(err) if err.kind() == io::ErrorKind::NotFound => {}
+                                Err(err) => return Err(Error::CacheWrite(err)),
+                            },
+                        }
+
                         Ok(archive)
                     }
                     .instrument(info_span!("download", wheel = %wheel))
                 };
 
-                let req = self.client.cached_client().uncached().get(url).build()?;
+                let req = self.client.download(url).build()?;
                 let cache_control = match self.client.connectivity() {
                     Connectivity::Online => CacheControl::from(
                         self.cache
@@ -224,32 +270,36 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
 
                 let download = |response: reqwest::Response| {
                     async {
-                        let reader = response
-                            .bytes_stream()
-                            .map_err(|err| self.handle_response_errors(err))
-                            .into_async_read();
+                        let size = response.content_length();
+                        let mut hasher = Sha256::new();
+                        let reader = ProgressStream::new(
+                            self.client.stream_download(response, None),
+                            wheel.name(),
+                            size,
+                            self.reporter.as_deref(),
+                        )
+                        .inspect_ok(|chunk| hasher.update(chunk))
+                        .map_err(|err| self.handle_response_errors(err))
+                        .into_async_read();
 
                         // Download and unzip the wheel to a temporary directory.
                         let temp_dir =
                             tempfile::tempdir_in(self.cache.root()).map_err(Error::CacheWrite)?;
                         uv_extract::stream::unzip(reader.compat(), temp_dir.path()).await?;
 
-                        // Persist the temporary directory to the directory store.
+                        // Persist the temporary directory to the directory store, addressed by the
+                        // digest of the downloaded wheel.
+                        let digest = format!("{:x}", hasher.finalize());
                         let archive = self
                             .cache
-                            .persist(temp_dir.into_path(), wheel_entry.path())
+                            .persist(temp_dir.into_path(), wheel_entry.path(), Some(&digest))
                             .map_err(Error::CacheRead)?;
                         Ok(archive)
                     }
                     .instrument(info_span!("download", wheel = %wheel))
                 };
 
-                let req = self
-                    .client
-                    .cached_client()
-                    .uncached()
-                    .get(wheel.url.raw().clone())
-                    .build()?;
+                let req = self.client.download(wheel.url.raw().clone()).build()?;
                 let cache_control = match self.client.connectivity() {
                     Connectivity::Online => CacheControl::from(
                         self.cache
@@ -321,19 +371,31 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
                 let lock = self.locks.acquire(&dist).await;
                 let _guard = lock.lock().await;
 
-                let built_wheel = self.builder.download_and_build(source_dist).boxed().await?;
+                // Pin Git dependencies to the precise commit, such that the installed distribution
+                // records the commit it was built from (e.g., in `direct_url.json`).
+                let source_dist = match self.precise(source_dist).await? {
+                    Some(url) => Cow::Owned(source_dist.clone().with_url(url)),
+                    None => Cow::Borrowed(source_dist),
+                };
+
+                let built_wheel = self
+                    .builder
+                    .download_and_build(&source_dist)
+                    .boxed()
+                    .await?;
+                let dist = Dist::Source(source_dist.into_owned());
 
                 // If the wheel was unzipped previously, respect it. Source distributions are
                 // cached under a unique build ID, so unzipped directories are never stale.
                 match built_wheel.target.canonicalize() {
                     Ok(archive) => Ok(LocalWheel::Unzipped(UnzippedWheel {
-                        dist: dist.clone(),
+                        dist,
                         archive,
                         filename: built_wheel.filename,
                     })),
                     Err(err) if err.kind() == io::ErrorKind::NotFound => {
                         Ok(LocalWheel::Built(BuiltWheel {
-                            dist: dist.clone(),
+                            dist,
                             path: built_wheel.path,
                             target: built_wheel.target,
                             filename: built_wheel.filename,
@@ -441,6 +503,8 @@ impl<'a, Context: BuildContext + Send + Sync> DistributionDatabase<'a, Context>
         } else {
             GitSource::new(url, git_dir)
         };
+        let source =
+            source.with_offline(matches!(self.client.connectivity(), Connectivity::Offline));
         let precise = tokio::task::spawn_blocking(move || source.fetch())
             .await?
             .map_err(Error::Git)?;
@@ -1,5 +1,8 @@
+use std::io;
 use std::path::{Path, PathBuf};
 
+use sha2::{Digest, Sha256};
+
 use distribution_filename::WheelFilename;
 use distribution_types::{CachedDist, Dist};
 
@@ -79,6 +82,21 @@ impl LocalWheel {
         }
     }
 
+    /// Compute the SHA-256 digest of the wheel archive, if it's stored on-disk.
+    ///
+    /// Returns `None` for wheels that were unzipped while downloading, as there's no archive to
+    /// read.
+    pub fn sha256(&self) -> io::Result<Option<String>> {
+        let path = match self {
+            Self::Unzipped(_) => return Ok(None),
+            Self::Disk(wheel) => &wheel.path,
+            Self::Built(wheel) => &wheel.path,
+        };
+        let mut hasher = Sha256::new();
+        io::copy(&mut fs_err::File::open(path)?, &mut hasher)?;
+        Ok(Some(format!("{:x}", hasher.finalize())))
+    }
+
     /// Convert a [`LocalWheel`] into a [`CachedDist`].
     pub fn into_cached_dist(self, archive: PathBuf) -> CachedDist {
         match self {
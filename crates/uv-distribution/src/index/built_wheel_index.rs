@@ -4,7 +4,9 @@ use uv_cache::{ArchiveTimestamp, Cache, CacheBucket, CacheShard, WheelCache};
 use uv_fs::symlinks;
 
 use crate::index::cached_wheel::CachedWheel;
-use crate::source::{read_http_manifest, read_timestamp_manifest, MANIFEST};
+use crate::source::{
+    read_cached_digest, read_http_manifest, read_timestamp_manifest, DIGEST, MANIFEST,
+};
 use crate::Error;
 
 /// A local index of built distributions for a specific source distribution.
@@ -47,6 +49,10 @@ impl BuiltWheelIndex {
             WheelCache::Path(&source_dist.url).remote_wheel_dir(source_dist.name().as_ref()),
         );
 
+        // Scope the cache to the contents of the source distribution.
+        let digest = read_cached_digest(&source_dist.path, &cache_shard.entry(DIGEST))?;
+        let cache_shard = cache_shard.shard(digest);
+
         // Determine the last-modified time of the source distribution.
         let Some(modified) = ArchiveTimestamp::from_path(&source_dist.path).expect("archived")
         else {
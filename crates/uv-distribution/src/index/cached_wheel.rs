@@ -3,6 +3,7 @@ use std::path::Path;
 use distribution_filename::WheelFilename;
 use distribution_types::{CachedDirectUrlDist, CachedRegistryDist};
 use pep508_rs::VerbatimUrl;
+use pypi_types::Hashes;
 use uv_cache::CacheEntry;
 
 #[derive(Debug, Clone)]
@@ -11,6 +12,8 @@ pub struct CachedWheel {
     pub filename: WheelFilename,
     /// The [`CacheEntry`] for the wheel.
     pub entry: CacheEntry,
+    /// The hashes of the archive from which the wheel was unzipped, as recorded alongside it.
+    pub hashes: Hashes,
 }
 
 impl CachedWheel {
@@ -20,7 +23,15 @@ impl CachedWheel {
         let filename = WheelFilename::from_stem(filename).ok()?;
         let archive = path.canonicalize().ok()?;
         let entry = CacheEntry::from_path(archive);
-        Some(Self { filename, entry })
+        let hashes = Hashes {
+            sha256: read_sha256(&path.with_file_name(format!("{}.sha256", filename.stem()))),
+            ..Hashes::default()
+        };
+        Some(Self {
+            filename,
+            entry,
+            hashes,
+        })
     }
 
     /// Convert a [`CachedWheel`] into a [`CachedRegistryDist`].
@@ -28,6 +39,7 @@ impl CachedWheel {
         CachedRegistryDist {
             filename: self.filename,
             path: self.entry.into_path_buf(),
+            hashes: self.hashes,
         }
     }
 
@@ -41,3 +53,11 @@ impl CachedWheel {
         }
     }
 }
+
+/// Read the SHA-256 digest recorded for a cached wheel, if any.
+pub(crate) fn read_sha256(path: &Path) -> Option<String> {
+    fs_err::read_to_string(path)
+        .ok()
+        .map(|digest| digest.trim().to_string())
+        .filter(|digest| !digest.is_empty())
+}
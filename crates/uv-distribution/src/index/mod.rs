@@ -1,4 +1,5 @@
 pub use built_wheel_index::BuiltWheelIndex;
+pub(crate) use cached_wheel::read_sha256;
 pub use registry_wheel_index::RegistryWheelIndex;
 
 mod built_wheel_index;
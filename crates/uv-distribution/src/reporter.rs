@@ -1,8 +1,12 @@
+use std::pin::Pin;
 use std::sync::Arc;
+use std::task::{ready, Context, Poll};
 
+use futures::{Stream, StreamExt};
 use url::Url;
 
 use distribution_types::SourceDist;
+use uv_normalize::PackageName;
 
 pub trait Reporter: Send + Sync {
     /// Callback to invoke when a source distribution build is kicked off.
@@ -16,6 +20,16 @@ pub trait Reporter: Send + Sync {
 
     /// Callback to invoke when a repository checkout completes.
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize);
+
+    /// Callback to invoke when the download of a distribution begins, with its size in bytes,
+    /// if known.
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize;
+
+    /// Callback to invoke when a chunk of a distribution has been downloaded.
+    fn on_download_progress(&self, id: usize, bytes: u64);
+
+    /// Callback to invoke when the download of a distribution completes.
+    fn on_download_complete(&self, name: &PackageName, id: usize);
 }
 
 /// A facade for converting from [`Reporter`] to [`uv_git::Reporter`].
@@ -38,3 +52,52 @@ impl uv_git::Reporter for Facade {
         self.reporter.on_checkout_complete(url, rev, index);
     }
 }
+
+/// A download stream that reports its progress to a [`Reporter`].
+pub(crate) struct ProgressStream<'a, S> {
+    stream: S,
+    name: &'a PackageName,
+    reporter: Option<(&'a dyn Reporter, usize)>,
+}
+
+impl<'a, S> ProgressStream<'a, S> {
+    pub(crate) fn new(
+        stream: S,
+        name: &'a PackageName,
+        size: Option<u64>,
+        reporter: Option<&'a dyn Reporter>,
+    ) -> Self {
+        let reporter = reporter.map(|reporter| (reporter, reporter.on_download_start(name, size)));
+        Self {
+            stream,
+            name,
+            reporter,
+        }
+    }
+}
+
+impl<S, B, E> Stream for ProgressStream<'_, S>
+where
+    S: Stream<Item = Result<B, E>> + Unpin,
+    B: AsRef<[u8]>,
+{
+    type Item = Result<B, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let item = ready!(self.stream.poll_next_unpin(cx));
+        match &item {
+            Some(Ok(chunk)) => {
+                if let Some((reporter, id)) = self.reporter {
+                    reporter.on_download_progress(id, chunk.as_ref().len() as u64);
+                }
+            }
+            Some(Err(_)) => {}
+            None => {
+                if let Some((reporter, id)) = self.reporter.take() {
+                    reporter.on_download_complete(self.name, id);
+                }
+            }
+        }
+        Poll::Ready(item)
+    }
+}
@@ -23,17 +23,18 @@ use pep508_rs::VerbatimUrl;
 use platform_tags::Tags;
 use pypi_types::Metadata21;
 use uv_cache::{
-    ArchiveTimestamp, CacheBucket, CacheEntry, CacheShard, CachedByTimestamp, Freshness, WheelCache,
+    archive_digest, ArchiveTimestamp, CacheBucket, CacheEntry, CacheShard, CachedByTimestamp,
+    Freshness, Timestamp, WheelCache,
 };
 use uv_client::{
     CacheControl, CachedClientError, Connectivity, DataWithCachePolicy, RegistryClient,
 };
-use uv_fs::{write_atomic, LockedFile};
+use uv_fs::{write_atomic, write_atomic_sync, LockedFile};
 use uv_git::{Fetch, GitSource};
-use uv_traits::{BuildContext, BuildKind, NoBuild, SourceBuildTrait};
+use uv_traits::{BuildContext, BuildKind, NoBuild, SetupPyStrategy, SourceBuildTrait};
 
 use crate::error::Error;
-use crate::reporter::Facade;
+use crate::reporter::{Facade, ProgressStream};
 use crate::source::built_wheel_metadata::BuiltWheelMetadata;
 use crate::source::manifest::Manifest;
 use crate::Reporter;
@@ -55,6 +56,10 @@ pub(crate) const MANIFEST: &str = "manifest.msgpack";
 /// The name of the file that contains the cached distribution metadata, encoded via `MsgPack`.
 pub(crate) const METADATA: &str = "metadata.msgpack";
 
+/// The name of the file that contains the cached digest of a local source tree, encoded via
+/// `MsgPack`.
+pub(crate) const DIGEST: &str = "digest.msgpack";
+
 impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
     /// Initialize a [`SourceDistCachedBuilder`] from a [`BuildContext`].
     pub fn new(build_context: &'a T, client: &'a RegistryClient, tags: &'a Tags) -> Self {
@@ -280,12 +285,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             .boxed()
             .instrument(info_span!("download", source_dist = %source_dist))
         };
-        let req = self
-            .client
-            .cached_client()
-            .uncached()
-            .get(url.clone())
-            .build()?;
+        let req = self.client.download(url.clone()).build()?;
         let manifest = self
             .client
             .cached_client()
@@ -301,9 +301,11 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         // manifest itself. There's also no need to lock, since we never replace entries within the
         // shard.
         let cache_shard = cache_shard.shard(manifest.id());
+        let source_dist_entry = cache_shard.entry(filename);
+        let cache_shard = self.settings_shard(cache_shard, source_dist);
 
         // If the cache contains a compatible wheel, return it.
-        if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, &cache_shard) {
+        if let Some(built_wheel) = self.find_built_wheel(source_dist, &cache_shard)? {
             return Ok(built_wheel);
         }
 
@@ -313,7 +315,6 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             .map(|reporter| reporter.on_build_start(source_dist));
 
         // Build the source distribution.
-        let source_dist_entry = cache_shard.entry(filename);
         let (disk_filename, wheel_filename, metadata) = self
             .build_source_dist(
                 source_dist,
@@ -383,12 +384,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             .boxed()
             .instrument(info_span!("download", source_dist = %source_dist))
         };
-        let req = self
-            .client
-            .cached_client()
-            .uncached()
-            .get(url.clone())
-            .build()?;
+        let req = self.client.download(url.clone()).build()?;
         let manifest = self
             .client
             .cached_client()
@@ -404,16 +400,24 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         // manifest itself. There's also no need to lock, since we never replace entries within the
         // shard.
         let cache_shard = cache_shard.shard(manifest.id());
+        let source_dist_entry = cache_shard.entry(filename);
+        let cache_shard = self.settings_shard(cache_shard, source_dist);
 
         // If the cache contains compatible metadata, return it.
         let metadata_entry = cache_shard.entry(METADATA);
-        if let Some(metadata) = read_cached_metadata(&metadata_entry).await? {
-            debug!("Using cached metadata for {source_dist}");
-            return Ok(metadata);
+        if self
+            .build_context
+            .cache()
+            .freshness(&metadata_entry, Some(source_dist.name()))
+            .is_ok_and(Freshness::is_fresh)
+        {
+            if let Some(metadata) = read_cached_metadata(&metadata_entry).await? {
+                debug!("Using cached metadata for {source_dist}");
+                return Ok(metadata);
+            }
         }
 
         // Otherwise, we either need to build the metadata or the wheel.
-        let source_dist_entry = cache_shard.entry(filename);
 
         // If the backend supports `prepare_metadata_for_build_wheel`, use it.
         if let Some(metadata) = self
@@ -475,6 +479,11 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 .remote_wheel_dir(path_source_dist.name().as_ref()),
         );
 
+        // Scope the cache to the contents of the source distribution, such that changes to the
+        // source (and not just to its `pyproject.toml` or `setup.py`) trigger a rebuild.
+        let digest = read_cached_digest(&path_source_dist.path, &cache_shard.entry(DIGEST))?;
+        let cache_shard = cache_shard.shard(digest);
+
         // Determine the last-modified time of the source distribution.
         let Some(modified) =
             ArchiveTimestamp::from_path(&path_source_dist.path).map_err(Error::CacheRead)?
@@ -497,9 +506,10 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         // manifest itself. There's also no need to lock, since we never replace entries within the
         // shard.
         let cache_shard = cache_shard.shard(manifest.id());
+        let cache_shard = self.settings_shard(cache_shard, source_dist);
 
         // If the cache contains a compatible wheel, return it.
-        if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, &cache_shard) {
+        if let Some(built_wheel) = self.find_built_wheel(source_dist, &cache_shard)? {
             return Ok(built_wheel);
         }
 
@@ -547,6 +557,11 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
                 .remote_wheel_dir(path_source_dist.name().as_ref()),
         );
 
+        // Scope the cache to the contents of the source distribution, such that changes to the
+        // source (and not just to its `pyproject.toml` or `setup.py`) trigger a rebuild.
+        let digest = read_cached_digest(&path_source_dist.path, &cache_shard.entry(DIGEST))?;
+        let cache_shard = cache_shard.shard(digest);
+
         // Determine the last-modified time of the source distribution.
         let Some(modified) =
             ArchiveTimestamp::from_path(&path_source_dist.path).map_err(Error::CacheRead)?
@@ -569,6 +584,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         // manifest itself. There's also no need to lock, since we never replace entries within the
         // shard.
         let cache_shard = cache_shard.shard(manifest.id());
+        let cache_shard = self.settings_shard(cache_shard, source_dist);
 
         // If the cache contains compatible metadata, return it.
         let metadata_entry = cache_shard.entry(METADATA);
@@ -641,9 +657,10 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             WheelCache::Git(&git_source_dist.url, &git_sha.to_short_string())
                 .remote_wheel_dir(git_source_dist.name().as_ref()),
         );
+        let cache_shard = self.settings_shard(cache_shard, source_dist);
 
         // If the cache contains a compatible wheel, return it.
-        if let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, &cache_shard) {
+        if let Some(built_wheel) = self.find_built_wheel(source_dist, &cache_shard)? {
             return Ok(built_wheel);
         }
 
@@ -697,6 +714,7 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             WheelCache::Git(&git_source_dist.url, &git_sha.to_short_string())
                 .remote_wheel_dir(git_source_dist.name().as_ref()),
         );
+        let cache_shard = self.settings_shard(cache_shard, source_dist);
 
         // If the cache contains compatible metadata, return it.
         let metadata_entry = cache_shard.entry(METADATA);
@@ -760,6 +778,49 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         Ok(metadata)
     }
 
+    /// Scope the cache shard to the settings used to build the source distribution (e.g., the PEP
+    /// 517 config settings), such that changing the settings triggers a rebuild.
+    ///
+    /// Builds with the default settings use the shard as-is.
+    fn settings_shard(&self, cache_shard: CacheShard, source_dist: &SourceDist) -> CacheShard {
+        let config_settings = self.build_context.config_settings(Some(source_dist.name()));
+        let setup_py = self.build_context.setup_py_strategy();
+        if config_settings.is_empty() && setup_py == SetupPyStrategy::default() {
+            cache_shard
+        } else {
+            cache_shard.shard(cache_key::digest(&(config_settings, setup_py)))
+        }
+    }
+
+    /// Find a compatible wheel in the given cache shard.
+    ///
+    /// Wheels that were built before the refresh cutoff for the source distribution (as in
+    /// `--refresh` or `--refresh-package`) are ignored, such that they're rebuilt even if the
+    /// source distribution itself is unchanged.
+    fn find_built_wheel(
+        &self,
+        source_dist: &SourceDist,
+        cache_shard: &CacheShard,
+    ) -> Result<Option<BuiltWheelMetadata>, Error> {
+        let Some(built_wheel) = BuiltWheelMetadata::find_in_cache(self.tags, cache_shard) else {
+            return Ok(None);
+        };
+        let freshness = self
+            .build_context
+            .cache()
+            .freshness(
+                &CacheEntry::from_path(&built_wheel.path),
+                Some(source_dist.name()),
+            )
+            .map_err(Error::CacheRead)?;
+        if freshness.is_fresh() {
+            Ok(Some(built_wheel))
+        } else {
+            debug!("Ignoring stale built wheel for {source_dist}");
+            Ok(None)
+        }
+    }
+
     /// Download and unzip a source distribution into the cache from an HTTP response.
     async fn persist_source_dist_url<'data>(
         &self,
@@ -779,10 +840,18 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
             info_span!("download_source_dist", filename = filename, source_dist = %source_dist);
         let temp_dir =
             tempfile::tempdir_in(self.build_context.cache().root()).map_err(Error::CacheWrite)?;
-        let reader = response
-            .bytes_stream()
-            .map_err(|err| std::io::Error::new(std::io::ErrorKind::Other, err))
-            .into_async_read();
+        let sha256 = match source_dist {
+            SourceDist::Registry(dist) => dist.file.hashes.sha256.as_deref(),
+            _ => None,
+        };
+        let size = response.content_length();
+        let reader = ProgressStream::new(
+            self.client.stream_download(response, sha256),
+            source_dist.name(),
+            size,
+            self.reporter.as_deref(),
+        )
+        .into_async_read();
         uv_extract::stream::archive(reader.compat(), filename, temp_dir.path()).await?;
         drop(span);
 
@@ -828,6 +897,8 @@ impl<'a, T: BuildContext> SourceDistCachedBuilder<'a, T> {
         } else {
             GitSource::new(url, git_dir)
         };
+        let source =
+            source.with_offline(matches!(self.client.connectivity(), Connectivity::Offline));
         let fetch = tokio::task::spawn_blocking(move || source.fetch())
             .await?
             .map_err(Error::Git)?;
@@ -1011,6 +1082,40 @@ pub(crate) fn read_timestamp_manifest(
     Ok(None)
 }
 
+/// Return the [`archive_digest`] of a local source distribution, reusing the digest stored in the
+/// cache entry if the source distribution's top-level modification time hasn't changed, rather
+/// than walking the entire source tree on every resolution.
+///
+/// If the cache entry is missing or stale, the digest is recomputed and stored.
+pub(crate) fn read_cached_digest(path: &Path, cache_entry: &CacheEntry) -> Result<String, Error> {
+    let modified = Timestamp::from_path(path).map_err(Error::CacheRead)?;
+
+    // If the cache entry is up-to-date, return it.
+    match fs_err::read(cache_entry.path()) {
+        Ok(cached) => {
+            let cached = rmp_serde::from_slice::<CachedByTimestamp<String>>(&cached)?;
+            if cached.timestamp == modified {
+                return Ok(cached.data);
+            }
+        }
+        Err(err) if err.kind() == std::io::ErrorKind::NotFound => {}
+        Err(err) => return Err(Error::CacheRead(err)),
+    }
+
+    // Otherwise, compute the digest and store it.
+    let digest = archive_digest(path).map_err(Error::CacheRead)?;
+    fs_err::create_dir_all(cache_entry.dir()).map_err(Error::CacheWrite)?;
+    write_atomic_sync(
+        cache_entry.path(),
+        rmp_serde::to_vec(&CachedByTimestamp {
+            timestamp: modified,
+            data: digest.clone(),
+        })?,
+    )
+    .map_err(Error::CacheWrite)?;
+    Ok(digest)
+}
+
 /// Read an existing timestamped [`Manifest`], if it exists and is up-to-date.
 ///
 /// If the cache entry is stale, a new entry will be created.
@@ -1019,8 +1124,9 @@ pub(crate) async fn refresh_timestamp_manifest(
     freshness: Freshness,
     modified: ArchiveTimestamp,
 ) -> Result<Manifest, Error> {
-    // If we know the exact modification time, we don't need to force a revalidate.
-    if matches!(modified, ArchiveTimestamp::Exact(_)) || freshness.is_fresh() {
+    // If the entry is stale (e.g., due to `--refresh`), we create a new manifest, even if the
+    // source distribution is unchanged, such that it's rebuilt.
+    if freshness.is_fresh() {
         if let Some(manifest) = read_timestamp_manifest(cache_entry, modified)? {
             return Ok(manifest);
         }
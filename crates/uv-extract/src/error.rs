@@ -18,4 +18,6 @@ pub enum Error {
     NonSingularArchive(Vec<OsString>),
     #[error("The top-level of the archive must only contain a list directory, but it's empty")]
     EmptyArchive,
+    #[error("Symlink `{}` in archive points outside of the archive: `{}`", path.display(), target.display())]
+    InvalidSymlink { path: PathBuf, target: PathBuf },
 }
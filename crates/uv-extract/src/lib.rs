@@ -3,6 +3,7 @@ pub use sync::*;
 
 mod error;
 pub mod stream;
+mod symlink;
 mod sync;
 mod tar;
 mod vendor;
@@ -6,13 +6,16 @@ use rustc_hash::FxHashSet;
 use tokio_util::compat::{FuturesAsyncReadCompatExt, TokioAsyncReadCompatExt};
 use tracing::warn;
 
-use crate::Error;
+use crate::{symlink, Error};
 
 /// Unzip a `.zip` archive into the target directory, without requiring `Seek`.
 ///
 /// This is useful for unzipping files as they're being downloaded. If the archive
 /// is already fully on disk, consider using `unzip_archive`, which can use multiple
 /// threads to work faster in that case.
+///
+/// The reader is always consumed to the end, such that any validation performed by the
+/// underlying stream upon completion (like hash verification) surfaces as an error here.
 pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
     reader: R,
     target: impl AsRef<Path>,
@@ -58,17 +61,13 @@ pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
         zip = entry.skip().await?;
     }
 
-    // On Unix, we need to set file permissions, which are stored in the central directory, at the
-    // end of the archive. The `ZipFileReader` reads until it sees a central directory signature,
-    // which indicates the first entry in the central directory. So we continue reading from there.
-    #[cfg(unix)]
+    // File permissions and types are stored in the central directory, at the end of the archive.
+    // The `ZipFileReader` reads until it sees a central directory signature, which indicates the
+    // first entry in the central directory. So we continue reading from there.
     {
-        use std::fs::Permissions;
-        use std::os::unix::fs::PermissionsExt;
-
         // To avoid lots of small reads to `reader` when parsing the central directory, wrap it in
         // a buffer.
-        let mut buf = futures::io::BufReader::new(reader);
+        let mut buf = futures::io::BufReader::new(&mut reader);
         let mut directory = async_zip::base::read::cd::CentralDirectoryReader::new(&mut buf);
         while let Some(entry) = directory.next().await? {
             if entry.dir()? {
@@ -79,24 +78,41 @@ pub async fn unzip<R: tokio::io::AsyncRead + Unpin>(
                 continue;
             };
 
+            // Symbolic links were written as files containing the link target; replace them with
+            // the links themselves.
+            if symlink::is_symlink(u32::from(mode)) {
+                let path = entry.filename().as_str()?;
+                symlink::materialize_async(target, Path::new(path)).await?;
+                continue;
+            }
+
             // The executable bit is the only permission we preserve, otherwise we use the OS defaults.
             // https://github.com/pypa/pip/blob/3898741e29b7279e7bffe044ecfbe20f6a438b1e/src/pip/_internal/utils/unpacking.py#L88-L100
-            let has_any_executable_bit = mode & 0o111;
-            if has_any_executable_bit != 0 {
-                // Construct the (expected) path to the file on-disk.
-                let path = entry.filename().as_str()?;
-                let path = target.join(path);
-
-                let permissions = fs_err::tokio::metadata(&path).await?.permissions();
-                fs_err::tokio::set_permissions(
-                    &path,
-                    Permissions::from_mode(permissions.mode() | 0o111),
-                )
-                .await?;
+            #[cfg(unix)]
+            {
+                use std::fs::Permissions;
+                use std::os::unix::fs::PermissionsExt;
+
+                let has_any_executable_bit = mode & 0o111;
+                if has_any_executable_bit != 0 {
+                    // Construct the (expected) path to the file on-disk.
+                    let path = entry.filename().as_str()?;
+                    let path = target.join(path);
+
+                    let permissions = fs_err::tokio::metadata(&path).await?.permissions();
+                    fs_err::tokio::set_permissions(
+                        &path,
+                        Permissions::from_mode(permissions.mode() | 0o111),
+                    )
+                    .await?;
+                }
             }
         }
     }
 
+    // Read any trailing bytes, to ensure that the stream runs to completion.
+    futures::io::copy(&mut reader, &mut futures::io::sink()).await?;
+
     Ok(())
 }
 
@@ -153,16 +169,23 @@ async fn untar_in<R: tokio::io::AsyncRead + Unpin, P: AsRef<Path>>(
 
 /// Unzip a `.tar.gz` archive into the target directory, without requiring `Seek`.
 ///
-/// This is useful for unpacking files as they're being downloaded.
+/// This is useful for unpacking files as they're being downloaded. As with [`unzip`], the reader
+/// is always consumed to the end.
 pub async fn untar<R: tokio::io::AsyncBufRead + Unpin>(
-    reader: R,
+    mut reader: R,
     target: impl AsRef<Path>,
 ) -> Result<(), Error> {
-    let decompressed_bytes = async_compression::tokio::bufread::GzipDecoder::new(reader);
+    let decompressed_bytes = async_compression::tokio::bufread::GzipDecoder::new(&mut reader);
     let mut archive = tokio_tar::ArchiveBuilder::new(decompressed_bytes)
         .set_preserve_mtime(false)
         .build();
-    Ok(untar_in(&mut archive, target.as_ref()).await?)
+    untar_in(&mut archive, target.as_ref()).await?;
+    drop(archive);
+
+    // Read any trailing bytes, to ensure that the stream runs to completion.
+    tokio::io::copy(&mut reader, &mut tokio::io::sink()).await?;
+
+    Ok(())
 }
 
 /// Unzip a `.zip` or `.tar.gz` archive into the target directory, without requiring `Seek`.
@@ -0,0 +1,91 @@
+use std::path::{Component, Path, PathBuf};
+
+use crate::Error;
+
+/// The file type bits of a Unix mode.
+const S_IFMT: u32 = 0o170_000;
+
+/// The file type bits of a symbolic link.
+const S_IFLNK: u32 = 0o120_000;
+
+/// Returns `true` if the Unix mode of an archive entry describes a symbolic link.
+///
+/// Zip archives store symbolic links as regular entries whose contents are the link target, with
+/// the file type recorded in the upper bits of the external attributes.
+pub(crate) fn is_symlink(mode: u32) -> bool {
+    mode & S_IFMT == S_IFLNK
+}
+
+/// Resolve the target of the symbolic link at `path` (relative to the archive root), returning
+/// the target relative to the archive root.
+///
+/// Links must be relative and must resolve to a path within the archive.
+pub(crate) fn resolve(path: &Path, link: &str) -> Result<PathBuf, Error> {
+    let link = Path::new(link);
+    let invalid = || Error::InvalidSymlink {
+        path: path.to_path_buf(),
+        target: link.to_path_buf(),
+    };
+
+    let mut resolved = path.parent().map(Path::to_path_buf).unwrap_or_default();
+    for component in link.components() {
+        match component {
+            Component::Normal(component) => resolved.push(component),
+            Component::CurDir => {}
+            Component::ParentDir => {
+                if !resolved.pop() {
+                    return Err(invalid());
+                }
+            }
+            Component::RootDir | Component::Prefix(_) => return Err(invalid()),
+        }
+    }
+    if resolved.as_os_str().is_empty() {
+        return Err(invalid());
+    }
+    Ok(resolved)
+}
+
+/// Replace the placeholder file written for a symbolic link entry at `target.join(path)` with the
+/// link itself.
+///
+/// On Windows, where creating symbolic links typically requires elevated privileges, the link
+/// target is copied in its place instead.
+pub(crate) fn materialize(target: &Path, path: &Path) -> Result<(), Error> {
+    let location = target.join(path);
+    let link = fs_err::read_to_string(&location)?;
+    let resolved = target.join(resolve(path, &link)?);
+    fs_err::remove_file(&location)?;
+    replace(&location, &link, &resolved)
+}
+
+/// Replace the placeholder file written for a symbolic link entry at `target.join(path)` with the
+/// link itself.
+///
+/// See [`materialize`].
+pub(crate) async fn materialize_async(target: &Path, path: &Path) -> Result<(), Error> {
+    let location = target.join(path);
+    let link = fs_err::tokio::read_to_string(&location).await?;
+    let resolved = target.join(resolve(path, &link)?);
+    fs_err::tokio::remove_file(&location).await?;
+    replace(&location, &link, &resolved)
+}
+
+#[cfg(unix)]
+fn replace(location: &Path, link: &str, _resolved: &Path) -> Result<(), Error> {
+    fs_err::os::unix::fs::symlink(link, location)?;
+    Ok(())
+}
+
+#[cfg(windows)]
+fn replace(location: &Path, _link: &str, resolved: &Path) -> Result<(), Error> {
+    if resolved.is_file() {
+        fs_err::copy(resolved, location)?;
+    } else {
+        tracing::warn!(
+            "Skipping symlink to a missing file or directory in archive: {}",
+            location.display()
+        );
+    }
+    Ok(())
+}
@@ -6,7 +6,7 @@ use rustc_hash::FxHashSet;
 use zip::ZipArchive;
 
 use crate::vendor::{CloneableSeekableReader, HasLength};
-use crate::Error;
+use crate::{symlink, Error};
 
 /// Unzip a `.zip` archive into the target directory.
 pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
@@ -16,6 +16,7 @@ pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
     // Unzip in parallel.
     let archive = ZipArchive::new(CloneableSeekableReader::new(reader))?;
     let directories = Mutex::new(FxHashSet::default());
+    let symlinks = Mutex::new(Vec::new());
     (0..archive.len())
         .par_bridge()
         .map(|file_number| {
@@ -48,6 +49,13 @@ pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
             let mut outfile = fs_err::File::create(&path)?;
             std::io::copy(&mut file, &mut outfile)?;
 
+            // Symbolic links are written as files containing the link target, and replaced once
+            // all other entries have been extracted, such that no entry is written through a link.
+            if file.unix_mode().is_some_and(symlink::is_symlink) {
+                symlinks.lock().unwrap().push(enclosed_name.to_path_buf());
+                return Ok(());
+            }
+
             // See `uv_extract::stream::unzip`. For simplicity, this is identical with the code there except for being
             // sync.
             #[cfg(unix)]
@@ -70,7 +78,13 @@ pub fn unzip<R: Send + std::io::Read + std::io::Seek + HasLength>(
 
             Ok(())
         })
-        .collect::<Result<_, Error>>()
+        .collect::<Result<(), Error>>()?;
+
+    for path in symlinks.into_inner().unwrap() {
+        symlink::materialize(target, &path)?;
+    }
+
+    Ok(())
 }
 
 /// Extract the top-level directory from an unpacked archive.
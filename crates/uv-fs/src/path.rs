@@ -1,5 +1,5 @@
 use std::borrow::Cow;
-use std::path::Path;
+use std::path::{Component, Path, PathBuf};
 
 pub trait Normalized {
     /// Normalize a [`Path`].
@@ -46,6 +46,59 @@ pub fn normalize_url_path(path: &str) -> Cow<'_, str> {
     }
 }
 
+/// Compute the path to `path`, relative to the directory `base`.
+///
+/// Both paths are expected to be absolute, and are normalized lexically (i.e., `..` components are
+/// resolved without touching the filesystem). Returns `None` if the paths don't share a root (e.g.,
+/// if they're on different drives on Windows).
+pub fn relative_to(path: impl AsRef<Path>, base: impl AsRef<Path>) -> Option<PathBuf> {
+    /// Resolve `.` and `..` components lexically.
+    fn normalize(path: &Path) -> Vec<Component> {
+        let mut components = Vec::new();
+        for component in path.components() {
+            match component {
+                Component::CurDir => {}
+                Component::ParentDir => {
+                    if matches!(components.last(), Some(Component::Normal(_))) {
+                        components.pop();
+                    }
+                }
+                component => components.push(component),
+            }
+        }
+        components
+    }
+
+    let path = normalize(path.as_ref());
+    let base = normalize(base.as_ref());
+
+    // The paths must share a root (e.g., the same drive on Windows).
+    let is_root =
+        |component: &Component| matches!(component, Component::Prefix(_) | Component::RootDir);
+    if path
+        .iter()
+        .take_while(|c| is_root(c))
+        .ne(base.iter().take_while(|c| is_root(c)))
+    {
+        return None;
+    }
+
+    let common = path
+        .iter()
+        .zip(base.iter())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    let mut relative = PathBuf::new();
+    for _ in common..base.len() {
+        relative.push(Component::ParentDir);
+    }
+    for component in &path[common..] {
+        relative.push(component);
+    }
+    Some(relative)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +141,31 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn relative() {
+        if cfg!(windows) {
+            return;
+        }
+
+        assert_eq!(
+            relative_to("/home/ferris/project/libs/foo", "/home/ferris/project"),
+            Some(PathBuf::from("libs/foo"))
+        );
+        assert_eq!(
+            relative_to("/home/ferris/libs/foo", "/home/ferris/project/requirements"),
+            Some(PathBuf::from("../../libs/foo"))
+        );
+        assert_eq!(
+            relative_to("/home/ferris/project", "/home/ferris/project"),
+            Some(PathBuf::new())
+        );
+        assert_eq!(
+            relative_to(
+                "/home/ferris/project/./libs/../foo",
+                "/home/ferris/other/.."
+            ),
+            Some(PathBuf::from("project/foo"))
+        );
+    }
 }
@@ -51,6 +51,17 @@ enum RefspecStrategy {
     First,
 }
 
+/// The depth of history to request when fetching refspecs.
+#[derive(Debug, Clone, Copy)]
+enum FetchDepth {
+    /// Fetch the complete history of each refspec.
+    Full,
+    /// Fetch only the commit at the tip of each refspec.
+    Shallow,
+    /// Fetch the remaining history of a repository that was previously fetched shallowly.
+    Unshallow,
+}
+
 impl GitReference {
     pub(crate) fn from_rev(rev: &str) -> Self {
         if rev.starts_with("refs/") {
@@ -248,6 +259,11 @@ impl GitDatabase {
         Ok(GitShortID(obj.short_id()?))
     }
 
+    /// Resolves a reference to an object ID, using only the objects the database currently has.
+    pub(crate) fn resolve(&self, reference: &GitReference) -> Result<git2::Oid> {
+        reference.resolve(&self.repo)
+    }
+
     /// Checks if the database contains the object of this `oid`.
     pub(crate) fn contains(&self, oid: git2::Oid) -> bool {
         self.repo.revparse_single(&oid.to_string()).is_ok()
@@ -990,18 +1006,31 @@ pub(crate) fn fetch(
         }
     }
 
+    // Unless we need to search the repository's history for a short commit hash, we only need
+    // the commit at the tip of each refspec, so we can perform a shallow fetch. Conversely, if a
+    // previous shallow fetch truncated the history, we need to fetch the remainder.
+    let depth = if !tags {
+        FetchDepth::Shallow
+    } else if repo.is_shallow() {
+        FetchDepth::Unshallow
+    } else {
+        FetchDepth::Full
+    };
+
     debug!("Performing a Git fetch for: {remote_url}");
     match strategy {
         FetchStrategy::Cli => {
             let result = match refspec_strategy {
-                RefspecStrategy::All => fetch_with_cli(repo, remote_url, refspecs.as_slice(), tags),
+                RefspecStrategy::All => {
+                    fetch_with_cli(repo, remote_url, refspecs.as_slice(), tags, depth)
+                }
                 RefspecStrategy::First => {
                     // Try each refspec
                     let mut errors = refspecs
                         .iter()
                         .map_while(|refspec| {
                             let fetch_result =
-                                fetch_with_cli(repo, remote_url, &[refspec.clone()], tags);
+                                fetch_with_cli(repo, remote_url, &[refspec.clone()], tags, depth);
 
                             // Stop after the first success and log failures
                             match fetch_result {
@@ -1048,6 +1077,16 @@ pub(crate) fn fetch(
                 if tags {
                     opts.download_tags(git2::AutotagOption::All);
                 }
+                match depth {
+                    FetchDepth::Full => {}
+                    FetchDepth::Shallow => {
+                        opts.depth(1);
+                    }
+                    FetchDepth::Unshallow => {
+                        // Equivalent to libgit2's `GIT_FETCH_DEPTH_UNSHALLOW`.
+                        opts.depth(i32::MAX);
+                    }
+                }
 
                 // The `fetch` operation here may fail spuriously due to a corrupt
                 // repository. It could also fail, however, for a whole slew of other
@@ -1108,12 +1147,22 @@ fn fetch_with_cli(
     url: &str,
     refspecs: &[String],
     tags: bool,
+    depth: FetchDepth,
 ) -> Result<()> {
     let mut cmd = ProcessBuilder::new("git");
     cmd.arg("fetch");
     if tags {
         cmd.arg("--tags");
     }
+    match depth {
+        FetchDepth::Full => {}
+        FetchDepth::Shallow => {
+            cmd.arg("--depth=1");
+        }
+        FetchDepth::Unshallow => {
+            cmd.arg("--unshallow");
+        }
+    }
     cmd.arg("--force") // handle force pushes
         .arg("--update-head-ok") // see discussion in #2078
         .arg(url)
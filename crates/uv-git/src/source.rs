@@ -3,7 +3,7 @@
 //! Source: <https://github.com/rust-lang/cargo/blob/23eb492cf920ce051abfc56bbaf838514dc8365c/src/cargo/sources/git/source.rs>
 use std::path::{Path, PathBuf};
 
-use anyhow::Result;
+use anyhow::{bail, Context, Result};
 use reqwest::Client;
 use tracing::{debug, instrument};
 use url::Url;
@@ -25,6 +25,8 @@ pub struct GitSource {
     cache: PathBuf,
     /// The reporter to use for this source.
     reporter: Option<Box<dyn Reporter>>,
+    /// Whether to avoid network access, using only the repositories already in the cache.
+    offline: bool,
 }
 
 impl GitSource {
@@ -36,9 +38,16 @@ impl GitSource {
             strategy: FetchStrategy::Cli,
             cache: cache.into(),
             reporter: None,
+            offline: false,
         }
     }
 
+    /// Avoid network access, using only the repositories already in the cache.
+    #[must_use]
+    pub fn with_offline(self, offline: bool) -> Self {
+        Self { offline, ..self }
+    }
+
     /// Set the [`Reporter`] to use for this `GIt` source.
     #[must_use]
     pub fn with_reporter(self, reporter: impl Reporter + 'static) -> Self {
@@ -61,6 +70,30 @@ impl GitSource {
             // which has that revision, then no update needs to happen.
             (Some(rev), Some(db)) if db.contains(rev.into()) => (db, rev, None),
 
+            // If we're offline, resolve the reference against the existing database, without
+            // fetching any updates.
+            (None, Some(db)) if self.offline => {
+                let rev = db
+                    .resolve(&self.git.reference)
+                    .with_context(|| {
+                        format!(
+                            "Network connectivity is disabled, but `{}` wasn't found in the cache for: `{}`",
+                            self.git.reference.as_str(),
+                            self.git.repository
+                        )
+                    })?;
+                (db, GitSha::from(rev), None)
+            }
+
+            // If we're offline and the database is missing (or lacks the locked revision), there's
+            // nothing we can do.
+            _ if self.offline => {
+                bail!(
+                    "Network connectivity is disabled, but the requested Git repository wasn't found in the cache for: `{}`",
+                    self.git.repository
+                );
+            }
+
             // ... otherwise we use this state to update the git database. Note
             // that we still check for being offline here, for example in the
             // situation that we have a locked revision but the database
@@ -12,6 +12,7 @@ use platform_tags::Tags;
 use uv_cache::Cache;
 use uv_client::RegistryClient;
 use uv_distribution::{DistributionDatabase, LocalWheel, Unzip};
+use uv_normalize::PackageName;
 use uv_traits::{BuildContext, InFlight};
 
 use crate::editable::BuiltEditable;
@@ -36,6 +37,8 @@ pub struct Downloader<'a, Context: BuildContext + Send + Sync> {
     database: DistributionDatabase<'a, Context>,
     cache: &'a Cache,
     reporter: Option<Arc<dyn Reporter>>,
+    /// The maximum number of distributions to fetch at once.
+    concurrency: usize,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
@@ -49,6 +52,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             database: DistributionDatabase::new(cache, tags, client, build_context),
             reporter: None,
             cache,
+            concurrency: build_context.concurrency().downloads,
         }
     }
 
@@ -60,6 +64,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
             reporter: Some(reporter.clone()),
             database: self.database.with_reporter(Facade::from(reporter.clone())),
             cache: self.cache,
+            concurrency: self.concurrency,
         }
     }
 
@@ -77,9 +82,8 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 }
                 Ok::<CachedDist, Error>(wheel)
             })
-            // TODO(charlie): The number of concurrent fetches, such that we limit the number of
-            // concurrent builds to the number of cores, while allowing more concurrent downloads.
-            .buffer_unordered(50)
+            // Builds are limited separately, by the build context.
+            .buffer_unordered(self.concurrency)
     }
 
     /// Download, build, and unzip a set of downloaded wheels.
@@ -133,7 +137,7 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 }
                 Ok::<_, Error>((editable, cached_dist, metadata))
             })
-            .buffer_unordered(50);
+            .buffer_unordered(self.concurrency);
 
         while let Some((editable, wheel, metadata)) = fetches.next().await.transpose()? {
             if let Some(reporter) = self.reporter.as_ref() {
@@ -205,8 +209,10 @@ impl<'a, Context: BuildContext + Send + Sync> Downloader<'a, Context> {
                 let temp_dir = tempfile::tempdir_in(cache.root())?;
                 download.unzip(temp_dir.path())?;
 
-                // Persist the temporary directory to the directory store.
-                Ok(cache.persist(temp_dir.into_path(), download.target())?)
+                // Persist the temporary directory to the directory store, addressed by the digest
+                // of the wheel.
+                let sha256 = download.sha256()?;
+                Ok(cache.persist(temp_dir.into_path(), download.target(), sha256.as_deref())?)
             }
         })
         .await?
@@ -241,6 +247,15 @@ pub trait Reporter: Send + Sync {
 
     /// Callback to invoke when a repository checkout completes.
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize);
+    /// Callback to invoke when the download of a distribution begins, with its size in bytes,
+    /// if known.
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize;
+
+    /// Callback to invoke when a chunk of a distribution has been downloaded.
+    fn on_download_progress(&self, id: usize, bytes: u64);
+
+    /// Callback to invoke when the download of a distribution completes.
+    fn on_download_complete(&self, name: &PackageName, id: usize);
 }
 
 /// A facade for converting from [`Reporter`] to [`uv_git::Reporter`].
@@ -270,4 +285,15 @@ impl uv_distribution::Reporter for Facade {
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize) {
         self.reporter.on_checkout_complete(url, rev, index);
     }
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize {
+        self.reporter.on_download_start(name, size)
+    }
+
+    fn on_download_progress(&self, id: usize, bytes: u64) {
+        self.reporter.on_download_progress(id, bytes);
+    }
+
+    fn on_download_complete(&self, name: &PackageName, id: usize) {
+        self.reporter.on_download_complete(name, id);
+    }
 }
@@ -8,6 +8,7 @@ use uv_interpreter::Virtualenv;
 pub struct Installer<'a> {
     venv: &'a Virtualenv,
     link_mode: install_wheel_rs::linker::LinkMode,
+    compile: bool,
     reporter: Option<Box<dyn Reporter>>,
 }
 
@@ -17,6 +18,7 @@ impl<'a> Installer<'a> {
         Self {
             venv,
             link_mode: install_wheel_rs::linker::LinkMode::default(),
+            compile: false,
             reporter: None,
         }
     }
@@ -27,6 +29,14 @@ impl<'a> Installer<'a> {
         Self { link_mode, ..self }
     }
 
+    /// Compile the installed Python files to bytecode.
+    ///
+    /// Wheels are installed (and compiled) in parallel, with one Python process per wheel.
+    #[must_use]
+    pub fn with_compile(self, compile: bool) -> Self {
+        Self { compile, ..self }
+    }
+
     /// Set the [`Reporter`] to use for this installer.
     #[must_use]
     pub fn with_reporter(self, reporter: impl Reporter + 'static) -> Self {
@@ -44,7 +54,8 @@ impl<'a> Installer<'a> {
                 let location = install_wheel_rs::InstallLocation::new(
                     self.venv.root(),
                     self.venv.interpreter().python_tuple(),
-                );
+                )
+                .with_python(self.venv.python_executable());
 
                 install_wheel_rs::linker::install_wheel(
                     &location,
@@ -58,6 +69,7 @@ impl<'a> Installer<'a> {
                         .as_ref(),
                     Some("uv"),
                     self.link_mode,
+                    self.compile,
                 )
                 .with_context(|| format!("Failed to install: {} ({wheel})", wheel.filename()))?;
 
@@ -3,7 +3,7 @@ pub use editable::{BuiltEditable, ResolvedEditable};
 pub use installer::{Installer, Reporter as InstallReporter};
 pub use plan::{Plan, Planner, Reinstall};
 // TODO(zanieb): Just import this properly everywhere else
-pub use site_packages::SitePackages;
+pub use site_packages::{Diagnostic, SitePackages};
 pub use uninstall::uninstall;
 pub use uv_traits::NoBinary;
 mod downloader;
@@ -8,8 +8,8 @@ use rustc_hash::FxHashMap;
 use tracing::{debug, warn};
 
 use distribution_types::{
-    BuiltDist, CachedDirectUrlDist, CachedDist, Dist, IndexLocations, InstalledDirectUrlDist,
-    InstalledDist, InstalledMetadata, InstalledVersion, Name, SourceDist,
+    BuiltDist, CachedDirectUrlDist, CachedDist, CachedRegistryDist, Dist, IndexLocations,
+    InstalledDirectUrlDist, InstalledDist, InstalledMetadata, InstalledVersion, Name, SourceDist,
 };
 use pep508_rs::{Requirement, VersionOrUrl};
 use platform_tags::Tags;
@@ -27,6 +27,7 @@ use crate::{ResolvedEditable, SitePackages};
 pub struct Planner<'a> {
     requirements: &'a [Requirement],
     editable_requirements: &'a [ResolvedEditable],
+    hashes: Option<&'a FxHashMap<PackageName, Vec<String>>>,
 }
 
 impl<'a> Planner<'a> {
@@ -36,6 +37,7 @@ impl<'a> Planner<'a> {
         Self {
             requirements,
             editable_requirements: &[],
+            hashes: None,
         }
     }
 
@@ -48,6 +50,19 @@ impl<'a> Planner<'a> {
         }
     }
 
+    /// Require that every cached distribution matches one of the given hashes, keyed by package
+    /// name (as in `--require-hashes`).
+    ///
+    /// Registry wheels are only reused from the cache if the digest recorded when they were
+    /// downloaded matches; all other cached distributions are downloaded (and verified) again.
+    #[must_use]
+    pub fn with_hashes(self, hashes: &'a FxHashMap<PackageName, Vec<String>>) -> Self {
+        Self {
+            hashes: Some(hashes),
+            ..self
+        }
+    }
+
     /// Partition a set of requirements into those that should be linked from the cache, those that
     /// need to be downloaded, and those that should be removed.
     ///
@@ -216,11 +231,28 @@ impl<'a> Planner<'a> {
                 continue;
             }
 
+            // In hash-checking mode, only reuse cached wheels with a matching recorded digest.
+            let hashes = self
+                .hashes
+                .map(|hashes| hashes.get(&requirement.name).map_or(&[][..], Vec::as_slice));
+            let matches_hashes = |distribution: &CachedRegistryDist| {
+                hashes.map_or(true, |hashes| {
+                    hashes.iter().any(|hash| distribution.hashes.matches(hash))
+                })
+            };
+            if hashes.is_some() && matches!(requirement.version_or_url, Some(VersionOrUrl::Url(_)))
+            {
+                debug!("Ignoring cached distribution in hash-checking mode: {requirement}");
+                remote.push(requirement.clone());
+                continue;
+            }
+
             // Identify any locally-available distributions that satisfy the requirement.
             match requirement.version_or_url.as_ref() {
                 None => {
-                    if let Some((_version, distribution)) =
-                        registry_index.get(&requirement.name).next()
+                    if let Some((_version, distribution)) = registry_index
+                        .get(&requirement.name)
+                        .find(|(_version, distribution)| matches_hashes(distribution))
                     {
                         debug!("Requirement already cached: {distribution}");
                         local.push(CachedDist::Registry(distribution.clone()));
@@ -232,7 +264,7 @@ impl<'a> Planner<'a> {
                         registry_index
                             .get(&requirement.name)
                             .find_map(|(version, distribution)| {
-                                if specifier.contains(version) {
+                                if specifier.contains(version) && matches_hashes(distribution) {
                                     Some(distribution)
                                 } else {
                                     None
@@ -43,9 +43,15 @@ impl<'a> SitePackages<'a> {
         // Index all installed packages by name.
         for entry in fs::read_dir(venv.site_packages())? {
             let entry = entry?;
-            if entry.file_type()?.is_dir() {
-                let path = entry.path();
-
+            let path = entry.path();
+
+            // Legacy `.egg-info` metadata may be stored as a file, rather than a directory, and
+            // legacy editable installs are represented by an `.egg-link` file.
+            if entry.file_type()?.is_dir()
+                || path
+                    .extension()
+                    .is_some_and(|ext| ext == "egg-info" || ext == "egg-link")
+            {
                 let Some(dist_info) = InstalledDist::try_from_path(&path)
                     .with_context(|| format!("Failed to read metadata: from {}", path.display()))?
                 else {
@@ -10,6 +10,9 @@ pub struct PyVenvConfiguration {
     pub(crate) virtualenv: bool,
     /// The version of the `uv` package used to create the virtual environment, if any.
     pub(crate) uv: bool,
+    /// The full version of the Python interpreter used to create the virtual environment, if
+    /// known (e.g., `3.12.1`).
+    pub(crate) version_info: Option<String>,
 }
 
 impl PyVenvConfiguration {
@@ -17,13 +20,14 @@ impl PyVenvConfiguration {
     pub fn parse(cfg: impl AsRef<Path>) -> Result<Self, Error> {
         let mut virtualenv = false;
         let mut uv = false;
+        let mut version_info = None;
 
         // Per https://snarky.ca/how-virtual-environments-work/, the `pyvenv.cfg` file is not a
         // valid INI file, and is instead expected to be parsed by partitioning each line on the
         // first equals sign.
         let content = fs::read_to_string(&cfg)?;
         for line in content.lines() {
-            let Some((key, _value)) = line.split_once('=') else {
+            let Some((key, value)) = line.split_once('=') else {
                 continue;
             };
             match key.trim() {
@@ -33,11 +37,18 @@ impl PyVenvConfiguration {
                 "uv" => {
                     uv = true;
                 }
+                "version_info" | "version" => {
+                    version_info = Some(value.trim().to_string());
+                }
                 _ => {}
             }
         }
 
-        Ok(Self { virtualenv, uv })
+        Ok(Self {
+            virtualenv,
+            uv,
+            version_info,
+        })
     }
 
     /// Returns true if the virtual environment was created with the `virtualenv` package.
@@ -49,6 +60,12 @@ impl PyVenvConfiguration {
     pub fn is_uv(&self) -> bool {
         self.uv
     }
+
+    /// Returns the full version of the Python interpreter used to create the virtual environment,
+    /// if known.
+    pub fn version_info(&self) -> Option<&str> {
+        self.version_info.as_deref()
+    }
 }
 
 #[derive(Debug, Error)]
@@ -89,5 +89,7 @@ interpreter_info = {
     "base_exec_prefix": sys.base_exec_prefix,
     "stdlib": sysconfig.get_path("stdlib"),
     "sys_executable": sys.executable,
+    # Set on free-threaded builds of CPython (3.13 and later).
+    "gil_disabled": bool(sysconfig.get_config_var("Py_GIL_DISABLED")),
 }
 print(json.dumps(interpreter_info))
@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::ffi::{OsStr, OsString};
 use std::io::Write;
 use std::path::{Path, PathBuf};
@@ -13,7 +14,7 @@ use pep440_rs::Version;
 use pep508_rs::MarkerEnvironment;
 use platform_host::Platform;
 use platform_tags::{Tags, TagsError};
-use uv_cache::{Cache, CacheBucket, CachedByTimestamp, Freshness, Timestamp};
+use uv_cache::{Cache, CacheBucket, Freshness, Timestamp};
 use uv_fs::write_atomic_sync;
 
 use crate::python_platform::PythonPlatform;
@@ -30,6 +31,7 @@ pub struct Interpreter {
     pub(crate) base_prefix: PathBuf,
     pub(crate) stdlib: PathBuf,
     pub(crate) sys_executable: PathBuf,
+    pub(crate) gil_disabled: bool,
     tags: OnceCell<Tags>,
 }
 
@@ -55,6 +57,7 @@ impl Interpreter {
             base_prefix: info.base_prefix,
             stdlib: info.stdlib,
             sys_executable: info.sys_executable,
+            gil_disabled: info.gil_disabled,
             tags: OnceCell::new(),
         })
     }
@@ -75,6 +78,7 @@ impl Interpreter {
             base_prefix,
             stdlib,
             sys_executable,
+            gil_disabled: false,
             tags: OnceCell::new(),
         }
     }
@@ -234,6 +238,7 @@ impl Interpreter {
                 self.python_tuple(),
                 self.implementation_name(),
                 self.implementation_tuple(),
+                self.gil_disabled(),
             )
         })
     }
@@ -284,6 +289,12 @@ impl Interpreter {
         (self.implementation_major(), self.implementation_minor())
     }
 
+    /// Returns `true` if this is a free-threaded build of Python (i.e., `Py_GIL_DISABLED` is set).
+    #[inline]
+    pub const fn gil_disabled(&self) -> bool {
+        self.gil_disabled
+    }
+
     pub fn implementation_name(&self) -> &str {
         &self.markers.implementation_name
     }
@@ -301,6 +312,108 @@ impl Interpreter {
     pub fn sys_executable(&self) -> &Path {
         &self.sys_executable
     }
+
+    /// Returns the [`ExternallyManaged`] marker for this interpreter, if it's marked as
+    /// externally managed (i.e., has an `EXTERNALLY-MANAGED` file in its `stdlib` directory).
+    ///
+    /// See: <https://packaging.python.org/en/latest/specifications/externally-managed-environments/>
+    pub fn is_externally_managed(&self) -> Option<ExternallyManaged> {
+        let contents = fs::read_to_string(self.stdlib.join("EXTERNALLY-MANAGED")).ok()?;
+        Some(ExternallyManaged::parse(
+            &contents,
+            messages_locale().as_deref(),
+        ))
+    }
+}
+
+/// The contents of an `EXTERNALLY-MANAGED` marker file, as defined by PEP 668.
+#[derive(Debug, Default, Clone)]
+pub struct ExternallyManaged {
+    error: Option<String>,
+}
+
+impl ExternallyManaged {
+    /// Parse the `Error` key from the `[externally-managed]` section of the marker file.
+    ///
+    /// The file is an INI file, as read by Python's `configparser`; values may continue onto
+    /// subsequent, indented lines. If a `locale` is provided (e.g., `en_GB.UTF-8`), a localized
+    /// `Error-en_GB` or `Error-en` key takes precedence over `Error`.
+    fn parse(contents: &str, locale: Option<&str>) -> Self {
+        let mut section = None;
+        let mut errors: HashMap<String, String> = HashMap::new();
+        let mut current: Option<String> = None;
+        for line in contents.lines() {
+            let trimmed = line.trim();
+            if trimmed.starts_with(['#', ';']) {
+                continue;
+            }
+            if let Some(key) = current.as_ref() {
+                if !trimmed.is_empty() && line.starts_with(char::is_whitespace) {
+                    if let Some(error) = errors.get_mut(key) {
+                        if !error.is_empty() {
+                            error.push('\n');
+                        }
+                        error.push_str(trimmed);
+                    }
+                    continue;
+                }
+            }
+            current = None;
+            if let Some(name) = trimmed
+                .strip_prefix('[')
+                .and_then(|name| name.strip_suffix(']'))
+            {
+                section = Some(name.trim().to_string());
+                continue;
+            }
+            if section.as_deref() != Some("externally-managed") {
+                continue;
+            }
+            let Some((key, value)) = trimmed.split_once(['=', ':']) else {
+                continue;
+            };
+            // Like `configparser`, treat keys as case-insensitive.
+            let key = key.trim().to_lowercase();
+            if key == "error" || key.starts_with("error-") {
+                errors.insert(key.clone(), value.trim().to_string());
+                current = Some(key);
+            }
+        }
+
+        // Prefer the most specific localized message, e.g., `Error-en_GB`, then `Error-en`.
+        let mut keys = Vec::new();
+        if let Some(locale) = locale {
+            let locale = locale.split(['.', '@']).next().unwrap_or_default();
+            if !locale.is_empty() {
+                keys.push(format!("error-{}", locale.to_lowercase()));
+                if let Some((language, _)) = locale.split_once('_') {
+                    keys.push(format!("error-{}", language.to_lowercase()));
+                }
+            }
+        }
+        keys.push("error".to_string());
+
+        let error = keys
+            .iter()
+            .find_map(|key| errors.remove(key).filter(|error| !error.is_empty()));
+        Self { error }
+    }
+
+    /// Return the error message provided by the distributor, if any.
+    pub fn into_error(self) -> Option<String> {
+        self.error
+    }
+}
+
+/// Return the user's locale for messages, as configured in the environment.
+///
+/// Follows the POSIX precedence of `LC_ALL`, `LC_MESSAGES`, and then `LANG`.
+fn messages_locale() -> Option<String> {
+    ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|value| !value.is_empty())
+        .filter(|value| value != "C" && value != "POSIX")
 }
 
 #[derive(Debug, Deserialize, Serialize, Clone)]
@@ -310,6 +423,18 @@ pub(crate) struct InterpreterInfo {
     pub(crate) base_prefix: PathBuf,
     pub(crate) stdlib: PathBuf,
     pub(crate) sys_executable: PathBuf,
+    #[serde(default)]
+    pub(crate) gil_disabled: bool,
+}
+
+/// A cached [`InterpreterInfo`], along with the metadata of the executable it was queried from.
+#[derive(Debug, Deserialize, Serialize)]
+struct CachedInterpreterInfo {
+    /// The last modified time of the (canonicalized) executable.
+    timestamp: Timestamp,
+    /// The size of the (canonicalized) executable, in bytes.
+    size: u64,
+    data: InterpreterInfo,
 }
 
 impl InterpreterInfo {
@@ -395,8 +520,9 @@ impl InterpreterInfo {
     /// A wrapper around [`markers::query_interpreter_info`] to cache the computed markers.
     ///
     /// Running a Python script is (relatively) expensive, and the markers won't change
-    /// unless the Python executable changes, so we use the executable's last modified
-    /// time as a cache key.
+    /// unless the Python executable changes, so we key the cache on the executable's path, and
+    /// invalidate it when the executable's last modified time or size changes (e.g., after a
+    /// Python upgrade that replaces the binary in-place).
     pub(crate) fn query_cached(executable: &Path, cache: &Cache) -> Result<Self, Error> {
         let executable_bytes = executable.as_os_str().as_encoded_bytes();
 
@@ -406,7 +532,9 @@ impl InterpreterInfo {
             format!("{}.msgpack", digest(&executable_bytes)),
         );
 
-        let modified = Timestamp::from_path(fs_err::canonicalize(executable)?)?;
+        let metadata = fs_err::metadata(fs_err::canonicalize(executable)?)?;
+        let modified = Timestamp::from_metadata(&metadata);
+        let size = metadata.len();
 
         // Read from the cache.
         if cache
@@ -414,9 +542,9 @@ impl InterpreterInfo {
             .is_ok_and(Freshness::is_fresh)
         {
             if let Ok(data) = fs::read(cache_entry.path()) {
-                match rmp_serde::from_slice::<CachedByTimestamp<Self>>(&data) {
+                match rmp_serde::from_slice::<CachedInterpreterInfo>(&data) {
                     Ok(cached) => {
-                        if cached.timestamp == modified {
+                        if cached.timestamp == modified && cached.size == size {
                             debug!(
                                 "Cached interpreter info for Python {}, skipping probing: {}",
                                 cached.data.markers.python_full_version,
@@ -456,8 +584,9 @@ impl InterpreterInfo {
             fs::create_dir_all(cache_entry.dir())?;
             write_atomic_sync(
                 cache_entry.path(),
-                rmp_serde::to_vec(&CachedByTimestamp {
+                rmp_serde::to_vec(&CachedInterpreterInfo {
                     timestamp: modified,
+                    size,
                     data: info.clone(),
                 })?,
             )?;
@@ -480,7 +609,7 @@ mod tests {
     use platform_host::Platform;
     use uv_cache::Cache;
 
-    use crate::Interpreter;
+    use crate::{ExternallyManaged, Interpreter};
 
     #[test]
     fn test_cache_invalidation() {
@@ -543,4 +672,102 @@ mod tests {
             Version::from_str("3.13").unwrap()
         );
     }
+
+    #[test]
+    fn free_threaded() {
+        let mock_dir = tempdir().unwrap();
+        let mocked_interpreter = mock_dir.path().join("python");
+        let json = indoc! {r##"
+            {
+                "markers": {
+                    "implementation_name": "cpython",
+                    "implementation_version": "3.13.0",
+                    "os_name": "posix",
+                    "platform_machine": "x86_64",
+                    "platform_python_implementation": "CPython",
+                    "platform_release": "6.5.0-13-generic",
+                    "platform_system": "Linux",
+                    "platform_version": "#13-Ubuntu SMP PREEMPT_DYNAMIC Fri Nov  3 12:16:05 UTC 2023",
+                    "python_full_version": "3.13.0",
+                    "python_version": "3.13",
+                    "sys_platform": "linux"
+                },
+                "base_exec_prefix": "/home/ferris/.pyenv/versions/3.13.0t",
+                "base_prefix": "/home/ferris/.pyenv/versions/3.13.0t",
+                "stdlib": "/usr/lib/python3.13t",
+                "sys_executable": "/home/ferris/projects/uv/.venv/bin/python",
+                "gil_disabled": true
+            }
+        "##};
+
+        let cache = Cache::temp().unwrap();
+        let platform = Platform::current().unwrap();
+
+        fs::write(
+            &mocked_interpreter,
+            formatdoc! {r##"
+            #!/bin/bash
+            echo '{json}'
+            "##},
+        )
+        .unwrap();
+        fs::set_permissions(
+            &mocked_interpreter,
+            std::os::unix::fs::PermissionsExt::from_mode(0o770),
+        )
+        .unwrap();
+        let interpreter = Interpreter::query(&mocked_interpreter, &platform, &cache).unwrap();
+        assert!(interpreter.gil_disabled());
+    }
+
+    #[test]
+    fn externally_managed() {
+        let contents = indoc! {r"
+            [externally-managed]
+            Error=To install Python packages system-wide, try apt install
+             python3-xyz, where xyz is the package you are trying to
+             install.
+        "};
+        assert_eq!(
+            ExternallyManaged::parse(contents, None).into_error().as_deref(),
+            Some("To install Python packages system-wide, try apt install\npython3-xyz, where xyz is the package you are trying to\ninstall.")
+        );
+
+        let contents = indoc! {r"
+            [externally-managed]
+        "};
+        assert_eq!(ExternallyManaged::parse(contents, None).into_error(), None);
+
+        let contents = indoc! {r"
+            # Provided by the distributor.
+            [externally-managed]
+            Error=This environment is externally managed.
+            Error-de=Diese Umgebung wird extern verwaltet.
+            Error-de_CH=Diese Umgebung wird extern verwaltet (CH).
+        "};
+        assert_eq!(
+            ExternallyManaged::parse(contents, Some("de_CH.UTF-8"))
+                .into_error()
+                .as_deref(),
+            Some("Diese Umgebung wird extern verwaltet (CH).")
+        );
+        assert_eq!(
+            ExternallyManaged::parse(contents, Some("de_DE.UTF-8"))
+                .into_error()
+                .as_deref(),
+            Some("Diese Umgebung wird extern verwaltet.")
+        );
+        assert_eq!(
+            ExternallyManaged::parse(contents, Some("fr_FR"))
+                .into_error()
+                .as_deref(),
+            Some("This environment is externally managed.")
+        );
+        assert_eq!(
+            ExternallyManaged::parse(contents, None)
+                .into_error()
+                .as_deref(),
+            Some("This environment is externally managed.")
+        );
+    }
 }
@@ -5,9 +5,12 @@ use std::path::PathBuf;
 use thiserror::Error;
 
 pub use crate::cfg::PyVenvConfiguration;
-pub use crate::interpreter::Interpreter;
-pub use crate::python_query::{find_default_python, find_requested_python};
+pub use crate::interpreter::{ExternallyManaged, Interpreter};
+pub use crate::python_query::{
+    find_all_pythons, find_default_python, find_requested_python, PythonSource,
+};
 pub use crate::python_version::PythonVersion;
+pub use crate::target_platform::TargetPlatform;
 pub use crate::virtual_env::Virtualenv;
 
 mod cfg;
@@ -15,6 +18,7 @@ mod interpreter;
 mod python_platform;
 mod python_query;
 mod python_version;
+mod target_platform;
 mod virtual_env;
 
 #[derive(Debug, Error)]
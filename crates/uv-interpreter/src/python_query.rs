@@ -2,13 +2,17 @@
 
 use std::borrow::Cow;
 use std::env;
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
 
 use tracing::{debug, instrument};
 
+use pep440_rs::VersionSpecifiers;
 use platform_host::Platform;
 use uv_cache::Cache;
 
+use crate::python_platform::PythonPlatform;
+use crate::virtual_env::detect_virtual_env;
 use crate::{Error, Interpreter};
 
 /// Find a python version/interpreter of a specific version.
@@ -16,6 +20,8 @@ use crate::{Error, Interpreter};
 /// Supported formats:
 /// * `-p 3.10` searches for an installed Python 3.10 (`py --list-paths` on Windows, `python3.10` on
 ///   Linux/Mac). Specifying a patch version is not supported.
+/// * `-p ">=3.10,<3.12"` or `-p 3.11.*` selects the newest discovered Python that satisfies the
+///   PEP 440 version specifiers (see [`find_all_pythons`] for the discovery order).
 /// * `-p python3.10` or `-p python.exe` looks for a binary in `PATH`.
 /// * `-p /home/ferris/.local/bin/python3.10` uses this exact Python.
 ///
@@ -55,6 +61,9 @@ pub fn find_requested_python(
             // SAFETY: Guaranteed by the Ok(versions) guard
             _ => unreachable!(),
         }
+    } else if let Some(specifiers) = parse_specifiers(request) {
+        // `-p ">=3.10,<3.12"` or `-p 3.11.*`
+        find_python_by_specifiers(&specifiers, platform, cache)
     } else if !request.contains(std::path::MAIN_SEPARATOR) {
         // `-p python3.10`; Generally not used on windows because all Python are `python.exe`.
         let Some(executable) = Interpreter::find_executable(request)? else {
@@ -68,14 +77,61 @@ pub fn find_requested_python(
     }
 }
 
+/// Parse a request like `>=3.10,<3.12` or `3.11.*` into [`VersionSpecifiers`].
+///
+/// Returns `None` if the request isn't a version specifier, e.g., if it's an executable name or
+/// a path.
+fn parse_specifiers(request: &str) -> Option<VersionSpecifiers> {
+    let request = request.trim();
+    if request.starts_with(['<', '>', '=', '!', '~']) {
+        VersionSpecifiers::from_str(request).ok()
+    } else if request.ends_with(".*") {
+        // A bare wildcard is shorthand for `==3.11.*`.
+        VersionSpecifiers::from_str(&format!("=={request}")).ok()
+    } else {
+        None
+    }
+}
+
+/// Find the newest discovered Python interpreter that satisfies the given specifiers.
+///
+/// If multiple interpreters share the newest version, the one discovered first is used.
+fn find_python_by_specifiers(
+    specifiers: &VersionSpecifiers,
+    platform: &Platform,
+    cache: &Cache,
+) -> Result<Option<Interpreter>, Error> {
+    let mut selected: Option<Interpreter> = None;
+    for (source, interpreter) in find_all_pythons(platform, cache)? {
+        if !specifiers.contains(interpreter.python_version()) {
+            debug!(
+                "Skipping Python {} from {source} at `{}`: does not satisfy `{specifiers}`",
+                interpreter.python_version(),
+                interpreter.sys_executable().display()
+            );
+            continue;
+        }
+        if selected.as_ref().map_or(true, |selected| {
+            interpreter.python_version() > selected.python_version()
+        }) {
+            selected = Some(interpreter);
+        }
+    }
+    Ok(selected)
+}
+
 /// Pick a sensible default for the python a user wants when they didn't specify a version.
 ///
-/// We prefer the test overwrite `UV_TEST_PYTHON_PATH` if it is set, otherwise `python3`/`python` or
-/// `python.exe` respectively.
+/// If a `.python-version` file is present in the current directory or one of its ancestors, the
+/// version it requests is used. Otherwise, we prefer the test overwrite `UV_TEST_PYTHON_PATH` if it
+/// is set, otherwise `python3`/`python` or `python.exe` respectively.
 #[instrument(skip_all)]
 pub fn find_default_python(platform: &Platform, cache: &Cache) -> Result<Interpreter, Error> {
     debug!("Starting interpreter discovery for default Python");
-    try_find_default_python(platform, cache)?.ok_or(if cfg!(windows) {
+    let version_file = version_file_request(&env::current_dir()?);
+    try_find_default_python(platform, cache)?.ok_or(if let Some(request) = version_file {
+        Error::NoSuchPython(request)
+    } else if cfg!(windows) {
         Error::NoPythonInstalledWindows
     } else if cfg!(unix) {
         Error::NoPythonInstalledUnix
@@ -89,12 +145,30 @@ pub(crate) fn try_find_default_python(
     platform: &Platform,
     cache: &Cache,
 ) -> Result<Option<Interpreter>, Error> {
+    // Like pyenv, respect the version in the nearest `.python-version` file.
+    if let Some(request) = version_file_request(&env::current_dir()?) {
+        debug!("Using Python {request} from `.python-version`");
+        return find_requested_python(&request, platform, cache);
+    }
     find_python(PythonVersionSelector::Default, platform, cache)
 }
 
+/// Return the Python version requested by the nearest `.python-version` file, if any.
+///
+/// Only the first entry is used. Entries that aren't version numbers (e.g., `system` or
+/// `pypy3.10-7.3.13`) are left to the regular discovery, which includes pyenv installations.
+fn version_file_request(current_dir: &Path) -> Option<String> {
+    let request = pyenv::version_file(current_dir)?.into_iter().next()?;
+    let is_version = request
+        .split('.')
+        .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()));
+    is_version.then_some(request)
+}
+
 /// Finds a python version matching `selector`.
 /// It searches for an existing installation in the following order:
 /// * (windows): Discover installations using `py --list-paths` (PEP514). Continue if `py` is not installed.
+/// * The pyenv versions selected through `PYENV_VERSION` or a `.python-version` file, if installed.
 /// * Search for the python binary in `PATH` (or `UV_TEST_PYTHON_PATH` if set). Visits each path and for each path resolves the
 ///   files in the following order:
 ///   * Major.Minor.Patch: `pythonx.y.z`, `pythonx.y`, `python.x`, `python`
@@ -102,6 +176,8 @@ pub(crate) fn try_find_default_python(
 ///   * Major: `pythonx`, `python`
 ///   * Default: `python3`, `python`
 ///   * (windows): For each of the above, test for the existence of `python.bat` shim (pyenv-windows) last.
+/// * All other pyenv installations (in `$PYENV_ROOT/versions`), newest first.
+/// * The Conda environments registered in `~/.conda/environments.txt`.
 ///
 /// (Windows): Filter out the windows store shim (Enabled in Settings/Apps/Advanced app settings/App execution aliases).
 fn find_python(
@@ -131,10 +207,18 @@ fn find_python(
         }
     }
 
+    // Prefer the pyenv version selected for the current directory, if any, as the pyenv shims would.
+    if UV_TEST_PYTHON_PATH.is_none() {
+        if let Some(interpreter) = pyenv::find_python(selector, true, platform, cache)? {
+            return Ok(Some(interpreter));
+        }
+    }
+
     let possible_names = selector.possible_names();
 
     #[allow(non_snake_case)]
     let PATH = UV_TEST_PYTHON_PATH
+        .clone()
         .or(env::var_os("PATH"))
         .unwrap_or_default();
 
@@ -197,9 +281,189 @@ fn find_python(
         }
     }
 
+    // Fall back to any pyenv installation, even if the pyenv shims aren't on the `PATH`.
+    if UV_TEST_PYTHON_PATH.is_none() {
+        if let Some(interpreter) = pyenv::find_python(selector, false, platform, cache)? {
+            return Ok(Some(interpreter));
+        }
+    }
+
+    // Fall back to the Python installed in any registered Conda environment.
+    if UV_TEST_PYTHON_PATH.is_none() {
+        let python_platform = PythonPlatform::from(platform.clone());
+        for executable in conda::environment_pythons(&python_platform) {
+            let interpreter = match Interpreter::query(&executable, platform, cache) {
+                Ok(interpreter) => interpreter,
+                Err(err) => {
+                    debug!(
+                        "Failed to query Conda environment `{}`: {err}",
+                        executable.display()
+                    );
+                    continue;
+                }
+            };
+            if let Some(interpreter) =
+                PythonInstallation::Interpreter(interpreter).select(selector, platform, cache)?
+            {
+                return Ok(Some(interpreter));
+            }
+        }
+    }
+
     Ok(None)
 }
 
+/// The source from which a Python interpreter was discovered.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum PythonSource {
+    /// The active virtual environment (`VIRTUAL_ENV`, `CONDA_PREFIX`, or a `.venv` directory).
+    ActiveEnvironment,
+    /// The `py` launcher on Windows, which reads the registry (PEP 514).
+    PyLauncher,
+    /// A directory on the `PATH` (or `UV_TEST_PYTHON_PATH`).
+    SearchPath,
+    /// A pyenv installation.
+    Pyenv,
+    /// A Conda environment registered in `~/.conda/environments.txt`.
+    Conda,
+}
+
+impl std::fmt::Display for PythonSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::ActiveEnvironment => f.write_str("active environment"),
+            Self::PyLauncher => f.write_str("py launcher"),
+            Self::SearchPath => f.write_str("PATH"),
+            Self::Pyenv => f.write_str("pyenv"),
+            Self::Conda => f.write_str("conda"),
+        }
+    }
+}
+
+/// Find all the Python interpreters that uv can discover, in the order in which they're
+/// considered, skipping duplicates and interpreters that can't be queried.
+#[instrument(skip_all)]
+pub fn find_all_pythons(
+    platform: &Platform,
+    cache: &Cache,
+) -> Result<Vec<(PythonSource, Interpreter)>, Error> {
+    #[allow(non_snake_case)]
+    let UV_TEST_PYTHON_PATH = env::var_os("UV_TEST_PYTHON_PATH");
+
+    let mut candidates: Vec<(PythonSource, PathBuf)> = Vec::new();
+
+    // The active virtual environment, if any.
+    let python_platform = PythonPlatform::from(platform.clone());
+    if let Some(venv) = detect_virtual_env(&python_platform)? {
+        candidates.push((
+            PythonSource::ActiveEnvironment,
+            python_platform.venv_python(venv),
+        ));
+    }
+
+    // The installations known to the `py` launcher.
+    if cfg!(windows) && UV_TEST_PYTHON_PATH.is_none() {
+        match windows::py_list_installations() {
+            Ok(installations) => candidates.extend(installations.iter().map(|installation| {
+                (
+                    PythonSource::PyLauncher,
+                    installation.executable().to_path_buf(),
+                )
+            })),
+            Err(Error::PyList(err)) if err.kind() == std::io::ErrorKind::NotFound => {
+                debug!("`py` is not installed");
+            }
+            Err(err) => return Err(err),
+        }
+    }
+
+    // Every `python`, `pythonX`, and `pythonX.Y` executable on the `PATH`.
+    #[allow(non_snake_case)]
+    let PATH = UV_TEST_PYTHON_PATH
+        .clone()
+        .or(env::var_os("PATH"))
+        .unwrap_or_default();
+    for dir in env::split_paths(&PATH) {
+        let Ok(entries) = fs_err::read_dir(&dir) else {
+            continue;
+        };
+        let mut executables = entries
+            .filter_map(Result::ok)
+            .map(|entry| entry.path())
+            .filter(|path| {
+                path.file_name()
+                    .and_then(|name| name.to_str())
+                    .is_some_and(is_python_executable_name)
+            })
+            .filter(|path| !(cfg!(windows) && windows::is_windows_store_shim(path)))
+            .collect::<Vec<_>>();
+        executables.sort();
+        candidates.extend(
+            executables
+                .into_iter()
+                .map(|path| (PythonSource::SearchPath, path)),
+        );
+    }
+
+    // All pyenv installations.
+    if UV_TEST_PYTHON_PATH.is_none() {
+        candidates.extend(
+            pyenv::all_pythons()?
+                .into_iter()
+                .map(|path| (PythonSource::Pyenv, path)),
+        );
+    }
+
+    // All registered Conda environments.
+    if UV_TEST_PYTHON_PATH.is_none() {
+        candidates.extend(
+            conda::environment_pythons(&python_platform)
+                .into_iter()
+                .map(|path| (PythonSource::Conda, path)),
+        );
+    }
+
+    // Query each interpreter, skipping duplicates (e.g., `python3` symlinked to `python3.12`).
+    let mut seen = Vec::new();
+    let mut interpreters = Vec::new();
+    for (source, executable) in candidates {
+        let canonical = fs_err::canonicalize(&executable).unwrap_or_else(|_| executable.clone());
+        if seen.contains(&canonical) {
+            continue;
+        }
+        seen.push(canonical);
+
+        match Interpreter::query(&executable, platform, cache) {
+            Ok(interpreter) => interpreters.push((source, interpreter)),
+            Err(err) => {
+                debug!("Skipping interpreter at `{}`: {err}", executable.display());
+            }
+        }
+    }
+
+    Ok(interpreters)
+}
+
+/// Returns `true` if the file name looks like a Python executable, i.e., `python`, `python3`, or
+/// `python3.12` (with an `.exe` suffix on Windows).
+fn is_python_executable_name(name: &str) -> bool {
+    let name = if cfg!(windows) {
+        let Some(name) = name.strip_suffix(".exe") else {
+            return false;
+        };
+        name
+    } else {
+        name
+    };
+    let Some(version) = name.strip_prefix("python") else {
+        return false;
+    };
+    version.is_empty()
+        || version
+            .split('.')
+            .all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_digit()))
+}
+
 #[derive(Debug, Clone)]
 enum PythonInstallation {
     PyListPath {
@@ -211,6 +475,16 @@ enum PythonInstallation {
 }
 
 impl PythonInstallation {
+    /// Returns the path to the Python executable of the installation.
+    fn executable(&self) -> &Path {
+        match self {
+            Self::PyListPath {
+                executable_path, ..
+            } => executable_path,
+            Self::Interpreter(interpreter) => interpreter.sys_executable(),
+        }
+    }
+
     fn major(&self) -> u8 {
         match self {
             Self::PyListPath { major, .. } => *major,
@@ -331,6 +605,317 @@ impl PythonVersionSelector {
     }
 }
 
+mod pyenv {
+    use std::cmp::Reverse;
+    use std::env;
+    use std::path::{Path, PathBuf};
+
+    use tracing::debug;
+
+    use platform_host::Platform;
+    use uv_cache::Cache;
+
+    use crate::python_query::{PythonInstallation, PythonVersionSelector};
+    use crate::{Error, Interpreter};
+
+    /// Find a Python interpreter installed by pyenv that matches the `selector`.
+    ///
+    /// If `selected_only` is set, only the versions selected via `PYENV_VERSION` or a
+    /// `.python-version` file are considered; otherwise, all installed versions are considered,
+    /// with the selected versions first.
+    pub(super) fn find_python(
+        selector: PythonVersionSelector,
+        selected_only: bool,
+        platform: &Platform,
+        cache: &Cache,
+    ) -> Result<Option<Interpreter>, Error> {
+        let Some(versions_dir) = versions_dir() else {
+            return Ok(None);
+        };
+
+        let current_dir = env::current_dir()?;
+        let selected = selected_versions(&current_dir);
+        if selected_only && selected.is_empty() {
+            return Ok(None);
+        }
+
+        for executable in installed_pythons(&versions_dir, &selected, selected_only)? {
+            let interpreter = match Interpreter::query(&executable, platform, cache) {
+                Ok(interpreter) => interpreter,
+                Err(err) => {
+                    // Don't fail on broken installations, as pyenv doesn't remove old versions.
+                    debug!(
+                        "Failed to query pyenv installation `{}`: {err}",
+                        executable.display()
+                    );
+                    continue;
+                }
+            };
+            if let Some(interpreter) =
+                PythonInstallation::Interpreter(interpreter).select(selector, platform, cache)?
+            {
+                return Ok(Some(interpreter));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Return the Python executables of all pyenv installations, with the selected versions first.
+    pub(super) fn all_pythons() -> Result<Vec<PathBuf>, Error> {
+        let Some(versions_dir) = versions_dir() else {
+            return Ok(Vec::new());
+        };
+        let selected = selected_versions(&env::current_dir()?);
+        installed_pythons(&versions_dir, &selected, false)
+    }
+
+    /// Return the directory containing the pyenv installations, i.e., `$PYENV_ROOT/versions`
+    /// (defaulting to `~/.pyenv/versions`, or `~/.pyenv/pyenv-win/versions` for pyenv-win).
+    fn versions_dir() -> Option<PathBuf> {
+        let root = env::var_os("PYENV_ROOT").map(PathBuf::from).or_else(|| {
+            env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" })
+                .map(|home| PathBuf::from(home).join(".pyenv"))
+        })?;
+        [
+            root.join("versions"),
+            root.join("pyenv-win").join("versions"),
+        ]
+        .into_iter()
+        .find(|dir| dir.is_dir())
+    }
+
+    /// Return the pyenv versions selected for the given directory, in priority order.
+    ///
+    /// Like pyenv, `PYENV_VERSION` (a colon-separated list) takes precedence over the nearest
+    /// `.python-version` file in the directory or its ancestors.
+    pub(super) fn selected_versions(current_dir: &Path) -> Vec<String> {
+        if let Some(versions) = env::var_os("PYENV_VERSION") {
+            return versions
+                .to_string_lossy()
+                .split(':')
+                .map(str::trim)
+                .filter(|version| !version.is_empty())
+                .map(ToString::to_string)
+                .collect();
+        }
+
+        version_file(current_dir).unwrap_or_default()
+    }
+
+    /// Read the versions listed in the nearest `.python-version` file in the given directory or
+    /// its ancestors, if any.
+    pub(super) fn version_file(current_dir: &Path) -> Option<Vec<String>> {
+        current_dir.ancestors().find_map(|dir| {
+            let content = fs_err::read_to_string(dir.join(".python-version")).ok()?;
+            debug!("Found `.python-version` in: {}", dir.display());
+            Some(parse_version_file(&content))
+        })
+    }
+
+    /// Parse the contents of a `.python-version` file into a list of version names.
+    pub(super) fn parse_version_file(content: &str) -> Vec<String> {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(ToString::to_string)
+            .collect()
+    }
+
+    /// Return the Python executables of the installed pyenv versions: first the selected
+    /// versions (in order), followed by all others (unless `selected_only` is set), newest first.
+    pub(super) fn installed_pythons(
+        versions_dir: &Path,
+        selected: &[String],
+        selected_only: bool,
+    ) -> Result<Vec<PathBuf>, Error> {
+        let mut installed = Vec::new();
+        for entry in fs_err::read_dir(versions_dir)? {
+            let entry = entry?;
+            let Ok(name) = entry.file_name().into_string() else {
+                continue;
+            };
+            if let Some(executable) = executable(&entry.path()) {
+                installed.push((name, executable));
+            }
+        }
+
+        // Sort CPython versions (e.g., `3.12.1`) numerically, newest first, followed by other
+        // distributions (e.g., `pypy3.10-7.3.13`) by name.
+        installed.sort_by_key(|(name, _)| {
+            let numeric = name
+                .split('.')
+                .map(str::parse::<u64>)
+                .collect::<Result<Vec<_>, _>>()
+                .ok();
+            (numeric.is_none(), Reverse(numeric), name.clone())
+        });
+
+        let mut executables = Vec::with_capacity(installed.len());
+        for version in selected {
+            if let Some((_, executable)) = installed.iter().find(|(name, _)| name == version) {
+                executables.push(executable.clone());
+            }
+        }
+        if !selected_only {
+            for (name, executable) in installed {
+                if !selected.contains(&name) {
+                    executables.push(executable);
+                }
+            }
+        }
+        Ok(executables)
+    }
+
+    /// Return the Python executable within a pyenv installation, if any.
+    fn executable(version_dir: &Path) -> Option<PathBuf> {
+        let candidates = if cfg!(windows) {
+            vec![version_dir.join("python.exe")]
+        } else {
+            vec![
+                version_dir.join("bin").join("python3"),
+                version_dir.join("bin").join("python"),
+            ]
+        };
+        candidates.into_iter().find(|path| path.is_file())
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::path::{Path, PathBuf};
+
+        use anyhow::Result;
+
+        use super::{installed_pythons, parse_version_file};
+
+        fn install(versions_dir: &Path, name: &str) -> Result<()> {
+            let bin = if cfg!(windows) {
+                versions_dir.join(name)
+            } else {
+                versions_dir.join(name).join("bin")
+            };
+            fs_err::create_dir_all(&bin)?;
+            let python = if cfg!(windows) {
+                "python.exe"
+            } else {
+                "python3"
+            };
+            fs_err::write(bin.join(python), "")?;
+            Ok(())
+        }
+
+        fn names(versions_dir: &Path, executables: &[PathBuf]) -> Vec<String> {
+            executables
+                .iter()
+                .map(|executable| {
+                    executable
+                        .strip_prefix(versions_dir)
+                        .unwrap()
+                        .components()
+                        .next()
+                        .unwrap()
+                        .as_os_str()
+                        .to_string_lossy()
+                        .to_string()
+                })
+                .collect()
+        }
+
+        #[test]
+        fn version_file() {
+            let content = "# comment\n3.11.7\n\n  pypy3.10-7.3.13  \n";
+            assert_eq!(
+                parse_version_file(content),
+                vec!["3.11.7".to_string(), "pypy3.10-7.3.13".to_string()]
+            );
+        }
+
+        #[test]
+        fn installed_order() -> Result<()> {
+            let temp_dir = tempfile::tempdir()?;
+            let versions_dir = temp_dir.path();
+            for name in ["3.9.18", "3.12.1", "3.10.13", "pypy3.10-7.3.13", "3.12.0"] {
+                install(versions_dir, name)?;
+            }
+            // A version without an interpreter is skipped.
+            fs_err::create_dir_all(versions_dir.join("3.13.0"))?;
+
+            let executables = installed_pythons(versions_dir, &[], false)?;
+            assert_eq!(
+                names(versions_dir, &executables),
+                vec!["3.12.1", "3.12.0", "3.10.13", "3.9.18", "pypy3.10-7.3.13"]
+            );
+
+            let selected = vec!["3.10.13".to_string(), "3.8.0".to_string()];
+            let executables = installed_pythons(versions_dir, &selected, false)?;
+            assert_eq!(
+                names(versions_dir, &executables),
+                vec!["3.10.13", "3.12.1", "3.12.0", "3.9.18", "pypy3.10-7.3.13"]
+            );
+
+            let executables = installed_pythons(versions_dir, &selected, true)?;
+            assert_eq!(names(versions_dir, &executables), vec!["3.10.13"]);
+
+            Ok(())
+        }
+    }
+}
+
+mod conda {
+    use std::env;
+    use std::path::{Path, PathBuf};
+
+    use crate::python_platform::PythonPlatform;
+
+    /// Return the Python executables of the Conda environments registered in
+    /// `~/.conda/environments.txt`, in order.
+    pub(super) fn environment_pythons(platform: &PythonPlatform) -> Vec<PathBuf> {
+        let Some(home) = env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" }) else {
+            return Vec::new();
+        };
+        let Ok(content) =
+            fs_err::read_to_string(Path::new(&home).join(".conda").join("environments.txt"))
+        else {
+            return Vec::new();
+        };
+        parse_environments(&content)
+            .into_iter()
+            .map(|prefix| platform.venv_python(prefix))
+            .filter(|python| python.is_file())
+            .collect()
+    }
+
+    /// Parse the environment prefixes listed in a Conda `environments.txt` file.
+    pub(super) fn parse_environments(content: &str) -> Vec<PathBuf> {
+        content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty())
+            .map(PathBuf::from)
+            .collect()
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use std::path::PathBuf;
+
+        use super::parse_environments;
+
+        #[test]
+        fn environments() {
+            let content = "/opt/conda\n\n/home/ferris/.conda/envs/gis  \n";
+            assert_eq!(
+                parse_environments(content),
+                vec![
+                    PathBuf::from("/opt/conda"),
+                    PathBuf::from("/home/ferris/.conda/envs/gis")
+                ]
+            );
+        }
+    }
+}
+
 mod windows {
     use std::path::PathBuf;
     use std::process::Command;
@@ -363,6 +948,18 @@ mod windows {
         platform: &Platform,
         cache: &Cache,
     ) -> Result<Option<Interpreter>, Error> {
+        // Find the first python of the version we want in the list
+        for installation in py_list_installations()? {
+            if let Some(interpreter) = installation.select(selector, platform, cache)? {
+                return Ok(Some(interpreter));
+            }
+        }
+
+        Ok(None)
+    }
+
+    /// Run `py --list-paths` and return all the installations it reports, in order.
+    pub(super) fn py_list_installations() -> Result<Vec<PythonInstallation>, Error> {
         let output = info_span!("py_list_paths")
             .in_scope(|| Command::new("py").arg("--list-paths").output())
             .map_err(Error::PyList)?;
@@ -379,7 +976,6 @@ mod windows {
             });
         }
 
-        // Find the first python of the version we want in the list
         let stdout =
             String::from_utf8(output.stdout).map_err(|err| Error::PythonSubcommandOutput {
                 message: format!("The stdout of `py --list-paths` isn't UTF-8 encoded: {err}"),
@@ -387,24 +983,21 @@ mod windows {
                 stderr: String::from_utf8_lossy(&output.stderr).trim().to_string(),
             })?;
 
+        let mut installations = Vec::new();
         for captures in PY_LIST_PATHS.captures_iter(&stdout) {
             let (_, [major, minor, path]) = captures.extract();
 
             if let (Some(major), Some(minor)) = (major.parse::<u8>().ok(), minor.parse::<u8>().ok())
             {
-                let installation = PythonInstallation::PyListPath {
+                installations.push(PythonInstallation::PyListPath {
                     major,
                     minor,
                     executable_path: PathBuf::from(path),
-                };
-
-                if let Some(interpreter) = installation.select(selector, platform, cache)? {
-                    return Ok(Some(interpreter));
-                }
+                });
             }
         }
 
-        Ok(None)
+        Ok(installations)
     }
 
     /// On Windows we might encounter the windows store proxy shim (Enabled in Settings/Apps/Advanced app settings/App execution aliases).
@@ -480,7 +1073,7 @@ mod tests {
     use platform_host::Platform;
     use uv_cache::Cache;
 
-    use crate::python_query::find_requested_python;
+    use crate::python_query::{find_requested_python, parse_specifiers};
     use crate::Error;
 
     fn format_err<T: std::fmt::Debug>(err: Result<T, Error>) -> String {
@@ -489,6 +1082,21 @@ mod tests {
             .join("\n  Caused by: ")
     }
 
+    #[test]
+    fn specifiers() {
+        assert_eq!(
+            parse_specifiers(">=3.10,<3.12").map(|specifiers| specifiers.to_string()),
+            Some(">=3.10, <3.12".to_string())
+        );
+        assert_eq!(
+            parse_specifiers("3.11.*").map(|specifiers| specifiers.to_string()),
+            Some("==3.11.*".to_string())
+        );
+        assert!(parse_specifiers("3.11").is_none());
+        assert!(parse_specifiers("python3.11").is_none());
+        assert!(parse_specifiers("/usr/bin/python3").is_none());
+    }
+
     #[test]
     fn no_such_python_version() {
         let request = "3.1000";
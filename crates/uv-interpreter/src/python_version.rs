@@ -66,7 +66,11 @@ impl PythonVersion {
         markers.python_full_version = self.0.clone();
 
         // Ex) `python_version == "3.12"`
-        markers.python_version = self.0;
+        markers.python_version = if self.patch().is_some() {
+            self.without_patch().0
+        } else {
+            self.0
+        };
 
         markers
     }
@@ -114,3 +118,46 @@ impl PythonVersion {
             .expect("dropping a patch should always be valid")
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pep508_rs::{MarkerEnvironment, StringVersion};
+
+    use crate::PythonVersion;
+
+    fn base() -> MarkerEnvironment {
+        MarkerEnvironment {
+            implementation_name: "cpython".to_string(),
+            implementation_version: StringVersion::from_str("3.12.1").unwrap(),
+            os_name: "posix".to_string(),
+            platform_machine: "x86_64".to_string(),
+            platform_python_implementation: "CPython".to_string(),
+            platform_release: "6.5.0-13-generic".to_string(),
+            platform_system: "Linux".to_string(),
+            platform_version: "#13-Ubuntu SMP PREEMPT_DYNAMIC Fri Nov  3 12:16:05 UTC 2023"
+                .to_string(),
+            python_full_version: StringVersion::from_str("3.12.1").unwrap(),
+            python_version: StringVersion::from_str("3.12").unwrap(),
+            sys_platform: "linux".to_string(),
+        }
+    }
+
+    #[test]
+    fn markers_minor() {
+        let markers = PythonVersion::from_str("3.8").unwrap().markers(&base());
+        assert_eq!(markers.python_version.to_string(), "3.8");
+        assert_eq!(markers.python_full_version.to_string(), "3.8");
+        assert_eq!(markers.implementation_version.to_string(), "3.8");
+        assert_eq!(markers.sys_platform, "linux");
+    }
+
+    #[test]
+    fn markers_patch() {
+        let markers = PythonVersion::from_str("3.8.18").unwrap().markers(&base());
+        assert_eq!(markers.python_version.to_string(), "3.8");
+        assert_eq!(markers.python_full_version.to_string(), "3.8.18");
+        assert_eq!(markers.implementation_version.to_string(), "3.8.18");
+    }
+}
@@ -0,0 +1,241 @@
+use std::str::FromStr;
+
+use pep508_rs::MarkerEnvironment;
+use platform_host::{Arch, Os, Platform};
+
+/// A target platform for which to resolve (and download) distributions, in lieu of the platform
+/// of the current interpreter (e.g., `linux`, `macos`, `windows`, or `aarch64-manylinux2014`).
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct TargetPlatform(Platform);
+
+impl FromStr for TargetPlatform {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // Ex) `linux`, `macos`, `windows`
+        match s {
+            "linux" => return Ok(Self(Platform::new(MANYLINUX_2014, Arch::X86_64))),
+            "macos" => return Ok(Self(Platform::new(MACOS, Arch::Aarch64))),
+            "windows" => return Ok(Self(Platform::new(Os::Windows, Arch::X86_64))),
+            _ => {}
+        }
+
+        // Ex) `x86_64-unknown-linux-gnu`, `aarch64-manylinux2014`, `x86_64-apple-darwin`
+        let Some((arch, os)) = s.split_once('-') else {
+            return Err(format!(
+                "Unknown Python platform `{s}`; expected `linux`, `macos`, `windows`, or a target such as `x86_64-manylinux2014`"
+            ));
+        };
+
+        let arch = match arch {
+            "x86_64" | "amd64" => Arch::X86_64,
+            "aarch64" | "arm64" => Arch::Aarch64,
+            "i686" | "x86" => Arch::X86,
+            "armv7l" => Arch::Armv7L,
+            "ppc64le" => Arch::Powerpc64Le,
+            "ppc64" => Arch::Powerpc64,
+            "s390x" => Arch::S390X,
+            _ => {
+                return Err(format!(
+                    "Unknown architecture `{arch}` in Python platform `{s}`"
+                ))
+            }
+        };
+
+        let os = match os {
+            "unknown-linux-gnu" | "manylinux2014" => MANYLINUX_2014,
+            "manylinux2010" => Os::Manylinux {
+                major: 2,
+                minor: 12,
+            },
+            "manylinux1" => Os::Manylinux { major: 2, minor: 5 },
+            "unknown-linux-musl" => Os::Musllinux { major: 1, minor: 2 },
+            "apple-darwin" => MACOS,
+            "pc-windows-msvc" => Os::Windows,
+            os => {
+                // Ex) `manylinux_2_28`, `musllinux_1_1`
+                let glibc = os
+                    .strip_prefix("manylinux_")
+                    .and_then(parse_version)
+                    .map(|(major, minor)| Os::Manylinux { major, minor });
+                let musl = os
+                    .strip_prefix("musllinux_")
+                    .and_then(parse_version)
+                    .map(|(major, minor)| Os::Musllinux { major, minor });
+                glibc.or(musl).ok_or_else(|| {
+                    format!("Unknown operating system `{os}` in Python platform `{s}`")
+                })?
+            }
+        };
+
+        if matches!(os, Os::Windows) && !matches!(arch, Arch::X86_64 | Arch::X86 | Arch::Aarch64) {
+            return Err(format!("Unsupported architecture `{arch}` for Windows"));
+        }
+        if matches!(os, Os::Macos { .. }) && !matches!(arch, Arch::X86_64 | Arch::Aarch64) {
+            return Err(format!("Unsupported architecture `{arch}` for macOS"));
+        }
+
+        Ok(Self(Platform::new(os, arch)))
+    }
+}
+
+/// The default `glibc` version for Linux targets, matching the `manylinux2014` policy.
+const MANYLINUX_2014: Os = Os::Manylinux {
+    major: 2,
+    minor: 17,
+};
+
+/// The default version for macOS targets, i.e., the least-recent, non-EOL macOS version.
+const MACOS: Os = Os::Macos {
+    major: 12,
+    minor: 0,
+};
+
+/// Parse a `{major}_{minor}` version, as in `manylinux_2_28`.
+fn parse_version(version: &str) -> Option<(u16, u16)> {
+    let (major, minor) = version.split_once('_')?;
+    Some((major.parse().ok()?, minor.parse().ok()?))
+}
+
+impl TargetPlatform {
+    /// Return the [`Platform`] to use when computing compatible wheel tags.
+    pub fn platform(&self) -> &Platform {
+        &self.0
+    }
+
+    /// Return a [`MarkerEnvironment`] compatible with the given [`TargetPlatform`], based on
+    /// a base [`MarkerEnvironment`].
+    ///
+    /// The returned [`MarkerEnvironment`] will preserve the base environment's Python version
+    /// markers, but override its platform markers.
+    pub fn markers(&self, base: &MarkerEnvironment) -> MarkerEnvironment {
+        let mut markers = base.clone();
+        let arch = self.0.arch();
+
+        match self.0.os() {
+            Os::Manylinux { .. } | Os::Musllinux { .. } => {
+                markers.os_name = "posix".to_string();
+                markers.platform_machine = arch.to_string();
+                markers.platform_system = "Linux".to_string();
+                markers.sys_platform = "linux".to_string();
+            }
+            Os::Macos { .. } => {
+                markers.os_name = "posix".to_string();
+                markers.platform_machine = match arch {
+                    Arch::Aarch64 => "arm64".to_string(),
+                    arch => arch.to_string(),
+                };
+                markers.platform_system = "Darwin".to_string();
+                markers.sys_platform = "darwin".to_string();
+            }
+            Os::Windows => {
+                markers.os_name = "nt".to_string();
+                markers.platform_machine = match arch {
+                    Arch::Aarch64 => "ARM64".to_string(),
+                    Arch::X86 => "x86".to_string(),
+                    _ => "AMD64".to_string(),
+                };
+                markers.platform_system = "Windows".to_string();
+                markers.sys_platform = "win32".to_string();
+            }
+            Os::FreeBsd { .. }
+            | Os::NetBsd { .. }
+            | Os::OpenBsd { .. }
+            | Os::Dragonfly { .. }
+            | Os::Illumos { .. }
+            | Os::Haiku { .. } => unreachable!("unsupported target platform: {:?}", self.0),
+        }
+
+        // The release and version of the target's kernel are unknown.
+        markers.platform_release = String::new();
+        markers.platform_version = String::new();
+
+        markers
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pep508_rs::{MarkerEnvironment, StringVersion};
+    use platform_host::{Arch, Os};
+
+    use crate::TargetPlatform;
+
+    fn base() -> MarkerEnvironment {
+        MarkerEnvironment {
+            implementation_name: "cpython".to_string(),
+            implementation_version: StringVersion::from_str("3.12.1").unwrap(),
+            os_name: "posix".to_string(),
+            platform_machine: "arm64".to_string(),
+            platform_python_implementation: "CPython".to_string(),
+            platform_release: "23.1.0".to_string(),
+            platform_system: "Darwin".to_string(),
+            platform_version: "Darwin Kernel Version 23.1.0".to_string(),
+            python_full_version: StringVersion::from_str("3.12.1").unwrap(),
+            python_version: StringVersion::from_str("3.12").unwrap(),
+            sys_platform: "darwin".to_string(),
+        }
+    }
+
+    #[test]
+    fn parse() {
+        let target = TargetPlatform::from_str("linux").unwrap();
+        assert_eq!(
+            target.platform().os(),
+            &Os::Manylinux {
+                major: 2,
+                minor: 17
+            }
+        );
+        assert_eq!(target.platform().arch(), Arch::X86_64);
+
+        let target = TargetPlatform::from_str("aarch64-manylinux2014").unwrap();
+        assert_eq!(
+            target,
+            TargetPlatform::from_str("aarch64-unknown-linux-gnu").unwrap()
+        );
+        assert_eq!(target.platform().arch(), Arch::Aarch64);
+
+        let target = TargetPlatform::from_str("x86_64-manylinux_2_28").unwrap();
+        assert_eq!(
+            target.platform().os(),
+            &Os::Manylinux {
+                major: 2,
+                minor: 28
+            }
+        );
+
+        let target = TargetPlatform::from_str("x86_64-musllinux_1_1").unwrap();
+        assert_eq!(
+            target.platform().os(),
+            &Os::Musllinux { major: 1, minor: 1 }
+        );
+
+        assert!(TargetPlatform::from_str("solaris").is_err());
+        assert!(TargetPlatform::from_str("x86_64-beos").is_err());
+        assert!(TargetPlatform::from_str("s390x-pc-windows-msvc").is_err());
+    }
+
+    #[test]
+    fn markers_linux() {
+        let markers = TargetPlatform::from_str("linux").unwrap().markers(&base());
+        assert_eq!(markers.sys_platform, "linux");
+        assert_eq!(markers.platform_system, "Linux");
+        assert_eq!(markers.platform_machine, "x86_64");
+        assert_eq!(markers.os_name, "posix");
+        assert_eq!(markers.python_version.to_string(), "3.12");
+    }
+
+    #[test]
+    fn markers_windows() {
+        let markers = TargetPlatform::from_str("windows")
+            .unwrap()
+            .markers(&base());
+        assert_eq!(markers.sys_platform, "win32");
+        assert_eq!(markers.platform_system, "Windows");
+        assert_eq!(markers.platform_machine, "AMD64");
+        assert_eq!(markers.os_name, "nt");
+    }
+}
@@ -1,5 +1,4 @@
 use std::env;
-use std::env::consts::EXE_SUFFIX;
 use std::path::{Path, PathBuf};
 
 use tracing::debug;
@@ -17,6 +16,9 @@ use crate::{Error, Interpreter};
 pub struct Virtualenv {
     root: PathBuf,
     interpreter: Interpreter,
+    /// Whether the root is an installation prefix without an interpreter of its own, rather than
+    /// a virtual environment.
+    prefix: bool,
 }
 
 impl Virtualenv {
@@ -40,6 +42,21 @@ impl Virtualenv {
         Ok(Self {
             root: venv,
             interpreter,
+            prefix: false,
+        })
+    }
+
+    /// Load the virtual environment at the given root directory (e.g., a project's `.venv`).
+    pub fn from_root(root: &Path, platform: Platform, cache: &Cache) -> Result<Self, Error> {
+        let platform = PythonPlatform::from(platform);
+        let venv = fs_err::canonicalize(root)?;
+        let executable = platform.venv_python(&venv);
+        let interpreter = Interpreter::query(&executable, &platform.0, cache)?;
+
+        Ok(Self {
+            root: venv,
+            interpreter,
+            prefix: false,
         })
     }
 
@@ -48,12 +65,33 @@ impl Virtualenv {
         Self {
             interpreter: interpreter.with_base_prefix(venv.to_path_buf()),
             root: venv.to_path_buf(),
+            prefix: false,
+        }
+    }
+
+    /// Target an installation prefix (as in `pip install --prefix`), using the layout the given
+    /// interpreter would use for that prefix.
+    ///
+    /// The prefix doesn't contain an interpreter, so scripts reference the given interpreter
+    /// instead.
+    pub fn from_prefix(interpreter: Interpreter, prefix: &Path) -> Self {
+        Self {
+            interpreter,
+            root: prefix.to_path_buf(),
+            prefix: true,
         }
     }
 
     /// Returns the location of the python interpreter
+    ///
+    /// Note that this isn't necessarily in [`Virtualenv::bin_dir`]: Conda environments on Windows
+    /// place the interpreter at the root of the environment.
     pub fn python_executable(&self) -> PathBuf {
-        self.bin_dir().join(format!("python{EXE_SUFFIX}"))
+        if self.prefix {
+            self.interpreter.sys_executable().to_path_buf()
+        } else {
+            self.interpreter.platform.venv_python(&self.root)
+        }
     }
 
     pub fn root(&self) -> &Path {
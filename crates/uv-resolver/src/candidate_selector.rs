@@ -3,7 +3,7 @@ use pubgrub::range::Range;
 use rustc_hash::FxHashMap;
 
 use distribution_types::CompatibleDist;
-use distribution_types::{DistributionMetadata, IncompatibleWheel, Name, PrioritizedDist};
+use distribution_types::{DistributionMetadata, IncompatibleDist, Name, PrioritizedDist};
 use pep440_rs::Version;
 use pep508_rs::{Requirement, VersionOrUrl};
 use uv_normalize::PackageName;
@@ -23,16 +23,13 @@ pub(crate) struct CandidateSelector {
 
 impl CandidateSelector {
     /// Return a [`CandidateSelector`] for the given [`Manifest`].
-    pub(crate) fn for_resolution(manifest: &Manifest, options: Options) -> Self {
+    pub(crate) fn for_resolution(manifest: &Manifest, options: &Options) -> Self {
         Self {
             resolution_strategy: ResolutionStrategy::from_mode(
                 options.resolution_mode,
                 manifest.requirements.as_slice(),
             ),
-            prerelease_strategy: PreReleaseStrategy::from_mode(
-                options.prerelease_mode,
-                manifest.requirements.as_slice(),
-            ),
+            prerelease_strategy: PreReleaseStrategy::from_mode(options.prerelease_mode, manifest),
             preferences: Preferences::from(manifest.preferences.as_slice()),
         }
     }
@@ -74,6 +71,14 @@ impl From<&[Requirement]> for Preferences {
                     let [version_specifier] = version_specifiers.as_ref() else {
                         return None;
                     };
+                    // Only exact pins (e.g., `black==23.10.1`) are treated as preferences; a
+                    // lower bound like `black>=23.10.1` would otherwise pin the minimum version.
+                    if !matches!(
+                        version_specifier.operator(),
+                        pep440_rs::Operator::Equal | pep440_rs::Operator::ExactEqual
+                    ) {
+                        return None;
+                    }
                     Some((
                         requirement.name.clone(),
                         version_specifier.version().clone(),
@@ -248,7 +253,11 @@ impl CandidateSelector {
             };
 
             // Skip empty candidates due to exclude newer
-            if dist.exclude_newer() && dist.incompatible_wheel().is_none() && dist.get().is_none() {
+            if dist.exclude_newer()
+                && dist.incompatible_wheel().is_none()
+                && dist.incompatible_source().is_none()
+                && dist.get().is_none()
+            {
                 continue;
             }
 
@@ -274,7 +283,7 @@ impl CandidateSelector {
 #[derive(Debug, Clone)]
 pub(crate) enum CandidateDist<'a> {
     Compatible(CompatibleDist<'a>),
-    Incompatible(Option<&'a IncompatibleWheel>),
+    Incompatible(IncompatibleDist),
     ExcludeNewer,
 }
 
@@ -282,15 +291,14 @@ impl<'a> From<&'a PrioritizedDist> for CandidateDist<'a> {
     fn from(value: &'a PrioritizedDist) -> Self {
         if let Some(dist) = value.get() {
             CandidateDist::Compatible(dist)
-        } else if value.exclude_newer() && value.incompatible_wheel().is_none() {
+        } else if value.exclude_newer()
+            && value.incompatible_wheel().is_none()
+            && value.incompatible_source().is_none()
+        {
             // If empty because of exclude-newer, mark as a special case
             CandidateDist::ExcludeNewer
         } else {
-            CandidateDist::Incompatible(
-                value
-                    .incompatible_wheel()
-                    .map(|(_, incompatibility)| incompatibility),
-            )
+            CandidateDist::Incompatible(value.incompatibility())
         }
     }
 }
@@ -8,14 +8,19 @@ use pubgrub::range::Range;
 use pubgrub::report::{DefaultStringReporter, DerivationTree, Reporter};
 use rustc_hash::FxHashMap;
 
-use distribution_types::{BuiltDist, IndexLocations, PathBuiltDist, PathSourceDist, SourceDist};
+use distribution_filename::WheelFilename;
+use distribution_types::{
+    BuiltDist, DistributionMetadata, IndexLocations, PackageId, PathBuiltDist, PathSourceDist,
+    SourceDist,
+};
 use once_map::OnceMap;
 use pep440_rs::Version;
 use pep508_rs::Requirement;
+use pypi_types::Metadata21;
 use uv_normalize::PackageName;
 
 use crate::candidate_selector::CandidateSelector;
-use crate::pubgrub::{PubGrubPackage, PubGrubPython, PubGrubReportFormatter};
+use crate::pubgrub::{PubGrubDistribution, PubGrubPackage, PubGrubPython, PubGrubReportFormatter};
 use crate::python_requirement::PythonRequirement;
 use crate::resolver::{UnavailablePackage, VersionsResponse};
 
@@ -24,6 +29,16 @@ pub enum ResolveError {
     #[error("Failed to find a version of {0} that satisfies the requirement")]
     NotFound(Requirement),
 
+    #[error("Failed to find a distribution of {0} that matches the provided hashes")]
+    NoMatchingHash(Requirement),
+
+    #[error("Hash mismatch for `{url}`\n\nExpected:\n{expected}\n\nComputed:\n  {computed}")]
+    HashMismatch {
+        url: String,
+        expected: String,
+        computed: String,
+    },
+
     #[error(transparent)]
     Client(#[from] uv_client::Error),
 
@@ -122,6 +137,7 @@ impl From<pubgrub::error::PubGrubError<PubGrubPackage, Range<Version>, Infallibl
                     python_requirement: None,
                     index_locations: None,
                     unavailable_packages: FxHashMap::default(),
+                    url_versions: FxHashMap::default(),
                 })
             }
             pubgrub::error::PubGrubError::SelfDependency { package, version } => {
@@ -143,6 +159,7 @@ pub struct NoSolutionError {
     python_requirement: Option<PythonRequirement>,
     index_locations: Option<IndexLocations>,
     unavailable_packages: FxHashMap<PackageName, UnavailablePackage>,
+    url_versions: FxHashMap<PubGrubPackage, Version>,
 }
 
 impl std::error::Error for NoSolutionError {}
@@ -164,6 +181,7 @@ impl std::fmt::Display for NoSolutionError {
             &self.selector,
             &self.index_locations,
             &self.unavailable_packages,
+            &self.url_versions,
         ) {
             write!(f, "\n\n{hint}")?;
         }
@@ -173,6 +191,17 @@ impl std::fmt::Display for NoSolutionError {
 }
 
 impl NoSolutionError {
+    /// Render the failure as a hierarchical explanation, in which each conclusion is followed by
+    /// the (indented) causes from which it was derived, down to the individual requirements that
+    /// conflict.
+    pub fn tree(&self) -> String {
+        let formatter = PubGrubReportFormatter {
+            available_versions: &self.available_versions,
+            python_requirement: self.python_requirement.as_ref(),
+        };
+        formatter.format_tree(&self.derivation_tree)
+    }
+
     /// Update the available versions attached to the error using the given package version index.
     ///
     /// Only packages used in the error's derivation tree will be retrieved.
@@ -224,6 +253,34 @@ impl NoSolutionError {
         self
     }
 
+    /// Update the versions provided by URL requirements attached to the error.
+    ///
+    /// Only packages used in the error's derivation tree will be retrieved.
+    #[must_use]
+    pub(crate) fn with_url_versions(
+        mut self,
+        distributions: &OnceMap<PackageId, Metadata21>,
+    ) -> Self {
+        let mut url_versions = FxHashMap::default();
+        for package in self.derivation_tree.packages() {
+            if let PubGrubPackage::Package(name, _, Some(url)) = package {
+                let version = if let Ok(filename) = WheelFilename::try_from(url.raw()) {
+                    Some(filename.version)
+                } else {
+                    let dist = PubGrubDistribution::from_url(name, url);
+                    distributions
+                        .get(&dist.package_id())
+                        .map(|metadata| metadata.version.clone())
+                };
+                if let Some(version) = version {
+                    url_versions.insert(package.clone(), version);
+                }
+            }
+        }
+        self.url_versions = url_versions;
+        self
+    }
+
     /// Update the candidate selector attached to the error.
     #[must_use]
     pub(crate) fn with_selector(mut self, selector: CandidateSelector) -> Self {
@@ -0,0 +1,38 @@
+use chrono::{DateTime, Utc};
+use rustc_hash::FxHashMap;
+
+use uv_normalize::PackageName;
+
+/// A cutoff for excluding distributions that were uploaded after a given timestamp, either for
+/// all packages or on a per-package basis.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ExcludeNewer {
+    /// The cutoff to apply to any package without a per-package cutoff.
+    global: Option<DateTime<Utc>>,
+    /// The per-package cutoffs, which take precedence over the global cutoff.
+    packages: FxHashMap<PackageName, DateTime<Utc>>,
+}
+
+impl ExcludeNewer {
+    /// Create a new [`ExcludeNewer`] from a global cutoff and a set of per-package cutoffs.
+    pub fn new(
+        global: Option<DateTime<Utc>>,
+        packages: FxHashMap<PackageName, DateTime<Utc>>,
+    ) -> Self {
+        Self { global, packages }
+    }
+
+    /// Return the cutoff for the given package, if any.
+    pub fn get(&self, package_name: &PackageName) -> Option<&DateTime<Utc>> {
+        self.packages.get(package_name).or(self.global.as_ref())
+    }
+}
+
+impl From<Option<DateTime<Utc>>> for ExcludeNewer {
+    fn from(global: Option<DateTime<Utc>>) -> Self {
+        Self {
+            global,
+            packages: FxHashMap::default(),
+        }
+    }
+}
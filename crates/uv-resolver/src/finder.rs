@@ -4,11 +4,12 @@
 
 use anyhow::Result;
 use futures::{stream, Stream, StreamExt, TryStreamExt};
+use itertools::Itertools;
 use rustc_hash::FxHashMap;
-use uv_traits::NoBinary;
+use uv_traits::{NoBinary, NoBuild};
 
 use distribution_filename::DistFilename;
-use distribution_types::{Dist, IndexUrl, Resolution};
+use distribution_types::{Dist, File, IndexUrl, Resolution};
 use pep508_rs::{Requirement, VersionOrUrl};
 use platform_tags::{TagCompatibility, Tags};
 use uv_client::{
@@ -18,6 +19,7 @@ use uv_interpreter::Interpreter;
 use uv_normalize::PackageName;
 
 use crate::error::ResolveError;
+use crate::HashAlgorithm;
 
 pub struct DistFinder<'a> {
     tags: &'a Tags,
@@ -26,6 +28,8 @@ pub struct DistFinder<'a> {
     interpreter: &'a Interpreter,
     flat_index: &'a FlatIndex,
     no_binary: &'a NoBinary,
+    no_build: &'a NoBuild,
+    hashes: Option<&'a FxHashMap<PackageName, Vec<String>>>,
 }
 
 impl<'a> DistFinder<'a> {
@@ -36,6 +40,7 @@ impl<'a> DistFinder<'a> {
         interpreter: &'a Interpreter,
         flat_index: &'a FlatIndex,
         no_binary: &'a NoBinary,
+        no_build: &'a NoBuild,
     ) -> Self {
         Self {
             tags,
@@ -44,6 +49,21 @@ impl<'a> DistFinder<'a> {
             interpreter,
             flat_index,
             no_binary,
+            no_build,
+            hashes: None,
+        }
+    }
+
+    /// Require that every distribution matches one of the given hashes, keyed by package name
+    /// (as in pip's hash-checking mode).
+    ///
+    /// Only files with a matching hash are considered when selecting a distribution from an index;
+    /// direct URL distributions are downloaded and verified against the provided hashes.
+    #[must_use]
+    pub fn with_hashes(self, hashes: &'a FxHashMap<PackageName, Vec<String>>) -> Self {
+        Self {
+            hashes: Some(hashes),
+            ..self
         }
     }
 
@@ -64,6 +84,9 @@ impl<'a> DistFinder<'a> {
         requirement: &Requirement,
         flat_index: Option<&FlatDistributions>,
     ) -> Result<(PackageName, Dist), ResolveError> {
+        let hashes = self
+            .hashes
+            .map(|hashes| hashes.get(&requirement.name).map_or(&[][..], Vec::as_slice));
         match requirement.version_or_url.as_ref() {
             None | Some(VersionOrUrl::VersionSpecifier(_)) => {
                 // Query the index(es) (cached) to get the URLs for the available files.
@@ -71,8 +94,13 @@ impl<'a> DistFinder<'a> {
                 let metadata = OwnedArchive::deserialize(&raw_metadata);
 
                 // Pick a version that satisfies the requirement.
-                let Some(dist) = self.select(requirement, metadata, &index, flat_index) else {
-                    return Err(ResolveError::NotFound(requirement.clone()));
+                let Some(dist) = self.select(requirement, metadata, &index, flat_index, hashes)
+                else {
+                    return Err(if hashes.is_some() {
+                        ResolveError::NoMatchingHash(requirement.clone())
+                    } else {
+                        ResolveError::NotFound(requirement.clone())
+                    });
                 };
 
                 if let Some(reporter) = self.reporter.as_ref() {
@@ -86,6 +114,27 @@ impl<'a> DistFinder<'a> {
                 // We have a URL; fetch the distribution directly.
                 let package_name = requirement.name.clone();
                 let package = Dist::from_url(package_name.clone(), url.clone())?;
+
+                // If hashes are required, verify the contents of the built distribution. (Source
+                // distributions can't be verified, and are rejected by the caller.)
+                if let (Some(hashes), Dist::Built(_)) = (hashes, &package) {
+                    let Some(algorithm) = hashes
+                        .iter()
+                        .find_map(|hash| HashAlgorithm::from_digest(hash))
+                    else {
+                        return Err(ResolveError::NoMatchingHash(requirement.clone()));
+                    };
+                    let digest = algorithm.fetch(url.raw(), self.client).await?;
+                    let computed = algorithm.hashes(digest.clone());
+                    if !hashes.iter().any(|hash| computed.matches(hash)) {
+                        return Err(ResolveError::HashMismatch {
+                            url: url.to_string(),
+                            expected: hashes.iter().map(|hash| format!("  {hash}")).join("\n"),
+                            computed: format!("{algorithm}:{digest}"),
+                        });
+                    }
+                }
+
                 Ok((package_name, package))
             }
         }
@@ -122,19 +171,32 @@ impl<'a> DistFinder<'a> {
     /// Select a version that satisfies the requirement.
     ///
     /// Wheels are preferred to source distributions unless `no_binary` excludes wheels
-    /// for the requirement.
+    /// for the requirement. Source distributions are never selected if `no_build` excludes them.
     fn select(
         &self,
         requirement: &Requirement,
         metadata: SimpleMetadata,
         index: &IndexUrl,
         flat_index: Option<&FlatDistributions>,
+        hashes: Option<&[String]>,
     ) -> Option<Dist> {
+        // If hashes are required, only consider files that match one of the hashes.
+        let matches_hashes = |file: Option<&File>| {
+            hashes.map_or(true, |hashes| {
+                file.is_some_and(|file| hashes.iter().any(|hash| file.hashes.matches(hash)))
+            })
+        };
+
         let no_binary = match self.no_binary {
             NoBinary::None => false,
             NoBinary::All => true,
             NoBinary::Packages(packages) => packages.contains(&requirement.name),
         };
+        let no_build = match self.no_build {
+            NoBuild::None => false,
+            NoBuild::All => true,
+            NoBuild::Packages(packages) => packages.contains(&requirement.name),
+        };
 
         // Prioritize the flat index by initializing the "best" matches with its entries.
         let matching_override = if let Some(flat_index) = flat_index {
@@ -150,13 +212,19 @@ impl<'a> DistFinder<'a> {
         };
         let (mut best_version, mut best_wheel, mut best_sdist) =
             if let Some((version, resolvable_dist)) = matching_override {
-                (
-                    Some(version.clone()),
-                    resolvable_dist
-                        .compatible_wheel()
-                        .map(|(dist, tag_priority)| (dist.dist.clone(), *tag_priority)),
-                    resolvable_dist.source().map(|dist| dist.dist.clone()),
-                )
+                let wheel = resolvable_dist
+                    .compatible_wheel()
+                    .filter(|(dist, _)| matches_hashes(dist.dist.file()))
+                    .map(|(dist, tag_priority)| (dist.dist.clone(), *tag_priority));
+                let sdist = resolvable_dist
+                    .source()
+                    .filter(|dist| matches_hashes(dist.dist.file()))
+                    .map(|dist| dist.dist.clone());
+                if wheel.is_some() || sdist.is_some() {
+                    (Some(version.clone()), wheel, sdist)
+                } else {
+                    (None, None, None)
+                }
             } else {
                 (None, None, None)
             };
@@ -191,6 +259,10 @@ impl<'a> DistFinder<'a> {
                         continue;
                     }
 
+                    if !matches_hashes(Some(&version_wheel.file)) {
+                        continue;
+                    }
+
                     best_version = Some(version.clone());
                     if let TagCompatibility::Compatible(priority) =
                         version_wheel.name.compatibility(self.tags)
@@ -213,7 +285,7 @@ impl<'a> DistFinder<'a> {
             }
 
             // Find the most-compatible sdist, if no wheel was found.
-            if best_wheel.is_none() {
+            if best_wheel.is_none() && !no_build {
                 for version_sdist in files.source_dists {
                     // Only add dists compatible with the python version.
                     // This is relevant for source dists which give no other indication of their
@@ -228,6 +300,10 @@ impl<'a> DistFinder<'a> {
                         continue;
                     }
 
+                    if !matches_hashes(Some(&version_sdist.file)) {
+                        continue;
+                    }
+
                     best_version = Some(version_sdist.name.version.clone());
                     best_sdist = Some(Dist::from_registry(
                         DistFilename::SourceDistFilename(version_sdist.name),
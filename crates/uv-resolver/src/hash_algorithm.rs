@@ -0,0 +1,167 @@
+use std::fmt::{Display, Formatter};
+
+use futures::{Stream, StreamExt};
+use sha2::digest::DynDigest;
+use sha2::{Sha256, Sha384, Sha512};
+use url::Url;
+
+use pypi_types::Hashes;
+use uv_cache::{Cache, CacheBucket, WheelCache};
+use uv_client::{CacheControl, Connectivity, ErrorKind, RegistryClient};
+use uv_normalize::PackageName;
+
+/// The algorithm used to compute the hashes emitted for each pinned distribution.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
+pub enum HashAlgorithm {
+    /// Emit SHA 256 digests, as published by most indexes.
+    #[default]
+    Sha256,
+    /// Emit SHA 384 digests, computed locally if the index doesn't publish them.
+    Sha384,
+    /// Emit SHA 512 digests, computed locally if the index doesn't publish them.
+    Sha512,
+}
+
+impl HashAlgorithm {
+    /// Parse the algorithm from an `<algorithm>:<hash>` digest (as in `--hash=sha256:...`).
+    pub(crate) fn from_digest(digest: &str) -> Option<Self> {
+        match digest.split_once(':')?.0 {
+            "sha256" => Some(Self::Sha256),
+            "sha384" => Some(Self::Sha384),
+            "sha512" => Some(Self::Sha512),
+            _ => None,
+        }
+    }
+
+    /// Return the digest for this algorithm from the given [`Hashes`], if present.
+    pub(crate) fn digest(self, hashes: &Hashes) -> Option<&str> {
+        match self {
+            Self::Sha256 => hashes.sha256.as_deref(),
+            Self::Sha384 => hashes.sha384.as_deref(),
+            Self::Sha512 => hashes.sha512.as_deref(),
+        }
+    }
+
+    /// Return a set of [`Hashes`] containing only the given digest for this algorithm.
+    pub(crate) fn hashes(self, digest: String) -> Hashes {
+        match self {
+            Self::Sha256 => Hashes {
+                sha256: Some(digest),
+                ..Hashes::default()
+            },
+            Self::Sha384 => Hashes {
+                sha384: Some(digest),
+                ..Hashes::default()
+            },
+            Self::Sha512 => Hashes {
+                sha512: Some(digest),
+                ..Hashes::default()
+            },
+        }
+    }
+
+    /// Compute the hex-encoded digest of the given byte stream.
+    pub(crate) async fn compute<E>(
+        self,
+        stream: impl Stream<Item = Result<impl AsRef<[u8]>, E>>,
+    ) -> Result<String, E> {
+        let mut stream = std::pin::pin!(stream);
+        let mut hasher: Box<dyn DynDigest + Send> = match self {
+            Self::Sha256 => Box::<Sha256>::default(),
+            Self::Sha384 => Box::<Sha384>::default(),
+            Self::Sha512 => Box::<Sha512>::default(),
+        };
+        while let Some(chunk) = stream.next().await {
+            hasher.update(chunk?.as_ref());
+        }
+        Ok(hasher
+            .finalize()
+            .iter()
+            .map(|byte| format!("{byte:02x}"))
+            .collect())
+    }
+
+    /// Download the file at the given URL (or read it from disk, for `file://` URLs) and compute
+    /// its hex-encoded digest.
+    pub(crate) async fn fetch(
+        self,
+        url: &Url,
+        client: &RegistryClient,
+    ) -> Result<String, uv_client::Error> {
+        if url.scheme() == "file" {
+            let path = url.to_file_path().map_err(|()| {
+                ErrorKind::Io(std::io::Error::new(
+                    std::io::ErrorKind::InvalidInput,
+                    format!("Invalid file URL: {url}"),
+                ))
+            })?;
+            let contents = fs_err::tokio::read(path).await.map_err(ErrorKind::Io)?;
+            return self
+                .compute(futures::stream::iter([Ok(contents)]))
+                .await
+                .map_err(|err| ErrorKind::Io(err).into());
+        }
+        let response = client
+            .download(url.clone())
+            .send()
+            .await
+            .map_err(ErrorKind::RequestMiddlewareError)?
+            .error_for_status()
+            .map_err(ErrorKind::RequestError)?;
+        self.compute(response.bytes_stream())
+            .await
+            .map_err(|err| ErrorKind::RequestError(err).into())
+    }
+
+    /// Like [`HashAlgorithm::fetch`], but caches the digests of remote files alongside the wheel
+    /// metadata, such that repeated resolutions don't download the same files again.
+    pub(crate) async fn fetch_cached(
+        self,
+        url: &Url,
+        package: &PackageName,
+        client: &RegistryClient,
+        cache: &Cache,
+    ) -> Result<String, uv_client::Error> {
+        if url.scheme() == "file" {
+            return self.fetch(url, client).await;
+        }
+
+        let cache_entry = cache.entry(
+            CacheBucket::Wheels,
+            WheelCache::Url(url).remote_wheel_dir(package.as_ref()),
+            format!("{self}.msgpack"),
+        );
+        let cache_control = match client.connectivity() {
+            Connectivity::Online => CacheControl::from(
+                cache
+                    .freshness(&cache_entry, Some(package))
+                    .map_err(ErrorKind::Io)?,
+            ),
+            Connectivity::Offline => CacheControl::AllowStale,
+        };
+        let req = client
+            .download(url.clone())
+            .build()
+            .map_err(ErrorKind::RequestError)?;
+        let digest = client
+            .cached_client()
+            .get_serde(req, &cache_entry, cache_control, |response| async move {
+                self.compute(response.bytes_stream())
+                    .await
+                    .map_err(|err| uv_client::Error::from(ErrorKind::RequestError(err)))
+            })
+            .await?;
+        Ok(digest)
+    }
+}
+
+impl Display for HashAlgorithm {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Sha256 => write!(f, "sha256"),
+            Self::Sha384 => write!(f, "sha384"),
+            Self::Sha512 => write!(f, "sha512"),
+        }
+    }
+}
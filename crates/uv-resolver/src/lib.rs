@@ -1,11 +1,16 @@
 pub use dependency_mode::DependencyMode;
 pub use error::ResolveError;
+pub use exclude_newer::ExcludeNewer;
 pub use finder::{DistFinder, Reporter as FinderReporter};
+pub use hash_algorithm::HashAlgorithm;
+pub use lock::{Lock, LockError, LockedDependency, LockedDistribution, LockedFile, LockedSource};
 pub use manifest::Manifest;
 pub use options::{Options, OptionsBuilder};
 pub use prerelease_mode::PreReleaseMode;
 pub use python_requirement::PythonRequirement;
-pub use resolution::{AnnotationStyle, Diagnostic, DisplayResolutionGraph, ResolutionGraph};
+pub use resolution::{
+    requirements_txt_entry, AnnotationStyle, Diagnostic, DisplayResolutionGraph, ResolutionGraph,
+};
 pub use resolution_mode::ResolutionMode;
 pub use resolver::{
     BuildId, DefaultResolverProvider, InMemoryIndex, PackageVersionsResult,
@@ -13,14 +18,19 @@ pub use resolver::{
     WheelMetadataResult,
 };
 pub use version_map::VersionMap;
+pub use yanks::YankedMode;
 
 mod candidate_selector;
 mod constraints;
 mod dependency_mode;
 mod editables;
 mod error;
+mod exclude_newer;
 mod finder;
+mod hash_algorithm;
+mod lock;
 mod manifest;
+mod markers;
 mod options;
 mod overrides;
 mod pins;
@@ -0,0 +1,505 @@
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
+
+use petgraph::graph::NodeIndex;
+use petgraph::visit::EdgeRef;
+use petgraph::Direction;
+use rustc_hash::FxHashMap;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+use distribution_types::{BuiltDist, Dist, File, FileLocation, Name, SourceDist};
+use pep440_rs::{Version, VersionSpecifiers};
+use pypi_types::Hashes;
+use uv_fs::Normalized;
+use uv_normalize::PackageName;
+
+use crate::resolution::{archive_url, portable_relative_path};
+use crate::ResolutionGraph;
+
+/// A lockfile (`uv.lock`), capturing a universal resolution of a project: every resolved
+/// distribution, its source and files, and the (possibly marker-conditional) dependency edges
+/// between them.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Lock {
+    /// The version of the lockfile format.
+    version: u32,
+    /// The Python versions supported by the project (i.e., its `requires-python`), if declared.
+    #[serde(
+        rename = "requires-python",
+        default,
+        skip_serializing_if = "Option::is_none"
+    )]
+    requires_python: Option<VersionSpecifiers>,
+    /// The locked distributions, sorted by name and version.
+    #[serde(
+        rename = "distribution",
+        default,
+        skip_serializing_if = "Vec::is_empty"
+    )]
+    distributions: Vec<LockedDistribution>,
+}
+
+/// A single distribution in a [`Lock`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedDistribution {
+    /// The name of the distribution.
+    pub name: PackageName,
+    /// The pinned version of the distribution. Omitted for distributions that are pinned by their
+    /// source alone (e.g., a Git repository or a local directory).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<Version>,
+    /// The source of the distribution.
+    pub source: LockedSource,
+    /// The environment markers under which the distribution is required, if it's only required
+    /// conditionally.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<String>,
+    /// The files of the distribution (e.g., the wheels and source distribution of a registry
+    /// release), sorted by filename.
+    #[serde(rename = "file", default, skip_serializing_if = "Vec::is_empty")]
+    pub files: Vec<LockedFile>,
+    /// The dependencies of the distribution, sorted by name and version.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub dependencies: Vec<LockedDependency>,
+}
+
+/// A file of a [`LockedDistribution`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedFile {
+    /// The name of the file (e.g., `anyio-4.0.0-py3-none-any.whl`).
+    pub filename: String,
+    /// The URL of the file, or, for files in a local directory (e.g., `--find-links`), its path
+    /// relative to the directory containing the lockfile. Omitted for distributions that are
+    /// pinned by URL or path, as the file is given by the source.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    /// The hash of the file (e.g., `sha256:...`), if known.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hash: Option<String>,
+}
+
+/// A dependency edge in a [`Lock`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LockedDependency {
+    /// The name of the dependency.
+    pub name: PackageName,
+    /// The version of the dependency, if it's pinned to a version.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub version: Option<Version>,
+    /// The environment markers under which the dependency is required, if it's only required
+    /// conditionally.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub marker: Option<String>,
+}
+
+/// The source of a [`LockedDistribution`], serialized as a `kind+location` string (e.g.,
+/// `registry+https://pypi.org/simple`).
+///
+/// Local paths are stored relative to the directory containing the lockfile.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LockedSource {
+    /// A distribution from a registry, identified by the index URL.
+    Registry(String),
+    /// A distribution at a direct URL.
+    Direct(String),
+    /// A distribution in a Git repository (e.g., `https://github.com/pallets/flask.git@3.0.0`).
+    Git(String),
+    /// A distribution at a local path (either an archive or a source tree).
+    Path(String),
+    /// An editable source tree at a local path.
+    Editable(String),
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum LockError {
+    #[error(transparent)]
+    Parse(#[from] toml::de::Error),
+    #[error(transparent)]
+    Serialize(#[from] toml::ser::Error),
+    #[error("Unsupported lockfile version: {found} (expected: {expected})")]
+    UnsupportedVersion { found: u32, expected: u32 },
+}
+
+impl Lock {
+    /// The current version of the lockfile format.
+    pub const VERSION: u32 = 1;
+
+    /// Create a [`Lock`] from a (universal) [`ResolutionGraph`], along with the files of each
+    /// registry distribution (as returned by [`ResolutionGraph::files`]) and the Python versions
+    /// supported by the project.
+    ///
+    /// Local paths are stored relative to `root`, the directory containing the lockfile.
+    pub fn from_resolution(
+        resolution: &ResolutionGraph,
+        files: &FxHashMap<NodeIndex, Vec<File>>,
+        requires_python: Option<VersionSpecifiers>,
+        root: &Path,
+    ) -> Self {
+        let petgraph = resolution.petgraph();
+
+        let mut distributions = petgraph
+            .node_indices()
+            .map(|index| {
+                let dist = &petgraph[index];
+                let name = dist.name();
+
+                let mut locked_files = if let Some(url) = archive_url(dist) {
+                    // The source identifies the archive, so only its hashes need to be recorded.
+                    let filename = url
+                        .path_segments()
+                        .and_then(Iterator::last)
+                        .unwrap_or_default()
+                        .to_string();
+                    let mut hashes = resolution
+                        .hashes(index)
+                        .iter()
+                        .filter_map(Hashes::to_string)
+                        .collect::<Vec<_>>();
+                    hashes.sort_unstable();
+                    hashes.dedup();
+                    if hashes.is_empty() {
+                        vec![LockedFile {
+                            filename,
+                            url: None,
+                            hash: None,
+                        }]
+                    } else {
+                        hashes
+                            .into_iter()
+                            .map(|hash| LockedFile {
+                                filename: filename.clone(),
+                                url: None,
+                                hash: Some(hash),
+                            })
+                            .collect()
+                    }
+                } else {
+                    files
+                        .get(&index)
+                        .into_iter()
+                        .flatten()
+                        .map(|file| LockedFile::from_file(file, root))
+                        .collect::<Vec<_>>()
+                };
+                locked_files.sort_unstable_by(|a, b| a.filename.cmp(&b.filename));
+
+                let mut dependencies = petgraph
+                    .edges_directed(index, Direction::Outgoing)
+                    .map(|edge| {
+                        let dependency = &petgraph[edge.target()];
+                        LockedDependency {
+                            name: dependency.name().clone(),
+                            version: dependency.version().cloned(),
+                            marker: resolution.edge_marker(edge.id()).map(ToString::to_string),
+                        }
+                    })
+                    .collect::<Vec<_>>();
+                dependencies
+                    .sort_unstable_by(|a, b| (&a.name, &a.version).cmp(&(&b.name, &b.version)));
+
+                LockedDistribution {
+                    name: name.clone(),
+                    version: dist.version().cloned(),
+                    source: LockedSource::from_dist(dist, root),
+                    marker: resolution.marker(index).map(ToString::to_string),
+                    files: locked_files,
+                    dependencies,
+                }
+            })
+            .collect::<Vec<_>>();
+        distributions.sort_unstable_by(LockedDistribution::cmp_key);
+
+        Self {
+            version: Self::VERSION,
+            requires_python,
+            distributions,
+        }
+    }
+
+    /// Parse a [`Lock`] from the contents of a `uv.lock` file.
+    pub fn from_toml(contents: &str) -> Result<Self, LockError> {
+        let lock: Self = toml::from_str(contents)?;
+        if lock.version != Self::VERSION {
+            return Err(LockError::UnsupportedVersion {
+                found: lock.version,
+                expected: Self::VERSION,
+            });
+        }
+        Ok(lock)
+    }
+
+    /// Serialize the [`Lock`] to the contents of a `uv.lock` file.
+    pub fn to_toml(&self) -> Result<String, LockError> {
+        let mut contents = String::from(
+            "# This file was autogenerated by uv; it is not intended to be edited manually.\n",
+        );
+        contents.push_str(&toml::to_string(self)?);
+        Ok(contents)
+    }
+
+    /// Return the Python versions supported by the locked project, if declared.
+    pub fn requires_python(&self) -> Option<&VersionSpecifiers> {
+        self.requires_python.as_ref()
+    }
+
+    /// Return the locked distributions, sorted by name and version.
+    pub fn distributions(&self) -> &[LockedDistribution] {
+        &self.distributions
+    }
+
+    /// Return the locked distribution for the given package and, if provided, version.
+    ///
+    /// If no version is provided, the first locked distribution with the given name is returned.
+    pub fn find(
+        &self,
+        name: &PackageName,
+        version: Option<&Version>,
+    ) -> Option<&LockedDistribution> {
+        self.distributions.iter().find(|distribution| {
+            &distribution.name == name
+                && version.map_or(true, |version| {
+                    distribution.version.as_ref() == Some(version)
+                })
+        })
+    }
+
+    /// Add distributions that aren't part of the resolution (e.g., the editable members of a
+    /// workspace), keeping the distributions sorted by name and version.
+    pub fn extend(&mut self, distributions: impl IntoIterator<Item = LockedDistribution>) {
+        self.distributions.extend(distributions);
+        self.distributions
+            .sort_unstable_by(LockedDistribution::cmp_key);
+    }
+}
+
+impl LockedDistribution {
+    /// Return the hashes of the distribution's files (e.g., `sha256:...`).
+    pub fn hashes(&self) -> Vec<String> {
+        self.files
+            .iter()
+            .filter_map(|file| file.hash.clone())
+            .collect()
+    }
+
+    /// Order distributions by name, then version.
+    fn cmp_key(a: &Self, b: &Self) -> std::cmp::Ordering {
+        (&a.name, &a.version).cmp(&(&b.name, &b.version))
+    }
+}
+
+impl LockedFile {
+    /// Create a [`LockedFile`] for a file listed by a registry, storing local paths relative to
+    /// `root`.
+    fn from_file(file: &File, root: &Path) -> Self {
+        let url = match &file.url {
+            FileLocation::RelativeUrl(base, url) => pypi_types::base_url_join_relative(base, url)
+                .map_or_else(|_| url.clone(), |url| url.to_string()),
+            FileLocation::AbsoluteUrl(url) => url.clone(),
+            FileLocation::Path(path) => portable_relative_path(path, root)
+                .unwrap_or_else(|| path.normalized_display().to_string()),
+        };
+        Self {
+            filename: file.filename.clone(),
+            url: Some(url),
+            hash: file.hashes.to_string(),
+        }
+    }
+}
+
+impl LockedSource {
+    /// Create a [`LockedSource::Editable`] for the source tree at `path`, stored relative to
+    /// `root`.
+    pub fn editable(path: &Path, root: &Path) -> Self {
+        Self::Editable(
+            portable_relative_path(path, root)
+                .unwrap_or_else(|| path.normalized_display().to_string()),
+        )
+    }
+
+    /// Determine the [`LockedSource`] for a resolved [`Dist`], storing any local paths relative to
+    /// `root`.
+    fn from_dist(dist: &Dist, root: &Path) -> Self {
+        let path = |path: &Path| {
+            portable_relative_path(path, root)
+                .unwrap_or_else(|| path.normalized_display().to_string())
+        };
+        match dist {
+            Dist::Built(BuiltDist::Registry(wheel)) => Self::Registry(wheel.index.to_string()),
+            Dist::Source(SourceDist::Registry(sdist)) => Self::Registry(sdist.index.to_string()),
+            Dist::Built(BuiltDist::DirectUrl(wheel)) => Self::Direct(wheel.url.raw().to_string()),
+            Dist::Source(SourceDist::DirectUrl(sdist)) => Self::Direct(sdist.url.raw().to_string()),
+            Dist::Source(SourceDist::Git(git)) => {
+                let url = git.url.raw().to_string();
+                Self::Git(
+                    url.strip_prefix("git+")
+                        .map(ToString::to_string)
+                        .unwrap_or(url),
+                )
+            }
+            Dist::Built(BuiltDist::Path(wheel)) => Self::Path(path(&wheel.path)),
+            Dist::Source(SourceDist::Path(sdist)) => {
+                if sdist.editable {
+                    Self::Editable(path(&sdist.path))
+                } else {
+                    Self::Path(path(&sdist.path))
+                }
+            }
+        }
+    }
+}
+
+impl Display for LockedSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Registry(url) => write!(f, "registry+{url}"),
+            Self::Direct(url) => write!(f, "direct+{url}"),
+            Self::Git(url) => write!(f, "git+{url}"),
+            Self::Path(path) => write!(f, "path+{path}"),
+            Self::Editable(path) => write!(f, "editable+{path}"),
+        }
+    }
+}
+
+impl FromStr for LockedSource {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((kind, location)) = s.split_once('+') else {
+            return Err(format!(
+                "Expected a source of the form `kind+location`, found: `{s}`"
+            ));
+        };
+        let location = location.to_string();
+        match kind {
+            "registry" => Ok(Self::Registry(location)),
+            "direct" => Ok(Self::Direct(location)),
+            "git" => Ok(Self::Git(location)),
+            "path" => Ok(Self::Path(location)),
+            "editable" => Ok(Self::Editable(location)),
+            kind => Err(format!("Unknown source kind `{kind}` in: `{s}`")),
+        }
+    }
+}
+
+impl Serialize for LockedSource {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for LockedSource {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let s = String::deserialize(deserializer)?;
+        Self::from_str(&s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use indoc::indoc;
+
+    use pep440_rs::Version;
+    use uv_normalize::PackageName;
+
+    use super::{Lock, LockError, LockedSource};
+
+    #[test]
+    fn source_roundtrip() {
+        for source in [
+            "registry+https://pypi.org/simple",
+            "direct+https://example.org/flask-3.0.0-py3-none-any.whl",
+            "git+https://github.com/pallets/flask.git@3.0.0",
+            "path+./libs/foo",
+            "editable+.",
+        ] {
+            assert_eq!(LockedSource::from_str(source).unwrap().to_string(), source);
+        }
+        assert!(LockedSource::from_str("https://pypi.org/simple").is_err());
+        assert!(LockedSource::from_str("svn+https://example.org").is_err());
+    }
+
+    #[test]
+    fn lock_roundtrip() {
+        let contents = indoc! {r#"
+            # This file was autogenerated by uv; it is not intended to be edited manually.
+            version = 1
+            requires-python = ">=3.8"
+
+            [[distribution]]
+            name = "click"
+            version = "8.1.7"
+            source = "registry+https://pypi.org/simple"
+
+            [[distribution.file]]
+            filename = "click-8.1.7-py3-none-any.whl"
+            url = "https://files.pythonhosted.org/packages/00/2e/d53fa4befbf2cfa713304affc7ca780ce4fc1fd8710527771b58311a3229/click-8.1.7-py3-none-any.whl"
+            hash = "sha256:ae74fb96c20a0277a1d615f1e4d73c8414f5a98db8b799a7931d1582f3390c28"
+
+            [[distribution.dependencies]]
+            name = "colorama"
+            version = "0.4.6"
+            marker = "platform_system == 'Windows'"
+
+            [[distribution]]
+            name = "colorama"
+            version = "0.4.6"
+            source = "registry+https://pypi.org/simple"
+            marker = "platform_system == 'Windows'"
+        "#};
+        let lock = Lock::from_toml(contents).unwrap();
+        assert_eq!(lock.requires_python().unwrap().to_string(), ">=3.8");
+        assert_eq!(lock.distributions().len(), 2);
+        assert_eq!(
+            lock.distributions()[0].hashes(),
+            ["sha256:ae74fb96c20a0277a1d615f1e4d73c8414f5a98db8b799a7931d1582f3390c28"]
+        );
+        assert_eq!(
+            lock.distributions()[0].dependencies[0].marker.as_deref(),
+            Some("platform_system == 'Windows'")
+        );
+        assert_eq!(Lock::from_toml(&lock.to_toml().unwrap()).unwrap(), lock);
+    }
+
+    #[test]
+    fn find() {
+        let contents = indoc! {r#"
+            version = 1
+
+            [[distribution]]
+            name = "idna"
+            version = "3.4"
+            source = "registry+https://pypi.org/simple"
+
+            [[distribution]]
+            name = "idna"
+            version = "3.6"
+            source = "registry+https://pypi.org/simple"
+        "#};
+        let lock = Lock::from_toml(contents).unwrap();
+        let name = PackageName::from_str("idna").unwrap();
+        let version = Version::from_str("3.6").unwrap();
+        assert_eq!(
+            lock.find(&name, Some(&version)).unwrap().version.as_ref(),
+            Some(&version)
+        );
+        assert!(lock.find(&name, None).is_some());
+        assert!(lock
+            .find(&name, Some(&Version::from_str("3.5").unwrap()))
+            .is_none());
+    }
+
+    #[test]
+    fn unsupported_version() {
+        let err = Lock::from_toml("version = 2\n").unwrap_err();
+        assert!(matches!(
+            err,
+            LockError::UnsupportedVersion {
+                found: 2,
+                expected: 1
+            }
+        ));
+    }
+}
@@ -0,0 +1,540 @@
+use std::str::FromStr;
+
+use pubgrub::range::Range;
+
+use pep440_rs::Version;
+use pep508_rs::{
+    MarkerEnvironment, MarkerExpression, MarkerOperator, MarkerTree, MarkerValue,
+    MarkerValueVersion, Requirement,
+};
+use uv_normalize::ExtraName;
+
+/// Returns `true` if the [`Requirement`] is relevant for the given environment and set of
+/// extras.
+///
+/// In a universal resolution (i.e., if `universal` is set), only the `extra` markers are evaluated
+/// directly; the requirement is relevant if its remaining markers can be satisfied by _any_ of the
+/// environments covered by the resolution (or by the current fork of the resolution).
+pub(crate) fn is_relevant(
+    requirement: &Requirement,
+    env: &MarkerEnvironment,
+    extras: &[ExtraName],
+    universal: Option<&Environments>,
+) -> bool {
+    if let Some(environments) = universal {
+        environments.includes(&MarkerDnf::from_marker(requirement.marker.as_ref(), extras))
+    } else {
+        requirement.evaluate_markers(env, extras)
+    }
+}
+
+/// The environments covered by a universal resolution, or by a single fork of a universal
+/// resolution.
+#[derive(Debug, Clone)]
+pub(crate) struct Environments {
+    /// The Python versions supported by the resolution (e.g., per the project's
+    /// `requires-python`), as a range of full Python versions.
+    python: Range<Version>,
+    /// The marker that selects the environments covered by the current fork.
+    marker: MarkerDnf,
+}
+
+impl Environments {
+    /// Cover every environment that runs one of the given Python versions.
+    pub(crate) fn new(python: Range<Version>) -> Self {
+        Self {
+            python,
+            marker: MarkerDnf::always(),
+        }
+    }
+
+    /// Return the marker that selects the environments covered by the current fork.
+    pub(crate) fn marker(&self) -> &MarkerDnf {
+        &self.marker
+    }
+
+    /// Returns `true` if the given marker is satisfied by at least one of the environments.
+    pub(crate) fn includes(&self, marker: &MarkerDnf) -> bool {
+        self.marker
+            .and(marker)
+            .0
+            .iter()
+            .any(|conjunction| is_satisfiable(conjunction, &self.python))
+    }
+
+    /// Narrow the environments to those that also satisfy the given marker.
+    #[must_use]
+    pub(crate) fn narrow(&self, marker: &MarkerDnf) -> Self {
+        Self {
+            python: self.python.clone(),
+            marker: self.marker.and(marker),
+        }
+    }
+}
+
+/// An environment marker in disjunctive normal form, i.e., a disjunction of conjunctions of
+/// marker expressions, used to track the environments in which a package is required.
+///
+/// An empty conjunction is always satisfied; an empty disjunction is never satisfied. Any `extra`
+/// expressions are evaluated on construction, as extras are resolved ahead of time and can't be
+/// expressed in a requirements file. Conjunctions that can't be satisfied by any environment
+/// (e.g., `sys_platform == 'win32' and sys_platform == 'linux'`) are dropped.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct MarkerDnf(Vec<Vec<MarkerExpression>>);
+
+impl MarkerDnf {
+    /// A marker that is never satisfied.
+    pub(crate) fn never() -> Self {
+        Self(Vec::new())
+    }
+
+    /// A marker that is always satisfied.
+    pub(crate) fn always() -> Self {
+        Self(vec![Vec::new()])
+    }
+
+    /// Convert an (optional) [`MarkerTree`] into disjunctive normal form, evaluating any `extra`
+    /// expressions against the given set of active extras.
+    pub(crate) fn from_marker(marker: Option<&MarkerTree>, extras: &[ExtraName]) -> Self {
+        let Some(marker) = marker else {
+            return Self::always();
+        };
+        match marker {
+            MarkerTree::Expression(expression) => {
+                let extra = match (&expression.l_value, &expression.r_value) {
+                    (MarkerValue::Extra, MarkerValue::QuotedString(extra))
+                    | (MarkerValue::QuotedString(extra), MarkerValue::Extra) => Some(extra),
+                    (MarkerValue::Extra, _) | (_, MarkerValue::Extra) => return Self::always(),
+                    _ => None,
+                };
+                if let Some(extra) = extra {
+                    let active =
+                        ExtraName::from_str(extra).is_ok_and(|extra| extras.contains(&extra));
+                    let satisfied = match expression.operator {
+                        MarkerOperator::Equal => active,
+                        MarkerOperator::NotEqual => !active,
+                        _ => true,
+                    };
+                    if satisfied {
+                        Self::always()
+                    } else {
+                        Self::never()
+                    }
+                } else {
+                    let mut marker = Self::never();
+                    marker.insert(vec![expression.clone()]);
+                    marker
+                }
+            }
+            MarkerTree::And(markers) => markers
+                .iter()
+                .map(|marker| Self::from_marker(Some(marker), extras))
+                .fold(Self::always(), |acc, marker| acc.and(&marker)),
+            MarkerTree::Or(markers) => {
+                let mut acc = Self::never();
+                for marker in markers {
+                    acc.or(Self::from_marker(Some(marker), extras));
+                }
+                acc
+            }
+        }
+    }
+
+    /// Returns `true` if the marker is always satisfied.
+    pub(crate) fn is_always(&self) -> bool {
+        self.0.iter().any(Vec::is_empty)
+    }
+
+    /// Returns `true` if the marker is never satisfied.
+    pub(crate) fn is_never(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns `true` if no environment satisfies both this marker and another.
+    pub(crate) fn is_disjoint(&self, other: &Self) -> bool {
+        self.and(other).is_never()
+    }
+
+    /// Return the conjunction of this marker and another.
+    #[must_use]
+    pub(crate) fn and(&self, other: &Self) -> Self {
+        let mut result = Self::never();
+        for left in &self.0 {
+            for right in &other.0 {
+                let mut conjunction = left.iter().chain(right.iter()).cloned().collect::<Vec<_>>();
+                conjunction.sort_by_cached_key(ToString::to_string);
+                conjunction.dedup();
+                simplify(&mut conjunction);
+                result.insert(conjunction);
+            }
+        }
+        result
+    }
+
+    /// Add the disjuncts of another marker to this marker.
+    ///
+    /// Returns `true` if this marker was widened as a result.
+    pub(crate) fn or(&mut self, other: Self) -> bool {
+        let mut changed = false;
+        for conjunction in other.0 {
+            changed |= self.insert(conjunction);
+        }
+        changed
+    }
+
+    /// Return the negation of this marker, or `None` if the marker includes an expression that
+    /// can't be negated (i.e., a `~=` comparison).
+    pub(crate) fn negate(&self) -> Option<Self> {
+        // By De Morgan's laws, the negation of a disjunction of conjunctions is the conjunction of
+        // the disjunctions of the negated expressions.
+        let mut result = Self::always();
+        for conjunction in &self.0 {
+            let mut negated = Self::never();
+            for expression in conjunction {
+                negated.insert(vec![negate(expression)?]);
+            }
+            result = result.and(&negated);
+        }
+        Some(result)
+    }
+
+    /// Insert a (sorted) conjunction, unless it's already implied by an existing conjunction or
+    /// can't be satisfied.
+    ///
+    /// Returns `true` if the conjunction was inserted.
+    fn insert(&mut self, conjunction: Vec<MarkerExpression>) -> bool {
+        if !is_satisfiable(&conjunction, &Range::full()) {
+            return false;
+        }
+
+        // If an existing conjunction is a subset of the new conjunction, the new conjunction is
+        // redundant.
+        if self
+            .0
+            .iter()
+            .any(|existing| existing.iter().all(|expr| conjunction.contains(expr)))
+        {
+            return false;
+        }
+
+        // Otherwise, drop any existing conjunctions that are implied by the new conjunction.
+        self.0
+            .retain(|existing| !conjunction.iter().all(|expr| existing.contains(expr)));
+
+        // If an existing conjunction is identical to the new conjunction except for a single
+        // negated expression (as in `a` and `not a and b`), the negated expression is redundant
+        // (`a or b`).
+        if let Some(reduced) = self
+            .0
+            .iter()
+            .find_map(|existing| resolve(existing, &conjunction))
+        {
+            self.insert(reduced);
+            return true;
+        }
+        if let Some((position, reduced)) =
+            self.0.iter().enumerate().find_map(|(position, existing)| {
+                resolve(&conjunction, existing).map(|reduced| (position, reduced))
+            })
+        {
+            self.0.remove(position);
+            self.insert(conjunction);
+            self.insert(reduced);
+            return true;
+        }
+
+        self.0.push(conjunction);
+        self.0.sort_by_cached_key(|conjunction| {
+            conjunction
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+        });
+        true
+    }
+
+    /// Convert the marker back into a [`MarkerTree`], or `None` if the marker is always
+    /// satisfied.
+    pub(crate) fn to_marker(&self) -> Option<MarkerTree> {
+        if self.is_always() {
+            return None;
+        }
+        let mut disjuncts = self
+            .0
+            .iter()
+            .map(|conjunction| {
+                if let [expression] = conjunction.as_slice() {
+                    MarkerTree::Expression(expression.clone())
+                } else {
+                    MarkerTree::And(
+                        conjunction
+                            .iter()
+                            .cloned()
+                            .map(MarkerTree::Expression)
+                            .collect(),
+                    )
+                }
+            })
+            .collect::<Vec<_>>();
+        if disjuncts.len() == 1 {
+            disjuncts.pop()
+        } else {
+            Some(MarkerTree::Or(disjuncts))
+        }
+    }
+}
+
+/// If `right` includes the negation of an expression in `left`, along with all other expressions
+/// in `left`, return `right` without the negated expression.
+///
+/// For example, given `a and b` and `not a and b and c`, return `b and c`, since
+/// `(a and b) or (not a and b and c)` is equivalent to `(a and b) or (b and c)`.
+fn resolve(left: &[MarkerExpression], right: &[MarkerExpression]) -> Option<Vec<MarkerExpression>> {
+    left.iter().find_map(|expr| {
+        let negated = negate(expr)?;
+        if !right.contains(&negated) {
+            return None;
+        }
+        if !left
+            .iter()
+            .filter(|other| *other != expr)
+            .all(|other| right.contains(other))
+        {
+            return None;
+        }
+        Some(
+            right
+                .iter()
+                .filter(|other| **other != negated)
+                .cloned()
+                .collect(),
+        )
+    })
+}
+
+/// Remove any Python version expressions that are implied by another expression in the
+/// conjunction, as in `python_version < '3.11' and python_version < '3.8'`, which simplifies to
+/// `python_version < '3.8'`.
+fn simplify(conjunction: &mut Vec<MarkerExpression>) {
+    let ranges = conjunction.iter().map(python_range).collect::<Vec<_>>();
+    let mut index = 0;
+    conjunction.retain(|_| {
+        let position = index;
+        index += 1;
+        let Some(outer) = &ranges[position] else {
+            return true;
+        };
+        // Of two equivalent expressions, retain the first.
+        !ranges.iter().enumerate().any(|(other, inner)| {
+            other != position
+                && inner.as_ref().is_some_and(|inner| {
+                    inner.intersection(outer) == *inner && (other < position || inner != outer)
+                })
+        })
+    });
+}
+
+/// Negate a single marker expression, or return `None` if the expression can't be negated.
+fn negate(expression: &MarkerExpression) -> Option<MarkerExpression> {
+    let operator = match expression.operator {
+        MarkerOperator::Equal => MarkerOperator::NotEqual,
+        MarkerOperator::NotEqual => MarkerOperator::Equal,
+        MarkerOperator::GreaterThan => MarkerOperator::LessEqual,
+        MarkerOperator::GreaterEqual => MarkerOperator::LessThan,
+        MarkerOperator::LessThan => MarkerOperator::GreaterEqual,
+        MarkerOperator::LessEqual => MarkerOperator::GreaterThan,
+        MarkerOperator::In => MarkerOperator::NotIn,
+        MarkerOperator::NotIn => MarkerOperator::In,
+        MarkerOperator::TildeEqual => return None,
+    };
+    Some(MarkerExpression {
+        l_value: expression.l_value.clone(),
+        operator,
+        r_value: expression.r_value.clone(),
+    })
+}
+
+/// Returns `true` if the conjunction of marker expressions can be satisfied by an environment
+/// running one of the given Python versions.
+///
+/// The check is conservative: expressions that can't be compared (e.g., `platform_release >=
+/// '5.0'`) are assumed to be satisfiable.
+fn is_satisfiable(conjunction: &[MarkerExpression], python: &Range<Version>) -> bool {
+    // Any expression that's negated elsewhere in the conjunction is a contradiction.
+    if conjunction
+        .iter()
+        .any(|expr| negate(expr).is_some_and(|negated| conjunction.contains(&negated)))
+    {
+        return false;
+    }
+
+    // A string marker can't equal two different values.
+    for (index, left) in conjunction.iter().enumerate() {
+        let (MarkerValue::MarkerEnvString(key), MarkerOperator::Equal) =
+            (&left.l_value, &left.operator)
+        else {
+            continue;
+        };
+        if conjunction[index + 1..].iter().any(|right| {
+            matches!(&right.l_value, MarkerValue::MarkerEnvString(other) if other == key)
+                && right.operator == MarkerOperator::Equal
+                && right.r_value != left.r_value
+        }) {
+            return false;
+        }
+    }
+
+    // The Python version markers must be satisfied by one of the given Python versions.
+    let python = conjunction
+        .iter()
+        .filter_map(python_range)
+        .fold(python.clone(), |range, expr| range.intersection(&expr));
+    !python.is_empty()
+}
+
+/// Return the full Python versions that satisfy a `python_version` or `python_full_version`
+/// expression, or `None` if the expression isn't a (comparable) Python version marker.
+fn python_range(expression: &MarkerExpression) -> Option<Range<Version>> {
+    let (MarkerValue::MarkerEnvVersion(key), MarkerValue::QuotedString(value)) =
+        (&expression.l_value, &expression.r_value)
+    else {
+        return None;
+    };
+    let version = Version::from_str(value).ok()?;
+    match key {
+        MarkerValueVersion::ImplementationVersion => None,
+        MarkerValueVersion::PythonFullVersion => match expression.operator {
+            MarkerOperator::Equal => Some(Range::singleton(version)),
+            MarkerOperator::NotEqual => Some(Range::singleton(version).complement()),
+            MarkerOperator::GreaterThan => Some(Range::strictly_higher_than(version)),
+            MarkerOperator::GreaterEqual => Some(Range::higher_than(version)),
+            MarkerOperator::LessThan => Some(Range::strictly_lower_than(version)),
+            MarkerOperator::LessEqual => Some(Range::lower_than(version)),
+            _ => None,
+        },
+        MarkerValueVersion::PythonVersion => {
+            // `python_version` only includes the major and minor version, so, e.g.,
+            // `python_version <= '3.11'` is satisfied by `3.11.4`.
+            let &[major, minor] = version.release() else {
+                return None;
+            };
+            let next = Version::new([major, minor + 1]);
+            match expression.operator {
+                MarkerOperator::Equal => Some(Range::from_range_bounds(version..next)),
+                MarkerOperator::NotEqual => {
+                    Some(Range::from_range_bounds(version..next).complement())
+                }
+                MarkerOperator::GreaterThan => Some(Range::higher_than(next)),
+                MarkerOperator::GreaterEqual => Some(Range::higher_than(version)),
+                MarkerOperator::LessThan => Some(Range::strictly_lower_than(version)),
+                MarkerOperator::LessEqual => Some(Range::strictly_lower_than(next)),
+                _ => None,
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use pubgrub::range::Range;
+
+    use pep440_rs::Version;
+    use pep508_rs::MarkerTree;
+    use uv_normalize::ExtraName;
+
+    use super::{Environments, MarkerDnf};
+
+    fn dnf(marker: &str) -> MarkerDnf {
+        MarkerDnf::from_marker(Some(&MarkerTree::from_str(marker).unwrap()), &[])
+    }
+
+    #[test]
+    fn evaluate_extras() {
+        let socks = ExtraName::from_str("socks").unwrap();
+        let marker = MarkerTree::from_str("sys_platform == 'win32' and extra == 'socks'").unwrap();
+        assert!(MarkerDnf::from_marker(Some(&marker), &[]).is_never());
+        assert_eq!(
+            MarkerDnf::from_marker(Some(&marker), &[socks])
+                .to_marker()
+                .unwrap()
+                .to_string(),
+            "sys_platform == 'win32'"
+        );
+    }
+
+    #[test]
+    fn absorption() {
+        let mut marker = dnf("sys_platform == 'win32' and python_version >= '3.8'");
+        assert!(marker.or(dnf("sys_platform == 'win32'")));
+        assert!(!marker.or(dnf("python_version >= '3.8' and sys_platform == 'win32'")));
+        assert_eq!(
+            marker.to_marker().unwrap().to_string(),
+            "sys_platform == 'win32'"
+        );
+    }
+
+    #[test]
+    fn conjunction() {
+        let marker = dnf("sys_platform == 'win32' or sys_platform == 'darwin'")
+            .and(&dnf("python_version >= '3.8'"));
+        assert_eq!(
+            marker.to_marker().unwrap().to_string(),
+            "(python_version >= '3.8' and sys_platform == 'darwin') or (python_version >= '3.8' and sys_platform == 'win32')"
+        );
+    }
+
+    #[test]
+    fn implication() {
+        let marker = dnf("python_version < '3.11'").and(&dnf("python_version < '3.8'"));
+        assert_eq!(
+            marker.to_marker().unwrap().to_string(),
+            "python_version < '3.8'"
+        );
+
+        let marker = dnf("python_version >= '3.8'").and(&dnf("python_full_version >= '3.8.0'"));
+        assert_eq!(
+            marker.to_marker().unwrap().to_string(),
+            "python_full_version >= '3.8.0'"
+        );
+    }
+
+    #[test]
+    fn contradiction() {
+        assert!(dnf("sys_platform == 'win32' and sys_platform == 'linux'").is_never());
+        assert!(dnf("sys_platform == 'win32' and sys_platform != 'win32'").is_never());
+        assert!(dnf("python_version < '3.11' and python_full_version >= '3.11.2'").is_never());
+        assert!(dnf("python_version <= '3.11'").is_disjoint(&dnf("python_version > '3.11'")));
+        assert!(!dnf("python_version <= '3.11'").is_disjoint(&dnf("python_version >= '3.11'")));
+    }
+
+    #[test]
+    fn negation() {
+        let marker = dnf("sys_platform == 'win32' or python_version < '3.11'");
+        let negated = marker.negate().unwrap();
+        assert_eq!(
+            negated.to_marker().unwrap().to_string(),
+            "python_version >= '3.11' and sys_platform != 'win32'"
+        );
+        assert!(marker.is_disjoint(&negated));
+
+        // The union of a marker and its negation is always satisfied.
+        let mut union = marker.clone();
+        union.or(negated);
+        assert!(union.is_always());
+
+        assert!(dnf("python_version ~= '3.11'").negate().is_none());
+    }
+
+    #[test]
+    fn environments() {
+        let environments = Environments::new(Range::higher_than(Version::new([3, 8])));
+        assert!(!environments.includes(&dnf("python_version < '3.8'")));
+        assert!(environments.includes(&dnf("python_version < '3.11'")));
+
+        let environments = environments.narrow(&dnf("sys_platform == 'win32'"));
+        assert!(!environments.includes(&dnf("sys_platform == 'linux'")));
+        assert!(environments.includes(&dnf("python_version < '3.11'")));
+    }
+}
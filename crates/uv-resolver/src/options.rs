@@ -1,14 +1,17 @@
-use chrono::{DateTime, Utc};
+use pep440_rs::VersionSpecifiers;
 
-use crate::{DependencyMode, PreReleaseMode, ResolutionMode};
+use crate::{DependencyMode, ExcludeNewer, PreReleaseMode, ResolutionMode, YankedMode};
 
 /// Options for resolving a manifest.
-#[derive(Debug, Default, Copy, Clone)]
+#[derive(Debug, Default, Clone)]
 pub struct Options {
     pub resolution_mode: ResolutionMode,
     pub prerelease_mode: PreReleaseMode,
     pub dependency_mode: DependencyMode,
-    pub exclude_newer: Option<DateTime<Utc>>,
+    pub exclude_newer: ExcludeNewer,
+    pub universal: bool,
+    pub requires_python: Option<VersionSpecifiers>,
+    pub yanked_mode: YankedMode,
 }
 
 /// Builder for [`Options`].
@@ -17,7 +20,10 @@ pub struct OptionsBuilder {
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
     dependency_mode: DependencyMode,
-    exclude_newer: Option<DateTime<Utc>>,
+    exclude_newer: ExcludeNewer,
+    universal: bool,
+    requires_python: Option<VersionSpecifiers>,
+    yanked_mode: YankedMode,
 }
 
 impl OptionsBuilder {
@@ -47,10 +53,36 @@ impl OptionsBuilder {
         self
     }
 
-    /// Sets the exclusion date.
+    /// Sets the exclusion date, either for all packages (as in `Some(timestamp)`) or with
+    /// per-package cutoffs (as in an [`ExcludeNewer`]).
+    #[must_use]
+    pub fn exclude_newer(mut self, exclude_newer: impl Into<ExcludeNewer>) -> Self {
+        self.exclude_newer = exclude_newer.into();
+        self
+    }
+
+    /// Sets whether to perform a universal resolution, in which environment markers are preserved
+    /// rather than evaluated against the current platform.
+    #[must_use]
+    pub fn universal(mut self, universal: bool) -> Self {
+        self.universal = universal;
+        self
+    }
+
+    /// Sets the Python versions covered by a universal resolution (e.g., a project's
+    /// `requires-python`).
+    ///
+    /// If unset, the resolution covers every Python version.
+    #[must_use]
+    pub fn requires_python(mut self, requires_python: Option<VersionSpecifiers>) -> Self {
+        self.requires_python = requires_python;
+        self
+    }
+
+    /// Sets the [`YankedMode`].
     #[must_use]
-    pub fn exclude_newer(mut self, exclude_newer: Option<DateTime<Utc>>) -> Self {
-        self.exclude_newer = exclude_newer;
+    pub fn yanked_mode(mut self, yanked_mode: YankedMode) -> Self {
+        self.yanked_mode = yanked_mode;
         self
     }
 
@@ -61,6 +93,9 @@ impl OptionsBuilder {
             prerelease_mode: self.prerelease_mode,
             dependency_mode: self.dependency_mode,
             exclude_newer: self.exclude_newer,
+            universal: self.universal,
+            requires_python: self.requires_python,
+            yanked_mode: self.yanked_mode,
         }
     }
 }
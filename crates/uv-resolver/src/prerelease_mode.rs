@@ -1,9 +1,12 @@
 use rustc_hash::FxHashSet;
 
-use pep508_rs::{Requirement, VersionOrUrl};
+use pep508_rs::VersionOrUrl;
 use uv_normalize::PackageName;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+use crate::Manifest;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum PreReleaseMode {
     /// Disallow all pre-release versions.
@@ -16,7 +19,7 @@ pub enum PreReleaseMode {
     IfNecessary,
 
     /// Allow pre-release versions for first-party packages with explicit pre-release markers in
-    /// their version requirements.
+    /// their version requirements (including those in constraints and overrides).
     Explicit,
 
     /// Allow pre-release versions if all versions of a package are pre-release, or if the package
@@ -48,51 +51,15 @@ pub(crate) enum PreReleaseStrategy {
 }
 
 impl PreReleaseStrategy {
-    pub(crate) fn from_mode(mode: PreReleaseMode, direct_dependencies: &[Requirement]) -> Self {
+    pub(crate) fn from_mode(mode: PreReleaseMode, manifest: &Manifest) -> Self {
         match mode {
             PreReleaseMode::Disallow => Self::Disallow,
             PreReleaseMode::Allow => Self::Allow,
             PreReleaseMode::IfNecessary => Self::IfNecessary,
-            PreReleaseMode::Explicit => Self::Explicit(
-                direct_dependencies
-                    .iter()
-                    .filter(|requirement| {
-                        let Some(version_or_url) = &requirement.version_or_url else {
-                            return false;
-                        };
-                        let version_specifiers = match version_or_url {
-                            VersionOrUrl::VersionSpecifier(version_specifiers) => {
-                                version_specifiers
-                            }
-                            VersionOrUrl::Url(_) => return false,
-                        };
-                        version_specifiers
-                            .iter()
-                            .any(pep440_rs::VersionSpecifier::any_prerelease)
-                    })
-                    .map(|requirement| requirement.name.clone())
-                    .collect(),
-            ),
-            PreReleaseMode::IfNecessaryOrExplicit => Self::IfNecessaryOrExplicit(
-                direct_dependencies
-                    .iter()
-                    .filter(|requirement| {
-                        let Some(version_or_url) = &requirement.version_or_url else {
-                            return false;
-                        };
-                        let version_specifiers = match version_or_url {
-                            VersionOrUrl::VersionSpecifier(version_specifiers) => {
-                                version_specifiers
-                            }
-                            VersionOrUrl::Url(_) => return false,
-                        };
-                        version_specifiers
-                            .iter()
-                            .any(pep440_rs::VersionSpecifier::any_prerelease)
-                    })
-                    .map(|requirement| requirement.name.clone())
-                    .collect(),
-            ),
+            PreReleaseMode::Explicit => Self::Explicit(explicit_prereleases(manifest)),
+            PreReleaseMode::IfNecessaryOrExplicit => {
+                Self::IfNecessaryOrExplicit(explicit_prereleases(manifest))
+            }
         }
     }
 
@@ -107,3 +74,32 @@ impl PreReleaseStrategy {
         }
     }
 }
+
+/// Return the set of packages with an explicit pre-release marker in any of their first-party
+/// version requirements, i.e., in the direct requirements, constraints, overrides, or the
+/// requirements of any editables.
+fn explicit_prereleases(manifest: &Manifest) -> FxHashSet<PackageName> {
+    manifest
+        .requirements
+        .iter()
+        .chain(manifest.constraints.iter())
+        .chain(manifest.overrides.iter())
+        .chain(
+            manifest
+                .editables
+                .iter()
+                .flat_map(|(_, metadata)| metadata.requires_dist.iter()),
+        )
+        .filter(|requirement| {
+            let Some(VersionOrUrl::VersionSpecifier(version_specifiers)) =
+                &requirement.version_or_url
+            else {
+                return false;
+            };
+            version_specifiers
+                .iter()
+                .any(pep440_rs::VersionSpecifier::any_prerelease)
+        })
+        .map(|requirement| requirement.name.clone())
+        .collect()
+}
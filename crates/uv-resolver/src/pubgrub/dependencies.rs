@@ -1,6 +1,7 @@
 use itertools::Itertools;
 use pubgrub::range::Range;
-use tracing::warn;
+use rustc_hash::FxHashMap;
+use tracing::{debug, warn};
 
 use distribution_types::Verbatim;
 use pep440_rs::Version;
@@ -8,17 +9,25 @@ use pep508_rs::{MarkerEnvironment, Requirement, VersionOrUrl};
 use uv_normalize::{ExtraName, PackageName};
 
 use crate::constraints::Constraints;
+use crate::markers::{is_relevant, Environments, MarkerDnf};
 use crate::overrides::Overrides;
 use crate::pubgrub::specifier::PubGrubSpecifier;
 use crate::pubgrub::PubGrubPackage;
 use crate::resolver::Urls;
 use crate::ResolveError;
 
-#[derive(Debug)]
-pub struct PubGrubDependencies(Vec<(PubGrubPackage, Range<Version>)>);
+/// A set of `PubGrub` dependencies, along with the environment markers under which each
+/// dependency is required (in a universal resolution).
+#[derive(Debug, Default, Clone)]
+pub struct PubGrubDependencies(Vec<(PubGrubPackage, Range<Version>, MarkerDnf)>);
 
 impl PubGrubDependencies {
     /// Generate a set of `PubGrub` dependencies from a set of requirements.
+    ///
+    /// If `universal` is set, environment markers (other than `extra`) are not evaluated against
+    /// the given environment; instead, any requirement that applies to at least one of the
+    /// given environments is included, along with its marker.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn from_requirements(
         requirements: &[Requirement],
         constraints: &Constraints,
@@ -27,19 +36,22 @@ impl PubGrubDependencies {
         source_extra: Option<&ExtraName>,
         urls: &Urls,
         env: &MarkerEnvironment,
+        universal: Option<&Environments>,
     ) -> Result<Self, ResolveError> {
         let mut dependencies = Vec::default();
+        let extras = source_extra.map(std::slice::from_ref).unwrap_or_default();
 
         // Iterate over all declared requirements.
         for requirement in overrides.apply(requirements) {
             // If the requirement isn't relevant for the current platform, skip it.
-            if let Some(extra) = source_extra {
-                if !requirement.evaluate_markers(env, std::slice::from_ref(extra)) {
-                    continue;
-                }
-            } else if !requirement.evaluate_markers(env, &[]) {
+            if !is_relevant(requirement, env, extras, universal) {
                 continue;
             }
+            let marker = if universal.is_some() {
+                MarkerDnf::from_marker(requirement.marker.as_ref(), extras)
+            } else {
+                MarkerDnf::always()
+            };
 
             // Add the package, plus any extra variants.
             for result in std::iter::once(to_pubgrub(requirement, None, urls)).chain(
@@ -62,18 +74,19 @@ impl PubGrubDependencies {
                     }
                 }
 
-                dependencies.push((package.clone(), version.clone()));
+                dependencies.push((package.clone(), version.clone(), marker.clone()));
 
                 // If the requirement was constrained, add those constraints.
                 for constraint in constraints.get(&requirement.name).into_iter().flatten() {
                     // If the requirement isn't relevant for the current platform, skip it.
-                    if let Some(extra) = source_extra {
-                        if !constraint.evaluate_markers(env, std::slice::from_ref(extra)) {
-                            continue;
-                        }
-                    } else if !constraint.evaluate_markers(env, &[]) {
+                    if !is_relevant(constraint, env, extras, universal) {
                         continue;
                     }
+                    let marker = if universal.is_some() {
+                        marker.and(&MarkerDnf::from_marker(constraint.marker.as_ref(), extras))
+                    } else {
+                        MarkerDnf::always()
+                    };
 
                     // Add the package, plus any extra variants.
                     for result in std::iter::once(to_pubgrub(constraint, None, urls)).chain(
@@ -96,7 +109,7 @@ impl PubGrubDependencies {
                             }
                         }
 
-                        dependencies.push((package.clone(), version.clone()));
+                        dependencies.push((package.clone(), version.clone(), marker.clone()));
                     }
                 }
             }
@@ -107,19 +120,117 @@ impl PubGrubDependencies {
 
     /// Add a [`PubGrubPackage`] and [`PubGrubVersion`] range into the dependencies.
     pub(crate) fn push(&mut self, package: PubGrubPackage, version: Range<Version>) {
-        self.0.push((package, version));
+        self.0.push((package, version, MarkerDnf::always()));
     }
 
     /// Iterate over the dependencies.
-    pub(crate) fn iter(&self) -> impl Iterator<Item = &(PubGrubPackage, Range<Version>)> {
-        self.0.iter()
+    pub(crate) fn iter(&self) -> impl Iterator<Item = (&PubGrubPackage, &Range<Version>)> {
+        self.0
+            .iter()
+            .map(|(package, version, _)| (package, version))
+    }
+
+    /// Split the dependencies into forks, one for each set of environments that requires a
+    /// different version (or range of versions) of the same package.
+    ///
+    /// For example, given `numpy>=2; python_version >= '3.10'` and
+    /// `numpy<2; python_version < '3.10'`, the dependencies are split into a fork for Python 3.10
+    /// and later, which depends on `numpy>=2`, and a fork for earlier versions of Python, which
+    /// depends on `numpy<2`. Each fork is then solved independently.
+    ///
+    /// If the requirements on a package overlap (as in `numpy>=2` and
+    /// `numpy<2; sys_platform == 'win32'`), they're combined as usual, rather than forked.
+    pub(crate) fn fork(self, environments: &Environments) -> Vec<(Environments, Self)> {
+        let Some(markers) = self.fork_markers(environments) else {
+            return vec![(environments.clone(), self)];
+        };
+
+        let mut forks = Vec::new();
+        for marker in markers {
+            let environments = environments.narrow(&marker);
+            let dependencies = Self(
+                self.0
+                    .iter()
+                    .filter(|(_, _, marker)| environments.includes(marker))
+                    .cloned()
+                    .collect(),
+            );
+            forks.extend(dependencies.fork(&environments));
+        }
+        forks
+    }
+
+    /// Return the markers on which the dependencies should be forked, if any.
+    fn fork_markers(&self, environments: &Environments) -> Option<Vec<MarkerDnf>> {
+        // Group the requirements on each package by marker, in order of appearance.
+        let mut packages: Vec<(&PackageName, Vec<(&MarkerDnf, Vec<_>)>)> = Vec::new();
+        let mut positions = FxHashMap::default();
+        for (package, version, marker) in &self.0 {
+            let PubGrubPackage::Package(name, ..) = package else {
+                continue;
+            };
+            let position = *positions.entry(name).or_insert_with(|| {
+                packages.push((name, Vec::new()));
+                packages.len() - 1
+            });
+            let groups = &mut packages[position].1;
+            if let Some((_, requirements)) =
+                groups.iter_mut().find(|(existing, _)| *existing == marker)
+            {
+                requirements.push((package, version));
+            } else {
+                groups.push((marker, vec![(package, version)]));
+            }
+        }
+
+        packages.into_iter().find_map(|(name, groups)| {
+            // Fork if the package is required with different requirements under disjoint
+            // markers.
+            if groups.len() < 2
+                || groups
+                    .iter()
+                    .all(|(_, requirements)| *requirements == groups[0].1)
+            {
+                return None;
+            }
+            let disjoint = groups.iter().enumerate().all(|(index, (left, _))| {
+                groups[index + 1..]
+                    .iter()
+                    .all(|(right, _)| !environments.narrow(left).includes(right))
+            });
+            if !disjoint {
+                return None;
+            }
+
+            // Include a fork for the environments in which the package isn't required at all.
+            let mut markers = groups
+                .iter()
+                .map(|(marker, _)| (*marker).clone())
+                .collect::<Vec<_>>();
+            let mut union = MarkerDnf::never();
+            for marker in &markers {
+                union.or(marker.clone());
+            }
+            let Some(remainder) = union.negate() else {
+                debug!("Not forking on {name}, as its markers can't be negated");
+                return None;
+            };
+            if environments.includes(&remainder) {
+                markers.push(remainder);
+            }
+            Some(markers)
+        })
     }
 }
 
 /// Convert a [`PubGrubDependencies`] to a [`DependencyConstraints`].
 impl From<PubGrubDependencies> for Vec<(PubGrubPackage, Range<Version>)> {
     fn from(dependencies: PubGrubDependencies) -> Self {
-        dependencies.0
+        dependencies
+            .0
+            .into_iter()
+            .map(|(package, version, _)| (package, version))
+            .collect()
     }
 }
 
@@ -6,7 +6,7 @@ use uv_normalize::PackageName;
 
 use crate::pubgrub::package::PubGrubPackage;
 
-#[derive(Debug, Default)]
+#[derive(Debug, Default, Clone)]
 pub(crate) struct PubGrubPriorities(FxHashMap<PackageName, usize>);
 
 impl PubGrubPriorities {
@@ -12,7 +12,7 @@ use pubgrub::range::Range;
 use pubgrub::report::{DerivationTree, Derived, External, ReportFormatter};
 use pubgrub::term::Term;
 use pubgrub::type_aliases::Map;
-use rustc_hash::FxHashMap;
+use rustc_hash::{FxHashMap, FxHashSet};
 use uv_normalize::PackageName;
 
 use crate::candidate_selector::CandidateSelector;
@@ -332,6 +332,54 @@ impl PubGrubReportFormatter<'_> {
         }
     }
 
+    /// Render a derivation tree as a hierarchical explanation, in which each conclusion is
+    /// followed by the (indented) causes from which it was derived.
+    ///
+    /// Unlike the default report, which flattens the tree into a sequence of numbered
+    /// paragraphs, this makes it possible to trace a conflict back to the requirements that
+    /// introduced it, even when the conflict is deep in the graph.
+    pub(crate) fn format_tree(
+        &self,
+        derivation_tree: &DerivationTree<PubGrubPackage, Range<Version>>,
+    ) -> String {
+        let mut output = String::new();
+        self.format_tree_node(derivation_tree, 0, &mut FxHashSet::default(), &mut output);
+        output
+    }
+
+    /// Write a single node in the derivation tree (and its causes) to the output.
+    fn format_tree_node(
+        &self,
+        derivation_tree: &DerivationTree<PubGrubPackage, Range<Version>>,
+        depth: usize,
+        seen: &mut FxHashSet<usize>,
+        output: &mut String,
+    ) {
+        let indent = "  ".repeat(depth);
+        match derivation_tree {
+            DerivationTree::External(external) => {
+                let external = self.format_external(external);
+                output.push_str(&format!("{indent}- {}\n", external.trim_end()));
+            }
+            DerivationTree::Derived(derived) => {
+                let terms = self.format_terms(&derived.terms);
+
+                // If the same incompatibility was already explained, refer back to it, rather
+                // than repeating the entire subtree.
+                if let Some(id) = derived.shared_id {
+                    if !seen.insert(id) {
+                        output.push_str(&format!("{indent}- {} (see above)\n", terms.trim_end()));
+                        return;
+                    }
+                }
+
+                output.push_str(&format!("{indent}- {}, because:\n", terms.trim_end()));
+                self.format_tree_node(&derived.cause1, depth + 1, seen, output);
+                self.format_tree_node(&derived.cause2, depth + 1, seen, output);
+            }
+        }
+    }
+
     /// Generate the [`PubGrubHints`] for a derivation tree.
     ///
     /// The [`PubGrubHints`] help users resolve errors by providing additional context or modifying
@@ -342,6 +390,7 @@ impl PubGrubReportFormatter<'_> {
         selector: &Option<CandidateSelector>,
         index_locations: &Option<IndexLocations>,
         unavailable_packages: &FxHashMap<PackageName, UnavailablePackage>,
+        url_versions: &FxHashMap<PubGrubPackage, Version>,
     ) -> IndexSet<PubGrubHint> {
         /// Returns `true` if pre-releases were allowed for a package.
         fn allowed_prerelease(package: &PubGrubPackage, selector: &CandidateSelector) -> bool {
@@ -384,6 +433,18 @@ impl PubGrubReportFormatter<'_> {
                         }
                     }
 
+                    // Check for no versions due to a URL requirement that provides a version outside
+                    // of the requested range.
+                    if let Some(version) = url_versions.get(package) {
+                        if !set.contains(version) {
+                            hints.insert(PubGrubHint::UrlVersionMismatch {
+                                package: package.clone(),
+                                version: version.clone(),
+                                range: self.simplify_set(set, package).into_owned(),
+                            });
+                        }
+                    }
+
                     // Check for no versions due to no `--find-links` flat index
                     if let Some(index_locations) = index_locations {
                         let no_find_links =
@@ -414,12 +475,14 @@ impl PubGrubReportFormatter<'_> {
                     selector,
                     index_locations,
                     unavailable_packages,
+                    url_versions,
                 ));
                 hints.extend(self.hints(
                     &derived.cause2,
                     selector,
                     index_locations,
                     unavailable_packages,
+                    url_versions,
                 ));
             }
         }
@@ -450,6 +513,15 @@ pub(crate) enum PubGrubHint {
     NoIndex,
     /// A package was not found in the registry, but
     Offline,
+    /// A package was requested via a URL, but the version provided by the URL doesn't satisfy a
+    /// version specifier for the same package.
+    UrlVersionMismatch {
+        package: PubGrubPackage,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        version: Version,
+        #[derivative(PartialEq = "ignore", Hash = "ignore")]
+        range: Range<Version>,
+    },
 }
 
 impl std::fmt::Display for PubGrubHint {
@@ -491,6 +563,22 @@ impl std::fmt::Display for PubGrubHint {
                     ":".bold(),
                 )
             }
+            Self::UrlVersionMismatch {
+                package,
+                version,
+                range,
+            } => {
+                write!(
+                    f,
+                    "{}{} {} was requested via a direct URL, which provides {}=={}, but {} was also required",
+                    "hint".bold().cyan(),
+                    ":".bold(),
+                    package.bold(),
+                    package.bold(),
+                    version.bold(),
+                    PackageRange::compatibility(package, range).bold()
+                )
+            }
         }
     }
 }
@@ -726,3 +814,74 @@ fn fmt_package(package: &PubGrubPackage) -> String {
         _ => format!("{package}"),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use indexmap::IndexMap;
+    use pubgrub::range::Range;
+    use pubgrub::report::{DerivationTree, Derived, External};
+    use pubgrub::term::Term;
+    use pubgrub::type_aliases::Map;
+    use uv_normalize::PackageName;
+
+    use crate::pubgrub::PubGrubPackage;
+
+    use super::PubGrubReportFormatter;
+
+    fn package(name: &str) -> PubGrubPackage {
+        PubGrubPackage::Package(PackageName::from_str(name).unwrap(), None, None)
+    }
+
+    #[test]
+    fn format_tree() {
+        let root = PubGrubPackage::Root(None);
+        let a = package("a");
+        let b = package("b");
+
+        // `a` can't be used, because it depends on `b`, which has no versions.
+        let a_unusable = DerivationTree::Derived(Derived {
+            terms: Map::from_iter([(a.clone(), Term::Positive(Range::full()))]),
+            shared_id: Some(0),
+            cause1: Box::new(DerivationTree::External(External::FromDependencyOf(
+                a.clone(),
+                Range::full(),
+                b.clone(),
+                Range::full(),
+            ))),
+            cause2: Box::new(DerivationTree::External(External::NoVersions(
+                b,
+                Range::full(),
+                None,
+            ))),
+        });
+
+        // The requirements are unsatisfiable, because they require `a`.
+        let derivation_tree = DerivationTree::Derived(Derived {
+            terms: Map::from_iter([(root.clone(), Term::Positive(Range::full()))]),
+            shared_id: None,
+            cause1: Box::new(DerivationTree::External(External::FromDependencyOf(
+                root,
+                Range::full(),
+                a,
+                Range::full(),
+            ))),
+            cause2: Box::new(a_unusable),
+        });
+
+        let available_versions = IndexMap::default();
+        let formatter = PubGrubReportFormatter {
+            available_versions: &available_versions,
+            python_requirement: None,
+        };
+
+        insta::assert_snapshot!(formatter.format_tree(&derivation_tree), @r###"
+        - the requirements are unsatisfiable, because:
+          - you require a
+          - all versions of a cannot be used, because:
+            - all versions of a depend on b
+            - there are no versions of b
+        "###);
+    }
+}
@@ -1,29 +1,40 @@
 use std::borrow::Cow;
 use std::hash::BuildHasherDefault;
+use std::path::Path;
 
 use anyhow::Result;
 use dashmap::DashMap;
+use futures::{StreamExt, TryStreamExt};
 use owo_colors::OwoColorize;
+use petgraph::graph::{EdgeIndex, NodeIndex};
 use petgraph::visit::EdgeRef;
 use petgraph::Direction;
 use pubgrub::range::Range;
 use pubgrub::solver::{Kind, State};
 use pubgrub::type_aliases::SelectedDependencies;
+use rkyv::{de::deserializers::SharedDeserializeMap, Deserialize};
 use rustc_hash::FxHashMap;
+use tracing::debug;
 use url::Url;
 
-use distribution_types::{Dist, DistributionMetadata, LocalEditable, Name, PackageId, Verbatim};
+use distribution_types::{
+    BuiltDist, Dist, DistributionMetadata, File, FileLocation, LocalEditable, Name, PackageId,
+    SourceDist, Verbatim, VersionOrUrl,
+};
 use once_map::OnceMap;
 use pep440_rs::Version;
-use pep508_rs::VerbatimUrl;
-use pypi_types::{Hashes, Metadata21};
+use pep508_rs::{MarkerTree, VerbatimUrl};
+use pypi_types::{Hashes, Metadata21, Yanked};
+use uv_cache::Cache;
+use uv_client::{ErrorKind, RegistryClient, VersionFiles};
 use uv_normalize::{ExtraName, PackageName};
 
 use crate::editables::Editables;
+use crate::markers::MarkerDnf;
 use crate::pins::FilePins;
 use crate::pubgrub::{PubGrubDistribution, PubGrubPackage, PubGrubPriority};
 use crate::resolver::VersionsResponse;
-use crate::ResolveError;
+use crate::{HashAlgorithm, ResolveError};
 
 /// Indicate the style of annotation comments, used to indicate the dependencies that requested each
 /// package.
@@ -39,20 +50,30 @@ pub enum AnnotationStyle {
 
 /// A complete resolution graph in which every node represents a pinned package and every edge
 /// represents a dependency between two pinned packages.
+///
+/// In a universal resolution, the same package may be pinned to a different version in each set
+/// of environments, in which case the graph contains a node for each version.
 #[derive(Debug)]
 pub struct ResolutionGraph {
     /// The underlying graph.
     petgraph: petgraph::graph::Graph<Dist, Range<Version>, petgraph::Directed>,
     /// The metadata for every distribution in this resolution.
-    hashes: FxHashMap<PackageName, Vec<Hashes>>,
+    hashes: FxHashMap<NodeIndex, Vec<Hashes>>,
     /// The set of editable requirements in this resolution.
     editables: Editables,
+    /// The environment markers under which each package is required, for universal resolutions.
+    /// Packages that are required unconditionally are omitted.
+    markers: FxHashMap<NodeIndex, MarkerTree>,
+    /// The environment markers on each dependency edge (from dependent to dependency), for
+    /// universal resolutions. Edges that are unconditional are omitted.
+    edge_markers: FxHashMap<EdgeIndex, MarkerTree>,
     /// Any diagnostics that were encountered while building the graph.
     diagnostics: Vec<Diagnostic>,
 }
 
 impl ResolutionGraph {
     /// Create a new graph from the resolved `PubGrub` state.
+    #[allow(clippy::too_many_arguments)]
     pub(crate) fn from_state(
         selection: &SelectedDependencies<PubGrubPackage, Version>,
         pins: &FilePins,
@@ -61,6 +82,8 @@ impl ResolutionGraph {
         redirects: &DashMap<Url, Url>,
         state: &State<PubGrubPackage, Range<Version>, PubGrubPriority>,
         editables: Editables,
+        markers: FxHashMap<PackageName, MarkerTree>,
+        edge_markers: FxHashMap<(PackageName, PackageName), MarkerTree>,
     ) -> Result<Self, ResolveError> {
         // TODO(charlie): petgraph is a really heavy and unnecessary dependency here. We should
         // write our own graph, given that our requirements are so simple.
@@ -84,20 +107,42 @@ impl ResolutionGraph {
                             .clone()
                     };
 
+                    // Add the distribution to the graph.
+                    let index = petgraph.add_node(pinned_package.clone());
+                    inverse.insert(package_name, index);
+
                     // Add its hashes to the index.
                     if let Some(versions_response) = packages.get(package_name) {
                         if let VersionsResponse::Found(ref version_map) = *versions_response {
-                            hashes.insert(package_name.clone(), {
+                            hashes.insert(index, {
                                 let mut hashes = version_map.hashes(version);
                                 hashes.sort_unstable();
+                                hashes.dedup();
                                 hashes
                             });
+
+                            // Flag any yanked versions that were selected (e.g., due to an
+                            // exact pin).
+                            if let Some(dist) = version_map.get(version).and_then(|dist| dist.get())
+                            {
+                                match dist.yanked() {
+                                    Yanked::Bool(false) => {}
+                                    Yanked::Bool(true) => {
+                                        diagnostics.push(Diagnostic::YankedVersion {
+                                            dist: pinned_package.clone(),
+                                            reason: None,
+                                        });
+                                    }
+                                    Yanked::Reason(reason) => {
+                                        diagnostics.push(Diagnostic::YankedVersion {
+                                            dist: pinned_package.clone(),
+                                            reason: Some(reason.clone()),
+                                        });
+                                    }
+                                }
+                            }
                         }
                     }
-
-                    // Add the distribution to the graph.
-                    let index = petgraph.add_node(pinned_package);
-                    inverse.insert(package_name, index);
                 }
                 PubGrubPackage::Package(package_name, None, Some(url)) => {
                     // Create the distribution.
@@ -111,20 +156,16 @@ impl ResolutionGraph {
                         Dist::from_url(package_name.clone(), url)?
                     };
 
-                    // Add its hashes to the index.
-                    if let Some(versions_response) = packages.get(package_name) {
-                        if let VersionsResponse::Found(ref version_map) = *versions_response {
-                            hashes.insert(package_name.clone(), {
-                                let mut hashes = version_map.hashes(version);
-                                hashes.sort_unstable();
-                                hashes
-                            });
-                        }
-                    }
-
                     // Add the distribution to the graph.
                     let index = petgraph.add_node(pinned_package);
                     inverse.insert(package_name, index);
+
+                    // Add its hashes to the index. A direct URL is pinned to a single artifact, so
+                    // the only hash we can report is the one included in the URL itself (e.g.,
+                    // `#sha256=...`).
+                    if let Some(hash) = url.raw().fragment().and_then(parse_hash_fragment) {
+                        hashes.insert(index, vec![hash]);
+                    }
                 }
                 PubGrubPackage::Package(package_name, Some(extra), None) => {
                     // Validate that the `extra` exists.
@@ -239,14 +280,115 @@ impl ResolutionGraph {
             }
         }
 
+        // Attach the markers to the corresponding nodes and edges.
+        let markers = markers
+            .into_iter()
+            .filter_map(|(package_name, marker)| Some((*inverse.get(&package_name)?, marker)))
+            .collect();
+        let edge_markers = edge_markers
+            .into_iter()
+            .filter_map(|((source, target), marker)| {
+                let edge = petgraph.find_edge(*inverse.get(&source)?, *inverse.get(&target)?)?;
+                Some((edge, marker))
+            })
+            .collect();
+
         Ok(Self {
             petgraph,
             hashes,
             editables,
+            markers,
+            edge_markers,
             diagnostics,
         })
     }
 
+    /// Merge the resolutions of each fork of a universal resolution into a single graph.
+    ///
+    /// A distribution that was selected in multiple forks is included once, under the disjunction
+    /// of its markers in each fork; as such, a package is only annotated with a marker if it's
+    /// required (or pinned to a given version) in some environments, but not others.
+    pub(crate) fn union(mut resolutions: Vec<Self>) -> Self {
+        // If the resolution wasn't forked, there's nothing to merge.
+        if resolutions.len() == 1 {
+            return resolutions.pop().expect("a single resolution");
+        }
+        let mut resolutions = resolutions.into_iter();
+        let mut union = resolutions.next().expect("at least one resolution");
+
+        let dnf = |marker: Option<&MarkerTree>| MarkerDnf::from_marker(marker, &[]);
+        let mut markers = union
+            .petgraph
+            .node_indices()
+            .map(|index| (index, dnf(union.markers.get(&index))))
+            .collect::<FxHashMap<_, _>>();
+        let mut edge_markers = union
+            .petgraph
+            .edge_indices()
+            .map(|edge| (edge, dnf(union.edge_markers.get(&edge))))
+            .collect::<FxHashMap<_, _>>();
+        let mut inverse = union
+            .petgraph
+            .node_indices()
+            .map(|index| (union.petgraph[index].package_id(), index))
+            .collect::<FxHashMap<_, _>>();
+
+        for resolution in resolutions {
+            // Add every distribution, or widen the marker of an existing distribution.
+            let mut indices = FxHashMap::default();
+            for index in resolution.petgraph.node_indices() {
+                let dist = &resolution.petgraph[index];
+                let target = *inverse.entry(dist.package_id()).or_insert_with(|| {
+                    let target = union.petgraph.add_node(dist.clone());
+                    if let Some(hashes) = resolution.hashes.get(&index) {
+                        union.hashes.insert(target, hashes.clone());
+                    }
+                    markers.insert(target, MarkerDnf::never());
+                    target
+                });
+                markers
+                    .get_mut(&target)
+                    .expect("every node has a marker")
+                    .or(dnf(resolution.markers.get(&index)));
+                indices.insert(index, target);
+            }
+
+            // Add every edge, or widen the marker of an existing edge.
+            for edge in resolution.petgraph.edge_references() {
+                let source = indices[&edge.source()];
+                let target = indices[&edge.target()];
+                let index = union
+                    .petgraph
+                    .update_edge(source, target, edge.weight().clone());
+                edge_markers
+                    .entry(index)
+                    .or_insert_with(MarkerDnf::never)
+                    .or(dnf(resolution.edge_markers.get(&edge.id())));
+            }
+
+            // Add any diagnostics that weren't already reported by another fork.
+            for diagnostic in resolution.diagnostics {
+                if !union
+                    .diagnostics
+                    .iter()
+                    .any(|existing| existing.message() == diagnostic.message())
+                {
+                    union.diagnostics.push(diagnostic);
+                }
+            }
+        }
+
+        union.markers = markers
+            .into_iter()
+            .filter_map(|(index, marker)| Some((index, marker.to_marker()?)))
+            .collect();
+        union.edge_markers = edge_markers
+            .into_iter()
+            .filter_map(|(edge, marker)| Some((edge, marker.to_marker()?)))
+            .collect();
+        union
+    }
+
     /// Return the number of packages in the graph.
     pub fn len(&self) -> usize {
         self.petgraph.node_count()
@@ -264,6 +406,26 @@ impl ResolutionGraph {
             .any(|index| self.petgraph[index].name() == name)
     }
 
+    /// Return the environment markers under which the given node is required, if the package is
+    /// only required conditionally (as in a universal resolution).
+    pub fn marker(&self, index: NodeIndex) -> Option<&MarkerTree> {
+        self.markers.get(&index)
+    }
+
+    /// Return the environment markers on the given dependency edge, if the dependency is only
+    /// required conditionally (as in a universal resolution).
+    pub fn edge_marker(&self, edge: EdgeIndex) -> Option<&MarkerTree> {
+        self.edge_markers.get(&edge)
+    }
+
+    /// Return the hashes for the given node, as recorded during resolution.
+    pub fn hashes(&self, index: NodeIndex) -> &[Hashes] {
+        self.hashes
+            .get(&index)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
     /// Return the [`Diagnostic`]s that were encountered while building the graph.
     pub fn diagnostics(&self) -> &[Diagnostic] {
         &self.diagnostics
@@ -273,6 +435,254 @@ impl ResolutionGraph {
     pub fn petgraph(&self) -> &petgraph::graph::Graph<Dist, Range<Version>, petgraph::Directed> {
         &self.petgraph
     }
+
+    /// Return the files of each pinned registry release that were included in the resolution,
+    /// keyed by node.
+    ///
+    /// The index is queried for the files of each release. Packages that aren't available on a
+    /// simple index (e.g., those provided via `--find-links`) are limited to the pinned file.
+    pub async fn files(
+        &self,
+        client: &RegistryClient,
+        concurrency: usize,
+    ) -> Result<FxHashMap<NodeIndex, Vec<File>>, ResolveError> {
+        futures::stream::iter(
+            self.petgraph
+                .node_indices()
+                .filter(|index| self.petgraph[*index].file().is_some()),
+        )
+        .map(|index| async move {
+            let files = self.release_files(index, client).await?;
+            Ok::<_, ResolveError>((index, files))
+        })
+        .buffer_unordered(concurrency)
+        .try_collect()
+        .await
+    }
+
+    /// Return the files of the pinned release for the given registry distribution that were
+    /// included in the resolution (e.g., omitting those that were excluded by `--exclude-newer`).
+    ///
+    /// If the files aren't listed on a simple index (e.g., the package is only provided via
+    /// `--find-links`, or `--no-index` was passed), only the pinned file is returned.
+    async fn release_files(
+        &self,
+        index: NodeIndex,
+        client: &RegistryClient,
+    ) -> Result<Vec<File>, ResolveError> {
+        let dist = &self.petgraph[index];
+        let (Some(pinned), VersionOrUrl::Version(version)) = (dist.file(), dist.version_or_url())
+        else {
+            return Ok(Vec::new());
+        };
+        let existing = self.hashes(index);
+
+        let mut files = match client.simple(dist.name()).await {
+            Ok((_, metadata)) => metadata
+                .iter()
+                .find_map(|datum| {
+                    let datum_version: Version = datum
+                        .version
+                        .deserialize(&mut SharedDeserializeMap::new())
+                        .expect("archived version always deserializes");
+                    (&datum_version == version).then(|| {
+                        datum
+                            .files
+                            .deserialize(&mut SharedDeserializeMap::new())
+                            .expect("archived version files should deserialize")
+                    })
+                })
+                .map(|files: VersionFiles| {
+                    files
+                        .all()
+                        .map(|(_, file)| file)
+                        .filter(|file| existing.contains(&file.hashes))
+                        .collect::<Vec<_>>()
+                })
+                .unwrap_or_default(),
+            Err(err)
+                if matches!(
+                    err.kind(),
+                    ErrorKind::NoIndex(_) | ErrorKind::PackageNotFound(_)
+                ) =>
+            {
+                Vec::new()
+            }
+            Err(err) => return Err(err.into()),
+        };
+
+        if files.is_empty() {
+            files.push(pinned.clone());
+        }
+        Ok(files)
+    }
+
+    /// Replace the hashes for every distribution in the graph with digests computed using the
+    /// given [`HashAlgorithm`].
+    ///
+    /// Direct URL and local archives are downloaded (or read from disk) and hashed locally, unless
+    /// the URL fragment already includes a digest for the requested algorithm.
+    ///
+    /// For registry distributions, the index is queried for every file of each pinned release.
+    /// Digests published by the index are used as-is; otherwise, the file is downloaded and the
+    /// digest is computed locally. Packages that aren't available on a simple index (e.g., those
+    /// provided via `--find-links`) are limited to the pinned file.
+    pub async fn with_hash_algorithm(
+        mut self,
+        algorithm: HashAlgorithm,
+        client: &RegistryClient,
+        cache: &Cache,
+        concurrency: usize,
+    ) -> Result<Self, ResolveError> {
+        // Determine the files to hash for each distribution.
+        let files = futures::stream::iter(self.petgraph.node_indices())
+            .map(|index| self.files_to_hash(index, algorithm, client))
+            .buffer_unordered(concurrency)
+            .try_collect::<Vec<_>>()
+            .await?;
+        let files = files.into_iter().flatten().collect::<Vec<_>>();
+
+        // Compute the digests concurrently, reusing any digests published by the index.
+        let digests = futures::stream::iter(
+            files
+                .iter()
+                .flat_map(|(index, files)| files.iter().map(move |file| (*index, file))),
+        )
+        .map(|(index, file)| async move {
+            let digest = match file {
+                ArchiveDigest::Published(digest) => digest.clone(),
+                ArchiveDigest::Compute(url) => {
+                    debug!("Computing {algorithm} digest for: {url}");
+                    let name = self.petgraph[index].name();
+                    algorithm.fetch_cached(url, name, client, cache).await?
+                }
+            };
+            Ok::<_, ResolveError>((index, algorithm.hashes(digest)))
+        })
+        .buffer_unordered(concurrency)
+        .try_collect::<Vec<_>>()
+        .await?;
+
+        for (index, _) in &files {
+            self.hashes.insert(*index, Vec::new());
+        }
+        for (index, hashes) in digests {
+            self.hashes.entry(index).or_default().push(hashes);
+        }
+        for hashes in self.hashes.values_mut() {
+            hashes.sort_unstable();
+            hashes.dedup();
+        }
+
+        Ok(self)
+    }
+
+    /// Return the files whose digests should be emitted for the given distribution, or `None` if
+    /// the existing hashes should be retained.
+    async fn files_to_hash(
+        &self,
+        index: NodeIndex,
+        algorithm: HashAlgorithm,
+        client: &RegistryClient,
+    ) -> Result<Option<(NodeIndex, Vec<ArchiveDigest>)>, ResolveError> {
+        let dist = &self.petgraph[index];
+        let existing = self.hashes(index);
+
+        // Hash direct URL and local archives, unless the URL includes a suitable digest.
+        if let Some(url) = archive_url(dist) {
+            if existing
+                .iter()
+                .any(|hashes| algorithm.digest(hashes).is_some())
+            {
+                return Ok(None);
+            }
+            return Ok(Some((index, vec![ArchiveDigest::Compute(url.clone())])));
+        }
+
+        let Some(pinned) = dist.file() else {
+            return Ok(None);
+        };
+
+        // If the source didn't publish any hashes (e.g., a `--find-links` directory), hash the
+        // pinned file.
+        if existing.is_empty() {
+            return Ok(Some((
+                index,
+                vec![ArchiveDigest::from_file(pinned, algorithm)?],
+            )));
+        }
+
+        // Otherwise, the SHA 256 digests recorded during resolution can be used as-is.
+        if algorithm == HashAlgorithm::Sha256 {
+            return Ok(None);
+        }
+
+        let files = self
+            .release_files(index, client)
+            .await?
+            .iter()
+            .map(|file| ArchiveDigest::from_file(file, algorithm))
+            .collect::<Result<Vec<_>, _>>()?;
+        Ok(Some((index, files)))
+    }
+}
+
+/// The digest of a file to include in the output, as published by the index or computed from the
+/// archive at the given URL.
+#[derive(Debug)]
+enum ArchiveDigest {
+    Published(String),
+    Compute(Url),
+}
+
+impl ArchiveDigest {
+    /// Return the digest of the given [`File`], if published; otherwise, the URL from which it
+    /// should be computed.
+    fn from_file(file: &File, algorithm: HashAlgorithm) -> Result<Self, uv_client::Error> {
+        if let Some(digest) = algorithm.digest(&file.hashes) {
+            return Ok(Self::Published(digest.to_string()));
+        }
+        let url = match &file.url {
+            FileLocation::RelativeUrl(base, url) => pypi_types::base_url_join_relative(base, url)
+                .map_err(ErrorKind::JoinRelativeError)?,
+            FileLocation::AbsoluteUrl(url) => Url::parse(url).map_err(ErrorKind::UrlParseError)?,
+            FileLocation::Path(path) => Url::from_file_path(path).expect("path is absolute"),
+        };
+        Ok(Self::Compute(url))
+    }
+}
+
+/// Parse the hashes from a URL fragment, as in: `sha256=6088930bfe239f0e6710546ab9c19c9ef35e29792895fed6e6e31a023a182a61`.
+///
+/// The fragment is parsed as a list of `&`-separated key-value pairs (e.g., `sha256=...&egg=foo`),
+/// and any keys other than hash algorithms are ignored.
+fn parse_hash_fragment(fragment: &str) -> Option<Hashes> {
+    let mut hashes = Hashes::default();
+    for (name, value) in fragment.split('&').filter_map(|pair| pair.split_once('=')) {
+        let value = Some(value.to_string());
+        match name {
+            "md5" => hashes.md5 = value,
+            "sha256" => hashes.sha256 = value,
+            "sha384" => hashes.sha384 = value,
+            "sha512" => hashes.sha512 = value,
+            _ => {}
+        }
+    }
+    (hashes != Hashes::default()).then_some(hashes)
+}
+
+/// Return the URL of the archive for a direct URL or local distribution, if it can be hashed.
+///
+/// Git repositories and local directories (including editables) aren't archives, so there's
+/// nothing to hash.
+pub(crate) fn archive_url(dist: &Dist) -> Option<&Url> {
+    match dist {
+        Dist::Built(BuiltDist::DirectUrl(dist)) => Some(dist.url.raw()),
+        Dist::Built(BuiltDist::Path(dist)) => Some(dist.url.raw()),
+        Dist::Source(SourceDist::DirectUrl(dist)) => Some(dist.url.raw()),
+        Dist::Source(SourceDist::Path(dist)) if dist.path.is_file() => Some(dist.url.raw()),
+        _ => None,
+    }
 }
 
 /// A [`std::fmt::Display`] implementation for the resolution graph.
@@ -290,11 +700,26 @@ pub struct DisplayResolutionGraph<'a> {
     /// The style of annotation comments, used to indicate the dependencies that requested each
     /// package.
     annotation_style: AnnotationStyle,
+    /// Whether to include the version range requested by each dependency in the annotations.
+    include_specifiers: bool,
+    /// The input files that requested each package (e.g., `-r requirements.in`), to include in
+    /// the annotations.
+    origins: FxHashMap<PackageName, Vec<String>>,
+    /// The directory against which local (`file://`) URLs should be relativized, if any.
+    relative_to: Option<&'a Path>,
 }
 
 impl<'a> From<&'a ResolutionGraph> for DisplayResolutionGraph<'a> {
     fn from(resolution: &'a ResolutionGraph) -> Self {
-        Self::new(resolution, &[], false, true, AnnotationStyle::default())
+        Self::new(
+            resolution,
+            &[],
+            false,
+            true,
+            AnnotationStyle::default(),
+            false,
+            FxHashMap::default(),
+        )
     }
 }
 
@@ -306,6 +731,8 @@ impl<'a> DisplayResolutionGraph<'a> {
         show_hashes: bool,
         include_annotations: bool,
         annotation_style: AnnotationStyle,
+        include_specifiers: bool,
+        origins: FxHashMap<PackageName, Vec<String>>,
     ) -> DisplayResolutionGraph<'a> {
         Self {
             resolution: underlying,
@@ -313,10 +740,185 @@ impl<'a> DisplayResolutionGraph<'a> {
             show_hashes,
             include_annotations,
             annotation_style,
+            include_specifiers,
+            origins,
+            relative_to: None,
+        }
+    }
+
+    /// Render local (`file://`) URLs as paths relative to the given directory (e.g., the directory
+    /// containing the output file), rather than as absolute URLs.
+    #[must_use]
+    pub fn with_relative_paths(mut self, base: &'a Path) -> Self {
+        self.relative_to = Some(base);
+        self
+    }
+
+    /// Return the path to a local URL, relative to the configured base directory, if relative
+    /// paths are enabled.
+    fn relative_path(&self, url: &VerbatimUrl) -> Option<String> {
+        let base = self.relative_to?;
+        let url = url.raw();
+        if url.scheme() != "file" || url.fragment().is_some() || url.query().is_some() {
+            return None;
         }
+        let path = url.to_file_path().ok()?;
+        portable_relative_path(&path, base)
+    }
+
+    /// Return the URL for the given distribution, relativized if enabled.
+    fn url(&self, url: &VerbatimUrl) -> String {
+        self.relative_path(url)
+            .unwrap_or_else(|| url.verbatim().to_string())
+    }
+
+    /// Return the formatted hashes for the given node, sorted and deduplicated, such that the
+    /// output is stable regardless of the order in which the index returned the files.
+    fn hashes(&self, index: NodeIndex) -> Vec<String> {
+        let mut hashes = self
+            .resolution
+            .hashes(index)
+            .iter()
+            .filter_map(Hashes::to_string)
+            .collect::<Vec<_>>();
+        hashes.sort_unstable();
+        hashes.dedup();
+        hashes
+    }
+
+    /// Serialize the graph as JSON.
+    ///
+    /// Each package is emitted with its name, version (for registry distributions), URL (for
+    /// direct URL and editable distributions), environment markers, and hashes (if enabled). Each
+    /// dependency edge is emitted with the version range required by the dependent package.
+    pub fn to_json(&self) -> serde_json::Value {
+        // Collect all packages, sorted by name.
+        let mut nodes = self
+            .resolution
+            .petgraph
+            .node_indices()
+            .filter(|index| {
+                !self
+                    .no_emit_packages
+                    .contains(self.resolution.petgraph[*index].name())
+            })
+            .collect::<Vec<_>>();
+        nodes.sort_unstable_by_key(|index| (self.resolution.petgraph[*index].name(), *index));
+
+        let packages = nodes
+            .iter()
+            .map(|index| {
+                let dist = &self.resolution.petgraph[*index];
+                let name = dist.name();
+
+                let (version, url, editable) = if let Some((editable, _)) =
+                    self.resolution.editables.get(name)
+                {
+                    (None, Some(self.url(editable.url())), true)
+                } else {
+                    match dist.version_or_url() {
+                        VersionOrUrl::Version(version) => (Some(version.to_string()), None, false),
+                        VersionOrUrl::Url(url) => (
+                            None,
+                            Some(self.relative_path(url).unwrap_or_else(|| url.to_string())),
+                            false,
+                        ),
+                    }
+                };
+
+                let hashes = if self.show_hashes {
+                    self.hashes(*index)
+                } else {
+                    Vec::new()
+                };
+
+                serde_json::json!({
+                    "name": name.to_string(),
+                    "version": version,
+                    "url": url,
+                    "editable": editable,
+                    "marker": self.resolution.marker(*index).map(ToString::to_string),
+                    "hashes": hashes,
+                })
+            })
+            .collect::<Vec<_>>();
+
+        let mut dependencies = self
+            .resolution
+            .petgraph
+            .edge_references()
+            .filter(|edge| nodes.contains(&edge.source()) && nodes.contains(&edge.target()))
+            .map(|edge| {
+                (
+                    self.resolution.petgraph[edge.source()].name(),
+                    self.resolution.petgraph[edge.target()].name(),
+                    edge.weight(),
+                )
+            })
+            .collect::<Vec<_>>();
+        dependencies.sort_unstable_by_key(|(source, target, _)| (*source, *target));
+
+        let dependencies = dependencies
+            .into_iter()
+            .map(|(source, target, range)| {
+                serde_json::json!({
+                    "from": source.to_string(),
+                    "to": target.to_string(),
+                    "range": range.to_string(),
+                })
+            })
+            .collect::<Vec<_>>();
+
+        serde_json::json!({
+            "packages": packages,
+            "dependencies": dependencies,
+        })
+    }
+}
+
+/// Render `path` relative to the directory `base`.
+///
+/// The path is always rendered with forward slashes and a leading `./` or `../`, such that it
+/// can be parsed back as a local path on any platform.
+pub(crate) fn portable_relative_path(path: &Path, base: &Path) -> Option<String> {
+    let relative = uv_fs::relative_to(path, base)?;
+    let relative = relative
+        .components()
+        .map(|component| component.as_os_str().to_string_lossy())
+        .collect::<Vec<_>>()
+        .join("/");
+    if relative.is_empty() {
+        Some(".".to_string())
+    } else if relative.starts_with("..") {
+        Some(relative)
+    } else {
+        Some(format!("./{relative}"))
     }
 }
 
+/// Format a single `requirements.txt` entry: the requirement itself (e.g., `flask==3.0.0`,
+/// `flask @ https://...`, or `-e ./path`), followed by its environment marker and `--hash` options,
+/// if any.
+///
+/// Shared by `uv pip compile` (via [`DisplayResolutionGraph`]) and `uv export`, such that both
+/// emit identical entries.
+pub fn requirements_txt_entry(
+    requirement: &str,
+    marker: Option<impl std::fmt::Display>,
+    hashes: &[String],
+) -> String {
+    let mut line = match marker {
+        Some(marker) => format!("{requirement} ; {marker}"),
+        None => requirement.to_string(),
+    };
+    for hash in hashes {
+        line.push_str(" \\\n");
+        line.push_str("    --hash=");
+        line.push_str(hash);
+    }
+    line
+}
+
 /// Write the graph in the `{name}=={version}` format of requirements.txt that pip uses.
 impl std::fmt::Display for DisplayResolutionGraph<'_> {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
@@ -332,8 +934,8 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
         enum NodeKey<'a> {
             /// A node linked to an editable distribution, sorted by verbatim representation.
             Editable(Cow<'a, str>),
-            /// A node linked to a non-editable distribution, sorted by package name.
-            Distribution(&'a PackageName),
+            /// A node linked to a non-editable distribution, sorted by package name and version.
+            Distribution(&'a PackageName, Option<&'a Version>),
         }
 
         impl<'a> Node<'a> {
@@ -349,7 +951,12 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
             fn key(&self) -> NodeKey<'a> {
                 match self {
                     Node::Editable(_, editable) => NodeKey::Editable(editable.verbatim()),
-                    Node::Distribution(name, _) => NodeKey::Distribution(name),
+                    Node::Distribution(name, dist) => match dist.version_or_url() {
+                        VersionOrUrl::Version(version) => {
+                            NodeKey::Distribution(name, Some(version))
+                        }
+                        VersionOrUrl::Url(_) => NodeKey::Distribution(name, None),
+                    },
                 }
             }
         }
@@ -380,54 +987,61 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
 
         // Print out the dependency graph.
         for (index, node) in nodes {
-            // Display the node itself.
-            let mut line = match node {
-                Node::Distribution(_, dist) => format!("{}", dist.verbatim()),
-                Node::Editable(_, editable) => format!("-e {}", editable.verbatim()),
-            };
-
-            // Display the distribution hashes, if any.
-            let mut has_hashes = false;
-            if self.show_hashes {
-                if let Some(hashes) = self
-                    .resolution
-                    .hashes
-                    .get(node.name())
-                    .filter(|hashes| !hashes.is_empty())
-                {
-                    for hash in hashes {
-                        if let Some(hash) = hash.to_string() {
-                            has_hashes = true;
-                            line.push_str(" \\\n");
-                            line.push_str("    --hash=");
-                            line.push_str(&hash);
-                        }
-                    }
+            // Display the node itself, along with its marker and hashes (if any).
+            let (requirement, marker) = match node {
+                Node::Distribution(name, dist) => {
+                    let requirement = match dist.version_or_url() {
+                        VersionOrUrl::Url(url) => match self.relative_path(url) {
+                            Some(path) => Cow::Owned(format!("{name} @ {path}")),
+                            None => dist.verbatim(),
+                        },
+                        VersionOrUrl::Version(_) => dist.verbatim(),
+                    };
+                    (requirement, self.resolution.marker(index))
                 }
-            }
+                Node::Editable(_, editable) => {
+                    (Cow::Owned(format!("-e {}", self.url(editable.url()))), None)
+                }
+            };
+            let hashes = if self.show_hashes {
+                self.hashes(index)
+            } else {
+                Vec::new()
+            };
+            let has_hashes = !hashes.is_empty();
+            let line = requirements_txt_entry(&requirement, marker, &hashes);
 
             // Determine the annotation comment and separator (between comment and requirement).
             let mut annotation = None;
 
             if self.include_annotations {
-                // Display all dependencies.
+                // Display all dependencies, along with the requested range (if enabled).
                 let mut edges = self
                     .resolution
                     .petgraph
                     .edges_directed(index, Direction::Incoming)
-                    .map(|edge| &self.resolution.petgraph[edge.source()])
+                    .map(|edge| {
+                        let name = self.resolution.petgraph[edge.source()].name();
+                        let range = edge.weight();
+                        if self.include_specifiers && *range != Range::full() {
+                            format!("{name} ({range})")
+                        } else {
+                            name.to_string()
+                        }
+                    })
                     .collect::<Vec<_>>();
-                edges.sort_unstable_by_key(|package| package.name());
+
+                // Include the input files that requested the package directly.
+                if let Some(origins) = self.origins.get(node.name()) {
+                    edges.extend(origins.iter().cloned());
+                }
+                edges.sort_unstable();
 
                 match self.annotation_style {
                     AnnotationStyle::Line => {
                         if !edges.is_empty() {
                             let separator = if has_hashes { "\n    " } else { "  " };
-                            let deps = edges
-                                .into_iter()
-                                .map(|dependency| dependency.name().to_string())
-                                .collect::<Vec<_>>()
-                                .join(", ");
+                            let deps = edges.join(", ");
                             let comment = format!("# via {deps}").green().to_string();
                             annotation = Some((separator, comment));
                         }
@@ -436,14 +1050,14 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
                         [] => {}
                         [edge] => {
                             let separator = "\n";
-                            let comment = format!("    # via {}", edge.name()).green().to_string();
+                            let comment = format!("    # via {edge}").green().to_string();
                             annotation = Some((separator, comment));
                         }
                         edges => {
                             let separator = "\n";
                             let deps = edges
                                 .iter()
-                                .map(|dependency| format!("    #   {}", dependency.name()))
+                                .map(|dependency| format!("    #   {dependency}"))
                                 .collect::<Vec<_>>()
                                 .join("\n");
                             let comment = format!("    # via\n{deps}").green().to_string();
@@ -453,14 +1067,15 @@ impl std::fmt::Display for DisplayResolutionGraph<'_> {
                 }
             }
 
-            if let Some((separator, comment)) = annotation {
-                // Assemble the line with the annotations and remove trailing whitespaces.
-                for line in format!("{line:24}{separator}{comment}").lines() {
-                    let line = line.trim_end();
-                    writeln!(f, "{line}")?;
-                }
+            // Assemble the line with the annotations (if any), and normalize the line endings and
+            // trailing whitespace, such that the output is identical across platforms.
+            let line = if let Some((separator, comment)) = annotation {
+                format!("{line:24}{separator}{comment}")
             } else {
-                // Write the line as is.
+                line
+            };
+            for line in line.lines() {
+                let line = line.trim_end();
                 writeln!(f, "{line}")?;
             }
         }
@@ -495,6 +1110,13 @@ pub enum Diagnostic {
         /// The extra that was requested. For example, `colorama` in `black[colorama]`.
         extra: ExtraName,
     },
+    YankedVersion {
+        /// The selected distribution that was marked as yanked by the index. For example,
+        /// `attrs==21.1.0`.
+        dist: Dist,
+        /// The reason the distribution was yanked, if provided by the index.
+        reason: Option<String>,
+    },
 }
 
 impl Diagnostic {
@@ -504,6 +1126,13 @@ impl Diagnostic {
             Self::MissingExtra { dist, extra } => {
                 format!("The package `{dist}` does not have an extra named `{extra}`.")
             }
+            Self::YankedVersion { dist, reason } => {
+                if let Some(reason) = reason {
+                    format!("`{dist}` is yanked (reason: \"{reason}\").")
+                } else {
+                    format!("`{dist}` is yanked.")
+                }
+            }
         }
     }
 
@@ -511,6 +1140,50 @@ impl Diagnostic {
     pub fn includes(&self, name: &PackageName) -> bool {
         match self {
             Self::MissingExtra { dist, .. } => name == dist.name(),
+            Self::YankedVersion { dist, .. } => name == dist.name(),
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use pypi_types::Hashes;
+
+    use super::parse_hash_fragment;
+
+    #[test]
+    fn parse_hash_fragment_single() {
+        assert_eq!(
+            parse_hash_fragment("sha256=abc"),
+            Some(Hashes {
+                sha256: Some("abc".to_string()),
+                ..Hashes::default()
+            })
+        );
+    }
+
+    #[test]
+    fn parse_hash_fragment_with_other_keys() {
+        assert_eq!(
+            parse_hash_fragment("sha256=abc&egg=foo"),
+            Some(Hashes {
+                sha256: Some("abc".to_string()),
+                ..Hashes::default()
+            })
+        );
+        assert_eq!(
+            parse_hash_fragment("egg=foo&sha512=def"),
+            Some(Hashes {
+                sha512: Some("def".to_string()),
+                ..Hashes::default()
+            })
+        );
+    }
+
+    #[test]
+    fn parse_hash_fragment_without_hash() {
+        assert_eq!(parse_hash_fragment("egg=foo"), None);
+        assert_eq!(parse_hash_fragment("subdirectory=bar&egg=foo"), None);
+        assert_eq!(parse_hash_fragment(""), None);
+    }
+}
@@ -3,7 +3,8 @@ use rustc_hash::FxHashSet;
 use pep508_rs::Requirement;
 use uv_normalize::PackageName;
 
-#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, serde::Deserialize)]
+#[serde(rename_all = "kebab-case")]
 #[cfg_attr(feature = "clap", derive(clap::ValueEnum))]
 pub enum ResolutionMode {
     /// Resolve the highest compatible version of each package.
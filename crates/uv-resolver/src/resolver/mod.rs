@@ -1,6 +1,7 @@
 //! Given a set of requirements, find a set of compatible packages.
 
 use std::borrow::Cow;
+use std::collections::VecDeque;
 use std::fmt::{Display, Formatter};
 use std::sync::Arc;
 
@@ -11,6 +12,7 @@ use itertools::Itertools;
 use pubgrub::error::PubGrubError;
 use pubgrub::range::Range;
 use pubgrub::solver::{Incompatibility, State};
+use pubgrub::type_aliases::SelectedDependencies;
 use rustc_hash::{FxHashMap, FxHashSet};
 use tokio::select;
 use tokio_stream::wrappers::ReceiverStream;
@@ -19,30 +21,31 @@ use url::Url;
 
 use distribution_filename::WheelFilename;
 use distribution_types::{
-    BuiltDist, Dist, DistributionMetadata, IncompatibleWheel, Name, RemoteSource, SourceDist,
-    VersionOrUrl,
+    BuiltDist, Dist, DistributionMetadata, IncompatibleDist, IncompatibleSource, IncompatibleWheel,
+    Name, RemoteSource, SourceDist, VersionOrUrl,
 };
 use pep440_rs::{Version, VersionSpecifiers, MIN_VERSION};
-use pep508_rs::{MarkerEnvironment, Requirement};
+use pep508_rs::{MarkerEnvironment, MarkerTree, Requirement};
 use platform_tags::{IncompatibleTag, Tags};
 use pypi_types::{Metadata21, Yanked};
 pub(crate) use urls::Urls;
 use uv_client::{FlatIndex, RegistryClient};
 use uv_distribution::DistributionDatabase;
 use uv_interpreter::Interpreter;
-use uv_normalize::PackageName;
-use uv_traits::BuildContext;
+use uv_normalize::{ExtraName, PackageName};
+use uv_traits::{BuildContext, Concurrency};
 
 use crate::candidate_selector::{CandidateDist, CandidateSelector};
 use crate::constraints::Constraints;
 use crate::editables::Editables;
 use crate::error::ResolveError;
 use crate::manifest::Manifest;
+use crate::markers::{is_relevant, Environments, MarkerDnf};
 use crate::overrides::Overrides;
 use crate::pins::FilePins;
 use crate::pubgrub::{
-    PubGrubDependencies, PubGrubDistribution, PubGrubPackage, PubGrubPriorities, PubGrubPython,
-    PubGrubSpecifier,
+    PubGrubDependencies, PubGrubDistribution, PubGrubPackage, PubGrubPriorities, PubGrubPriority,
+    PubGrubPython, PubGrubSpecifier,
 };
 use crate::python_requirement::PythonRequirement;
 use crate::resolution::ResolutionGraph;
@@ -70,7 +73,7 @@ pub(crate) enum UnavailableVersion {
     /// Version is incompatible because it is yanked
     Yanked(Yanked),
     /// Version is incompatible because it has no usable distributions
-    NoDistributions(Option<IncompatibleWheel>),
+    NoDistributions(IncompatibleDist),
 }
 
 /// The package is unavailable and cannot be used
@@ -101,6 +104,9 @@ pub struct Resolver<'a, Provider: ResolverProvider> {
     urls: Urls,
     dependency_mode: DependencyMode,
     markers: &'a MarkerEnvironment,
+    /// If set, environment markers are preserved, rather than evaluated against `markers`, and
+    /// the resolution covers each of the given environments.
+    universal: Option<Environments>,
     python_requirement: PythonRequirement,
     selector: CandidateSelector,
     index: &'a InMemoryIndex,
@@ -110,6 +116,8 @@ pub struct Resolver<'a, Provider: ResolverProvider> {
     visited: DashSet<PackageName>,
     reporter: Option<Arc<dyn Reporter>>,
     provider: Provider,
+    /// The maximum number of concurrent metadata requests.
+    concurrency: usize,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> Resolver<'a, DefaultResolverProvider<'a, Context>> {
@@ -134,17 +142,19 @@ impl<'a, Context: BuildContext + Send + Sync> Resolver<'a, DefaultResolverProvid
             flat_index,
             tags,
             PythonRequirement::new(interpreter, markers),
-            options.exclude_newer,
+            options.exclude_newer.clone(),
             build_context.no_binary(),
+            build_context.no_build(),
         );
-        Self::new_custom_io(
+        Ok(Self::new_custom_io(
             manifest,
             options,
             markers,
             PythonRequirement::new(interpreter, markers),
             index,
             provider,
-        )
+        )?
+        .with_concurrency(build_context.concurrency().downloads))
     }
 }
 
@@ -158,14 +168,27 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         index: &'a InMemoryIndex,
         provider: Provider,
     ) -> Result<Self, ResolveError> {
-        let selector = CandidateSelector::for_resolution(&manifest, options);
-
-        // Determine the allowed yanked package versions
-        let allowed_yanks = manifest
-            .requirements
-            .iter()
-            .chain(manifest.constraints.iter())
-            .collect();
+        let selector = CandidateSelector::for_resolution(&manifest, &options);
+
+        // Determine the allowed yanked package versions.
+        let allowed_yanks = AllowedYanks::from_manifest(&manifest, options.yanked_mode);
+
+        // Determine the environments covered by a universal resolution: any platform, running any
+        // Python version permitted by `requires-python` (or, if unset, any Python version).
+        let universal = if options.universal {
+            let python = match options.requires_python.as_ref() {
+                Some(requires_python) => requires_python
+                    .iter()
+                    .map(PubGrubSpecifier::try_from)
+                    .fold_ok(Range::full(), |range, specifier| {
+                        range.intersection(&specifier.into())
+                    })?,
+                None => Range::full(),
+            };
+            Some(Environments::new(python))
+        } else {
+            None
+        };
 
         Ok(Self {
             index,
@@ -174,19 +197,30 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
             selector,
             allowed_yanks,
             dependency_mode: options.dependency_mode,
-            urls: Urls::from_manifest(&manifest, markers)?,
+            urls: Urls::from_manifest(&manifest, markers, universal.as_ref())?,
             project: manifest.project,
             requirements: manifest.requirements,
             constraints: Constraints::from_requirements(manifest.constraints),
             overrides: Overrides::from_requirements(manifest.overrides),
             editables: Editables::from_requirements(manifest.editables),
             markers,
+            universal,
             python_requirement,
             reporter: None,
             provider,
+            concurrency: Concurrency::DEFAULT_DOWNLOADS,
         })
     }
 
+    /// Set the maximum number of concurrent metadata requests.
+    #[must_use]
+    pub fn with_concurrency(self, concurrency: usize) -> Self {
+        Self {
+            concurrency,
+            ..self
+        }
+    }
+
     /// Set the [`Reporter`] to use for this installer.
     #[must_use]
     pub fn with_reporter(self, reporter: impl Reporter + 'static) -> Self {
@@ -203,7 +237,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         // A channel to fetch package metadata (e.g., given `flask`, fetch all versions) and version
         // metadata (e.g., given `flask==1.0.0`, fetch the metadata for that version).
         // Channel size is set to the same size as the task buffer for simplicity.
-        let (request_sink, request_stream) = tokio::sync::mpsc::channel(50);
+        let (request_sink, request_stream) = tokio::sync::mpsc::channel(self.concurrency);
 
         // Run the fetcher.
         let requests_fut = self.fetch(request_stream).fuse();
@@ -227,6 +261,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                             .with_python_requirement(&self.python_requirement)
                             .with_index_locations(self.provider.index_locations())
                             .with_unavailable_packages(&self.unavailable_packages)
+                            .with_url_versions(&self.index.distributions)
                         )
                     } else {
                         err
@@ -241,6 +276,12 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
     }
 
     /// Run the `PubGrub` solver.
+    ///
+    /// In a universal resolution, the solver forks whenever a package's requirements differ across
+    /// disjoint sets of environments (e.g., `numpy>=2; python_version >= '3.10'` and
+    /// `numpy<2; python_version < '3.10'`). Each fork is solved independently, and the resulting
+    /// resolutions are merged, such that a package may be pinned to a different version in each
+    /// fork.
     #[instrument(skip_all)]
     async fn solve(
         &self,
@@ -248,228 +289,293 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
     ) -> Result<ResolutionGraph, ResolveError> {
         let root = PubGrubPackage::Root(self.project.clone());
 
-        // Keep track of the packages for which we've requested metadata.
-        let mut pins = FilePins::default();
-        let mut priorities = PubGrubPriorities::default();
-
         // Start the solve.
-        let mut state = State::init(root.clone(), MIN_VERSION.clone());
-        let mut added_dependencies: FxHashMap<PubGrubPackage, FxHashSet<Version>> =
-            FxHashMap::default();
-        let mut next = root;
+        let mut forks = vec![ForkState {
+            state: State::init(root.clone(), MIN_VERSION.clone()),
+            pins: FilePins::default(),
+            priorities: PubGrubPriorities::default(),
+            added_dependencies: FxHashMap::default(),
+            next: root,
+            environments: self.universal.clone(),
+        }];
+        let mut resolutions = Vec::new();
 
         debug!(
             "Solving with target Python version {}",
             self.python_requirement.target()
         );
 
-        loop {
-            // Run unit propagation.
-            state.unit_propagation(next)?;
+        'forks: while let Some(mut fork) = forks.pop() {
+            if let Some(marker) = fork
+                .environments
+                .as_ref()
+                .and_then(|environments| environments.marker().to_marker())
+            {
+                debug!("Solving fork: {marker}");
+            }
 
-            // Pre-visit all candidate packages, to allow metadata to be fetched in parallel.
-            Self::pre_visit(state.partial_solution.prioritized_packages(), request_sink).await?;
+            loop {
+                // Run unit propagation.
+                fork.state.unit_propagation(fork.next.clone())?;
 
-            // Choose a package version.
-            let Some(highest_priority_pkg) =
-                state
-                    .partial_solution
-                    .pick_highest_priority_pkg(|package, _range| {
-                        priorities.get(package).unwrap_or_default()
-                    })
-            else {
-                let selection = state.partial_solution.extract_solution();
-                return ResolutionGraph::from_state(
-                    &selection,
-                    &pins,
-                    &self.index.packages,
-                    &self.index.distributions,
-                    &self.index.redirects,
-                    &state,
-                    self.editables.clone(),
-                );
-            };
-            next = highest_priority_pkg;
-
-            let term_intersection = state
-                .partial_solution
-                .term_intersection_for_package(&next)
-                .ok_or_else(|| {
-                    PubGrubError::Failure("a package was chosen but we don't have a term.".into())
-                })?;
-            let decision = self
-                .choose_version(
-                    &next,
-                    term_intersection.unwrap_positive(),
-                    &mut pins,
+                // Pre-visit all candidate packages, to allow metadata to be fetched in parallel.
+                Self::pre_visit(
+                    fork.state.partial_solution.prioritized_packages(),
                     request_sink,
                 )
                 .await?;
 
-            // Pick the next compatible version.
-            let version = match decision {
-                None => {
-                    debug!("No compatible version found for: {next}");
-
-                    let term_intersection = state
-                        .partial_solution
-                        .term_intersection_for_package(&next)
-                        .expect("a package was chosen but we don't have a term.");
-
-                    let reason = {
-                        if let PubGrubPackage::Package(ref package_name, _, _) = next {
-                            // Check if the decision was due to the package being unavailable
-                            self.unavailable_packages
-                                .get(package_name)
-                                .map(|entry| match *entry {
-                                    UnavailablePackage::NoIndex => {
-                                        "was not found in the provided package locations"
-                                    }
-                                    UnavailablePackage::Offline => "was not found in the cache",
-                                    UnavailablePackage::NotFound => {
-                                        "was not found in the package registry"
-                                    }
-                                })
-                        } else {
-                            None
-                        }
+                // Choose a package version.
+                let Some(highest_priority_pkg) = fork
+                    .state
+                    .partial_solution
+                    .pick_highest_priority_pkg(|package, _range| {
+                        fork.priorities.get(package).unwrap_or_default()
+                    })
+                else {
+                    let selection = fork.state.partial_solution.extract_solution();
+                    let (markers, edge_markers) = match fork.environments.as_ref() {
+                        Some(environments) => self.package_markers(&selection, environments),
+                        None => (FxHashMap::default(), FxHashMap::default()),
                     };
+                    resolutions.push(ResolutionGraph::from_state(
+                        &selection,
+                        &fork.pins,
+                        &self.index.packages,
+                        &self.index.distributions,
+                        &self.index.redirects,
+                        &fork.state,
+                        self.editables.clone(),
+                        markers,
+                        edge_markers,
+                    )?);
+                    continue 'forks;
+                };
+                fork.next = highest_priority_pkg;
 
-                    let inc = Incompatibility::no_versions(
-                        next.clone(),
-                        term_intersection.clone(),
-                        reason.map(ToString::to_string),
-                    );
+                let term_intersection = fork
+                    .state
+                    .partial_solution
+                    .term_intersection_for_package(&fork.next)
+                    .ok_or_else(|| {
+                        PubGrubError::Failure(
+                            "a package was chosen but we don't have a term.".into(),
+                        )
+                    })?;
+                let decision = self
+                    .choose_version(
+                        &fork.next,
+                        term_intersection.unwrap_positive(),
+                        &mut fork.pins,
+                        request_sink,
+                    )
+                    .await?;
+
+                // Pick the next compatible version.
+                let version = match decision {
+                    None => {
+                        debug!("No compatible version found for: {}", fork.next);
+
+                        let term_intersection = fork
+                            .state
+                            .partial_solution
+                            .term_intersection_for_package(&fork.next)
+                            .expect("a package was chosen but we don't have a term.");
+
+                        let reason = {
+                            if let PubGrubPackage::Package(ref package_name, _, _) = fork.next {
+                                // Check if the decision was due to the package being unavailable
+                                self.unavailable_packages.get(package_name).map(
+                                    |entry| match *entry {
+                                        UnavailablePackage::NoIndex => {
+                                            "was not found in the provided package locations"
+                                        }
+                                        UnavailablePackage::Offline => "was not found in the cache",
+                                        UnavailablePackage::NotFound => {
+                                            "was not found in the package registry"
+                                        }
+                                    },
+                                )
+                            } else {
+                                None
+                            }
+                        };
 
-                    state.add_incompatibility(inc);
-                    continue;
-                }
-                Some(version) => version,
-            };
-            let version = match version {
-                ResolverVersion::Available(version) => version,
-                ResolverVersion::Unavailable(version, unavailable) => {
-                    let reason = match unavailable {
-                        UnavailableVersion::RequiresPython(requires_python) => {
-                            // Incompatible requires-python versions are special in that we track
-                            // them as incompatible dependencies instead of marking the package version
-                            // as unavailable directly
-                            let python_version = requires_python
-                                .iter()
-                                .map(PubGrubSpecifier::try_from)
-                                .fold_ok(Range::full(), |range, specifier| {
-                                    range.intersection(&specifier.into())
-                                })?;
-
-                            let package = &next;
-                            for kind in [PubGrubPython::Installed, PubGrubPython::Target] {
-                                state.add_incompatibility(Incompatibility::from_dependency(
-                                    package.clone(),
-                                    Range::singleton(version.clone()),
-                                    (PubGrubPackage::Python(kind), python_version.clone()),
-                                ));
+                        let inc = Incompatibility::no_versions(
+                            fork.next.clone(),
+                            term_intersection.clone(),
+                            reason.map(ToString::to_string),
+                        );
+
+                        fork.state.add_incompatibility(inc);
+                        continue;
+                    }
+                    Some(version) => version,
+                };
+                let version = match version {
+                    ResolverVersion::Available(version) => version,
+                    ResolverVersion::Unavailable(version, unavailable) => {
+                        let reason = match unavailable {
+                            UnavailableVersion::RequiresPython(requires_python) => {
+                                // Incompatible requires-python versions are special in that we track
+                                // them as incompatible dependencies instead of marking the package version
+                                // as unavailable directly
+                                let python_version = requires_python
+                                    .iter()
+                                    .map(PubGrubSpecifier::try_from)
+                                    .fold_ok(Range::full(), |range, specifier| {
+                                        range.intersection(&specifier.into())
+                                    })?;
+
+                                let package = &fork.next;
+                                for kind in [PubGrubPython::Installed, PubGrubPython::Target] {
+                                    fork.state.add_incompatibility(
+                                        Incompatibility::from_dependency(
+                                            package.clone(),
+                                            Range::singleton(version.clone()),
+                                            (PubGrubPackage::Python(kind), python_version.clone()),
+                                        ),
+                                    );
+                                }
+                                fork.state
+                                    .partial_solution
+                                    .add_decision(fork.next.clone(), version);
+                                continue;
                             }
-                            state.partial_solution.add_decision(next.clone(), version);
-                            continue;
-                        }
-                        UnavailableVersion::Yanked(yanked) => match yanked {
-                            Yanked::Bool(_) => "it was yanked".to_string(),
-                            Yanked::Reason(reason) => format!(
-                                "it was yanked (reason: {})",
-                                reason.trim().trim_end_matches('.')
-                            ),
-                        },
-                        UnavailableVersion::NoDistributions(best_incompatible) => {
-                            if let Some(best_incompatible) = best_incompatible {
+                            UnavailableVersion::Yanked(yanked) => match yanked {
+                                Yanked::Bool(_) => "it was yanked".to_string(),
+                                Yanked::Reason(reason) => format!(
+                                    "it was yanked (reason: {})",
+                                    reason.trim().trim_end_matches('.')
+                                ),
+                            },
+                            UnavailableVersion::NoDistributions(best_incompatible) => {
                                 match best_incompatible {
-                                    IncompatibleWheel::NoBinary => "no source distribution is available and using wheels is disabled".to_string(),
-                                    IncompatibleWheel::RequiresPython => "no wheels are available that meet your required Python version".to_string(),
-                                    IncompatibleWheel::Tag(tag) => {
-                                        match tag {
-                                            IncompatibleTag::Invalid => "no wheels are available with valid tags".to_string(),
-                                            IncompatibleTag::Python => "no wheels are available with a matching Python implementation".to_string(),
-                                            IncompatibleTag::Abi => "no wheels are available with a matching Python ABI".to_string(),
-                                            IncompatibleTag::Platform => "no wheels are available with a matching platform".to_string(),
+                                    IncompatibleDist::Wheel(incompatibility) => match incompatibility {
+                                        IncompatibleWheel::NoBinary => "no source distribution is available and using wheels is disabled".to_string(),
+                                        IncompatibleWheel::RequiresPython => "no wheels are available that meet your required Python version".to_string(),
+                                        IncompatibleWheel::Tag(tag) => {
+                                            match tag {
+                                                IncompatibleTag::Invalid => "no wheels are available with valid tags".to_string(),
+                                                IncompatibleTag::Python => "no wheels are available with a matching Python implementation".to_string(),
+                                                IncompatibleTag::Abi => "no wheels are available with a matching Python ABI".to_string(),
+                                                IncompatibleTag::Platform => "no wheels are available with a matching platform".to_string(),
+                                            }
                                         }
+                                    },
+                                    IncompatibleDist::Source(incompatibility) => match incompatibility {
+                                        IncompatibleSource::NoBuild => "no usable wheels are available and building from source is disabled".to_string(),
+                                    },
+                                    IncompatibleDist::Unavailable => {
+                                        // TODO(zanieb): It's unclear why we would encounter this case still
+                                        "no wheels are available for your system".to_string()
                                     }
                                 }
-                            } else {
-                                // TODO(zanieb): It's unclear why we would encounter this case still
-                                "no wheels are available for your system".to_string()
-                            }
-                        }
-                    };
-                    state.add_incompatibility(Incompatibility::unavailable(
-                        next.clone(),
-                        version.clone(),
-                        reason,
-                    ));
-                    continue;
-                }
-            };
-
-            self.on_progress(&next, &version);
-
-            if added_dependencies
-                .entry(next.clone())
-                .or_default()
-                .insert(version.clone())
-            {
-                // Retrieve that package dependencies.
-                let package = &next;
-                let dependencies = match self
-                    .get_dependencies(package, &version, &mut priorities, request_sink)
-                    .await?
-                {
-                    Dependencies::Unavailable(reason) => {
-                        let message = {
-                            if matches!(package, PubGrubPackage::Root(_)) {
-                                // Including front-matter for the root package is redundant
-                                reason.clone()
-                            } else {
-                                format!("its dependencies are unusable because {reason}")
                             }
                         };
-                        state.add_incompatibility(Incompatibility::unavailable(
-                            package.clone(),
+                        fork.state.add_incompatibility(Incompatibility::unavailable(
+                            fork.next.clone(),
                             version.clone(),
-                            message,
+                            reason,
                         ));
                         continue;
                     }
-                    Dependencies::Available(constraints)
-                        if constraints
-                            .iter()
-                            .any(|(dependency, _)| dependency == package) =>
-                    {
-                        return Err(PubGrubError::SelfDependency {
-                            package: package.clone(),
-                            version: version.clone(),
-                        }
-                        .into());
-                    }
-                    Dependencies::Available(constraints) => constraints,
                 };
 
-                // Add that package and version if the dependencies are not problematic.
-                let dep_incompats = state.add_incompatibility_from_dependencies(
-                    package.clone(),
-                    version.clone(),
-                    dependencies,
-                );
+                // If we've already selected a version for this package, then a prior decision was
+                // discarded, i.e., we backtracked.
+                let backtracked = fork
+                    .added_dependencies
+                    .get(&fork.next)
+                    .is_some_and(|versions| !versions.is_empty());
 
-                state.partial_solution.add_version(
-                    package.clone(),
-                    version,
-                    dep_incompats,
-                    &state.incompatibility_store,
-                );
-            } else {
-                // `dep_incompats` are already in `incompatibilities` so we know there are not satisfied
-                // terms and can add the decision directly.
-                state.partial_solution.add_decision(next.clone(), version);
+                self.on_progress(&fork.next, &version);
+
+                if backtracked {
+                    self.on_backtrack(&fork.next);
+                }
+
+                if fork
+                    .added_dependencies
+                    .entry(fork.next.clone())
+                    .or_default()
+                    .insert(version.clone())
+                {
+                    // Retrieve that package dependencies.
+                    let package = fork.next.clone();
+                    let dependencies = match self
+                        .get_dependencies(
+                            &package,
+                            &version,
+                            &mut fork.priorities,
+                            fork.environments.as_ref(),
+                            request_sink,
+                        )
+                        .await?
+                    {
+                        Dependencies::Unavailable(reason) => {
+                            let message = {
+                                if matches!(package, PubGrubPackage::Root(_)) {
+                                    // Including front-matter for the root package is redundant
+                                    reason.clone()
+                                } else {
+                                    format!("its dependencies are unusable because {reason}")
+                                }
+                            };
+                            fork.state.add_incompatibility(Incompatibility::unavailable(
+                                package.clone(),
+                                version.clone(),
+                                message,
+                            ));
+                            continue;
+                        }
+                        Dependencies::Available(constraints)
+                            if constraints
+                                .iter()
+                                .any(|(dependency, _)| *dependency == package) =>
+                        {
+                            return Err(PubGrubError::SelfDependency {
+                                package: package.clone(),
+                                version: version.clone(),
+                            }
+                            .into());
+                        }
+                        Dependencies::Available(constraints) => constraints,
+                    };
+
+                    // In a universal resolution, fork if the dependencies differ across
+                    // environments, solving each fork independently.
+                    if let Some(environments) = fork.environments.as_ref() {
+                        let mut forked = dependencies.fork(environments);
+                        if forked.len() > 1 {
+                            for (environments, dependencies) in forked {
+                                let mut forked = fork.clone();
+                                forked.environments = Some(environments);
+                                forked.add_dependencies(
+                                    package.clone(),
+                                    version.clone(),
+                                    dependencies,
+                                );
+                                forks.push(forked);
+                            }
+                            continue 'forks;
+                        }
+                        let (_, dependencies) = forked.pop().expect("at least one fork");
+                        fork.add_dependencies(package, version, dependencies);
+                    } else {
+                        fork.add_dependencies(package, version, dependencies);
+                    }
+                } else {
+                    // `dep_incompats` are already in `incompatibilities` so we know there are not satisfied
+                    // terms and can add the decision directly.
+                    fork.state
+                        .partial_solution
+                        .add_decision(fork.next.clone(), version);
+                }
             }
         }
+
+        Ok(ResolutionGraph::union(resolutions))
     }
 
     /// Visit a [`PubGrubPackage`] prior to selection. This should be called on a [`PubGrubPackage`]
@@ -661,7 +767,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                         // If the version is incompatible because no distributions match, exit early.
                         return Ok(Some(ResolverVersion::Unavailable(
                             candidate.version().clone(),
-                            UnavailableVersion::NoDistributions(incompatibility.cloned()),
+                            UnavailableVersion::NoDistributions(incompatibility.clone()),
                         )));
                     }
                 };
@@ -736,6 +842,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         package: &PubGrubPackage,
         version: &Version,
         priorities: &mut PubGrubPriorities,
+        environments: Option<&Environments>,
         request_sink: &tokio::sync::mpsc::Sender<Request>,
     ) -> Result<Dependencies, ResolveError> {
         match package {
@@ -749,6 +856,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     None,
                     &self.urls,
                     self.markers,
+                    environments,
                 );
 
                 let mut constraints = match constraints {
@@ -784,10 +892,12 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     }
                 }
 
-                Ok(Dependencies::Available(constraints.into()))
+                Ok(Dependencies::Available(constraints))
             }
 
-            PubGrubPackage::Python(_) => Ok(Dependencies::Available(Vec::default())),
+            PubGrubPackage::Python(_) => {
+                Ok(Dependencies::Available(PubGrubDependencies::default()))
+            }
 
             PubGrubPackage::Package(package_name, extra, url) => {
                 // If we're excluding transitive dependencies, short-circuit.
@@ -811,7 +921,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                             .ok_or(ResolveError::Unregistered)?;
                     }
 
-                    return Ok(Dependencies::Available(Vec::default()));
+                    return Ok(Dependencies::Available(PubGrubDependencies::default()));
                 }
 
                 // Determine if the distribution is editable.
@@ -824,6 +934,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                         extra.as_ref(),
                         &self.urls,
                         self.markers,
+                        environments,
                     )?;
 
                     for (package, version) in constraints.iter() {
@@ -842,7 +953,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                         );
                     }
 
-                    return Ok(Dependencies::Available(constraints.into()));
+                    return Ok(Dependencies::Available(constraints));
                 }
 
                 // Determine the distribution to lookup.
@@ -880,6 +991,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     extra.as_ref(),
                     &self.urls,
                     self.markers,
+                    environments,
                 )?;
 
                 for (package, version) in constraints.iter() {
@@ -898,7 +1010,7 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
                     );
                 }
 
-                Ok(Dependencies::Available(constraints.into()))
+                Ok(Dependencies::Available(constraints))
             }
         }
     }
@@ -910,12 +1022,15 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
     ) -> Result<(), ResolveError> {
         let mut response_stream = ReceiverStream::new(request_stream)
             .map(|request| self.process_request(request).boxed())
-            .buffer_unordered(50);
+            .buffer_unordered(self.concurrency);
 
         while let Some(response) = response_stream.next().await {
             match response? {
                 Some(Response::Package(package_name, version_map)) => {
                     trace!("Received package metadata for: {package_name}");
+                    if let Some(reporter) = self.reporter.as_ref() {
+                        reporter.on_package_fetched(&package_name);
+                    }
                     self.index.packages.done(package_name, version_map);
                 }
                 Some(Response::Dist {
@@ -1084,6 +1199,139 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         }
     }
 
+    /// Determine the environment markers under which each selected package is required, for use
+    /// in a universal resolution, along with the markers on each dependency edge between selected
+    /// packages.
+    ///
+    /// The markers are restricted to the environments covered by the fork that produced the
+    /// selection. Packages (and edges) that are required unconditionally are omitted from the
+    /// returned maps.
+    #[allow(clippy::type_complexity)]
+    fn package_markers(
+        &self,
+        selection: &SelectedDependencies<PubGrubPackage, Version>,
+        environments: &Environments,
+    ) -> (
+        FxHashMap<PackageName, MarkerTree>,
+        FxHashMap<(PackageName, PackageName), MarkerTree>,
+    ) {
+        // Collect the selected base packages, along with any of their enabled extras.
+        let mut packages = FxHashMap::default();
+        let mut extras: FxHashMap<&PackageName, Vec<ExtraName>> = FxHashMap::default();
+        for (package, version) in selection {
+            if let PubGrubPackage::Package(package_name, extra, url) = package {
+                if let Some(extra) = extra {
+                    extras.entry(package_name).or_default().push(extra.clone());
+                } else {
+                    packages.insert(package_name, (version, url.as_ref()));
+                }
+            }
+        }
+
+        // Collect the relevant requirements for each selected package, along with their markers.
+        let mut requirements: FxHashMap<&PackageName, Vec<(PackageName, MarkerDnf)>> =
+            FxHashMap::default();
+        for (package_name, (version, url)) in &packages {
+            let requires_dist = if let Some((_, metadata)) = self.editables.get(package_name) {
+                metadata.requires_dist.clone()
+            } else {
+                let dist = match url {
+                    Some(url) => PubGrubDistribution::from_url(package_name, url),
+                    None => PubGrubDistribution::from_registry(package_name, version),
+                };
+                let Some(metadata) = self.index.distributions.get(&dist.package_id()) else {
+                    continue;
+                };
+                metadata.requires_dist.clone()
+            };
+            let extras = extras
+                .get(package_name)
+                .map(Vec::as_slice)
+                .unwrap_or_default();
+            requirements.insert(
+                *package_name,
+                self.overrides
+                    .apply(&requires_dist)
+                    .filter(|requirement| {
+                        is_relevant(requirement, self.markers, extras, Some(environments))
+                    })
+                    .filter(|requirement| packages.contains_key(&requirement.name))
+                    .map(|requirement| {
+                        (
+                            requirement.name.clone(),
+                            MarkerDnf::from_marker(requirement.marker.as_ref(), extras),
+                        )
+                    })
+                    .collect(),
+            );
+        }
+
+        // Seed the markers with the direct requirements and editables, within the environments
+        // covered by the fork.
+        let mut markers: FxHashMap<PackageName, MarkerDnf> = FxHashMap::default();
+        let mut queue = VecDeque::new();
+        for requirement in self.overrides.apply(&self.requirements) {
+            if !packages.contains_key(&requirement.name)
+                || !is_relevant(requirement, self.markers, &[], Some(environments))
+            {
+                continue;
+            }
+            if markers
+                .entry(requirement.name.clone())
+                .or_insert_with(MarkerDnf::never)
+                .or(environments
+                    .marker()
+                    .and(&MarkerDnf::from_marker(requirement.marker.as_ref(), &[])))
+            {
+                queue.push_back(requirement.name.clone());
+            }
+        }
+        for (_, metadata) in self.editables.iter() {
+            markers.insert(metadata.name.clone(), environments.marker().clone());
+            queue.push_back(metadata.name.clone());
+        }
+
+        // Propagate the markers through the dependency graph, until we reach a fixed point. Each
+        // dependency is required under the conjunction of its parent's marker and the marker on
+        // the requirement itself.
+        while let Some(package_name) = queue.pop_front() {
+            let parent = markers[&package_name].clone();
+            for (name, marker) in requirements.get(&package_name).into_iter().flatten() {
+                if markers
+                    .entry(name.clone())
+                    .or_insert_with(MarkerDnf::never)
+                    .or(parent.and(marker))
+                {
+                    queue.push_back(name.clone());
+                }
+            }
+        }
+
+        // Determine the markers on each edge, i.e., the disjunction of the markers on every
+        // requirement from the parent on the dependency, within the environments covered by the
+        // fork.
+        let mut edge_markers: FxHashMap<(PackageName, PackageName), MarkerDnf> =
+            FxHashMap::default();
+        for (package_name, requirements) in &requirements {
+            for (name, marker) in requirements {
+                edge_markers
+                    .entry(((*package_name).clone(), name.clone()))
+                    .or_insert_with(MarkerDnf::never)
+                    .or(environments.marker().and(marker));
+            }
+        }
+
+        let markers = markers
+            .into_iter()
+            .filter_map(|(package_name, marker)| Some((package_name, marker.to_marker()?)))
+            .collect();
+        let edge_markers = edge_markers
+            .into_iter()
+            .filter_map(|(edge, marker)| Some((edge, marker.to_marker()?)))
+            .collect();
+        (markers, edge_markers)
+    }
+
     fn on_progress(&self, package: &PubGrubPackage, version: &Version) {
         if let Some(reporter) = self.reporter.as_ref() {
             match package {
@@ -1099,6 +1347,14 @@ impl<'a, Provider: ResolverProvider> Resolver<'a, Provider> {
         }
     }
 
+    fn on_backtrack(&self, package: &PubGrubPackage) {
+        if let Some(reporter) = self.reporter.as_ref() {
+            if let PubGrubPackage::Package(package_name, _extra, _url) = package {
+                reporter.on_backtrack(package_name);
+            }
+        }
+    }
+
     fn on_complete(&self) {
         if let Some(reporter) = self.reporter.as_ref() {
             reporter.on_complete();
@@ -1147,6 +1403,52 @@ enum Response {
     },
 }
 
+/// The state of a single fork of the resolution.
+///
+/// A universal resolution starts with a single fork that covers every environment, which is split
+/// whenever a package's dependencies differ across disjoint sets of environments.
+#[derive(Clone)]
+struct ForkState {
+    /// The `PubGrub` state of the fork.
+    state: State<PubGrubPackage, Range<Version>, PubGrubPriority>,
+    /// The files that were pinned for each selected package version.
+    pins: FilePins,
+    /// The priorities of the packages visited in the fork.
+    priorities: PubGrubPriorities,
+    /// The package versions whose dependencies were already added to the state.
+    added_dependencies: FxHashMap<PubGrubPackage, FxHashSet<Version>>,
+    /// The next package on which to run unit propagation.
+    next: PubGrubPackage,
+    /// The environments covered by the fork, in a universal resolution.
+    environments: Option<Environments>,
+}
+
+impl ForkState {
+    /// Add the dependencies of the selected package version to the state.
+    fn add_dependencies(
+        &mut self,
+        package: PubGrubPackage,
+        version: Version,
+        dependencies: PubGrubDependencies,
+    ) {
+        let dependencies: Vec<(PubGrubPackage, Range<Version>)> = dependencies.into();
+
+        // Add that package and version if the dependencies are not problematic.
+        let dep_incompats = self.state.add_incompatibility_from_dependencies(
+            package.clone(),
+            version.clone(),
+            dependencies,
+        );
+
+        self.state.partial_solution.add_version(
+            package,
+            version,
+            dep_incompats,
+            &self.state.incompatibility_store,
+        );
+    }
+}
+
 /// An enum used by [`DependencyProvider`] that holds information about package dependencies.
 /// For each [Package] there is a set of versions allowed as a dependency.
 #[derive(Clone)]
@@ -1154,7 +1456,7 @@ enum Dependencies {
     /// Package dependencies are not available.
     Unavailable(String),
     /// Container for all available package versions.
-    Available(Vec<(PubGrubPackage, Range<Version>)>),
+    Available(PubGrubDependencies),
 }
 
 fn uncapitalize<T: AsRef<str>>(string: T) -> String {
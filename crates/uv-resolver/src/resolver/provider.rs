@@ -1,7 +1,6 @@
 use std::future::Future;
 
 use anyhow::Result;
-use chrono::{DateTime, Utc};
 use url::Url;
 
 use distribution_types::{Dist, IndexLocations};
@@ -10,10 +9,11 @@ use pypi_types::Metadata21;
 use uv_client::{FlatIndex, RegistryClient};
 use uv_distribution::DistributionDatabase;
 use uv_normalize::PackageName;
-use uv_traits::{BuildContext, NoBinary};
+use uv_traits::{BuildContext, NoBinary, NoBuild};
 
 use crate::python_requirement::PythonRequirement;
 use crate::version_map::VersionMap;
+use crate::ExcludeNewer;
 
 pub type PackageVersionsResult = Result<VersionsResponse, uv_client::Error>;
 pub type WheelMetadataResult = Result<(Metadata21, Option<Url>), uv_distribution::Error>;
@@ -66,8 +66,9 @@ pub struct DefaultResolverProvider<'a, Context: BuildContext + Send + Sync> {
     flat_index: FlatIndex,
     tags: Tags,
     python_requirement: PythonRequirement,
-    exclude_newer: Option<DateTime<Utc>>,
+    exclude_newer: ExcludeNewer,
     no_binary: NoBinary,
+    no_build: NoBuild,
 }
 
 impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Context> {
@@ -79,8 +80,9 @@ impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Contex
         flat_index: &'a FlatIndex,
         tags: &'a Tags,
         python_requirement: PythonRequirement,
-        exclude_newer: Option<DateTime<Utc>>,
+        exclude_newer: ExcludeNewer,
         no_binary: &'a NoBinary,
+        no_build: &'a NoBuild,
     ) -> Self {
         Self {
             fetcher,
@@ -90,6 +92,7 @@ impl<'a, Context: BuildContext + Send + Sync> DefaultResolverProvider<'a, Contex
             python_requirement,
             exclude_newer,
             no_binary: no_binary.clone(),
+            no_build: no_build.clone(),
         }
     }
 }
@@ -113,9 +116,10 @@ impl<'a, Context: BuildContext + Send + Sync> ResolverProvider
                 &index,
                 &self.tags,
                 &self.python_requirement,
-                self.exclude_newer.as_ref(),
+                self.exclude_newer.get(package_name),
                 self.flat_index.get(package_name).cloned(),
                 &self.no_binary,
+                &self.no_build,
             ))),
             Err(err) => match err.into_kind() {
                 uv_client::ErrorKind::PackageNotFound(_) => {
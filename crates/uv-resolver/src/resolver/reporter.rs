@@ -8,9 +8,18 @@ use uv_normalize::PackageName;
 pub type BuildId = usize;
 
 pub trait Reporter: Send + Sync {
-    /// Callback to invoke when a dependency is resolved.
+    /// Callback to invoke when a dependency is resolved, i.e., when a version (or URL) is
+    /// selected for a package.
     fn on_progress(&self, name: &PackageName, version: VersionOrUrl);
 
+    /// Callback to invoke when the available versions of a package have been fetched from the
+    /// index.
+    fn on_package_fetched(&self, _name: &PackageName) {}
+
+    /// Callback to invoke when the resolver backtracks, revisiting a package for which a version
+    /// was previously selected.
+    fn on_backtrack(&self, _name: &PackageName) {}
+
     /// Callback to invoke when the resolution is complete.
     fn on_complete(&self);
 
@@ -25,6 +34,15 @@ pub trait Reporter: Send + Sync {
 
     /// Callback to invoke when a repository checkout completes.
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize);
+    /// Callback to invoke when the download of a distribution begins, with its size in bytes,
+    /// if known.
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize;
+
+    /// Callback to invoke when a chunk of a distribution has been downloaded.
+    fn on_download_progress(&self, id: usize, bytes: u64);
+
+    /// Callback to invoke when the download of a distribution completes.
+    fn on_download_complete(&self, name: &PackageName, id: usize);
 }
 
 /// A facade for converting from [`Reporter`] to [`uv_distribution::Reporter`].
@@ -48,4 +66,15 @@ impl uv_distribution::Reporter for Facade {
     fn on_checkout_complete(&self, url: &Url, rev: &str, index: usize) {
         self.reporter.on_checkout_complete(url, rev, index);
     }
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize {
+        self.reporter.on_download_start(name, size)
+    }
+
+    fn on_download_progress(&self, id: usize, bytes: u64) {
+        self.reporter.on_download_progress(id, bytes);
+    }
+
+    fn on_download_complete(&self, name: &PackageName, id: usize) {
+        self.reporter.on_download_complete(name, id);
+    }
 }
@@ -4,6 +4,7 @@ use distribution_types::Verbatim;
 use pep508_rs::{MarkerEnvironment, VerbatimUrl};
 use uv_normalize::PackageName;
 
+use crate::markers::{is_relevant, Environments};
 use crate::{Manifest, ResolveError};
 
 #[derive(Debug, Default)]
@@ -13,6 +14,7 @@ impl Urls {
     pub(crate) fn from_manifest(
         manifest: &Manifest,
         markers: &MarkerEnvironment,
+        universal: Option<&Environments>,
     ) -> Result<Self, ResolveError> {
         let mut urls = FxHashMap::default();
 
@@ -22,7 +24,7 @@ impl Urls {
             .iter()
             .chain(manifest.constraints.iter())
         {
-            if !requirement.evaluate_markers(markers, &[]) {
+            if !is_relevant(requirement, markers, &[], universal) {
                 continue;
             }
 
@@ -75,7 +77,7 @@ impl Urls {
         // Add any overrides. Conflicts here are fine, as the overrides are meant to be
         // authoritative.
         for requirement in &manifest.overrides {
-            if !requirement.evaluate_markers(markers, &[]) {
+            if !is_relevant(requirement, markers, &[], universal) {
                 continue;
             }
 
@@ -4,18 +4,75 @@ use pep440_rs::Version;
 use pep508_rs::Requirement;
 use uv_normalize::PackageName;
 
+use crate::Manifest;
+
+/// The policy for selecting package versions that are marked as yanked by the relevant index.
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum YankedMode {
+    /// Allow yanked versions, but only if they're pinned exactly (e.g., `attrs==21.1.0`) in the
+    /// requirements, constraints, or overrides. This matches the behavior described in PEP 592.
+    #[default]
+    Explicit,
+    /// Allow any yanked version.
+    Allow,
+    /// Disallow all yanked versions, even if they're pinned exactly.
+    Deny,
+}
+
+impl YankedMode {
+    /// Determine the yanked mode from the `--allow-yanked` and `--deny-yanked` command-line
+    /// arguments.
+    pub fn from_args(allow_yanked: bool, deny_yanked: bool) -> Self {
+        if allow_yanked {
+            Self::Allow
+        } else if deny_yanked {
+            Self::Deny
+        } else {
+            Self::Explicit
+        }
+    }
+}
+
 /// A set of package versions that are permitted, even if they're marked as yanked by the
 /// relevant index.
 #[derive(Debug, Default)]
-pub(crate) struct AllowedYanks(FxHashMap<PackageName, FxHashSet<Version>>);
+pub(crate) struct AllowedYanks {
+    /// Whether all yanked versions are permitted.
+    all: bool,
+    /// The exact versions that are permitted for each package.
+    packages: FxHashMap<PackageName, FxHashSet<Version>>,
+}
 
 impl AllowedYanks {
+    /// Determine the allowed yanked package versions for a [`Manifest`] under the given
+    /// [`YankedMode`].
+    ///
+    /// Since overrides replace the requirements for a package throughout the graph, an exact pin
+    /// in an override is treated the same as an exact pin in the requirements themselves.
+    pub(crate) fn from_manifest(manifest: &Manifest, mode: YankedMode) -> Self {
+        match mode {
+            YankedMode::Explicit => manifest
+                .requirements
+                .iter()
+                .chain(manifest.constraints.iter())
+                .chain(manifest.overrides.iter())
+                .collect(),
+            YankedMode::Allow => Self {
+                all: true,
+                packages: FxHashMap::default(),
+            },
+            YankedMode::Deny => Self::default(),
+        }
+    }
+
     /// Returns `true` if the given package version is allowed, even if it's marked as yanked by
     /// the relevant index.
     pub(crate) fn allowed(&self, package_name: &PackageName, version: &Version) -> bool {
-        self.0
-            .get(package_name)
-            .is_some_and(|allowed_yanks| allowed_yanks.contains(version))
+        self.all
+            || self
+                .packages
+                .get(package_name)
+                .is_some_and(|allowed_yanks| allowed_yanks.contains(version))
     }
 }
 
@@ -41,6 +98,9 @@ impl<'a> FromIterator<&'a Requirement> for AllowedYanks {
                     .insert(specifier.version().clone());
             }
         }
-        Self(allowed_yanks)
+        Self {
+            all: false,
+            packages: allowed_yanks,
+        }
     }
 }
@@ -17,11 +17,15 @@ use platform_tags::Tags;
 use uv_cache::Cache;
 use uv_client::{FlatIndex, RegistryClientBuilder};
 use uv_interpreter::{Interpreter, Virtualenv};
+use uv_normalize::PackageName;
 use uv_resolver::{
     DisplayResolutionGraph, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode,
     ResolutionGraph, ResolutionMode, Resolver,
 };
-use uv_traits::{BuildContext, BuildKind, NoBinary, NoBuild, SetupPyStrategy, SourceBuildTrait};
+use uv_traits::{
+    BuildContext, BuildIsolation, BuildKind, Concurrency, ConfigSettings, NoBinary, NoBuild,
+    SetupPyStrategy, SourceBuildTrait,
+};
 
 // Exclude any packages uploaded after this date.
 static EXCLUDE_NEWER: Lazy<DateTime<Utc>> = Lazy::new(|| {
@@ -69,10 +73,22 @@ impl BuildContext for DummyContext {
         &NoBinary::None
     }
 
+    fn build_isolation(&self) -> BuildIsolation {
+        BuildIsolation::Isolated
+    }
+
     fn setup_py_strategy(&self) -> SetupPyStrategy {
         SetupPyStrategy::default()
     }
 
+    fn config_settings(&self, _: Option<&PackageName>) -> ConfigSettings {
+        ConfigSettings::default()
+    }
+
+    fn concurrency(&self) -> Concurrency {
+        Concurrency::default()
+    }
+
     fn index_locations(&self) -> &IndexLocations {
         &self.index_locations
     }
@@ -115,7 +131,7 @@ async fn resolve(
     markers: &'static MarkerEnvironment,
     tags: &Tags,
 ) -> Result<ResolutionGraph> {
-    let client = RegistryClientBuilder::new(Cache::temp()?).build();
+    let client = RegistryClientBuilder::new(Cache::temp()?).build()?;
     let flat_index = FlatIndex::default();
     let index = InMemoryIndex::default();
     let interpreter = Interpreter::artificial(
@@ -679,6 +695,7 @@ static TAGS_311: Lazy<Tags> = Lazy::new(|| {
         (3, 11),
         "cpython",
         (3, 11),
+        false,
     )
     .unwrap()
 });
@@ -711,6 +728,7 @@ static TAGS_310: Lazy<Tags> = Lazy::new(|| {
         (3, 10),
         "cpython",
         (3, 10),
+        false,
     )
     .unwrap()
 });
@@ -10,6 +10,7 @@ use std::str::FromStr;
 use anyhow::Result;
 use serde::ser::SerializeMap;
 
+use cache_key::{CacheKey, CacheKeyHasher};
 use distribution_types::{CachedDist, DistributionId, IndexLocations, Resolution, SourceDist};
 use once_map::OnceMap;
 use pep508_rs::Requirement;
@@ -76,12 +77,23 @@ pub trait BuildContext: Sync {
     /// Whether using pre-built wheels is disabled.
     fn no_binary(&self) -> &NoBinary;
 
+    /// Whether source distribution builds should run in an isolated environment, or against the
+    /// packages already installed in a shared environment.
+    fn build_isolation(&self) -> BuildIsolation;
+
     /// The index locations being searched.
     fn index_locations(&self) -> &IndexLocations;
 
     /// The strategy to use when building source distributions that lack a `pyproject.toml`.
     fn setup_py_strategy(&self) -> SetupPyStrategy;
 
+    /// The settings to pass to the PEP 517 build backend of the given package, including any
+    /// package-specific settings.
+    fn config_settings(&self, package: Option<&PackageName>) -> ConfigSettings;
+
+    /// The limits on concurrent downloads and builds.
+    fn concurrency(&self) -> Concurrency;
+
     /// Resolve the given requirements into a ready-to-install set of package versions.
     fn resolve<'a>(
         &'a self,
@@ -141,6 +153,53 @@ pub struct InFlight {
     pub downloads: OnceMap<DistributionId, Result<CachedDist, String>>,
 }
 
+/// The limits on the number of concurrent operations.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub struct Concurrency {
+    /// The maximum number of concurrent network requests, e.g., when fetching metadata or
+    /// downloading distributions.
+    pub downloads: usize,
+    /// The maximum number of concurrent source distribution builds.
+    pub builds: usize,
+}
+
+impl Concurrency {
+    /// The default maximum number of concurrent network requests.
+    pub const DEFAULT_DOWNLOADS: usize = 50;
+
+    /// The default maximum number of concurrent builds, i.e., the available parallelism.
+    pub fn default_builds() -> usize {
+        std::thread::available_parallelism().map_or(1, std::num::NonZeroUsize::get)
+    }
+}
+
+impl Default for Concurrency {
+    fn default() -> Self {
+        Self {
+            downloads: Self::DEFAULT_DOWNLOADS,
+            builds: Self::default_builds(),
+        }
+    }
+}
+
+/// Whether to enforce build isolation when building source distributions.
+#[derive(Debug, Default, Copy, Clone)]
+pub enum BuildIsolation<'a> {
+    /// Create a fresh virtual environment for each build, and install the build requirements
+    /// into it.
+    #[default]
+    Isolated,
+    /// Run builds against the given environment, without installing any build requirements.
+    Shared(&'a Virtualenv),
+}
+
+impl<'a> BuildIsolation<'a> {
+    /// Returns `true` if build isolation is enforced.
+    pub fn is_isolated(&self) -> bool {
+        matches!(self, Self::Isolated)
+    }
+}
+
 /// The strategy to use when building source distributions that lack a `pyproject.toml`.
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum SetupPyStrategy {
@@ -151,6 +210,15 @@ pub enum SetupPyStrategy {
     Setuptools,
 }
 
+impl CacheKey for SetupPyStrategy {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            Self::Pep517 => "pep517".cache_key(state),
+            Self::Setuptools => "setuptools".cache_key(state),
+        }
+    }
+}
+
 #[derive(Copy, Clone, Debug, Default, PartialEq, Eq)]
 pub enum BuildKind {
     /// A regular PEP 517 wheel build
@@ -315,6 +383,31 @@ impl FromStr for ConfigSettingEntry {
     }
 }
 
+/// A [`ConfigSettingEntry`] scoped to a single package, as in `PACKAGE:KEY=VALUE`.
+#[derive(Debug, Clone)]
+pub struct ConfigSettingPackageEntry {
+    /// The package to which the setting applies.
+    package: PackageName,
+    /// The setting itself.
+    setting: ConfigSettingEntry,
+}
+
+impl FromStr for ConfigSettingPackageEntry {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let Some((package, setting)) = s.split_once(':') else {
+            return Err(anyhow::anyhow!(
+                "Invalid config setting: {s} (expected `PACKAGE:KEY=VALUE`)"
+            ));
+        };
+        Ok(Self {
+            package: PackageName::from_str(package.trim())?,
+            setting: ConfigSettingEntry::from_str(setting)?,
+        })
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 enum ConfigSettingValue {
     /// The value consists of a single string.
@@ -323,6 +416,21 @@ enum ConfigSettingValue {
     List(Vec<String>),
 }
 
+impl CacheKey for ConfigSettingValue {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        match self {
+            Self::String(value) => {
+                0usize.cache_key(state);
+                value.cache_key(state);
+            }
+            Self::List(values) => {
+                1usize.cache_key(state);
+                values.cache_key(state);
+            }
+        }
+    }
+}
+
 /// Settings to pass to a PEP 517 build backend, structured as a map from (string) key to string or
 /// list of strings.
 ///
@@ -330,6 +438,12 @@ enum ConfigSettingValue {
 #[derive(Debug, Default, Clone)]
 pub struct ConfigSettings(BTreeMap<String, ConfigSettingValue>);
 
+impl CacheKey for ConfigSettings {
+    fn cache_key(&self, state: &mut CacheKeyHasher) {
+        self.0.cache_key(state);
+    }
+}
+
 impl FromIterator<ConfigSettingEntry> for ConfigSettings {
     fn from_iter<T: IntoIterator<Item = ConfigSettingEntry>>(iter: T) -> Self {
         let mut config = BTreeMap::default();
@@ -353,6 +467,57 @@ impl FromIterator<ConfigSettingEntry> for ConfigSettings {
     }
 }
 
+impl ConfigSettings {
+    /// Returns `true` if no settings were provided.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Merge two sets of settings, with the values in `other` taking precedence over those in
+    /// `self` for any shared keys.
+    #[must_use]
+    pub fn merge(self, other: Self) -> Self {
+        let mut config = self.0;
+        config.extend(other.0);
+        Self(config)
+    }
+}
+
+/// Settings to pass to the PEP 517 build backends of specific packages, applied on top of any
+/// global [`ConfigSettings`].
+#[derive(Debug, Default, Clone)]
+pub struct PackageConfigSettings(BTreeMap<PackageName, ConfigSettings>);
+
+impl FromIterator<ConfigSettingPackageEntry> for PackageConfigSettings {
+    fn from_iter<T: IntoIterator<Item = ConfigSettingPackageEntry>>(iter: T) -> Self {
+        let mut entries: BTreeMap<PackageName, Vec<ConfigSettingEntry>> = BTreeMap::default();
+        for entry in iter {
+            entries
+                .entry(entry.package)
+                .or_default()
+                .push(entry.setting);
+        }
+        Self(
+            entries
+                .into_iter()
+                .map(|(package, settings)| (package, settings.into_iter().collect()))
+                .collect(),
+        )
+    }
+}
+
+impl PackageConfigSettings {
+    /// Returns the settings for the given package, if any.
+    pub fn get(&self, package: &PackageName) -> Option<&ConfigSettings> {
+        self.0.get(package)
+    }
+
+    /// Returns `true` if no package-specific settings were provided.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
 #[cfg(feature = "serde")]
 impl ConfigSettings {
     /// Convert the settings to a string that can be passed directly to a PEP 517 build backend.
@@ -479,6 +644,63 @@ mod tests {
         );
     }
 
+    #[test]
+    fn collect_package_config_settings() -> Result<(), Error> {
+        let settings: PackageConfigSettings = vec![
+            ConfigSettingPackageEntry::from_str("numpy:setup-args=-Dblas=openblas")?,
+            ConfigSettingPackageEntry::from_str("numpy:setup-args=-Dlapack=openblas")?,
+            ConfigSettingPackageEntry::from_str("Foo_Bar:key=value")?,
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(
+            settings
+                .get(&PackageName::from_str("numpy")?)
+                .unwrap()
+                .0
+                .get("setup-args"),
+            Some(&ConfigSettingValue::List(vec![
+                "-Dblas=openblas".to_string(),
+                "-Dlapack=openblas".to_string()
+            ]))
+        );
+        assert_eq!(
+            settings
+                .get(&PackageName::from_str("foo-bar")?)
+                .unwrap()
+                .0
+                .get("key"),
+            Some(&ConfigSettingValue::String("value".to_string()))
+        );
+        assert!(settings.get(&PackageName::from_str("scipy")?).is_none());
+
+        assert!(ConfigSettingPackageEntry::from_str("key=value").is_err());
+
+        Ok(())
+    }
+
+    #[test]
+    fn merge_config_settings() {
+        let global: ConfigSettings = vec![
+            ConfigSettingEntry::from_str("key=global").unwrap(),
+            ConfigSettingEntry::from_str("other=global").unwrap(),
+        ]
+        .into_iter()
+        .collect();
+        let package: ConfigSettings = vec![ConfigSettingEntry::from_str("key=package").unwrap()]
+            .into_iter()
+            .collect();
+        let merged = global.merge(package);
+        assert_eq!(
+            merged.0.get("key"),
+            Some(&ConfigSettingValue::String("package".to_string()))
+        );
+        assert_eq!(
+            merged.0.get("other"),
+            Some(&ConfigSettingValue::String("global".to_string()))
+        );
+    }
+
     #[test]
     #[cfg(feature = "serde")]
     fn escape_for_python() {
@@ -0,0 +1,139 @@
+use std::fmt::Write;
+
+use anyhow::{anyhow, Context, Result};
+use owo_colors::OwoColorize;
+use url::Url;
+
+use distribution_types::IndexLocations;
+use pep440_rs::{Operator, VersionPattern, VersionSpecifier, VersionSpecifiers};
+use pep508_rs::{Requirement, VersionOrUrl};
+use uv_cache::Cache;
+use uv_client::{Connectivity, Timeouts, TlsSettings};
+use uv_fs::Normalized;
+use uv_installer::NoBinary;
+use uv_interpreter::PythonVersion;
+use uv_normalize::ExtraName;
+use uv_resolver::{ExcludeNewer, Lock, LockedSource, PreReleaseMode, ResolutionMode};
+use uv_traits::{Concurrency, ConfigSettings, NoBuild, SetupPyStrategy};
+
+use crate::commands::{lock, ExitStatus, Upgrade};
+use crate::printer::Printer;
+use crate::pyproject::{DependencyType, PyProjectTomlMut};
+use crate::requirements::ExtrasSpecification;
+use crate::workspace::environment_root;
+
+/// Add one or more dependencies to the project's `pyproject.toml`, and update the lockfile.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn add(
+    requirements: Vec<Requirement>,
+    dependency_type: DependencyType,
+    prerelease_mode: PreReleaseMode,
+    index_locations: IndexLocations,
+    connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    concurrency: Concurrency,
+    python_version: Option<PythonVersion>,
+    exclude_newer: ExcludeNewer,
+    cache: Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let root = std::env::current_dir()?;
+    let path = root.join("pyproject.toml");
+    if !path.is_file() {
+        return Err(anyhow!(
+            "No `pyproject.toml` found in: {}",
+            root.normalized_display()
+        ));
+    }
+    let original = uv_fs::read_to_string(&path)?;
+    let mut pyproject = PyProjectTomlMut::from_toml(&original)
+        .with_context(|| format!("Failed to parse `{}`", path.normalized_display()))?;
+
+    for requirement in &requirements {
+        pyproject.add_dependency(requirement, &dependency_type)?;
+    }
+    fs_err::write(&path, pyproject.to_string())?;
+
+    // Update the lockfile, allowing the added packages to move from any previously locked
+    // versions. If the lockfile can't be updated, restore the original `pyproject.toml`.
+    let extras = match &dependency_type {
+        DependencyType::Optional(extra) => vec![extra.clone()],
+        DependencyType::Production | DependencyType::Dev => Vec::<ExtraName>::new(),
+    };
+    let status = lock(
+        if extras.is_empty() {
+            ExtrasSpecification::None
+        } else {
+            ExtrasSpecification::Some(&extras)
+        },
+        ResolutionMode::default(),
+        prerelease_mode,
+        Upgrade::Packages(requirements.iter().map(|req| req.name.clone()).collect()),
+        index_locations,
+        SetupPyStrategy::default(),
+        ConfigSettings::default(),
+        connectivity,
+        tls,
+        retries,
+        proxy,
+        timeouts,
+        concurrency,
+        &NoBuild::None,
+        &NoBinary::None,
+        python_version,
+        exclude_newer,
+        cache,
+        printer,
+    )
+    .await;
+    if !matches!(status, Ok(ExitStatus::Success)) {
+        fs_err::write(&path, original)?;
+        return status;
+    }
+
+    // For any requirement added without a version constraint, require at least the locked
+    // version (e.g., `anyio` becomes `anyio>=4.0.0`).
+    let contents = fs_err::read_to_string(environment_root(&root)?.join("uv.lock"))?;
+    let lock = Lock::from_toml(&contents)?;
+    let mut constrained = false;
+    for requirement in requirements {
+        if requirement.version_or_url.is_some() {
+            continue;
+        }
+        let Some(version) = lock
+            .find(&requirement.name, None)
+            .filter(|distribution| matches!(distribution.source, LockedSource::Registry(_)))
+            .and_then(|distribution| distribution.version.clone())
+        else {
+            continue;
+        };
+        let specifier = VersionSpecifier::new(
+            Operator::GreaterThanEqual,
+            VersionPattern::verbatim(version),
+        )?;
+        pyproject.add_dependency(
+            &Requirement {
+                version_or_url: Some(VersionOrUrl::VersionSpecifier(VersionSpecifiers::from(
+                    specifier,
+                ))),
+                ..requirement
+            },
+            &dependency_type,
+        )?;
+        constrained = true;
+    }
+    if constrained {
+        fs_err::write(&path, pyproject.to_string())?;
+    }
+
+    writeln!(
+        printer,
+        "{}",
+        format!("Updated {}", "pyproject.toml".bold()).dimmed()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
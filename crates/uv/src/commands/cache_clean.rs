@@ -3,7 +3,7 @@ use std::fmt::Write;
 use anyhow::{Context, Result};
 use owo_colors::OwoColorize;
 
-use uv_cache::Cache;
+use uv_cache::{Cache, Removal};
 use uv_fs::Normalized;
 use uv_normalize::PackageName;
 
@@ -11,9 +11,12 @@ use crate::commands::ExitStatus;
 use crate::printer::Printer;
 
 /// Clear the cache.
+///
+/// If `dry_run` is set, report what would be removed, without removing anything.
 pub(crate) fn cache_clean(
     cache: &Cache,
     packages: &[PackageName],
+    dry_run: bool,
     mut printer: Printer,
 ) -> Result<ExitStatus> {
     if !cache.root().exists() {
@@ -26,97 +29,93 @@ pub(crate) fn cache_clean(
     }
 
     if packages.is_empty() {
-        writeln!(
-            printer,
-            "Clearing cache at: {}",
-            cache.root().normalized_display().cyan()
-        )?;
+        if dry_run {
+            writeln!(
+                printer,
+                "Would clear cache at: {}",
+                cache.root().normalized_display().cyan()
+            )?;
+        } else {
+            writeln!(
+                printer,
+                "Clearing cache at: {}",
+                cache.root().normalized_display().cyan()
+            )?;
+        }
 
-        let summary = cache.clear().with_context(|| {
+        let summary = if dry_run {
+            cache.clear_dry_run()
+        } else {
+            cache.clear()
+        }
+        .with_context(|| {
             format!(
                 "Failed to clear cache at: {}",
                 cache.root().normalized_display()
             )
         })?;
 
-        // Write a summary of the number of files and directories removed.
-        match (summary.num_files, summary.num_dirs) {
-            (0, 0) => {
-                write!(printer, "No cache entries found")?;
-            }
-            (0, 1) => {
-                write!(printer, "Removed 1 directory")?;
-            }
-            (0, num_dirs_removed) => {
-                write!(printer, "Removed {num_dirs_removed} directories")?;
-            }
-            (1, _) => {
-                write!(printer, "Removed 1 file")?;
-            }
-            (num_files_removed, _) => {
-                write!(printer, "Removed {num_files_removed} files")?;
-            }
-        }
-
-        // If any, write a summary of the total byte count removed.
-        if summary.total_bytes > 0 {
-            let bytes = if summary.total_bytes < 1024 {
-                format!("{}B", summary.total_bytes)
+        write_summary(&summary, None, dry_run, printer)?;
+    } else {
+        for package in packages {
+            let summary = if dry_run {
+                cache.remove_dry_run(package)?
             } else {
-                let (bytes, unit) = human_readable_bytes(summary.total_bytes);
-                format!("{bytes:.1}{unit}")
+                cache.remove(package)?
             };
-            write!(printer, " ({})", bytes.green())?;
-        }
 
-        writeln!(printer)?;
-    } else {
-        for package in packages {
-            let summary = cache.remove(package)?;
+            write_summary(&summary, Some(package), dry_run, printer)?;
+        }
+    }
 
-            // Write a summary of the number of files and directories removed.
-            match (summary.num_files, summary.num_dirs) {
-                (0, 0) => {
-                    write!(printer, "No cache entries found for {}", package.cyan())?;
-                }
-                (0, 1) => {
-                    write!(printer, "Removed 1 directory for {}", package.cyan())?;
-                }
-                (0, num_dirs_removed) => {
-                    write!(
-                        printer,
-                        "Removed {num_dirs_removed} directories for {}",
-                        package.cyan()
-                    )?;
-                }
-                (1, _) => {
-                    write!(printer, "Removed 1 file for {}", package.cyan())?;
-                }
-                (num_files_removed, _) => {
-                    write!(
-                        printer,
-                        "Removed {num_files_removed} files for {}",
-                        package.cyan()
-                    )?;
-                }
-            }
+    Ok(ExitStatus::Success)
+}
 
-            // If any, write a summary of the total byte count removed.
-            if summary.total_bytes > 0 {
-                let bytes = if summary.total_bytes < 1024 {
-                    format!("{}B", summary.total_bytes)
-                } else {
-                    let (bytes, unit) = human_readable_bytes(summary.total_bytes);
-                    format!("{bytes:.1}{unit}")
-                };
-                write!(printer, " ({})", bytes.green())?;
-            }
+/// Write a summary of the number of files and directories removed, and the total byte count.
+pub(super) fn write_summary(
+    summary: &Removal,
+    package: Option<&PackageName>,
+    dry_run: bool,
+    mut printer: Printer,
+) -> Result<()> {
+    let verb = if dry_run { "Would remove" } else { "Removed" };
+    let suffix = package
+        .map(|package| format!(" for {}", package.cyan()))
+        .unwrap_or_default();
 
-            writeln!(printer)?;
+    // Write a summary of the number of files and directories removed.
+    match (summary.num_files, summary.num_dirs) {
+        (0, 0) => {
+            write!(printer, "No cache entries found{suffix}")?;
+        }
+        (0, 1) => {
+            write!(printer, "{verb} 1 directory{suffix}")?;
+        }
+        (0, num_dirs_removed) => {
+            write!(printer, "{verb} {num_dirs_removed} directories{suffix}")?;
+        }
+        (1, _) => {
+            write!(printer, "{verb} 1 file{suffix}")?;
+        }
+        (num_files_removed, _) => {
+            write!(printer, "{verb} {num_files_removed} files{suffix}")?;
         }
     }
 
-    Ok(ExitStatus::Success)
+    // If any, write a summary of the total byte count removed.
+    if summary.total_bytes > 0 {
+        let bytes = if summary.total_bytes < 1024 {
+            format!("{}B", summary.total_bytes)
+        } else {
+            let (bytes, unit) = human_readable_bytes(summary.total_bytes);
+            format!("{bytes:.1}{unit}")
+        };
+        write!(printer, " ({})", bytes.green())?;
+    }
+
+    writeln!(printer)?;
+
+    Ok(())
 }
 
 /// Formats a number of bytes into a human readable SI-prefixed size.
@@ -128,7 +127,7 @@ pub(crate) fn cache_clean(
     clippy::cast_precision_loss,
     clippy::cast_sign_loss
 )]
-fn human_readable_bytes(bytes: u64) -> (f32, &'static str) {
+pub(super) fn human_readable_bytes(bytes: u64) -> (f32, &'static str) {
     static UNITS: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
     let bytes = bytes as f32;
     let i = ((bytes.log2() / 10.0) as usize).min(UNITS.len() - 1);
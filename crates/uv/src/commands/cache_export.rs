@@ -0,0 +1,50 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use flate2::write::GzEncoder;
+use flate2::Compression;
+use owo_colors::OwoColorize;
+
+use uv_cache::Cache;
+use uv_fs::Normalized;
+use uv_warnings::warn_user;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+use crate::requirements::{RequirementsSource, RequirementsSpecification};
+
+/// Write the cache entries for the given requirements to a gzipped tar archive.
+pub(crate) fn cache_export(
+    cache: &Cache,
+    sources: &[RequirementsSource],
+    output: &Path,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let RequirementsSpecification { requirements, .. } =
+        RequirementsSpecification::from_simple_sources(sources)?;
+
+    let file = fs_err::File::create(output)?;
+    let mut encoder = GzEncoder::new(file, Compression::default());
+    let summary = cache
+        .export(
+            requirements.iter().map(|requirement| &requirement.name),
+            &mut encoder,
+        )
+        .and_then(|summary| encoder.finish().map(|_| summary))
+        .with_context(|| format!("Failed to export cache to: {}", output.normalized_display()))?;
+
+    for package in &summary.missing {
+        warn_user!("No cache entries found for {package}");
+    }
+
+    let s = if summary.num_files == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "Exported {} to: {}",
+        format!("{} file{s}", summary.num_files).bold(),
+        output.normalized_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,37 @@
+use std::fmt::Write;
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use flate2::read::GzDecoder;
+use owo_colors::OwoColorize;
+
+use uv_cache::Cache;
+use uv_fs::Normalized;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Read cache entries from a gzipped tar archive produced by `uv cache export`.
+pub(crate) fn cache_import(
+    cache: &Cache,
+    archive: &Path,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let file = fs_err::File::open(archive)?;
+    let num_files = cache.import(GzDecoder::new(file)).with_context(|| {
+        format!(
+            "Failed to import cache from: {}",
+            archive.normalized_display()
+        )
+    })?;
+
+    let s = if num_files == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "Imported {} into: {}",
+        format!("{num_files} file{s}").bold(),
+        cache.root().normalized_display().cyan()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
@@ -0,0 +1,76 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use uv_cache::{Cache, CacheBucket, Removal};
+use uv_fs::Normalized;
+
+use crate::commands::cache_clean::human_readable_bytes;
+use crate::commands::ExitStatus;
+
+/// Show the number of entries in, and the size of, each cache bucket.
+pub(crate) fn cache_info(cache: &Cache) -> Result<ExitStatus> {
+    let mut output = String::new();
+    writeln!(
+        output,
+        "Cache directory: {}",
+        cache.root().normalized_display().cyan()
+    )?;
+
+    if !cache.root().exists() {
+        anstream::print!("{output}");
+        return Ok(ExitStatus::Success);
+    }
+
+    writeln!(output)?;
+    let mut total = Removal::default();
+    for bucket in CacheBucket::iter() {
+        let usage = cache.usage(bucket)?;
+        writeln!(
+            output,
+            "{:<18} {:<36} {:>8} {:>10}",
+            bucket.to_string(),
+            description(bucket).dimmed(),
+            format!("{} files", usage.num_files),
+            format_bytes(usage.total_bytes).green()
+        )?;
+        total += usage;
+    }
+    writeln!(
+        output,
+        "{:<18} {:<36} {:>8} {:>10}",
+        "total".bold(),
+        "",
+        format!("{} files", total.num_files),
+        format_bytes(total.total_bytes).green().bold()
+    )?;
+
+    anstream::print!("{output}");
+
+    Ok(ExitStatus::Success)
+}
+
+/// A short description of the contents of the given bucket.
+fn description(bucket: CacheBucket) -> &'static str {
+    match bucket {
+        CacheBucket::Wheels => "Downloaded wheels and their metadata",
+        CacheBucket::BuiltWheels => "Wheels built from source distributions",
+        CacheBucket::FlatIndex => "Responses from `--find-links` indexes",
+        CacheBucket::Git => "Git repositories",
+        CacheBucket::Interpreter => "Python interpreter information",
+        CacheBucket::Simple => "Responses from the simple index",
+        CacheBucket::Archive => "Unzipped wheels",
+        CacheBucket::Environments => "Ephemeral environments for scripts",
+    }
+}
+
+/// Format a number of bytes for display.
+fn format_bytes(bytes: u64) -> String {
+    if bytes < 1024 {
+        format!("{bytes}B")
+    } else {
+        let (bytes, unit) = human_readable_bytes(bytes);
+        format!("{bytes:.1}{unit}")
+    }
+}
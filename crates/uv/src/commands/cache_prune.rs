@@ -0,0 +1,63 @@
+use std::fmt::Write;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use owo_colors::OwoColorize;
+
+use uv_cache::{Cache, Removal};
+use uv_fs::Normalized;
+
+use crate::commands::cache_clean::write_summary;
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Remove least-recently-used entries from the cache.
+///
+/// If `dry_run` is set, report what would be removed, without removing anything.
+pub(crate) fn cache_prune(
+    cache: &Cache,
+    older_than: Option<Duration>,
+    max_size: Option<u64>,
+    dry_run: bool,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    if !cache.root().exists() {
+        writeln!(
+            printer,
+            "No cache found at: {}",
+            cache.root().normalized_display().cyan()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    writeln!(
+        printer,
+        "{} cache at: {}",
+        if dry_run { "Would prune" } else { "Pruning" },
+        cache.root().normalized_display().cyan()
+    )?;
+
+    let pruned = cache
+        .prune(older_than, max_size, dry_run)
+        .with_context(|| {
+            format!(
+                "Failed to prune cache at: {}",
+                cache.root().normalized_display()
+            )
+        })?;
+
+    let mut summary = Removal::default();
+    for (package, removal) in pruned {
+        writeln!(
+            printer,
+            "{} {}",
+            if dry_run { "Would remove" } else { "Removed" },
+            package.cyan()
+        )?;
+        summary += removal;
+    }
+
+    write_summary(&summary, None, dry_run, printer)?;
+
+    Ok(ExitStatus::Success)
+}
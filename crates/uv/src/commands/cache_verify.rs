@@ -0,0 +1,124 @@
+use std::fmt::Write;
+use std::path::{Path, PathBuf};
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use uv_cache::{Cache, CacheBucket};
+use uv_fs::Normalized;
+
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+
+/// Verify the unzipped wheels and built wheels in the cache against their recorded hashes.
+///
+/// If `evict` is set, remove any corrupted entries, such that they're downloaded or built again
+/// on next use.
+pub(crate) fn cache_verify(cache: &Cache, evict: bool, mut printer: Printer) -> Result<ExitStatus> {
+    if !cache.root().exists() {
+        writeln!(
+            printer,
+            "No cache found at: {}",
+            cache.root().normalized_display().cyan()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    writeln!(
+        printer,
+        "Verifying cache at: {}",
+        cache.root().normalized_display().cyan()
+    )?;
+
+    let mut num_verified = 0usize;
+    let mut corrupted = Vec::new();
+
+    // Verify the unzipped wheels against the hashes in their `RECORD` files.
+    for path in uv_fs::directories(cache.bucket(CacheBucket::Archive)) {
+        match install_wheel_rs::verify_unpacked_wheel(&path) {
+            Ok(mismatched) => {
+                num_verified += 1;
+                if !mismatched.is_empty() {
+                    corrupted.push((path, mismatched));
+                }
+            }
+            Err(err) => debug!(
+                "Skipping unverifiable cache entry {}: {err}",
+                path.normalized_display()
+            ),
+        }
+    }
+
+    // Verify the built wheels against their CRCs and the hashes in their `RECORD` files.
+    for path in built_wheels(&cache.bucket(CacheBucket::BuiltWheels)) {
+        match install_wheel_rs::verify_wheel_archive(&path) {
+            Ok(mismatched) => {
+                num_verified += 1;
+                if !mismatched.is_empty() {
+                    corrupted.push((path, mismatched));
+                }
+            }
+            Err(err) => {
+                // A wheel archive that can't be read at all is corrupted.
+                corrupted.push((path, vec![err.to_string()]));
+            }
+        }
+    }
+
+    for (path, mismatched) in &corrupted {
+        writeln!(
+            printer,
+            "{}{} {} ({})",
+            "error".red().bold(),
+            ":".bold(),
+            path.normalized_display(),
+            mismatched.join(", ")
+        )?;
+        if evict {
+            if path.is_dir() {
+                fs_err::remove_dir_all(path)?;
+            } else {
+                fs_err::remove_file(path)?;
+            }
+        }
+    }
+
+    let s = if num_verified == 1 { "y" } else { "ies" };
+    match (corrupted.len(), evict) {
+        (0, _) => {
+            writeln!(printer, "Verified {num_verified} cache entr{s}")?;
+            Ok(ExitStatus::Success)
+        }
+        (num_corrupted, true) => {
+            writeln!(
+                printer,
+                "Verified {num_verified} cache entr{s}; evicted {num_corrupted} corrupted"
+            )?;
+            Ok(ExitStatus::Success)
+        }
+        (num_corrupted, false) => {
+            writeln!(
+                printer,
+                "Verified {num_verified} cache entr{s}; found {num_corrupted} corrupted (run with `--evict` to remove)"
+            )?;
+            Ok(ExitStatus::Failure)
+        }
+    }
+}
+
+/// Return the paths to the built wheel archives (`.whl` files) in the given bucket.
+fn built_wheels(bucket: &Path) -> Vec<PathBuf> {
+    walkdir::WalkDir::new(bucket)
+        .into_iter()
+        .filter_map(Result::ok)
+        .filter(|entry| entry.file_type().is_file())
+        .filter(|entry| {
+            entry
+                .path()
+                .extension()
+                .is_some_and(|ext| ext.eq_ignore_ascii_case("whl"))
+        })
+        .map(walkdir::DirEntry::into_path)
+        .collect()
+}
@@ -0,0 +1,317 @@
+use std::collections::VecDeque;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use rustc_hash::FxHashSet;
+use tracing::debug;
+
+use pep508_rs::{MarkerEnvironment, MarkerTree};
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_fs::Normalized;
+use uv_interpreter::{find_default_python, PythonVersion, TargetPlatform};
+use uv_normalize::PackageName;
+use uv_resolver::{requirements_txt_entry, Lock, LockedDistribution, LockedSource};
+
+use crate::commands::pip_compile::{cmd, write_header, OutputWriter};
+use crate::commands::{ExitStatus, ExportFormat};
+use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+use crate::workspace::Workspace;
+
+/// Export the project's lockfile (`uv.lock`) to an alternate format, such as a
+/// `requirements.txt` file that can be consumed by `pip`.
+///
+/// Only the distributions that are reachable from the project's requirements (along with the
+/// requested extras and dependency groups) are included. If a target platform or Python version
+/// is provided, the lockfile's markers are evaluated against it, and only the distributions that
+/// apply to the target are included.
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn export(
+    format: ExportFormat,
+    hashes: bool,
+    extras: ExtrasSpecification<'_>,
+    groups: &[String],
+    python_platform: Option<TargetPlatform>,
+    python_version: Option<PythonVersion>,
+    output_file: Option<&Path>,
+    quiet: bool,
+    cache: &Cache,
+) -> Result<ExitStatus> {
+    // Read the lockfile from the current directory, or from the workspace root if the project is
+    // part of a workspace.
+    let project_root = std::env::current_dir()?;
+    let pyproject = project_root.join("pyproject.toml");
+    if !pyproject.is_file() {
+        return Err(anyhow!(
+            "No `pyproject.toml` found in: {}",
+            project_root.normalized_display()
+        ));
+    }
+    let workspace = Workspace::discover(&project_root)?;
+    let root = workspace
+        .as_ref()
+        .map_or(project_root, |workspace| workspace.root().to_path_buf());
+    let lockfile = root.join("uv.lock");
+    if !lockfile.is_file() {
+        return Err(anyhow!(
+            "No `uv.lock` found in: {} (run `uv lock` to create one)",
+            root.normalized_display()
+        ));
+    }
+    let contents = fs_err::read_to_string(&lockfile)?;
+    let lock = Lock::from_toml(&contents)
+        .with_context(|| format!("Failed to parse: {}", lockfile.normalized_display()))?;
+
+    // If a target was provided, determine the markers against which to evaluate the lockfile.
+    let markers = if python_platform.is_some() || python_version.is_some() {
+        let interpreter = find_default_python(&Platform::current()?, cache)?;
+        let markers = interpreter.markers().clone();
+        let markers = match python_platform {
+            Some(python_platform) => python_platform.markers(&markers),
+            None => markers,
+        };
+        Some(match python_version {
+            Some(python_version) => python_version.markers(&markers),
+            None => markers,
+        })
+    } else {
+        None
+    };
+
+    // Select the distributions that are reachable from the project's requirements.
+    let mut distributions = reachable_distributions(
+        &lock,
+        pyproject,
+        workspace.as_ref(),
+        &extras,
+        groups,
+        markers.as_ref(),
+    )?;
+
+    // Sort the distributions by name, but with editable packages first.
+    distributions.sort_unstable_by_key(|distribution| {
+        (
+            !matches!(distribution.source, LockedSource::Editable(_)),
+            &distribution.name,
+        )
+    });
+
+    let output_file = output_file.filter(|output_file| *output_file != Path::new("-"));
+    let mut writer = OutputWriter::new(!quiet || output_file.is_none(), output_file)?;
+    match format {
+        ExportFormat::RequirementsTxt => {
+            write_header(&mut writer, &cmd(true, true))?;
+
+            // If the project itself is buildable, include it in editable mode, as in `uv sync`.
+            // (The members of a workspace are already included in the lockfile as editables.)
+            if workspace.is_none() && is_buildable(&root.join("pyproject.toml"))? {
+                writeln!(writer, "-e .")?;
+            }
+
+            for distribution in distributions {
+                writeln!(
+                    writer,
+                    "{}",
+                    to_requirements_txt(distribution, hashes, markers.is_none())?
+                )?;
+            }
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Select the locked distributions that are reachable from the project's requirements (or those
+/// of every workspace member), along with the requested extras and dependency groups.
+///
+/// If target markers are provided, any edges (and distributions) that don't apply to the target
+/// are skipped.
+pub(crate) fn reachable_distributions<'lock>(
+    lock: &'lock Lock,
+    pyproject: PathBuf,
+    workspace: Option<&Workspace>,
+    extras: &ExtrasSpecification<'_>,
+    groups: &[String],
+    markers: Option<&MarkerEnvironment>,
+) -> Result<Vec<&'lock LockedDistribution>> {
+    // The only dependency group is `dev` (i.e., `tool.uv.dev-dependencies`).
+    if let Some(group) = groups.iter().find(|group| *group != "dev") {
+        return Err(anyhow!(
+            "Unknown dependency group `{group}` (expected: `dev`)"
+        ));
+    }
+
+    // Read the project's requirements (or those of every workspace member), which form the roots
+    // of the exported dependency graph.
+    let sources = match workspace {
+        Some(workspace) => workspace
+            .members()
+            .iter()
+            .map(|member| RequirementsSource::PyprojectToml(member.pyproject()))
+            .collect::<Vec<_>>(),
+        None => vec![RequirementsSource::PyprojectToml(pyproject)],
+    };
+    let RequirementsSpecification {
+        mut requirements,
+        extras: used_extras,
+        ..
+    } = RequirementsSpecification::from_sources(&sources, &[], &[], extras)?;
+
+    // Check that all provided extras are used.
+    if let ExtrasSpecification::Some(extras) = extras {
+        let mut unused_extras = extras
+            .iter()
+            .filter(|extra| !used_extras.contains(extra))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        if !unused_extras.is_empty() {
+            unused_extras.sort_unstable();
+            unused_extras.dedup();
+            let s = if unused_extras.len() == 1 { "" } else { "s" };
+            return Err(anyhow!(
+                "Requested extra{s} not found: {}",
+                unused_extras.join(", ")
+            ));
+        }
+    }
+
+    if !groups.is_empty() {
+        for source in &sources {
+            if let RequirementsSource::PyprojectToml(pyproject) = source {
+                requirements.extend(crate::pyproject::dev_dependencies(pyproject)?);
+            }
+        }
+    }
+
+    // Dependencies on workspace members are satisfied by the members themselves, which are
+    // included as editables.
+    if let Some(workspace) = workspace {
+        requirements.retain(|requirement| !workspace.contains(&requirement.name));
+    }
+
+    // Walk the lockfile from the roots, skipping any edges that don't apply to the target.
+    let mut queue = VecDeque::new();
+    for requirement in requirements {
+        if markers.is_some_and(|markers| !requirement.evaluate_markers(markers, &[])) {
+            debug!("Skipping {requirement} (marker does not apply to the target)");
+            continue;
+        }
+        queue.push_back((requirement.name, None));
+    }
+    if let Some(workspace) = workspace {
+        queue.extend(
+            workspace
+                .members()
+                .iter()
+                .filter(|member| member.buildable)
+                .map(|member| (member.name.clone(), None)),
+        );
+    }
+
+    // A package may be locked at a different version in each set of environments (e.g., for each
+    // supported Python version), so a requirement without a pinned version may match several
+    // distributions.
+    let mut seen = FxHashSet::default();
+    let mut distributions = Vec::new();
+    while let Some((name, version)) = queue.pop_front() {
+        let mut candidates = lock
+            .distributions()
+            .iter()
+            .filter(|distribution| {
+                distribution.name == name
+                    && (version.is_none() || distribution.version.as_ref() == version.as_ref())
+            })
+            .peekable();
+        if candidates.peek().is_none() {
+            return Err(anyhow!(
+                "`{name}` is missing from the lockfile (run `uv lock` to update it)"
+            ));
+        }
+        for distribution in candidates {
+            if !seen.insert((&distribution.name, distribution.version.as_ref())) {
+                continue;
+            }
+            if let Some(markers) = markers {
+                if !applies(distribution.marker.as_deref(), markers, &name)? {
+                    debug!("Skipping {name} (marker does not apply to the target)");
+                    continue;
+                }
+            }
+
+            // The dependencies of workspace members are already included via their requirements,
+            // which reflect the requested extras and groups (rather than those used when locking).
+            if !matches!(distribution.source, LockedSource::Editable(_)) {
+                for dependency in &distribution.dependencies {
+                    if let Some(markers) = markers {
+                        if !applies(dependency.marker.as_deref(), markers, &name)? {
+                            continue;
+                        }
+                    }
+                    queue.push_back((dependency.name.clone(), dependency.version.clone()));
+                }
+            }
+            distributions.push(distribution);
+        }
+    }
+
+    Ok(distributions)
+}
+
+/// Returns `true` if the given (optional) marker from the lockfile applies to the target
+/// environment.
+fn applies(marker: Option<&str>, markers: &MarkerEnvironment, name: &PackageName) -> Result<bool> {
+    let Some(marker) = marker else {
+        return Ok(true);
+    };
+    let marker =
+        MarkerTree::from_str(marker).with_context(|| format!("Invalid marker for `{name}`"))?;
+    Ok(marker.evaluate(markers, &[]))
+}
+
+/// Returns `true` if the `pyproject.toml` at the given path declares a build system.
+fn is_buildable(pyproject: &Path) -> Result<bool> {
+    let contents = fs_err::read_to_string(pyproject)?;
+    let pyproject_toml = toml::from_str::<pyproject_toml::PyProjectToml>(&contents)
+        .with_context(|| format!("Failed to parse: {}", pyproject.normalized_display()))?;
+    Ok(pyproject_toml.build_system.is_some())
+}
+
+/// Render a locked distribution as a `requirements.txt` entry, in the same format as
+/// `uv pip compile`.
+///
+/// Local paths are stored in the lockfile relative to its directory, and are rendered as-is. If
+/// `include_markers` is `false` (i.e., the markers have already been evaluated against a target),
+/// the distribution's markers are omitted.
+fn to_requirements_txt(
+    distribution: &LockedDistribution,
+    include_hashes: bool,
+    include_markers: bool,
+) -> Result<String> {
+    let name = &distribution.name;
+    let requirement = match &distribution.source {
+        LockedSource::Registry(_) => {
+            let Some(version) = distribution.version.as_ref() else {
+                return Err(anyhow!(
+                    "Locked registry distribution `{name}` is missing a version"
+                ));
+            };
+            format!("{name}=={version}")
+        }
+        LockedSource::Direct(url) => format!("{name} @ {url}"),
+        LockedSource::Git(url) => format!("{name} @ git+{url}"),
+        LockedSource::Path(path) => format!("{name} @ {path}"),
+        LockedSource::Editable(path) => format!("-e {path}"),
+    };
+    let marker = if include_markers && !matches!(distribution.source, LockedSource::Editable(_)) {
+        distribution.marker.as_deref()
+    } else {
+        None
+    };
+    let hashes = if include_hashes {
+        distribution.hashes()
+    } else {
+        Vec::new()
+    };
+    Ok(requirements_txt_entry(&requirement, marker, &hashes))
+}
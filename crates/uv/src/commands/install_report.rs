@@ -0,0 +1,162 @@
+use std::path::Path;
+
+use anyhow::{Context, Result};
+use serde::Serialize;
+use url::Url;
+
+use distribution_types::{BuiltDist, CachedDist, Dist, FileLocation, Name, SourceDist};
+use pypi_types::Hashes;
+use uv_cache::{Cache, CacheBucket};
+
+/// A machine-readable report of the distributions installed into an environment.
+#[derive(Debug, Serialize)]
+pub(crate) struct InstallReport {
+    /// The version of the report format.
+    version: &'static str,
+    /// The distributions that were installed, sorted by name.
+    install: Vec<InstallReportEntry>,
+}
+
+#[derive(Debug, Serialize)]
+struct InstallReportEntry {
+    /// The name of the installed package.
+    name: String,
+    /// The version of the installed package.
+    version: String,
+    /// The URL from which the distribution was fetched, if it was fetched during this operation.
+    url: Option<String>,
+    /// The known hashes of the fetched distribution, as `<algorithm>:<digest>`.
+    hashes: Vec<String>,
+    /// Whether the distribution was linked from the cache, rather than fetched or built.
+    cached: bool,
+    /// Whether the installed wheel was built from a source distribution.
+    built: bool,
+    /// Whether the distribution was installed in editable mode.
+    editable: bool,
+}
+
+impl Default for InstallReport {
+    fn default() -> Self {
+        Self {
+            version: "1",
+            install: Vec::new(),
+        }
+    }
+}
+
+impl InstallReport {
+    /// Build a report from the installation plan.
+    ///
+    /// `local` contains the distributions that were linked from the cache (or built as editables),
+    /// `remote` the distributions that were fetched, and `wheels` the wheels that were fetched or
+    /// built for them.
+    pub(crate) fn from_plan(
+        local: &[CachedDist],
+        remote: &[Dist],
+        wheels: &[CachedDist],
+        cache: &Cache,
+    ) -> Self {
+        let built_wheels = cache.bucket(CacheBucket::BuiltWheels);
+
+        let mut install = Vec::with_capacity(local.len() + remote.len());
+
+        for dist in local {
+            let editable = dist.editable();
+            install.push(InstallReportEntry {
+                name: dist.name().to_string(),
+                version: dist.filename().version.to_string(),
+                url: match dist {
+                    CachedDist::Registry(_) => None,
+                    CachedDist::Url(dist) => Some(dist.url.to_string()),
+                },
+                hashes: Vec::new(),
+                cached: !editable,
+                built: editable || is_built(dist.path(), &built_wheels),
+                editable,
+            });
+        }
+
+        for dist in remote {
+            let wheel = wheels.iter().find(|wheel| wheel.name() == dist.name());
+            let version = wheel
+                .map(|wheel| wheel.filename().version.to_string())
+                .or_else(|| dist.version().map(ToString::to_string))
+                .unwrap_or_default();
+            install.push(InstallReportEntry {
+                name: dist.name().to_string(),
+                version,
+                url: url(dist),
+                hashes: dist
+                    .file()
+                    .map(|file| hashes(&file.hashes))
+                    .unwrap_or_default(),
+                cached: false,
+                built: matches!(dist, Dist::Source(_)),
+                editable: wheel.is_some_and(CachedDist::editable),
+            });
+        }
+
+        install.sort_by(|a, b| a.name.cmp(&b.name));
+
+        Self {
+            install,
+            ..Self::default()
+        }
+    }
+
+    /// Write the report to the given path, or to `stdout` if the path is `-`.
+    pub(crate) fn write(&self, path: &Path) -> Result<()> {
+        let json = serde_json::to_string_pretty(self)?;
+        if path == Path::new("-") {
+            anstream::println!("{json}");
+        } else {
+            fs_err::write(path, json + "\n").with_context(|| {
+                format!("Failed to write install report to: {}", path.display())
+            })?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns `true` if the cached wheel at the given path was built from a source distribution.
+fn is_built(path: &Path, built_wheels: &Path) -> bool {
+    path.starts_with(built_wheels)
+}
+
+/// Return the URL from which a remote distribution is fetched.
+fn url(dist: &Dist) -> Option<String> {
+    if let Some(file) = dist.file() {
+        return match &file.url {
+            FileLocation::RelativeUrl(base, url) => pypi_types::base_url_join_relative(base, url)
+                .ok()
+                .map(|url| url.to_string()),
+            FileLocation::AbsoluteUrl(url) => Some(url.clone()),
+            FileLocation::Path(path) => Url::from_file_path(path).ok().map(|url| url.to_string()),
+        };
+    }
+    match dist {
+        Dist::Built(BuiltDist::DirectUrl(dist)) => Some(dist.url.to_string()),
+        Dist::Built(BuiltDist::Path(dist)) => Some(dist.url.to_string()),
+        Dist::Source(SourceDist::DirectUrl(dist)) => Some(dist.url.to_string()),
+        Dist::Source(SourceDist::Git(dist)) => Some(dist.url.to_string()),
+        Dist::Source(SourceDist::Path(dist)) => Some(dist.url.to_string()),
+        Dist::Built(BuiltDist::Registry(_)) | Dist::Source(SourceDist::Registry(_)) => None,
+    }
+}
+
+/// Format all known digests as `<algorithm>:<digest>`.
+fn hashes(hashes: &Hashes) -> Vec<String> {
+    [
+        ("md5", &hashes.md5),
+        ("sha256", &hashes.sha256),
+        ("sha384", &hashes.sha384),
+        ("sha512", &hashes.sha512),
+    ]
+    .into_iter()
+    .filter_map(|(algorithm, digest)| {
+        digest
+            .as_ref()
+            .map(|digest| format!("{algorithm}:{digest}"))
+    })
+    .collect()
+}
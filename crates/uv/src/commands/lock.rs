@@ -0,0 +1,411 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use anstream::eprint;
+use anyhow::{anyhow, Context, Result};
+use owo_colors::OwoColorize;
+use tracing::debug;
+use url::Url;
+
+use distribution_types::{IndexLocations, Name};
+use pep440_rs::{Operator, VersionSpecifier, VersionSpecifiers};
+use pep508_rs::Requirement;
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder, Timeouts, TlsSettings,
+};
+use uv_dispatch::BuildDispatch;
+use uv_fs::Normalized;
+use uv_installer::NoBinary;
+use uv_interpreter::{Interpreter, PythonVersion};
+use uv_resolver::{
+    ExcludeNewer, InMemoryIndex, Lock, LockedDependency, LockedDistribution, LockedSource,
+    Manifest, OptionsBuilder, PreReleaseMode, ResolutionMode, Resolver,
+};
+use uv_traits::{Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+
+use crate::commands::pip_compile::InMemoryIndexRef;
+use crate::commands::reporters::ResolverReporter;
+use crate::commands::{elapsed, ExitStatus, Upgrade};
+use crate::printer::Printer;
+use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+use crate::workspace::{Workspace, WorkspaceMember};
+
+/// Resolve the dependencies of the project in the current directory into a universal lockfile
+/// (`uv.lock`).
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn lock(
+    extras: ExtrasSpecification<'_>,
+    resolution_mode: ResolutionMode,
+    prerelease_mode: PreReleaseMode,
+    upgrade: Upgrade,
+    index_locations: IndexLocations,
+    setup_py: SetupPyStrategy,
+    config_settings: ConfigSettings,
+    connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    concurrency: Concurrency,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    python_version: Option<PythonVersion>,
+    exclude_newer: ExcludeNewer,
+    cache: Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    // The project is the `pyproject.toml` in the current directory; the lockfile lives alongside,
+    // unless the project is part of a workspace, in which case all of the workspace members are
+    // locked together at the workspace root.
+    let project_root = std::env::current_dir()?;
+    let pyproject = project_root.join("pyproject.toml");
+    if !pyproject.is_file() {
+        return Err(anyhow!(
+            "No `pyproject.toml` found in: {}",
+            project_root.normalized_display()
+        ));
+    }
+    let workspace = Workspace::discover(&project_root)?;
+    let root = workspace
+        .as_ref()
+        .map_or(project_root, |workspace| workspace.root().to_path_buf());
+    let lockfile = root.join("uv.lock");
+    let sources = match &workspace {
+        Some(workspace) => workspace
+            .members()
+            .iter()
+            .map(|member| RequirementsSource::PyprojectToml(member.pyproject()))
+            .collect::<Vec<_>>(),
+        None => vec![RequirementsSource::PyprojectToml(pyproject)],
+    };
+
+    // Read the project's requirements.
+    let RequirementsSpecification {
+        project,
+        mut requirements,
+        constraints,
+        overrides,
+        editables,
+        hashes: _hashes,
+        index_url,
+        extra_index_urls,
+        no_index,
+        find_links,
+        extras: used_extras,
+        origins: _origins,
+    } = RequirementsSpecification::from_sources(&sources, &[], &[], &extras)?;
+
+    if !editables.is_empty() {
+        return Err(anyhow!(
+            "Editable requirements are not supported by `uv lock`"
+        ));
+    }
+
+    // Check that all provided extras are used.
+    if let ExtrasSpecification::Some(extras) = &extras {
+        let mut unused_extras = extras
+            .iter()
+            .filter(|extra| !used_extras.contains(extra))
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        if !unused_extras.is_empty() {
+            unused_extras.sort_unstable();
+            unused_extras.dedup();
+            let s = if unused_extras.len() == 1 { "" } else { "s" };
+            return Err(anyhow!(
+                "Requested extra{s} not found: {}",
+                unused_extras.join(", ")
+            ));
+        }
+    }
+
+    // Development dependencies are always locked alongside the project's dependencies. The lock
+    // must satisfy the `requires-python` of every project.
+    let mut requires_python = Vec::new();
+    for source in &sources {
+        if let RequirementsSource::PyprojectToml(pyproject) = source {
+            requirements.extend(crate::pyproject::dev_dependencies(pyproject)?);
+            if let Some(specifiers) = crate::pyproject::requires_python(pyproject)? {
+                requires_python.extend(specifiers.iter().cloned());
+            }
+        }
+    }
+    let requires_python = (!requires_python.is_empty())
+        .then(|| requires_python.into_iter().collect::<VersionSpecifiers>());
+
+    // Dependencies on workspace members are satisfied by the members themselves, which are
+    // installed as editables rather than resolved.
+    if let Some(workspace) = &workspace {
+        requirements.retain(|requirement| !workspace.contains(&requirement.name));
+    }
+
+    // Incorporate any index locations from the project.
+    let index_locations =
+        index_locations.combine(index_url, extra_index_urls, find_links, no_index);
+
+    // Prefer the versions pinned in the existing lockfile, if any.
+    let preferences = if lockfile.exists() && !upgrade.is_all() {
+        read_lock_preferences(&lockfile, &upgrade)?
+    } else {
+        Vec::new()
+    };
+
+    // Find an interpreter to use for building distributions.
+    let platform = Platform::current()?;
+    let interpreter = Interpreter::find_best(python_version.as_ref(), &platform, &cache)?;
+    debug!(
+        "Using Python {} interpreter at {} for builds",
+        interpreter.python_version(),
+        interpreter.sys_executable().normalized_display().cyan()
+    );
+
+    // Create a shared in-memory index.
+    let source_index = InMemoryIndex::default();
+
+    // Unless a Python version was requested, resolve for the oldest Python version permitted by
+    // `requires-python`, so that the locked versions are compatible with all of them.
+    let target =
+        python_version.or_else(|| requires_python.as_ref().and_then(lowest_python_version));
+
+    // If we're resolving against a different Python version, use a separate index, as in
+    // `uv pip compile`.
+    let top_level_index = if target.is_some() {
+        InMemoryIndexRef::Owned(InMemoryIndex::default())
+    } else {
+        InMemoryIndexRef::Borrowed(&source_index)
+    };
+
+    // The resolution is universal, so the tags and markers of the current platform are only used
+    // to select the distributions that are built or inspected along the way.
+    let tags = interpreter.tags()?;
+    let markers = match target {
+        Some(target) => Cow::Owned(target.markers(interpreter.markers())),
+        None => Cow::Borrowed(interpreter.markers()),
+    };
+
+    // Instantiate a client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .index_urls(index_locations.index_urls())
+        .connectivity(connectivity)
+        .tls(tls)
+        .retries(retries)
+        .proxy(proxy)
+        .timeouts(timeouts)
+        .build()?;
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let client = FlatIndexClient::new(&client, &cache);
+        let entries = client.fetch(index_locations.flat_index()).await?;
+        FlatIndex::from_entries(entries, tags, no_build, no_binary)
+    };
+
+    // Track in-flight downloads, builds, etc., across resolutions.
+    let in_flight = InFlight::default();
+
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        &cache,
+        &interpreter,
+        &index_locations,
+        &flat_index,
+        &source_index,
+        &in_flight,
+        interpreter.sys_executable().to_path_buf(),
+        setup_py,
+        &config_settings,
+        no_build,
+        no_binary,
+        concurrency,
+    )
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer.clone())
+            .build(),
+    );
+
+    // Create a manifest of the requirements.
+    let manifest = Manifest::new(
+        requirements,
+        constraints,
+        overrides,
+        preferences,
+        project,
+        Vec::new(),
+    );
+
+    let options = OptionsBuilder::new()
+        .resolution_mode(resolution_mode)
+        .prerelease_mode(prerelease_mode)
+        .exclude_newer(exclude_newer)
+        .universal(true)
+        .requires_python(requires_python.clone())
+        .build();
+
+    // Resolve the dependencies.
+    let resolver = Resolver::new(
+        manifest,
+        options,
+        &markers,
+        &interpreter,
+        tags,
+        &client,
+        &flat_index,
+        &top_level_index,
+        &build_dispatch,
+    )?
+    .with_reporter(ResolverReporter::from(printer));
+
+    let resolution = match resolver.resolve().await {
+        Err(uv_resolver::ResolveError::NoSolution(err)) => {
+            let report = miette::Report::msg(format!("{err}"))
+                .context("No solution found when resolving dependencies:");
+            eprint!("{report:?}");
+
+            // In verbose mode, include the full derivation of the conflict.
+            if printer == Printer::Verbose {
+                eprint!("\n\nDerivation of the conflict:\n{}", err.tree());
+            }
+            return Ok(ExitStatus::Failure);
+        }
+        result => result,
+    }?;
+
+    let s = if resolution.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Resolved {} in {}",
+            format!("{} package{}", resolution.len(), s).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    // Mark the resolved packages' cache entries as recently used.
+    cache.touch(resolution.petgraph().node_weights().map(Name::name));
+
+    // Notify the user of any diagnostics.
+    for diagnostic in resolution.diagnostics() {
+        writeln!(
+            printer,
+            "{}{} {}",
+            "warning".yellow().bold(),
+            ":".bold(),
+            diagnostic.message().bold()
+        )?;
+    }
+
+    // Write the lockfile, including any (buildable) workspace members as editables.
+    let files = resolution.files(&client, concurrency.downloads).await?;
+    let mut lock = Lock::from_resolution(&resolution, &files, requires_python, &root);
+    if let Some(workspace) = &workspace {
+        let members = workspace
+            .members()
+            .iter()
+            .filter(|member| member.buildable)
+            .map(|member| member_distribution(member, &root, &extras, &lock))
+            .collect::<Result<Vec<_>>>()?;
+        lock.extend(members);
+    }
+    fs_err::write(&lockfile, lock.to_toml()?)?;
+
+    Ok(ExitStatus::Success)
+}
+
+/// Read the pinned versions of registry distributions from an existing lockfile, to use as
+/// preferences for the resolution.
+fn read_lock_preferences(lockfile: &Path, upgrade: &Upgrade) -> Result<Vec<Requirement>> {
+    let contents = fs_err::read_to_string(lockfile)?;
+    let lock = Lock::from_toml(&contents)
+        .with_context(|| format!("Failed to parse: {}", lockfile.normalized_display()))?;
+    lock.distributions()
+        .iter()
+        .filter(|distribution| matches!(distribution.source, LockedSource::Registry(_)))
+        .filter(|distribution| match upgrade {
+            Upgrade::None => true,
+            Upgrade::All => false,
+            Upgrade::Packages(packages) => !packages.contains(&distribution.name),
+        })
+        .filter_map(|distribution| {
+            let version = distribution.version.as_ref()?;
+            Some(
+                Requirement::from_str(&format!("{}=={version}", distribution.name))
+                    .map_err(anyhow::Error::from),
+            )
+        })
+        .collect()
+}
+
+/// Create the locked (editable) distribution for a workspace member, with an edge to each of its
+/// dependencies (pinned to the version in the `lock`, if the dependency is locked at a single
+/// version).
+fn member_distribution(
+    member: &WorkspaceMember,
+    root: &Path,
+    extras: &ExtrasSpecification,
+    lock: &Lock,
+) -> Result<LockedDistribution> {
+    let RequirementsSpecification { requirements, .. } = RequirementsSpecification::from_sources(
+        &[RequirementsSource::PyprojectToml(member.pyproject())],
+        &[],
+        &[],
+        extras,
+    )?;
+    let mut dependencies = requirements
+        .into_iter()
+        .map(|requirement| {
+            let mut versions = lock
+                .distributions()
+                .iter()
+                .filter(|distribution| distribution.name == requirement.name)
+                .map(|distribution| distribution.version.clone());
+            let version = match (versions.next(), versions.next()) {
+                (Some(version), None) => version,
+                _ => None,
+            };
+            LockedDependency {
+                version,
+                name: requirement.name,
+                marker: requirement.marker.as_ref().map(ToString::to_string),
+            }
+        })
+        .collect::<Vec<_>>();
+    dependencies.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+    dependencies.dedup_by(|a, b| a.name == b.name && a.marker == b.marker);
+
+    Ok(LockedDistribution {
+        name: member.name.clone(),
+        version: member.version.clone(),
+        source: LockedSource::editable(&member.root, root),
+        marker: None,
+        files: Vec::new(),
+        dependencies,
+    })
+}
+
+/// Return the lowest Python version permitted by the `requires-python` specifiers, if they're
+/// bounded from below.
+fn lowest_python_version(requires_python: &VersionSpecifiers) -> Option<PythonVersion> {
+    let version = requires_python
+        .iter()
+        .filter(|specifier| {
+            matches!(
+                specifier.operator(),
+                Operator::GreaterThanEqual
+                    | Operator::TildeEqual
+                    | Operator::Equal
+                    | Operator::ExactEqual
+            )
+        })
+        .map(VersionSpecifier::version)
+        .max()?;
+    PythonVersion::from_str(&version.to_string()).ok()
+}
@@ -1,27 +1,67 @@
+use std::path::Path;
 use std::process::ExitCode;
 use std::time::Duration;
 
+use anyhow::{anyhow, Result};
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+pub(crate) use add::add;
 pub(crate) use cache_clean::cache_clean;
 pub(crate) use cache_dir::cache_dir;
+pub(crate) use cache_export::cache_export;
+pub(crate) use cache_import::cache_import;
+pub(crate) use cache_info::cache_info;
+pub(crate) use cache_prune::cache_prune;
+pub(crate) use cache_verify::cache_verify;
 use distribution_types::InstalledMetadata;
+pub(crate) use export::export;
+pub(crate) use lock::lock;
+pub(crate) use pip_check::pip_check;
 pub(crate) use pip_compile::{extra_name_with_clap_error, pip_compile, Upgrade};
+pub(crate) use pip_download::pip_download;
 pub(crate) use pip_freeze::pip_freeze;
 pub(crate) use pip_install::pip_install;
 pub(crate) use pip_list::pip_list;
+pub(crate) use pip_show::pip_show;
 pub(crate) use pip_sync::pip_sync;
 pub(crate) use pip_uninstall::pip_uninstall;
+use platform_host::Platform;
+pub(crate) use python_list::python_list;
+pub(crate) use remove::remove;
+pub(crate) use run::run;
+pub(crate) use sync::sync;
+use uv_cache::Cache;
+use uv_fs::Normalized;
+use uv_interpreter::{find_default_python, Virtualenv};
 pub(crate) use venv::venv;
 pub(crate) use version::version;
 
+mod add;
 mod cache_clean;
 mod cache_dir;
+mod cache_export;
+mod cache_import;
+mod cache_info;
+mod cache_prune;
+mod cache_verify;
+mod export;
+mod install_report;
+mod lock;
+mod pip_check;
 mod pip_compile;
+mod pip_download;
 mod pip_freeze;
 mod pip_install;
 mod pip_list;
+mod pip_show;
 mod pip_sync;
 mod pip_uninstall;
+mod python_list;
+mod remove;
 mod reporters;
+mod run;
+mod sync;
 mod venv;
 mod version;
 
@@ -38,6 +78,9 @@ pub(crate) enum ExitStatus {
     /// The command failed with an unexpected error.
     #[allow(unused)]
     Error,
+
+    /// The command's child process exited with the given exit code.
+    External(u8),
 }
 
 impl From<ExitStatus> for ExitCode {
@@ -46,6 +89,7 @@ impl From<ExitStatus> for ExitCode {
             ExitStatus::Success => Self::from(0),
             ExitStatus::Failure => Self::from(1),
             ExitStatus::Error => Self::from(2),
+            ExitStatus::External(code) => Self::from(code),
         }
     }
 }
@@ -63,6 +107,78 @@ pub(super) fn elapsed(duration: Duration) -> String {
     }
 }
 
+/// Determine the environment to operate on.
+///
+/// If `--system` is set, use the default Python's own installation; otherwise, use the active
+/// virtual environment.
+pub(super) fn detect_environment(
+    system: bool,
+    platform: Platform,
+    cache: &Cache,
+) -> Result<Virtualenv> {
+    if system {
+        let interpreter = find_default_python(&platform, cache)?;
+        let prefix = interpreter.base_prefix().to_path_buf();
+        Ok(Virtualenv::from_prefix(interpreter, &prefix))
+    } else {
+        Ok(Virtualenv::from_env(platform, cache)?)
+    }
+}
+
+/// Determine the environment to install into.
+///
+/// If a `--prefix` is provided, install into it using the interpreter of the active virtual
+/// environment, falling back to the default Python if no virtual environment is active. Otherwise,
+/// defer to [`detect_environment`].
+pub(super) fn install_target(
+    prefix: Option<&Path>,
+    system: bool,
+    platform: Platform,
+    cache: &Cache,
+) -> Result<Virtualenv> {
+    let Some(prefix) = prefix else {
+        return detect_environment(system, platform, cache);
+    };
+
+    let interpreter = match Virtualenv::from_env(platform.clone(), cache) {
+        Ok(venv) => venv.interpreter().clone(),
+        Err(uv_interpreter::Error::NotFound) => find_default_python(&platform, cache)?,
+        Err(err) => return Err(err.into()),
+    };
+
+    fs_err::create_dir_all(prefix)?;
+    let prefix = fs_err::canonicalize(prefix)?;
+    Ok(Virtualenv::from_prefix(interpreter, &prefix))
+}
+
+/// Refuse to modify a system environment that's marked as externally managed (PEP 668), unless
+/// `--break-system-packages` is set.
+pub(super) fn check_externally_managed(
+    venv: &Virtualenv,
+    break_system_packages: bool,
+) -> Result<()> {
+    let Some(externally_managed) = venv.interpreter().is_externally_managed() else {
+        return Ok(());
+    };
+
+    if break_system_packages {
+        debug!("Ignoring externally managed environment due to `--break-system-packages`");
+        return Ok(());
+    }
+
+    let executable = venv.python_executable();
+    let executable = executable.normalized_display();
+    Err(match externally_managed.into_error() {
+        Some(error) => anyhow!(
+            "The interpreter at {executable} is externally managed, and indicates the following:\n\n{}\n\nConsider creating a virtual environment with `uv venv`, or pass `--break-system-packages` to install anyway.",
+            textwrap::indent(&error, "  ").green()
+        ),
+        None => anyhow!(
+            "The interpreter at {executable} is externally managed. Instead, create a virtual environment with `uv venv`, or pass `--break-system-packages` to install anyway."
+        ),
+    })
+}
+
 #[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
 pub(super) enum ChangeEventKind {
     /// The package was removed from the environment.
@@ -82,3 +198,30 @@ pub(crate) enum VersionFormat {
     Text,
     Json,
 }
+
+#[derive(Debug, Default, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum CompileFormat {
+    /// Write the resolution as a `requirements.txt` file.
+    #[default]
+    RequirementsTxt,
+    /// Write the resolution as a JSON document, including every package and dependency edge.
+    Json,
+}
+
+#[derive(Debug, Default, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum ExportFormat {
+    /// Export the lockfile as a `requirements.txt` file.
+    #[default]
+    RequirementsTxt,
+}
+
+#[derive(Debug, Default, Clone, Copy, clap::ValueEnum)]
+pub(crate) enum ListFormat {
+    /// Display the packages in a human-readable table.
+    #[default]
+    Columns,
+    /// Display the packages as a JSON array, as in `pip list --format json`.
+    Json,
+    /// Display the packages as `requirements.txt`-style pins.
+    Freeze,
+}
@@ -0,0 +1,87 @@
+use std::fmt::Write;
+
+use anyhow::Result;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_fs::Normalized;
+use uv_installer::{Diagnostic, SitePackages};
+
+use crate::commands::{detect_environment, elapsed, ExitStatus};
+use crate::printer::Printer;
+
+/// Verify that the installed packages in the current environment have compatible dependencies.
+///
+/// Exits with [`ExitStatus::Failure`] if any dependencies are missing, and with
+/// [`ExitStatus::Error`] if any installed packages are incompatible with one another (or with
+/// the current Python version).
+pub(crate) fn pip_check(system: bool, cache: &Cache, mut printer: Printer) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = detect_environment(system, platform, cache)?;
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().normalized_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+    let num_packages = site_packages.iter().count();
+
+    // Validate that the environment is consistent.
+    let mut diagnostics = site_packages.diagnostics()?;
+    diagnostics.sort_by_cached_key(Diagnostic::message);
+
+    let s = if num_packages == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Checked {} in {}",
+            format!("{num_packages} package{s}").bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    if diagnostics.is_empty() {
+        writeln!(
+            printer,
+            "{}",
+            "All installed packages are compatible".green()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let s = if diagnostics.len() == 1 { "y" } else { "ies" };
+    writeln!(
+        printer,
+        "{}",
+        format!("Found {} incompatibilit{s}", diagnostics.len()).bold()
+    )?;
+    for diagnostic in &diagnostics {
+        writeln!(
+            printer,
+            "{}{} {}",
+            "error".red().bold(),
+            ":".bold(),
+            diagnostic.message().bold()
+        )?;
+    }
+
+    // Distinguish between missing dependencies and genuine conflicts.
+    if diagnostics
+        .iter()
+        .all(|diagnostic| matches!(diagnostic, Diagnostic::MissingDependency { .. }))
+    {
+        Ok(ExitStatus::Failure)
+    } else {
+        Ok(ExitStatus::Error)
+    }
+}
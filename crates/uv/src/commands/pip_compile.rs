@@ -8,34 +8,40 @@ use std::str::FromStr;
 
 use anstream::{eprint, AutoStream};
 use anyhow::{anyhow, Context, Result};
-use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 use tempfile::tempdir_in;
 use tracing::debug;
+use url::Url;
 
-use distribution_types::{IndexLocations, LocalEditable};
+use distribution_types::{IndexLocations, LocalEditable, Name, Verbatim};
 use pep508_rs::Requirement;
 use platform_host::Platform;
 use platform_tags::Tags;
+use pypi_types::Metadata21;
 use requirements_txt::EditableRequirement;
 use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder, Timeouts, TlsSettings,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Normalized;
 use uv_installer::{Downloader, NoBinary};
-use uv_interpreter::{Interpreter, PythonVersion};
+use uv_interpreter::{Interpreter, PythonVersion, TargetPlatform};
 use uv_normalize::{ExtraName, PackageName};
 use uv_resolver::{
-    AnnotationStyle, DependencyMode, DisplayResolutionGraph, InMemoryIndex, Manifest,
-    OptionsBuilder, PreReleaseMode, ResolutionMode, Resolver,
+    AnnotationStyle, DependencyMode, DisplayResolutionGraph, ExcludeNewer, HashAlgorithm,
+    InMemoryIndex, Manifest, OptionsBuilder, PreReleaseMode, ResolutionMode, Resolver, YankedMode,
+};
+use uv_traits::{
+    BuildContext, BuildKind, Concurrency, ConfigSettings, InFlight, NoBuild, PackageConfigSettings,
+    SetupPyStrategy, SourceBuildTrait,
 };
-use uv_traits::{ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
 use uv_warnings::warn_user;
 
 use crate::commands::reporters::{DownloadReporter, ResolverReporter};
-use crate::commands::{elapsed, ExitStatus};
+use crate::commands::{elapsed, CompileFormat, ExitStatus};
 use crate::printer::Printer;
 use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
 
@@ -45,88 +51,130 @@ pub(crate) async fn pip_compile(
     requirements: &[RequirementsSource],
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
+    build_constraints: &[RequirementsSource],
     extras: ExtrasSpecification<'_>,
     output_file: Option<&Path>,
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
+    yanked_mode: YankedMode,
     dependency_mode: DependencyMode,
     upgrade: Upgrade,
     generate_hashes: bool,
+    hash_algorithm: HashAlgorithm,
     no_emit_packages: Vec<PackageName>,
     include_annotations: bool,
     include_header: bool,
+    custom_compile_command: Option<String>,
     include_index_url: bool,
     include_find_links: bool,
+    relative_paths: bool,
     index_locations: IndexLocations,
     setup_py: SetupPyStrategy,
     config_settings: ConfigSettings,
+    package_config_settings: PackageConfigSettings,
     connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    concurrency: Concurrency,
     no_build: &NoBuild,
+    no_binary: &NoBinary,
     python_version: Option<PythonVersion>,
-    exclude_newer: Option<DateTime<Utc>>,
+    python_platform: Option<TargetPlatform>,
+    universal: bool,
+    exclude_newer: ExcludeNewer,
     annotation_style: AnnotationStyle,
+    include_specifiers: bool,
+    format: CompileFormat,
     quiet: bool,
     cache: Cache,
     mut printer: Printer,
 ) -> Result<ExitStatus> {
     let start = std::time::Instant::now();
 
+    // An output file of `-` refers to stdout, which is already the default.
+    let output_file = output_file.filter(|output_file| *output_file != Path::new("-"));
+
+    // Stdin can only be consumed once, so reject multiple sources that read from it.
+    if requirements
+        .iter()
+        .chain(constraints)
+        .chain(overrides)
+        .filter(|source| {
+            matches!(source, RequirementsSource::RequirementsTxt(path) if path == Path::new("-"))
+        })
+        .count()
+        > 1
+    {
+        return Err(anyhow!(
+            "Requirements can only be read from stdin (`-`) once, but `-` was provided multiple times"
+        ));
+    }
+
     // If the user requests `extras` but does not provide a pyproject toml source
     if !matches!(extras, ExtrasSpecification::None)
-        && !requirements
-            .iter()
-            .any(|source| matches!(source, RequirementsSource::PyprojectToml(_)))
+        && !requirements.iter().any(|source| {
+            matches!(
+                source,
+                RequirementsSource::PyprojectToml(_)
+                    | RequirementsSource::SetupPy(_)
+                    | RequirementsSource::SetupCfg(_)
+            )
+        })
     {
         return Err(anyhow!(
-            "Requesting extras requires a pyproject.toml input file."
+            "Requesting extras requires a pyproject.toml, setup.py, or setup.cfg input file."
         ));
     }
 
+    // Separate out any `setup.py` and `setup.cfg` sources, which need to be built to determine
+    // their requirements.
+    let (source_trees, requirements): (Vec<_>, Vec<_>) =
+        requirements.iter().cloned().partition(|source| {
+            matches!(
+                source,
+                RequirementsSource::SetupPy(_) | RequirementsSource::SetupCfg(_)
+            )
+        });
+
+    // Annotations only attribute packages to input files if there are multiple inputs.
+    let num_sources = source_trees.len() + requirements.len();
+
     // Read all requirements from the provided sources.
     let RequirementsSpecification {
-        project,
-        requirements,
+        mut project,
+        mut requirements,
         constraints,
         overrides,
         editables,
+        hashes: _hashes,
         index_url,
         extra_index_urls,
         no_index,
         find_links,
-        extras: used_extras,
-    } = RequirementsSpecification::from_sources(requirements, constraints, overrides, &extras)?;
+        extras: mut used_extras,
+        origins,
+    } = RequirementsSpecification::from_sources(&requirements, constraints, overrides, &extras)?;
+
+    // Read the build constraints.
+    let build_constraints = RequirementsSpecification::from_build_constraints(build_constraints)?;
+    let origins = if num_sources > 1 {
+        origins
+    } else {
+        FxHashMap::default()
+    };
 
     // Incorporate any index locations from the provided sources.
     let index_locations =
         index_locations.combine(index_url, extra_index_urls, find_links, no_index);
 
-    // Check that all provided extras are used
-    if let ExtrasSpecification::Some(extras) = extras {
-        let mut unused_extras = extras
-            .iter()
-            .filter(|extra| !used_extras.contains(extra))
-            .collect::<Vec<_>>();
-        if !unused_extras.is_empty() {
-            unused_extras.sort_unstable();
-            unused_extras.dedup();
-            let s = if unused_extras.len() == 1 { "" } else { "s" };
-            return Err(anyhow!(
-                "Requested extra{s} not found: {}",
-                unused_extras.iter().join(", ")
-            ));
-        }
-    }
-
     let preferences: Vec<Requirement> = output_file
         // As an optimization, skip reading the lockfile is we're upgrading all packages anyway.
         .filter(|_| !upgrade.is_all())
         .filter(|output_file| output_file.exists())
-        .map(Path::to_path_buf)
-        .map(RequirementsSource::from_path)
-        .as_ref()
-        .map(|source| RequirementsSpecification::from_source(source, &extras))
+        .map(|output_file| read_preferences(output_file, &extras))
         .transpose()?
-        .map(|spec| spec.requirements)
         .map(|requirements| match upgrade {
             // Respect all pinned versions from the existing lockfile.
             Upgrade::None => requirements,
@@ -171,42 +219,58 @@ pub(crate) async fn pip_compile(
     // Create a shared in-memory index.
     let source_index = InMemoryIndex::default();
 
-    // If we're resolving against a different Python version, use a separate index. Source
-    // distributions will be built against the installed version, and so the index may contain
-    // different package priorities than in the top-level resolution.
-    let top_level_index = if python_version.is_some() {
+    // If we're resolving against a different Python version or platform, use a separate index.
+    // Source distributions will be built against the installed version, and so the index may
+    // contain different package priorities than in the top-level resolution.
+    let top_level_index = if python_version.is_some() || python_platform.is_some() {
         InMemoryIndexRef::Owned(InMemoryIndex::default())
     } else {
         InMemoryIndexRef::Borrowed(&source_index)
     };
 
-    // Determine the tags, markers, and interpreter to use for resolution.
-    let tags = if let Some(python_version) = python_version.as_ref() {
+    // Determine the tags, markers, and interpreter to use for resolution. If a target Python
+    // version or platform is requested, it overrides that of the interpreter.
+    let tags = if python_version.is_some() || python_platform.is_some() {
         Cow::Owned(Tags::from_env(
-            interpreter.platform(),
-            (python_version.major(), python_version.minor()),
+            python_platform
+                .as_ref()
+                .map_or(interpreter.platform(), TargetPlatform::platform),
+            python_version
+                .as_ref()
+                .map_or(interpreter.python_tuple(), |python_version| {
+                    (python_version.major(), python_version.minor())
+                }),
             interpreter.implementation_name(),
             interpreter.implementation_tuple(),
+            interpreter.gil_disabled(),
         )?)
     } else {
         Cow::Borrowed(interpreter.tags()?)
     };
-    let markers = python_version.map_or_else(
-        || Cow::Borrowed(interpreter.markers()),
-        |python_version| Cow::Owned(python_version.markers(interpreter.markers())),
-    );
+    let markers = match (python_platform.as_ref(), python_version) {
+        (None, None) => Cow::Borrowed(interpreter.markers()),
+        (Some(python_platform), None) => Cow::Owned(python_platform.markers(interpreter.markers())),
+        (None, Some(python_version)) => Cow::Owned(python_version.markers(interpreter.markers())),
+        (Some(python_platform), Some(python_version)) => {
+            Cow::Owned(python_version.markers(&python_platform.markers(interpreter.markers())))
+        }
+    };
 
     // Instantiate a client.
     let client = RegistryClientBuilder::new(cache.clone())
         .index_urls(index_locations.index_urls())
         .connectivity(connectivity)
-        .build();
+        .tls(tls)
+        .retries(retries)
+        .proxy(proxy)
+        .timeouts(timeouts)
+        .build()?;
 
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
         let client = FlatIndexClient::new(&client, &cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, &tags)
+        FlatIndex::from_entries(entries, &tags, no_build, no_binary)
     };
 
     // Track in-flight downloads, builds, etc., across resolutions.
@@ -224,9 +288,63 @@ pub(crate) async fn pip_compile(
         setup_py,
         &config_settings,
         no_build,
-        &NoBinary::None,
+        no_binary,
+        concurrency,
+    )
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer.clone())
+            .build(),
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_build_constraints(&build_constraints)
+    .with_package_config_settings(&package_config_settings);
+
+    // Build the metadata for any `setup.py` and `setup.cfg` sources, and add their requirements.
+    for source in &source_trees {
+        let metadata = read_source_tree_metadata(source, &build_dispatch).await?;
+
+        // Determine which of the declared extras were requested.
+        let activated = match &extras {
+            ExtrasSpecification::None => vec![],
+            ExtrasSpecification::All => metadata.provides_extras.clone(),
+            ExtrasSpecification::Some(extras) => extras
+                .iter()
+                .filter(|extra| metadata.provides_extras.contains(extra))
+                .cloned()
+                .collect(),
+        };
+        used_extras.extend(activated.iter().cloned());
+
+        // Requirements gated on an activated extra become unconditional; those gated on any
+        // other extra are left as-is, and so are never activated during resolution.
+        requirements.extend(metadata.requires_dist.into_iter().map(|mut requirement| {
+            requirement.marker = requirement
+                .marker
+                .and_then(|marker| marker.simplify_extras(&activated));
+            requirement
+        }));
+
+        if project.is_none() {
+            project = Some(metadata.name);
+        }
+    }
+
+    // Check that all provided extras are used
+    if let ExtrasSpecification::Some(extras) = extras {
+        let mut unused_extras = extras
+            .iter()
+            .filter(|extra| !used_extras.contains(extra))
+            .collect::<Vec<_>>();
+        if !unused_extras.is_empty() {
+            unused_extras.sort_unstable();
+            unused_extras.dedup();
+            let s = if unused_extras.len() == 1 { "" } else { "s" };
+            return Err(anyhow!(
+                "Requested extra{s} not found: {}",
+                unused_extras.iter().join(", ")
+            ));
+        }
+    }
 
     // Build the editables and add their requirements
     let editable_metadata = if editables.is_empty() {
@@ -285,8 +403,10 @@ pub(crate) async fn pip_compile(
     let options = OptionsBuilder::new()
         .resolution_mode(resolution_mode)
         .prerelease_mode(prerelease_mode)
+        .yanked_mode(yanked_mode)
         .dependency_mode(dependency_mode)
         .exclude_newer(exclude_newer)
+        .universal(universal)
         .build();
 
     // Resolve the dependencies.
@@ -308,6 +428,11 @@ pub(crate) async fn pip_compile(
             let report = miette::Report::msg(format!("{err}"))
                 .context("No solution found when resolving dependencies:");
             eprint!("{report:?}");
+
+            // In verbose mode, include the full derivation of the conflict.
+            if printer == Printer::Verbose {
+                eprint!("\n\nDerivation of the conflict:\n{}", err.tree());
+            }
             return Ok(ExitStatus::Failure);
         }
         result => result,
@@ -325,6 +450,9 @@ pub(crate) async fn pip_compile(
         .dimmed()
     )?;
 
+    // Mark the resolved packages' cache entries as recently used.
+    cache.touch(resolution.petgraph().node_weights().map(Name::name));
+
     // Notify the user of any diagnostics.
     for diagnostic in resolution.diagnostics() {
         writeln!(
@@ -336,27 +464,69 @@ pub(crate) async fn pip_compile(
         )?;
     }
 
+    // If hashes were requested, compute any digests that aren't published by the index.
+    let resolution = if generate_hashes {
+        resolution
+            .with_hash_algorithm(hash_algorithm, &client, &cache, concurrency.downloads)
+            .await?
+    } else {
+        resolution
+    };
+
     // Write the resolved dependencies to the output channel.
     let mut writer = OutputWriter::new(!quiet || output_file.is_none(), output_file)?;
 
+    // If requested, render local paths relative to the directory containing the output file (or
+    // the current directory, when writing to stdout).
+    let relative_to = if relative_paths {
+        let current_dir = env::current_dir()?;
+        Some(match output_file.and_then(Path::parent) {
+            Some(parent) => current_dir.join(parent),
+            None => current_dir,
+        })
+    } else {
+        None
+    };
+
+    let graph = DisplayResolutionGraph::new(
+        &resolution,
+        &no_emit_packages,
+        generate_hashes,
+        include_annotations,
+        annotation_style,
+        include_specifiers,
+        origins,
+    );
+    let graph = if let Some(relative_to) = relative_to.as_deref() {
+        graph.with_relative_paths(relative_to)
+    } else {
+        graph
+    };
+
+    // If requested, write the resolution as JSON, omitting any comments.
+    if matches!(format, CompileFormat::Json) {
+        let json = graph.to_json();
+        writeln!(writer, "{}", serde_json::to_string_pretty(&json)?)?;
+        return Ok(ExitStatus::Success);
+    }
+
     if include_header {
-        writeln!(
-            writer,
-            "{}",
-            "# This file was autogenerated by uv via the following command:".green()
-        )?;
-        writeln!(
-            writer,
-            "{}",
-            format!("#    {}", cmd(include_index_url, include_find_links,)).green()
+        write_header(
+            &mut writer,
+            &custom_compile_command.unwrap_or_else(|| cmd(include_index_url, include_find_links)),
         )?;
     }
 
     // Write the index locations to the output channel.
     let mut wrote_index = false;
 
-    // If necessary, include the `--index-url` and `--extra-index-url` locations.
+    // If necessary, include the `--index-url` and `--extra-index-url` locations (or
+    // `--no-index`, if index lookups were disabled).
     if include_index_url {
+        if index_locations.no_index() {
+            writeln!(writer, "--no-index")?;
+            wrote_index = true;
+        }
         if let Some(index) = index_locations.index() {
             writeln!(writer, "--index-url {index}")?;
             wrote_index = true;
@@ -380,41 +550,130 @@ pub(crate) async fn pip_compile(
         writeln!(writer)?;
     }
 
-    write!(
-        writer,
-        "{}",
-        DisplayResolutionGraph::new(
-            &resolution,
-            &no_emit_packages,
-            generate_hashes,
-            include_annotations,
-            annotation_style,
-        )
-    )?;
+    write!(writer, "{graph}")?;
 
-    // If any "unsafe" packages were excluded, notify the user.
-    let excluded = no_emit_packages
-        .into_iter()
-        .filter(|name| resolution.contains(name))
+    // If any "unsafe" packages were excluded, include their pins as comments, such that they're
+    // visible (but not installed) when using the output file.
+    let mut excluded = resolution
+        .petgraph()
+        .node_indices()
+        .filter(|index| no_emit_packages.contains(resolution.petgraph()[*index].name()))
         .collect::<Vec<_>>();
+    excluded.sort_unstable_by_key(|index| (resolution.petgraph()[*index].name(), *index));
     if !excluded.is_empty() {
         writeln!(writer)?;
         writeln!(
             writer,
             "{}",
-            "# The following packages were excluded from the output:".green()
+            "# The following packages are considered to be unsafe in a requirements file:".green()
         )?;
-        for package in excluded {
-            writeln!(writer, "# {package}")?;
+        for index in excluded {
+            let dist = &resolution.petgraph()[index];
+            if let Some(marker) = resolution.marker(index) {
+                writeln!(
+                    writer,
+                    "{}",
+                    format!("# {} ; {}", dist.verbatim(), marker).green()
+                )?;
+            } else {
+                writeln!(writer, "{}", format!("# {}", dist.verbatim()).green())?;
+            }
         }
     }
 
     Ok(ExitStatus::Success)
 }
 
+/// Read the pinned versions from an existing output file, to use as preferences for the
+/// resolution.
+///
+/// The format is detected from the file's contents, rather than the requested output format, so
+/// that the existing pins are respected even if the output format changed since the last run.
+fn read_preferences(
+    output_file: &Path,
+    extras: &ExtrasSpecification<'_>,
+) -> Result<Vec<Requirement>> {
+    let contents = fs_err::read_to_string(output_file)?;
+    if contents.trim_start().starts_with('{') {
+        read_json_preferences(output_file, &contents)
+    } else {
+        let source = RequirementsSource::from_path(output_file.to_path_buf());
+        RequirementsSpecification::from_source(&source, extras).map(|spec| spec.requirements)
+    }
+}
+
+/// Read the pinned versions from the contents of an existing output file in the JSON format.
+fn read_json_preferences(output_file: &Path, contents: &str) -> Result<Vec<Requirement>> {
+    let json: serde_json::Value = serde_json::from_str(contents)
+        .with_context(|| format!("Failed to parse: {}", output_file.normalized_display()))?;
+    json.get("packages")
+        .and_then(serde_json::Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(|package| {
+            let name = package.get("name")?.as_str()?;
+            let version = package.get("version")?.as_str()?;
+            Some(Requirement::from_str(&format!("{name}=={version}")).map_err(anyhow::Error::from))
+        })
+        .collect()
+}
+
+/// Build the metadata for a `setup.py` or `setup.cfg` source by invoking the PEP 517
+/// `prepare_metadata_for_build_wheel` hook on the enclosing source tree.
+async fn read_source_tree_metadata(
+    source: &RequirementsSource,
+    build_dispatch: &BuildDispatch<'_>,
+) -> Result<Metadata21> {
+    let (RequirementsSource::SetupPy(path) | RequirementsSource::SetupCfg(path)) = source else {
+        unreachable!("Expected a `setup.py` or `setup.cfg` source, found: {source}");
+    };
+
+    // The source tree is the directory containing the `setup.py` or `setup.cfg` file.
+    let source_tree = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+        .unwrap_or_else(|| Path::new("."));
+    let source_tree = fs_err::canonicalize(source_tree)?;
+
+    // A `setup.cfg` file is only read by `setuptools` when invoked through a `setup.py` or
+    // `pyproject.toml` in the same directory.
+    if matches!(source, RequirementsSource::SetupCfg(_))
+        && !source_tree.join("setup.py").is_file()
+        && !source_tree.join("pyproject.toml").is_file()
+    {
+        return Err(anyhow!(
+            "Failed to determine the requirements of `{source}`: expected a `setup.py` or `pyproject.toml` file in the same directory"
+        ));
+    }
+
+    let mut builder = build_dispatch
+        .setup_build(
+            &source_tree,
+            None,
+            &source.to_string(),
+            None,
+            BuildKind::Wheel,
+        )
+        .await
+        .with_context(|| format!("Failed to build: {source}"))?;
+    let Some(metadata_directory) = builder
+        .metadata()
+        .await
+        .with_context(|| format!("Failed to build: {source}"))?
+    else {
+        return Err(anyhow!(
+            "Failed to determine the requirements of `{source}`: the build backend does not support `prepare_metadata_for_build_wheel`"
+        ));
+    };
+
+    let contents = fs_err::read(metadata_directory.join("METADATA"))?;
+    Metadata21::parse(&contents)
+        .with_context(|| format!("Failed to parse the metadata of: {source}"))
+}
+
 /// Format the `uv` command used to generate the output file.
 #[allow(clippy::fn_params_excessive_bools)]
-fn cmd(include_index_url: bool, include_find_links: bool) -> String {
+pub(crate) fn cmd(include_index_url: bool, include_find_links: bool) -> String {
     let args = env::args_os()
         .skip(1)
         .map(|arg| arg.normalized_display().to_string())
@@ -461,6 +720,18 @@ fn cmd(include_index_url: bool, include_find_links: bool) -> String {
                 return Some(None);
             }
 
+            // Always skip the `--upgrade-package` flag (and its value), since selective upgrades
+            // are a one-off operation.
+            if arg == "--upgrade-package" || arg == "-P" {
+                *skip_next = Some(true);
+                return Some(None);
+            }
+            // Includes the attached short form (e.g., `-Pclick`, `-P=click`).
+            if arg.starts_with("--upgrade-package=") || arg.starts_with("-P") {
+                *skip_next = None;
+                return Some(None);
+            }
+
             // Always skip the `--quiet` flag.
             if arg == "--quiet" || arg == "-q" {
                 *skip_next = None;
@@ -481,9 +752,25 @@ fn cmd(include_index_url: bool, include_find_links: bool) -> String {
     format!("uv {args}")
 }
 
+/// Write the header of a generated `requirements.txt` file, recording the command that generated
+/// it.
+pub(crate) fn write_header(writer: &mut OutputWriter, command: &str) -> Result<()> {
+    writeln!(
+        writer,
+        "{}",
+        format!(
+            "# This file was autogenerated by uv v{} via the following command:",
+            env!("CARGO_PKG_VERSION")
+        )
+        .green()
+    )?;
+    writeln!(writer, "{}", format!("#    {command}").green())?;
+    Ok(())
+}
+
 /// A multi-casting writer that writes to both the standard output and an output file, if present.
 #[allow(clippy::disallowed_types)]
-struct OutputWriter {
+pub(crate) struct OutputWriter {
     stdout: Option<AutoStream<std::io::Stdout>>,
     output_file: Option<AutoStream<std::fs::File>>,
 }
@@ -491,12 +778,14 @@ struct OutputWriter {
 #[allow(clippy::disallowed_types)]
 impl OutputWriter {
     /// Create a new output writer.
-    fn new(include_stdout: bool, output_file: Option<&Path>) -> Result<Self> {
+    pub(crate) fn new(include_stdout: bool, output_file: Option<&Path>) -> Result<Self> {
         let stdout = include_stdout.then(|| AutoStream::<std::io::Stdout>::auto(stdout()));
         let output_file = output_file
             .map(|output_file| {
+                // Never write ANSI escape codes to the output file, even if color is forced (e.g.,
+                // via `--color always`), so that the file is identical regardless of the terminal.
                 let output_file = fs_err::File::create(output_file)?;
-                let output_file = AutoStream::auto(output_file.into());
+                let output_file = AutoStream::never(output_file.into());
                 Ok::<AutoStream<std::fs::File>, std::io::Error>(output_file)
             })
             .transpose()?;
@@ -507,7 +796,7 @@ impl OutputWriter {
     }
 
     /// Write the given arguments to both the standard output and the output file, if present.
-    fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> std::io::Result<()> {
+    pub(crate) fn write_fmt(&mut self, args: std::fmt::Arguments<'_>) -> std::io::Result<()> {
         use std::io::Write;
 
         if let Some(output_file) = &mut self.output_file {
@@ -568,7 +857,7 @@ pub(crate) fn extra_name_with_clap_error(arg: &str) -> Result<ExtraName> {
 }
 
 /// An owned or unowned [`InMemoryIndex`].
-enum InMemoryIndexRef<'a> {
+pub(crate) enum InMemoryIndexRef<'a> {
     Owned(InMemoryIndex),
     Borrowed(&'a InMemoryIndex),
 }
@@ -0,0 +1,393 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+use std::path::Path;
+
+use anstream::eprint;
+use anyhow::{anyhow, Context, Result};
+use futures::{StreamExt, TryStreamExt};
+use owo_colors::OwoColorize;
+use tokio::io::AsyncWriteExt;
+use tracing::debug;
+use url::Url;
+
+use distribution_types::{
+    BuiltDist, Dist, File, FileLocation, IndexLocations, RemoteSource, Resolution, SourceDist,
+};
+use platform_host::Platform;
+use platform_tags::Tags;
+use uv_cache::Cache;
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder, Timeouts,
+    TlsSettings,
+};
+use uv_dispatch::BuildDispatch;
+use uv_fs::Normalized;
+use uv_installer::NoBinary;
+use uv_interpreter::{Interpreter, PythonVersion, TargetPlatform};
+use uv_resolver::{
+    DependencyMode, ExcludeNewer, InMemoryIndex, Manifest, OptionsBuilder, PreReleaseMode,
+    ResolutionMode, Resolver,
+};
+use uv_traits::{Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_warnings::warn_user;
+
+use crate::commands::pip_compile::InMemoryIndexRef;
+use crate::commands::reporters::ResolverReporter;
+use crate::commands::{elapsed, ExitStatus};
+use crate::printer::Printer;
+use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
+
+/// Resolve a set of requirements, and download the resulting distributions to a directory,
+/// without installing them.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+pub(crate) async fn pip_download(
+    requirements: &[RequirementsSource],
+    constraints: &[RequirementsSource],
+    overrides: &[RequirementsSource],
+    build_constraints: &[RequirementsSource],
+    dest: &Path,
+    resolution_mode: ResolutionMode,
+    prerelease_mode: PreReleaseMode,
+    dependency_mode: DependencyMode,
+    index_locations: IndexLocations,
+    setup_py: SetupPyStrategy,
+    config_settings: ConfigSettings,
+    connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    concurrency: Concurrency,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    python_version: Option<PythonVersion>,
+    python_platform: Option<TargetPlatform>,
+    exclude_newer: ExcludeNewer,
+    cache: Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    // Read all requirements from the provided sources.
+    let RequirementsSpecification {
+        project,
+        requirements,
+        constraints,
+        overrides,
+        editables,
+        hashes: _hashes,
+        index_url,
+        extra_index_urls,
+        no_index,
+        find_links,
+        extras: _extras,
+        origins: _origins,
+    } = RequirementsSpecification::from_sources(
+        requirements,
+        constraints,
+        overrides,
+        &ExtrasSpecification::None,
+    )?;
+
+    // Editables are local source trees, and so there's nothing to download.
+    if !editables.is_empty() {
+        return Err(anyhow!(
+            "Editable requirements are not supported by `uv pip download`"
+        ));
+    }
+
+    // Read the build constraints.
+    let build_constraints = RequirementsSpecification::from_build_constraints(build_constraints)?;
+
+    // Incorporate any index locations from the provided sources.
+    let index_locations =
+        index_locations.combine(index_url, extra_index_urls, find_links, no_index);
+
+    // Find an interpreter to use for building distributions.
+    let platform = Platform::current()?;
+    let interpreter = Interpreter::find_best(python_version.as_ref(), &platform, &cache)?;
+    debug!(
+        "Using Python {} interpreter at {} for builds",
+        interpreter.python_version(),
+        interpreter.sys_executable().normalized_display().cyan()
+    );
+
+    // Create a shared in-memory index.
+    let source_index = InMemoryIndex::default();
+
+    // If we're resolving against a different Python version or platform, use a separate index,
+    // as in `uv pip compile`.
+    let top_level_index = if python_version.is_some() || python_platform.is_some() {
+        InMemoryIndexRef::Owned(InMemoryIndex::default())
+    } else {
+        InMemoryIndexRef::Borrowed(&source_index)
+    };
+
+    // Determine the tags and markers to use for resolution. If a Python version or platform is
+    // requested, distributions are selected for that target, rather than for the interpreter.
+    let tags = if python_version.is_some() || python_platform.is_some() {
+        Cow::Owned(Tags::from_env(
+            python_platform
+                .as_ref()
+                .map_or(interpreter.platform(), TargetPlatform::platform),
+            python_version
+                .as_ref()
+                .map_or(interpreter.python_tuple(), |python_version| {
+                    (python_version.major(), python_version.minor())
+                }),
+            interpreter.implementation_name(),
+            interpreter.implementation_tuple(),
+            interpreter.gil_disabled(),
+        )?)
+    } else {
+        Cow::Borrowed(interpreter.tags()?)
+    };
+    let markers = match (python_platform.as_ref(), python_version) {
+        (None, None) => Cow::Borrowed(interpreter.markers()),
+        (Some(python_platform), None) => Cow::Owned(python_platform.markers(interpreter.markers())),
+        (None, Some(python_version)) => Cow::Owned(python_version.markers(interpreter.markers())),
+        (Some(python_platform), Some(python_version)) => {
+            Cow::Owned(python_version.markers(&python_platform.markers(interpreter.markers())))
+        }
+    };
+
+    // Instantiate a client.
+    let client = RegistryClientBuilder::new(cache.clone())
+        .index_urls(index_locations.index_urls())
+        .connectivity(connectivity)
+        .tls(tls)
+        .retries(retries)
+        .proxy(proxy)
+        .timeouts(timeouts)
+        .build()?;
+
+    // Resolve the flat indexes from `--find-links`.
+    let flat_index = {
+        let client = FlatIndexClient::new(&client, &cache);
+        let entries = client.fetch(index_locations.flat_index()).await?;
+        FlatIndex::from_entries(entries, &tags, no_build, no_binary)
+    };
+
+    // Track in-flight downloads, builds, etc., across resolutions.
+    let in_flight = InFlight::default();
+
+    let build_dispatch = BuildDispatch::new(
+        &client,
+        &cache,
+        &interpreter,
+        &index_locations,
+        &flat_index,
+        &source_index,
+        &in_flight,
+        interpreter.sys_executable().to_path_buf(),
+        setup_py,
+        &config_settings,
+        no_build,
+        no_binary,
+        concurrency,
+    )
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer.clone())
+            .build(),
+    )
+    .with_build_constraints(&build_constraints);
+
+    // Create a manifest of the requirements.
+    let manifest = Manifest::new(
+        requirements,
+        constraints,
+        overrides,
+        Vec::new(),
+        project,
+        Vec::new(),
+    );
+
+    let options = OptionsBuilder::new()
+        .resolution_mode(resolution_mode)
+        .prerelease_mode(prerelease_mode)
+        .dependency_mode(dependency_mode)
+        .exclude_newer(exclude_newer)
+        .build();
+
+    // Resolve the dependencies.
+    let resolver = Resolver::new(
+        manifest,
+        options,
+        &markers,
+        &interpreter,
+        &tags,
+        &client,
+        &flat_index,
+        &top_level_index,
+        &build_dispatch,
+    )?
+    .with_reporter(ResolverReporter::from(printer));
+
+    let resolution = match resolver.resolve().await {
+        Err(uv_resolver::ResolveError::NoSolution(err)) => {
+            let report = miette::Report::msg(format!("{err}"))
+                .context("No solution found when resolving dependencies:");
+            eprint!("{report:?}");
+
+            // In verbose mode, include the full derivation of the conflict.
+            if printer == Printer::Verbose {
+                eprint!("\n\nDerivation of the conflict:\n{}", err.tree());
+            }
+            return Ok(ExitStatus::Failure);
+        }
+        result => result,
+    }?;
+
+    let s = if resolution.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Resolved {} in {}",
+            format!("{} package{}", resolution.len(), s).bold(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    // Notify the user of any diagnostics.
+    for diagnostic in resolution.diagnostics() {
+        writeln!(
+            printer,
+            "{}{} {}",
+            "warning".yellow().bold(),
+            ":".bold(),
+            diagnostic.message().bold()
+        )?;
+    }
+
+    let resolution = Resolution::from(resolution);
+
+    // Download each distribution to the destination directory.
+    let start = std::time::Instant::now();
+    fs_err::tokio::create_dir_all(dest).await?;
+
+    let mut downloads = futures::stream::iter(resolution.distributions())
+        .map(|dist| download(dist, dest, &client))
+        .buffer_unordered(concurrency.downloads)
+        .try_collect::<Vec<_>>()
+        .await?
+        .into_iter()
+        .flatten()
+        .collect::<Vec<_>>();
+    downloads.sort_unstable();
+
+    let s = if downloads.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Saved {} to {} in {}",
+            format!("{} file{}", downloads.len(), s).bold(),
+            dest.normalized_display(),
+            elapsed(start.elapsed())
+        )
+        .dimmed()
+    )?;
+
+    for filename in downloads {
+        writeln!(printer, " {} {}", "+".green(), filename.bold())?;
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// The location of a distribution archive, to be copied into the destination directory.
+enum Artifact<'a> {
+    /// A remote archive, along with its expected SHA-256 digest, if known.
+    Url(Url, Option<&'a str>),
+    /// A local archive.
+    Path(&'a Path),
+}
+
+/// Download the archive for the given distribution to the destination directory, returning its
+/// filename.
+///
+/// Distributions that aren't backed by an archive (i.e., Git repositories and local source
+/// trees) are skipped with a warning.
+async fn download(dist: &Dist, dest: &Path, client: &RegistryClient) -> Result<Option<String>> {
+    let (filename, artifact) = match dist {
+        Dist::Built(BuiltDist::Registry(wheel)) => {
+            (wheel.file.filename.clone(), file_artifact(&wheel.file)?)
+        }
+        Dist::Source(SourceDist::Registry(sdist)) => {
+            (sdist.file.filename.clone(), file_artifact(&sdist.file)?)
+        }
+        Dist::Built(BuiltDist::DirectUrl(wheel)) => (
+            wheel.filename.to_string(),
+            Artifact::Url(wheel.url.to_url(), None),
+        ),
+        Dist::Source(SourceDist::DirectUrl(sdist)) => (
+            sdist.url.filename()?.to_string(),
+            Artifact::Url(sdist.url.to_url(), None),
+        ),
+        Dist::Built(BuiltDist::Path(wheel)) => {
+            (wheel.filename.to_string(), Artifact::Path(&wheel.path))
+        }
+        Dist::Source(SourceDist::Path(sdist)) if sdist.path.is_file() => {
+            let filename = sdist
+                .path
+                .file_name()
+                .map(|filename| filename.to_string_lossy().to_string())
+                .ok_or_else(|| anyhow!("Invalid path: {}", sdist.path.normalized_display()))?;
+            (filename, Artifact::Path(&sdist.path))
+        }
+        Dist::Source(SourceDist::Path(_) | SourceDist::Git(_)) => {
+            warn_user!("Skipping {dist}, which isn't available as an archive");
+            return Ok(None);
+        }
+    };
+
+    let target = dest.join(&filename);
+    if target.exists() {
+        debug!("Already downloaded: {}", target.normalized_display());
+        return Ok(Some(filename));
+    }
+
+    match artifact {
+        Artifact::Path(path) => {
+            debug!("Copying {dist} from: {}", path.normalized_display());
+            fs_err::tokio::copy(path, &target).await?;
+        }
+        Artifact::Url(url, sha256) => {
+            debug!("Downloading {dist} from: {url}");
+            let response = client
+                .download(url.clone())
+                .send()
+                .await?
+                .error_for_status()?;
+
+            // Stream the archive to a temporary file alongside the target, such that an
+            // interrupted download never leaves a partial file in the destination directory.
+            let temp_file = tempfile::NamedTempFile::new_in(dest)?;
+            let mut writer = tokio::fs::File::from_std(temp_file.reopen()?);
+            let mut stream = client.stream_download(response, sha256);
+            while let Some(chunk) = stream.next().await {
+                let chunk = chunk.with_context(|| format!("Failed to download: {url}"))?;
+                writer.write_all(&chunk).await?;
+            }
+            writer.flush().await?;
+            temp_file.persist(&target)?;
+        }
+    }
+
+    Ok(Some(filename))
+}
+
+/// Return the location of a [`File`] from a registry.
+fn file_artifact(file: &File) -> Result<Artifact<'_>> {
+    let sha256 = file.hashes.sha256.as_deref();
+    Ok(match &file.url {
+        FileLocation::RelativeUrl(base, url) => {
+            Artifact::Url(pypi_types::base_url_join_relative(base, url)?, sha256)
+        }
+        FileLocation::AbsoluteUrl(url) => Artifact::Url(Url::parse(url)?, sha256),
+        FileLocation::Path(path) => Artifact::Path(path),
+    })
+}
@@ -11,16 +11,21 @@ use platform_host::Platform;
 use uv_cache::Cache;
 use uv_fs::Normalized;
 use uv_installer::SitePackages;
-use uv_interpreter::Virtualenv;
 
-use crate::commands::ExitStatus;
+use crate::commands::{detect_environment, ExitStatus};
 use crate::printer::Printer;
 
 /// Enumerate the installed packages in the current environment.
-pub(crate) fn pip_freeze(cache: &Cache, strict: bool, mut printer: Printer) -> Result<ExitStatus> {
+pub(crate) fn pip_freeze(
+    cache: &Cache,
+    strict: bool,
+    exclude_editable: bool,
+    system: bool,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
     // Detect the current Python interpreter.
     let platform = Platform::current()?;
-    let venv = Virtualenv::from_env(platform, cache)?;
+    let venv = detect_environment(system, platform, cache)?;
 
     debug!(
         "Using Python {} environment at {}",
@@ -32,12 +37,19 @@ pub(crate) fn pip_freeze(cache: &Cache, strict: bool, mut printer: Printer) -> R
     let site_packages = SitePackages::from_executable(&venv)?;
     for dist in site_packages
         .iter()
+        .filter(|dist| !(exclude_editable && dist.is_editable()))
         .sorted_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(b.version())))
     {
         match dist {
             InstalledDist::Registry(dist) => {
                 println!("{}=={}", dist.name().bold(), dist.version);
             }
+            InstalledDist::EggInfo(dist) => {
+                println!("{}=={}", dist.name().bold(), dist.version);
+            }
+            InstalledDist::LegacyEditable(dist) => {
+                println!("-e {}", dist.target_url);
+            }
             InstalledDist::Url(dist) => {
                 if dist.editable {
                     println!("-e {}", dist.url);
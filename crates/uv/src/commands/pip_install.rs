@@ -5,11 +5,11 @@ use std::path::Path;
 
 use anstream::eprint;
 use anyhow::{anyhow, Context, Result};
-use chrono::{DateTime, Utc};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
 use tempfile::tempdir_in;
 use tracing::debug;
+use url::Url;
 
 use distribution_types::{
     IndexLocations, InstalledMetadata, LocalDist, LocalEditable, Name, Resolution,
@@ -21,7 +21,10 @@ use platform_tags::Tags;
 use pypi_types::Yanked;
 use requirements_txt::EditableRequirement;
 use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder, Timeouts,
+    TlsSettings,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Normalized;
 use uv_installer::{
@@ -30,13 +33,19 @@ use uv_installer::{
 use uv_interpreter::{Interpreter, Virtualenv};
 use uv_normalize::PackageName;
 use uv_resolver::{
-    DependencyMode, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode,
-    ResolutionGraph, ResolutionMode, Resolver,
+    DependencyMode, ExcludeNewer, InMemoryIndex, Manifest, Options, OptionsBuilder, PreReleaseMode,
+    ResolutionGraph, ResolutionMode, Resolver, YankedMode,
+};
+use uv_traits::{
+    BuildIsolation, Concurrency, ConfigSettings, InFlight, NoBuild, PackageConfigSettings,
+    SetupPyStrategy,
 };
-use uv_traits::{ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
 
+use crate::commands::install_report::InstallReport;
 use crate::commands::reporters::{DownloadReporter, InstallReporter, ResolverReporter};
-use crate::commands::{elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::commands::{
+    check_externally_managed, elapsed, install_target, ChangeEvent, ChangeEventKind, ExitStatus,
+};
 use crate::printer::Printer;
 use crate::requirements::{ExtrasSpecification, RequirementsSource, RequirementsSpecification};
 
@@ -48,23 +57,37 @@ pub(crate) async fn pip_install(
     requirements: &[RequirementsSource],
     constraints: &[RequirementsSource],
     overrides: &[RequirementsSource],
+    build_constraints: &[RequirementsSource],
     extras: &ExtrasSpecification<'_>,
     resolution_mode: ResolutionMode,
     prerelease_mode: PreReleaseMode,
+    yanked_mode: YankedMode,
     dependency_mode: DependencyMode,
     upgrade: Upgrade,
     index_locations: IndexLocations,
     reinstall: &Reinstall,
     link_mode: LinkMode,
+    compile: bool,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    concurrency: Concurrency,
     config_settings: &ConfigSettings,
+    package_config_settings: &PackageConfigSettings,
+    no_build_isolation: bool,
     no_build: &NoBuild,
     no_binary: &NoBinary,
     strict: bool,
-    exclude_newer: Option<DateTime<Utc>>,
+    exclude_newer: ExcludeNewer,
+    prefix: Option<&Path>,
+    system: bool,
+    break_system_packages: bool,
+    report: Option<&Path>,
     cache: Cache,
-    mut printer: Printer,
+    printer: Printer,
 ) -> Result<ExitStatus> {
     let start = std::time::Instant::now();
 
@@ -75,13 +98,18 @@ pub(crate) async fn pip_install(
         constraints,
         overrides,
         editables,
+        hashes: _hashes,
         index_url,
         extra_index_urls,
         no_index,
         find_links,
         extras: used_extras,
+        origins: _origins,
     } = specification(requirements, constraints, overrides, extras)?;
 
+    // Read the build constraints.
+    let build_constraints = RequirementsSpecification::from_build_constraints(build_constraints)?;
+
     // Incorporate any index locations from the provided sources.
     let index_locations =
         index_locations.combine(index_url, extra_index_urls, find_links, no_index);
@@ -105,18 +133,101 @@ pub(crate) async fn pip_install(
 
     // Detect the current Python interpreter.
     let platform = Platform::current()?;
-    let venv = Virtualenv::from_env(platform, &cache)?;
+    let venv = install_target(prefix, system, platform, &cache)?;
     debug!(
         "Using Python {} environment at {}",
         venv.interpreter().python_version(),
         venv.python_executable().normalized_display().cyan()
     );
 
+    // If we're modifying the system interpreter's environment, respect PEP 668.
+    if system {
+        check_externally_managed(&venv, break_system_packages)?;
+    }
+
+    install_requirements(
+        requirements,
+        constraints,
+        overrides,
+        project,
+        editables,
+        &build_constraints,
+        &venv,
+        resolution_mode,
+        prerelease_mode,
+        yanked_mode,
+        dependency_mode,
+        upgrade,
+        &index_locations,
+        reinstall,
+        link_mode,
+        compile,
+        setup_py,
+        connectivity,
+        tls,
+        retries,
+        proxy,
+        timeouts,
+        concurrency,
+        config_settings,
+        package_config_settings,
+        no_build_isolation,
+        no_build,
+        no_binary,
+        strict,
+        exclude_newer,
+        report,
+        &cache,
+        start,
+        printer,
+    )
+    .await
+}
+
+/// Resolve the given requirements and install them into the environment, alongside any packages
+/// that are already installed.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+pub(crate) async fn install_requirements(
+    requirements: Vec<Requirement>,
+    constraints: Vec<Requirement>,
+    overrides: Vec<Requirement>,
+    project: Option<PackageName>,
+    editables: Vec<EditableRequirement>,
+    build_constraints: &[Requirement],
+    venv: &Virtualenv,
+    resolution_mode: ResolutionMode,
+    prerelease_mode: PreReleaseMode,
+    yanked_mode: YankedMode,
+    dependency_mode: DependencyMode,
+    upgrade: Upgrade,
+    index_locations: &IndexLocations,
+    reinstall: &Reinstall,
+    link_mode: LinkMode,
+    compile: bool,
+    setup_py: SetupPyStrategy,
+    connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    concurrency: Concurrency,
+    config_settings: &ConfigSettings,
+    package_config_settings: &PackageConfigSettings,
+    no_build_isolation: bool,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    strict: bool,
+    exclude_newer: ExcludeNewer,
+    report: Option<&Path>,
+    cache: &Cache,
+    start: std::time::Instant,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
     let _lock = venv.lock()?;
 
     // Determine the set of installed packages.
     let site_packages =
-        SitePackages::from_executable(&venv).context("Failed to list installed packages")?;
+        SitePackages::from_executable(venv).context("Failed to list installed packages")?;
 
     // If the requirements are already satisfied, we're done. Ideally, the resolver would be fast
     // enough to let us remove this check. But right now, for large environments, it's an order of
@@ -137,6 +248,12 @@ pub(crate) async fn pip_install(
             )
             .dimmed()
         )?;
+
+        // Nothing was installed, but the caller may still expect a report.
+        if let Some(report) = report {
+            InstallReport::default().write(report)?;
+        }
+
         return Ok(ExitStatus::Success);
     }
 
@@ -149,13 +266,17 @@ pub(crate) async fn pip_install(
     let client = RegistryClientBuilder::new(cache.clone())
         .index_urls(index_locations.index_urls())
         .connectivity(connectivity)
-        .build();
+        .tls(tls)
+        .retries(retries)
+        .proxy(proxy)
+        .timeouts(timeouts)
+        .build()?;
 
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
-        let client = FlatIndexClient::new(&client, &cache);
+        let client = FlatIndexClient::new(&client, cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, tags)
+        FlatIndex::from_entries(entries, tags, no_build, no_binary)
     };
 
     // Create a shared in-memory index.
@@ -164,11 +285,18 @@ pub(crate) async fn pip_install(
     // Track in-flight downloads, builds, etc., across resolutions.
     let in_flight = InFlight::default();
 
+    // Determine whether to enable build isolation.
+    let build_isolation = if no_build_isolation {
+        BuildIsolation::Shared(venv)
+    } else {
+        BuildIsolation::Isolated
+    };
+
     let resolve_dispatch = BuildDispatch::new(
         &client,
-        &cache,
+        cache,
         &interpreter,
-        &index_locations,
+        index_locations,
         &flat_index,
         &index,
         &in_flight,
@@ -177,8 +305,16 @@ pub(crate) async fn pip_install(
         config_settings,
         no_build,
         no_binary,
+        concurrency,
     )
-    .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
+    .with_options(
+        OptionsBuilder::new()
+            .exclude_newer(exclude_newer.clone())
+            .build(),
+    )
+    .with_build_constraints(build_constraints)
+    .with_package_config_settings(package_config_settings)
+    .with_build_isolation(build_isolation);
 
     // Build all editable distributions. The editables are shared between resolution and
     // installation, and should live for the duration of the command. If an editable is already
@@ -191,7 +327,7 @@ pub(crate) async fn pip_install(
         build_editables(
             &editables,
             editable_wheel_dir.path(),
-            &cache,
+            cache,
             tags,
             &client,
             &resolve_dispatch,
@@ -203,8 +339,9 @@ pub(crate) async fn pip_install(
     let options = OptionsBuilder::new()
         .resolution_mode(resolution_mode)
         .prerelease_mode(prerelease_mode)
+        .yanked_mode(yanked_mode)
         .dependency_mode(dependency_mode)
-        .exclude_newer(exclude_newer)
+        .exclude_newer(exclude_newer.clone())
         .build();
 
     // Resolve the requirements.
@@ -234,6 +371,11 @@ pub(crate) async fn pip_install(
             let report = miette::Report::msg(format!("{err}"))
                 .context("No solution found when resolving dependencies:");
             eprint!("{report:?}");
+
+            // In verbose mode, include the full derivation of the conflict.
+            if printer == Printer::Verbose {
+                eprint!("\n\nDerivation of the conflict:\n{}", err.tree());
+            }
             return Ok(ExitStatus::Failure);
         }
         Err(err) => return Err(err.into()),
@@ -249,9 +391,9 @@ pub(crate) async fn pip_install(
     } else {
         BuildDispatch::new(
             &client,
-            &cache,
+            cache,
             &interpreter,
-            &index_locations,
+            index_locations,
             &flat_index,
             &index,
             &in_flight,
@@ -260,32 +402,46 @@ pub(crate) async fn pip_install(
             config_settings,
             no_build,
             no_binary,
+            concurrency,
+        )
+        .with_options(
+            OptionsBuilder::new()
+                .exclude_newer(exclude_newer.clone())
+                .build(),
         )
-        .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build())
+        .with_build_constraints(build_constraints)
+        .with_package_config_settings(package_config_settings)
+        .with_build_isolation(build_isolation)
     };
 
     // Sync the environment.
-    install(
+    let install_report = install(
         &resolution,
         editables,
         site_packages,
         reinstall,
         no_binary,
         link_mode,
-        &index_locations,
+        compile,
+        index_locations,
         tags,
         &client,
         &in_flight,
         &install_dispatch,
-        &cache,
-        &venv,
+        cache,
+        venv,
         printer,
     )
     .await?;
 
     // Validate the environment.
     if strict {
-        validate(&resolution, &venv, printer)?;
+        validate(&resolution, venv, printer)?;
+    }
+
+    // Write the install report, if requested.
+    if let Some(report) = report {
+        install_report.write(report)?;
     }
 
     Ok(ExitStatus::Success)
@@ -485,6 +641,7 @@ async fn install(
     reinstall: &Reinstall,
     no_binary: &NoBinary,
     link_mode: LinkMode,
+    compile: bool,
     index_urls: &IndexLocations,
     tags: &Tags,
     client: &RegistryClient,
@@ -493,9 +650,12 @@ async fn install(
     cache: &Cache,
     venv: &Virtualenv,
     mut printer: Printer,
-) -> Result<(), Error> {
+) -> Result<InstallReport, Error> {
     let start = std::time::Instant::now();
 
+    // Mark the resolved packages' cache entries as recently used.
+    cache.touch(resolution.packages());
+
     let requirements = resolution.requirements();
 
     // Map the built editables to their resolved form.
@@ -538,7 +698,7 @@ async fn install(
             .dimmed()
         )?;
 
-        return Ok(());
+        return Ok(InstallReport::default());
     }
 
     // Map any registry-based requirements back to those returned by the resolver.
@@ -581,6 +741,8 @@ async fn install(
         wheels
     };
 
+    let report = InstallReport::from_plan(&local, &remote, &wheels, cache);
+
     // Remove any existing installations.
     if !reinstalls.is_empty() {
         for dist_info in &reinstalls {
@@ -602,6 +764,7 @@ async fn install(
         let start = std::time::Instant::now();
         uv_installer::Installer::new(venv)
             .with_link_mode(link_mode)
+            .with_compile(compile)
             .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
             .install(&wheels)?;
 
@@ -685,7 +848,7 @@ async fn install(
         }
     }
 
-    Ok(())
+    Ok(report)
 }
 
 /// Validate the installed packages in the virtual environment.
@@ -2,35 +2,60 @@ use std::cmp::max;
 use std::fmt::Write;
 
 use anstream::println;
-use anyhow::Result;
+use anyhow::{bail, Result};
+use futures::{stream, StreamExt, TryStreamExt};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use serde::Serialize;
 use tracing::debug;
 use unicode_width::UnicodeWidthStr;
+use url::Url;
 
-use distribution_types::Name;
+use distribution_types::{File, IndexLocations, InstalledDist, Name};
+use pep440_rs::Version;
 use platform_host::Platform;
+use platform_tags::{TagCompatibility, Tags};
+use pypi_types::Yanked;
 use uv_cache::Cache;
+use uv_client::{
+    Connectivity, ErrorKind, OwnedArchive, RegistryClient, RegistryClientBuilder, SimpleMetadata,
+    Timeouts, TlsSettings,
+};
 use uv_fs::Normalized;
 use uv_installer::SitePackages;
-use uv_interpreter::Virtualenv;
+use uv_interpreter::Interpreter;
 use uv_normalize::PackageName;
 
-use crate::commands::ExitStatus;
+use crate::commands::{detect_environment, ExitStatus, ListFormat};
 use crate::printer::Printer;
 
 /// Enumerate the installed packages in the current environment.
-pub(crate) fn pip_list(
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn pip_list(
     cache: &Cache,
     strict: bool,
     editable: bool,
     exclude_editable: bool,
     exclude: &[PackageName],
+    format: ListFormat,
+    outdated: bool,
+    index_locations: IndexLocations,
+    connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    system: bool,
     mut printer: Printer,
 ) -> Result<ExitStatus> {
+    if outdated && matches!(format, ListFormat::Freeze) {
+        bail!("`--outdated` cannot be used with `--format freeze`");
+    }
+
     // Detect the current Python interpreter.
     let platform = Platform::current()?;
-    let venv = Virtualenv::from_env(platform, cache)?;
+    let venv = detect_environment(system, platform, cache)?;
 
     debug!(
         "Using Python {} environment at {}",
@@ -42,55 +67,110 @@ pub(crate) fn pip_list(
     let site_packages = SitePackages::from_executable(&venv)?;
 
     // Filter if `--editable` is specified; always sort by name.
-    let results = site_packages
+    let mut results = site_packages
         .iter()
         .filter(|f| (!f.is_editable() && !editable) || (f.is_editable() && !exclude_editable))
         .filter(|f| !exclude.contains(f.name()))
         .sorted_unstable_by(|a, b| a.name().cmp(b.name()).then(a.version().cmp(b.version())))
         .collect_vec();
-    if results.is_empty() {
-        return Ok(ExitStatus::Success);
-    }
 
-    // The package name and version are always present.
-    let mut columns = vec![
-        Column {
-            header: String::from("Package"),
-            rows: results.iter().map(|f| f.name().to_string()).collect_vec(),
-        },
-        Column {
-            header: String::from("Version"),
-            rows: results
-                .iter()
-                .map(|f| f.version().to_string())
-                .collect_vec(),
-        },
-    ];
-
-    // Editable column is only displayed if at least one editable package is found.
-    if results.iter().any(|f| f.is_editable()) {
-        columns.push(Column {
-            header: String::from("Editable project location"),
-            rows: results
-                .iter()
-                .map(|f| f.as_editable())
-                .map(|e| {
-                    if let Some(url) = e {
-                        url.to_file_path()
-                            .unwrap()
-                            .into_os_string()
-                            .into_string()
-                            .unwrap()
-                    } else {
-                        String::new()
-                    }
-                })
-                .collect_vec(),
+    // If `--outdated` is specified, query the index for the latest version of each package, and
+    // retain those for which a newer version is available.
+    let latest = if outdated {
+        let client = RegistryClientBuilder::new(cache.clone())
+            .index_urls(index_locations.index_urls())
+            .connectivity(connectivity)
+            .tls(tls)
+            .retries(retries)
+            .proxy(proxy)
+            .timeouts(timeouts)
+            .build()?;
+        let tags = venv.interpreter().tags()?;
+        let latest = find_latest(&results, &client, venv.interpreter(), tags).await?;
+        results.retain(|dist| {
+            latest
+                .get(dist.name())
+                .is_some_and(|latest| latest.version > *dist.version())
         });
-    }
+        latest
+    } else {
+        FxHashMap::default()
+    };
+
+    match format {
+        ListFormat::Columns => {
+            if results.is_empty() {
+                return Ok(ExitStatus::Success);
+            }
+
+            // The package name and version are always present.
+            let mut columns = vec![
+                Column {
+                    header: String::from("Package"),
+                    rows: results.iter().map(|f| f.name().to_string()).collect_vec(),
+                },
+                Column {
+                    header: String::from("Version"),
+                    rows: results
+                        .iter()
+                        .map(|f| f.version().to_string())
+                        .collect_vec(),
+                },
+            ];
+
+            // The latest version and file type are only displayed with `--outdated`.
+            if outdated {
+                columns.push(Column {
+                    header: String::from("Latest"),
+                    rows: results
+                        .iter()
+                        .map(|f| latest[f.name()].version.to_string())
+                        .collect_vec(),
+                });
+                columns.push(Column {
+                    header: String::from("Type"),
+                    rows: results
+                        .iter()
+                        .map(|f| latest[f.name()].filetype.to_string())
+                        .collect_vec(),
+                });
+            }
+
+            // Editable column is only displayed if at least one editable package is found.
+            if results.iter().any(|f| f.is_editable()) {
+                columns.push(Column {
+                    header: String::from("Editable project location"),
+                    rows: results
+                        .iter()
+                        .map(|f| editable_project_location(f).unwrap_or_default())
+                        .collect_vec(),
+                });
+            }
 
-    for elems in Multizip(columns.iter().map(Column::fmt_padded).collect_vec()) {
-        println!("{0}", elems.join(" "));
+            for elems in Multizip(columns.iter().map(Column::fmt_padded).collect_vec()) {
+                println!("{0}", elems.join(" "));
+            }
+        }
+        ListFormat::Json => {
+            let rows = results
+                .iter()
+                .map(|dist| Entry {
+                    name: dist.name().to_string(),
+                    version: dist.version().to_string(),
+                    latest_version: latest
+                        .get(dist.name())
+                        .map(|latest| latest.version.to_string()),
+                    latest_filetype: latest.get(dist.name()).map(|latest| latest.filetype),
+                    editable_project_location: editable_project_location(dist),
+                })
+                .collect_vec();
+            println!("{}", serde_json::to_string(&rows)?);
+        }
+        ListFormat::Freeze => {
+            for dist in &results {
+                println!("{}=={}", dist.name().bold(), dist.version());
+            }
+        }
     }
 
     // Validate that the environment is consistent.
@@ -109,6 +189,138 @@ pub(crate) fn pip_list(
     Ok(ExitStatus::Success)
 }
 
+/// Return the path to the project directory of an editable installation, if any.
+fn editable_project_location(dist: &InstalledDist) -> Option<String> {
+    let url = dist.as_editable()?;
+    Some(
+        url.to_file_path()
+            .unwrap()
+            .into_os_string()
+            .into_string()
+            .unwrap(),
+    )
+}
+
+/// An entry in the JSON output of `pip list`, matching the format used by pip.
+#[derive(Debug, Serialize)]
+struct Entry {
+    name: String,
+    version: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latest_version: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    latest_filetype: Option<FileType>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    editable_project_location: Option<String>,
+}
+
+/// The latest available version of a package.
+#[derive(Debug)]
+struct Latest {
+    version: Version,
+    filetype: FileType,
+}
+
+#[derive(Debug, Copy, Clone, Serialize)]
+#[serde(rename_all = "lowercase")]
+enum FileType {
+    Wheel,
+    Sdist,
+}
+
+impl std::fmt::Display for FileType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Wheel => write!(f, "wheel"),
+            Self::Sdist => write!(f, "sdist"),
+        }
+    }
+}
+
+/// Query the index for the latest version of each package that's compatible with the current
+/// interpreter, in parallel.
+async fn find_latest(
+    dists: &[&InstalledDist],
+    client: &RegistryClient,
+    interpreter: &Interpreter,
+    tags: &Tags,
+) -> Result<FxHashMap<PackageName, Latest>> {
+    let latest: Vec<Option<(PackageName, Latest)>> = stream::iter(dists)
+        .map(|dist| async move {
+            let (_, raw_metadata) = match client.simple(dist.name()).await {
+                Ok(metadata) => metadata,
+                Err(err) if matches!(err.kind(), ErrorKind::PackageNotFound(_)) => {
+                    // Packages that aren't available on the index (e.g., those installed from a
+                    // local path) are never outdated.
+                    debug!("Package not found on the index: {}", dist.name());
+                    return Ok::<_, anyhow::Error>(None);
+                }
+                Err(err) => return Err(err.into()),
+            };
+            let metadata = OwnedArchive::deserialize(&raw_metadata);
+            Ok(select_latest(metadata, dist.version(), interpreter, tags)
+                .map(|latest| (dist.name().clone(), latest)))
+        })
+        .buffer_unordered(32)
+        .try_collect()
+        .await?;
+    Ok(latest.into_iter().flatten().collect())
+}
+
+/// Select the latest version with a file that's compatible with the current interpreter.
+///
+/// Yanked files are ignored, as are pre-releases, unless the installed version is itself a
+/// pre-release.
+fn select_latest(
+    metadata: SimpleMetadata,
+    installed: &Version,
+    interpreter: &Interpreter,
+    tags: &Tags,
+) -> Option<Latest> {
+    let allow_prerelease = installed.any_prerelease();
+    let is_compatible = |file: &File| {
+        !file.yanked.as_ref().is_some_and(Yanked::is_yanked)
+            && file
+                .requires_python
+                .as_ref()
+                .map_or(true, |requires_python| {
+                    requires_python.contains(interpreter.python_version())
+                })
+    };
+
+    for datum in metadata.into_iter().rev() {
+        if datum.version.any_prerelease() && !allow_prerelease {
+            continue;
+        }
+
+        if datum.files.wheels.iter().any(|wheel| {
+            is_compatible(&wheel.file)
+                && matches!(
+                    wheel.name.compatibility(tags),
+                    TagCompatibility::Compatible(_)
+                )
+        }) {
+            return Some(Latest {
+                version: datum.version,
+                filetype: FileType::Wheel,
+            });
+        }
+
+        if datum
+            .files
+            .source_dists
+            .iter()
+            .any(|sdist| is_compatible(&sdist.file))
+        {
+            return Some(Latest {
+                version: datum.version,
+                filetype: FileType::Sdist,
+            });
+        }
+    }
+    None
+}
+
 #[derive(Debug)]
 struct Column {
     /// The header of the column.
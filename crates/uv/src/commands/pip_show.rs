@@ -0,0 +1,149 @@
+use std::fmt::Write;
+
+use anstream::println;
+use anyhow::Result;
+use itertools::Itertools;
+use owo_colors::OwoColorize;
+use tracing::debug;
+
+use distribution_types::{InstalledDist, Name};
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_fs::Normalized;
+use uv_installer::SitePackages;
+use uv_normalize::PackageName;
+
+use crate::commands::{detect_environment, ExitStatus};
+use crate::printer::Printer;
+
+/// Show information about one or more installed packages.
+pub(crate) fn pip_show(
+    packages: &[PackageName],
+    files: bool,
+    system: bool,
+    cache: &Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    // Detect the current Python interpreter.
+    let platform = Platform::current()?;
+    let venv = detect_environment(system, platform, cache)?;
+
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().normalized_display().cyan()
+    );
+
+    // Build the installed index.
+    let site_packages = SitePackages::from_executable(&venv)?;
+    let markers = venv.interpreter().markers();
+
+    // Determine the packages to show, in the order they were requested.
+    let mut distributions = vec![];
+    let mut missing = vec![];
+    for package in packages {
+        let installed = site_packages.get_packages(package);
+        if installed.is_empty() {
+            missing.push(package);
+        } else {
+            distributions.extend(installed);
+        }
+    }
+
+    if !missing.is_empty() {
+        writeln!(
+            printer,
+            "{}{} Package(s) not found for: {}",
+            "warning".yellow().bold(),
+            ":".bold(),
+            missing.iter().join(", ").bold()
+        )?;
+    }
+
+    if distributions.is_empty() {
+        return Ok(ExitStatus::Failure);
+    }
+
+    for (i, dist) in distributions.iter().enumerate() {
+        if i > 0 {
+            println!("---");
+        }
+
+        let metadata = dist.metadata()?;
+
+        // Determine the (unconditional) dependencies of the package.
+        let requires = metadata
+            .requires_dist
+            .iter()
+            .filter(|requirement| requirement.evaluate_markers(markers, &[]))
+            .map(|requirement| &requirement.name)
+            .unique()
+            .sorted_unstable()
+            .join(", ");
+
+        // Determine the installed packages that depend on the package.
+        let mut required_by = vec![];
+        for installed in site_packages.iter() {
+            let Ok(metadata) = installed.metadata() else {
+                continue;
+            };
+            if metadata.requires_dist.iter().any(|requirement| {
+                requirement.name == *dist.name() && requirement.evaluate_markers(markers, &[])
+            }) {
+                required_by.push(installed.name().clone());
+            }
+        }
+        let required_by = required_by
+            .into_iter()
+            .unique()
+            .sorted_unstable()
+            .join(", ");
+
+        // The location is the directory that contains the `.dist-info` directory.
+        let location = dist.path().parent().unwrap_or(dist.path());
+
+        println!("Name: {}", dist.name());
+        println!("Version: {}", dist.version());
+        println!("Location: {}", location.normalized_display());
+        if let Some(url) = dist.as_editable() {
+            if let Ok(path) = url.to_file_path() {
+                println!("Editable project location: {}", path.normalized_display());
+            }
+        }
+        println!("Requires: {requires}");
+        println!("Required-by: {required_by}");
+
+        // If requested, list the installed files, relative to the location.
+        if files {
+            println!("Files:");
+            match installed_files(dist)? {
+                Some(files) => {
+                    for file in files {
+                        println!("  {file}");
+                    }
+                }
+                None => {
+                    println!("Cannot locate RECORD");
+                }
+            }
+        }
+    }
+
+    Ok(ExitStatus::Success)
+}
+
+/// Read the files installed by a distribution from its `RECORD` file, relative to the
+/// `site-packages` directory.
+fn installed_files(dist: &InstalledDist) -> Result<Option<Vec<String>>> {
+    if matches!(
+        dist,
+        InstalledDist::EggInfo(_) | InstalledDist::LegacyEditable(_)
+    ) {
+        return Ok(None);
+    }
+    let Ok(mut record) = fs_err::File::open(dist.path().join("RECORD")) else {
+        return Ok(None);
+    };
+    let record = install_wheel_rs::read_record_file(&mut record)?;
+    Ok(Some(record.into_iter().map(|entry| entry.path).collect()))
+}
@@ -1,31 +1,47 @@
 use std::fmt::Write;
+use std::path::Path;
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use itertools::Itertools;
 use owo_colors::OwoColorize;
+use rustc_hash::{FxHashMap, FxHashSet};
 use tracing::debug;
+use url::Url;
 
 use distribution_types::{
-    IndexLocations, InstalledDist, InstalledMetadata, LocalDist, LocalEditable, Name,
+    CachedDist, Dist, DistributionMetadata, IndexLocations, InstalledDist, InstalledMetadata,
+    LocalDist, LocalEditable, Name,
 };
 use install_wheel_rs::linker::LinkMode;
+use pep440_rs::Operator;
+use pep508_rs::{Requirement, VersionOrUrl};
 use platform_host::Platform;
 use platform_tags::Tags;
 use pypi_types::Yanked;
 use requirements_txt::EditableRequirement;
 use uv_cache::{ArchiveTimestamp, Cache};
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, RegistryClient, RegistryClientBuilder, Timeouts,
+    TlsSettings,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Normalized;
 use uv_installer::{
     Downloader, NoBinary, Plan, Planner, Reinstall, ResolvedEditable, SitePackages,
 };
 use uv_interpreter::Virtualenv;
+use uv_normalize::PackageName;
 use uv_resolver::InMemoryIndex;
-use uv_traits::{ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{
+    BuildIsolation, Concurrency, ConfigSettings, InFlight, NoBuild, PackageConfigSettings,
+    SetupPyStrategy,
+};
 
+use crate::commands::install_report::InstallReport;
 use crate::commands::reporters::{DownloadReporter, FinderReporter, InstallReporter};
-use crate::commands::{elapsed, ChangeEvent, ChangeEventKind, ExitStatus};
+use crate::commands::{
+    check_externally_managed, elapsed, install_target, ChangeEvent, ChangeEventKind, ExitStatus,
+};
 use crate::printer::Printer;
 use crate::requirements::{RequirementsSource, RequirementsSpecification};
 
@@ -33,15 +49,31 @@ use crate::requirements::{RequirementsSource, RequirementsSpecification};
 #[allow(clippy::too_many_arguments)]
 pub(crate) async fn pip_sync(
     sources: &[RequirementsSource],
+    build_constraints: &[RequirementsSource],
     reinstall: &Reinstall,
     link_mode: LinkMode,
+    compile: bool,
     index_locations: IndexLocations,
     setup_py: SetupPyStrategy,
     connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    concurrency: Concurrency,
     config_settings: &ConfigSettings,
+    package_config_settings: &PackageConfigSettings,
+    no_build_isolation: bool,
     no_build: &NoBuild,
     no_binary: &NoBinary,
     strict: bool,
+    require_hashes: bool,
+    dry_run: bool,
+    check: bool,
+    prefix: Option<&Path>,
+    system: bool,
+    break_system_packages: bool,
+    report: Option<&Path>,
     cache: Cache,
     mut printer: Printer,
 ) -> Result<ExitStatus> {
@@ -54,13 +86,24 @@ pub(crate) async fn pip_sync(
         constraints: _constraints,
         overrides: _overrides,
         editables,
+        hashes,
         index_url,
         extra_index_urls,
         no_index,
         find_links,
         extras: _extras,
+        origins: _origins,
     } = RequirementsSpecification::from_simple_sources(sources)?;
 
+    // Read the build constraints.
+    let build_constraints = RequirementsSpecification::from_build_constraints(build_constraints)?;
+
+    // Enable hash-checking mode if requested, or if any requirement includes a hash (as in pip).
+    let require_hashes = require_hashes || !hashes.is_empty();
+    if require_hashes {
+        validate_hashes(&requirements, &editables, &hashes)?;
+    }
+
     let num_requirements = requirements.len() + editables.len();
     if num_requirements == 0 {
         writeln!(printer, "No requirements found")?;
@@ -73,13 +116,91 @@ pub(crate) async fn pip_sync(
 
     // Detect the current Python interpreter.
     let platform = Platform::current()?;
-    let venv = Virtualenv::from_env(platform, &cache)?;
+    let venv = install_target(prefix, system, platform, &cache)?;
     debug!(
         "Using Python {} environment at {}",
         venv.interpreter().python_version(),
         venv.python_executable().normalized_display().cyan()
     );
 
+    // If we're only auditing the environment, compare it against the requirements and exit.
+    if check {
+        return check_environment(&requirements, &editables, &venv, start, printer);
+    }
+
+    // If we're modifying the system interpreter's environment, respect PEP 668.
+    if system {
+        check_externally_managed(&venv, break_system_packages)?;
+    }
+
+    sync_requirements(
+        &requirements,
+        editables,
+        &hashes,
+        require_hashes,
+        &build_constraints,
+        &venv,
+        reinstall,
+        link_mode,
+        compile,
+        &index_locations,
+        setup_py,
+        connectivity,
+        tls,
+        retries,
+        proxy,
+        timeouts,
+        concurrency,
+        config_settings,
+        package_config_settings,
+        no_build_isolation,
+        no_build,
+        no_binary,
+        strict,
+        dry_run,
+        report,
+        &cache,
+        start,
+        printer,
+    )
+    .await
+}
+
+/// Install exactly the given (pinned) requirements into the environment, removing any packages
+/// that aren't required.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+pub(crate) async fn sync_requirements(
+    requirements: &[Requirement],
+    editables: Vec<EditableRequirement>,
+    hashes: &FxHashMap<PackageName, Vec<String>>,
+    require_hashes: bool,
+    build_constraints: &[Requirement],
+    venv: &Virtualenv,
+    reinstall: &Reinstall,
+    link_mode: LinkMode,
+    compile: bool,
+    index_locations: &IndexLocations,
+    setup_py: SetupPyStrategy,
+    connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    concurrency: Concurrency,
+    config_settings: &ConfigSettings,
+    package_config_settings: &PackageConfigSettings,
+    no_build_isolation: bool,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    strict: bool,
+    dry_run: bool,
+    report: Option<&Path>,
+    cache: &Cache,
+    start: std::time::Instant,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let num_requirements = requirements.len() + editables.len();
+
     let _lock = venv.lock()?;
 
     // Determine the current environment markers.
@@ -89,13 +210,17 @@ pub(crate) async fn pip_sync(
     let client = RegistryClientBuilder::new(cache.clone())
         .index_urls(index_locations.index_urls())
         .connectivity(connectivity)
-        .build();
+        .tls(tls)
+        .retries(retries)
+        .proxy(proxy)
+        .timeouts(timeouts)
+        .build()?;
 
     // Resolve the flat indexes from `--find-links`.
     let flat_index = {
-        let client = FlatIndexClient::new(&client, &cache);
+        let client = FlatIndexClient::new(&client, cache);
         let entries = client.fetch(index_locations.flat_index()).await?;
-        FlatIndex::from_entries(entries, tags)
+        FlatIndex::from_entries(entries, tags, no_build, no_binary)
     };
 
     // Create a shared in-memory index.
@@ -104,12 +229,19 @@ pub(crate) async fn pip_sync(
     // Track in-flight downloads, builds, etc., across resolutions.
     let in_flight = InFlight::default();
 
+    // Determine whether to enable build isolation.
+    let build_isolation = if no_build_isolation {
+        BuildIsolation::Shared(venv)
+    } else {
+        BuildIsolation::Isolated
+    };
+
     // Prep the build context.
     let build_dispatch = BuildDispatch::new(
         &client,
-        &cache,
+        cache,
         venv.interpreter(),
-        &index_locations,
+        index_locations,
         &flat_index,
         &index,
         &in_flight,
@@ -118,20 +250,24 @@ pub(crate) async fn pip_sync(
         config_settings,
         no_build,
         no_binary,
-    );
+        concurrency,
+    )
+    .with_build_constraints(build_constraints)
+    .with_package_config_settings(package_config_settings)
+    .with_build_isolation(build_isolation);
 
     // Determine the set of installed packages.
     let site_packages =
-        SitePackages::from_executable(&venv).context("Failed to list installed packages")?;
+        SitePackages::from_executable(venv).context("Failed to list installed packages")?;
 
     // Resolve any editables.
     let resolved_editables = resolve_editables(
         editables,
         &site_packages,
         reinstall,
-        &venv,
+        venv,
         tags,
-        &cache,
+        cache,
         &client,
         &build_dispatch,
         printer,
@@ -139,21 +275,28 @@ pub(crate) async fn pip_sync(
     .await?;
 
     // Partition into those that should be linked from the cache (`local`), those that need to be
-    // downloaded (`remote`), and those that should be removed (`extraneous`).
+    // downloaded (`remote`), and those that should be removed (`extraneous`). In hash-checking
+    // mode, cached wheels are only reused if they match one of the provided hashes.
+    let planner = Planner::with_requirements(requirements)
+        .with_editable_requirements(&resolved_editables.editables);
+    let planner = if require_hashes {
+        planner.with_hashes(hashes)
+    } else {
+        planner
+    };
     let Plan {
         local,
         remote,
         reinstalls,
         extraneous,
-    } = Planner::with_requirements(&requirements)
-        .with_editable_requirements(&resolved_editables.editables)
+    } = planner
         .build(
             site_packages,
             reinstall,
             no_binary,
-            &index_locations,
-            &cache,
-            &venv,
+            index_locations,
+            cache,
+            venv,
             tags,
         )
         .context("Failed to determine installation plan")?;
@@ -172,6 +315,11 @@ pub(crate) async fn pip_sync(
             .dimmed()
         )?;
 
+        // Nothing was installed, but the caller may still expect a report.
+        if let Some(report) = report {
+            InstallReport::default().write(report)?;
+        }
+
         return Ok(ExitStatus::Success);
     }
 
@@ -181,11 +329,35 @@ pub(crate) async fn pip_sync(
     } else {
         let start = std::time::Instant::now();
 
-        let wheel_finder =
-            uv_resolver::DistFinder::new(tags, &client, venv.interpreter(), &flat_index, no_binary)
-                .with_reporter(FinderReporter::from(printer).with_length(remote.len() as u64));
+        let wheel_finder = uv_resolver::DistFinder::new(
+            tags,
+            &client,
+            venv.interpreter(),
+            &flat_index,
+            no_binary,
+            no_build,
+        )
+        .with_reporter(FinderReporter::from(printer).with_length(remote.len() as u64));
+        let wheel_finder = if require_hashes {
+            wheel_finder.with_hashes(hashes)
+        } else {
+            wheel_finder
+        };
         let resolution = wheel_finder.resolve(&remote).await?;
 
+        // In hash-checking mode, only built distributions are supported, since the hash of a
+        // source distribution says nothing about the wheel that's built from it.
+        if require_hashes {
+            if let Some(dist) = resolution
+                .distributions()
+                .find(|dist| matches!(dist, Dist::Source(_)))
+            {
+                return Err(anyhow!(
+                    "In `--require-hashes` mode, source distributions are not supported: {dist}"
+                ));
+            }
+        }
+
         let s = if resolution.len() == 1 { "" } else { "s" };
         writeln!(
             printer,
@@ -201,13 +373,23 @@ pub(crate) async fn pip_sync(
         resolution.into_distributions().collect::<Vec<_>>()
     };
 
+    // If this is a dry run, report the changes that would be made, without modifying the
+    // environment.
+    if dry_run {
+        report_dry_run(&local, &remote, &reinstalls, &extraneous, printer)?;
+        return Ok(ExitStatus::Failure);
+    }
+
+    // Mark the requirements' cache entries as recently used.
+    cache.touch(requirements.iter().map(|requirement| &requirement.name));
+
     // Download, build, and unzip any missing distributions.
     let wheels = if remote.is_empty() {
         Vec::new()
     } else {
         let start = std::time::Instant::now();
 
-        let downloader = Downloader::new(&cache, tags, &client, &build_dispatch)
+        let downloader = Downloader::new(cache, tags, &client, &build_dispatch)
             .with_reporter(DownloadReporter::from(printer).with_length(remote.len() as u64));
 
         let wheels = downloader
@@ -230,6 +412,8 @@ pub(crate) async fn pip_sync(
         wheels
     };
 
+    let install_report = InstallReport::from_plan(&local, &remote, &wheels, cache);
+
     // Remove any unnecessary packages.
     if !extraneous.is_empty() || !reinstalls.is_empty() {
         let start = std::time::Instant::now();
@@ -267,8 +451,9 @@ pub(crate) async fn pip_sync(
     let wheels = wheels.into_iter().chain(local).collect::<Vec<_>>();
     if !wheels.is_empty() {
         let start = std::time::Instant::now();
-        uv_installer::Installer::new(&venv)
+        uv_installer::Installer::new(venv)
             .with_link_mode(link_mode)
+            .with_compile(compile)
             .with_reporter(InstallReporter::from(printer).with_length(wheels.len() as u64))
             .install(&wheels)?;
 
@@ -329,7 +514,7 @@ pub(crate) async fn pip_sync(
 
     // Validate that the environment is consistent.
     if strict {
-        let site_packages = SitePackages::from_executable(&venv)?;
+        let site_packages = SitePackages::from_executable(venv)?;
         for diagnostic in site_packages.diagnostics()? {
             writeln!(
                 printer,
@@ -368,9 +553,300 @@ pub(crate) async fn pip_sync(
         }
     }
 
+    // Write the install report, if requested.
+    if let Some(report) = report {
+        install_report.write(report)?;
+    }
+
     Ok(ExitStatus::Success)
 }
 
+/// Validate that every requirement is eligible for hash-checking mode, i.e., pinned to an exact
+/// version or to a URL, and accompanied by at least one hash.
+fn validate_hashes(
+    requirements: &[Requirement],
+    editables: &[EditableRequirement],
+    hashes: &FxHashMap<PackageName, Vec<String>>,
+) -> Result<()> {
+    if let Some(editable) = editables.first() {
+        return Err(anyhow!(
+            "In `--require-hashes` mode, editable requirements are not supported: {editable}"
+        ));
+    }
+
+    for requirement in requirements {
+        match &requirement.version_or_url {
+            Some(VersionOrUrl::VersionSpecifier(specifiers))
+                if specifiers.len() == 1
+                    && matches!(
+                        specifiers[0].operator(),
+                        Operator::Equal | Operator::ExactEqual
+                    ) => {}
+            Some(VersionOrUrl::Url(url))
+                if !url.scheme().contains('+')
+                    && !url.to_file_path().is_ok_and(|path| path.is_dir()) => {}
+            _ => {
+                return Err(anyhow!(
+                    "In `--require-hashes` mode, all requirements must be pinned to an exact version (with `==`) or to a distribution URL, but found: {requirement}"
+                ));
+            }
+        }
+
+        if hashes.get(&requirement.name).map_or(true, Vec::is_empty) {
+            return Err(anyhow!(
+                "In `--require-hashes` mode, all requirements must have a hash, but none were provided for: {requirement}"
+            ));
+        }
+    }
+
+    Ok(())
+}
+
+/// Report the changes that would be made to the environment, for a dry run.
+/// A difference between the installed environment and the requirements, as reported by
+/// `--check`.
+#[derive(Debug, Copy, Clone, Eq, PartialEq, Ord, PartialOrd)]
+enum Discrepancy {
+    /// The package is installed, but isn't required (or doesn't match the requirement).
+    Extraneous,
+    /// The package is required, but isn't installed (or doesn't match the requirement).
+    Missing,
+    /// The package is installed, but one of its files doesn't match its `RECORD` entry.
+    Modified,
+}
+
+/// Compare the installed packages against the requirements, without modifying the environment.
+///
+/// Reports any missing, extraneous, or mismatched packages, along with any installed files that
+/// don't match the hashes in their `RECORD` file.
+fn check_environment(
+    requirements: &[Requirement],
+    editables: &[EditableRequirement],
+    venv: &Virtualenv,
+    start: std::time::Instant,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let site_packages =
+        SitePackages::from_executable(venv).context("Failed to list installed packages")?;
+
+    // The packages that are accounted for by the requirements.
+    let mut required = FxHashSet::default();
+    // The installed distributions that satisfy the requirements.
+    let mut satisfied = Vec::new();
+    // The differences, as (name, kind, description).
+    let mut differences = Vec::new();
+
+    for requirement in requirements {
+        if !requirement.evaluate_markers(venv.interpreter().markers(), &[]) {
+            continue;
+        }
+        required.insert(requirement.name.clone());
+
+        let installed = site_packages.get_packages(&requirement.name);
+        if let [dist] = installed.as_slice() {
+            let matches = match requirement.version_or_url.as_ref() {
+                None => true,
+                Some(VersionOrUrl::VersionSpecifier(specifiers)) => {
+                    specifiers.contains(dist.version())
+                }
+                Some(VersionOrUrl::Url(url)) => {
+                    matches!(dist, InstalledDist::Url(dist) if &dist.url == url.raw())
+                }
+            };
+            if matches {
+                satisfied.push(*dist);
+                continue;
+            }
+        }
+
+        for dist in installed {
+            differences.push((
+                dist.name().to_string(),
+                Discrepancy::Extraneous,
+                dist.installed_version().to_string(),
+            ));
+        }
+        differences.push((
+            requirement.name.to_string(),
+            Discrepancy::Missing,
+            match requirement.version_or_url.as_ref() {
+                None => String::new(),
+                Some(VersionOrUrl::VersionSpecifier(specifiers)) => specifiers.to_string(),
+                Some(VersionOrUrl::Url(url)) => format!(" @ {url}"),
+            },
+        ));
+    }
+
+    for editable in editables {
+        let installed = site_packages.get_editables(editable.raw());
+        required.extend(installed.iter().map(|dist| dist.name().clone()));
+        if let [dist] = installed.as_slice() {
+            satisfied.push(*dist);
+            continue;
+        }
+
+        for dist in installed {
+            differences.push((
+                dist.name().to_string(),
+                Discrepancy::Extraneous,
+                dist.installed_version().to_string(),
+            ));
+        }
+        differences.push((
+            format!("-e {editable}"),
+            Discrepancy::Missing,
+            String::new(),
+        ));
+    }
+
+    // Any other installed packages are extraneous.
+    for dist in site_packages.iter() {
+        if !required.contains(dist.name()) {
+            differences.push((
+                dist.name().to_string(),
+                Discrepancy::Extraneous,
+                dist.installed_version().to_string(),
+            ));
+        }
+    }
+
+    // Verify the installed files of any packages that satisfy the requirements.
+    for dist in satisfied {
+        if !matches!(dist, InstalledDist::Registry(_) | InstalledDist::Url(_)) {
+            continue;
+        }
+        let modified = install_wheel_rs::verify_installed_dist(dist.path())
+            .with_context(|| format!("Failed to verify installed files for: {dist}"))?;
+        for path in modified {
+            differences.push((
+                dist.name().to_string(),
+                Discrepancy::Modified,
+                format!("{} (modified: {path})", dist.installed_version()),
+            ));
+        }
+    }
+
+    if differences.is_empty() {
+        let num_requirements = requirements.len() + editables.len();
+        let s = if num_requirements == 1 { "" } else { "s" };
+        writeln!(
+            printer,
+            "{}",
+            format!(
+                "Audited {} in {}",
+                format!("{num_requirements} package{s}").bold(),
+                elapsed(start.elapsed())
+            )
+            .dimmed()
+        )?;
+        return Ok(ExitStatus::Success);
+    }
+
+    let s = if differences.len() == 1 { "" } else { "s" };
+    writeln!(
+        printer,
+        "{}",
+        format!(
+            "Found {} between the environment and the requirements",
+            format!("{} difference{s}", differences.len()).bold()
+        )
+        .dimmed()
+    )?;
+
+    for (name, kind, description) in differences.into_iter().sorted_unstable_by(|a, b| {
+        a.0.cmp(&b.0)
+            .then_with(|| a.1.cmp(&b.1))
+            .then_with(|| a.2.cmp(&b.2))
+    }) {
+        let marker = match kind {
+            Discrepancy::Extraneous => "-".red().to_string(),
+            Discrepancy::Missing => "+".green().to_string(),
+            Discrepancy::Modified => "~".yellow().to_string(),
+        };
+        writeln!(printer, " {marker} {}{}", name.bold(), description.dimmed())?;
+    }
+
+    Ok(ExitStatus::Failure)
+}
+
+fn report_dry_run(
+    local: &[CachedDist],
+    remote: &[Dist],
+    reinstalls: &[InstalledDist],
+    extraneous: &[InstalledDist],
+    mut printer: Printer,
+) -> Result<()> {
+    let removed = reinstalls.len() + extraneous.len();
+    if removed > 0 {
+        let s = if removed == 1 { "" } else { "s" };
+        writeln!(
+            printer,
+            "{}",
+            format!("Would uninstall {}", format!("{removed} package{s}").bold()).dimmed()
+        )?;
+    }
+
+    let added = local.len() + remote.len();
+    if added > 0 {
+        let s = if added == 1 { "" } else { "s" };
+        writeln!(
+            printer,
+            "{}",
+            format!("Would install {}", format!("{added} package{s}").bold()).dimmed()
+        )?;
+    }
+
+    for (name, kind, version) in extraneous
+        .iter()
+        .chain(reinstalls)
+        .map(|dist| {
+            (
+                dist.name(),
+                ChangeEventKind::Removed,
+                dist.installed_version().to_string(),
+            )
+        })
+        .chain(local.iter().map(|dist| {
+            (
+                dist.name(),
+                ChangeEventKind::Added,
+                dist.installed_version().to_string(),
+            )
+        }))
+        .chain(remote.iter().map(|dist| {
+            (
+                dist.name(),
+                ChangeEventKind::Added,
+                dist.version_or_url().to_string(),
+            )
+        }))
+        .sorted_unstable_by(|a, b| a.0.cmp(b.0).then_with(|| a.1.cmp(&b.1)))
+    {
+        match kind {
+            ChangeEventKind::Added => {
+                writeln!(
+                    printer,
+                    " {} {}{}",
+                    "+".green(),
+                    name.as_ref().bold(),
+                    version.dimmed()
+                )?;
+            }
+            ChangeEventKind::Removed => {
+                writeln!(
+                    printer,
+                    " {} {}{}",
+                    "-".red(),
+                    name.as_ref().bold(),
+                    version.dimmed()
+                )?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
 #[derive(Debug)]
 struct ResolvedEditables {
     /// The set of resolved editables, including both those that were already installed and those
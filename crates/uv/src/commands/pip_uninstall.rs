@@ -8,15 +8,17 @@ use distribution_types::{InstalledMetadata, Name};
 use platform_host::Platform;
 use uv_cache::Cache;
 use uv_fs::Normalized;
-use uv_interpreter::Virtualenv;
 
-use crate::commands::{elapsed, ExitStatus};
+use crate::commands::{check_externally_managed, detect_environment, elapsed, ExitStatus};
 use crate::printer::Printer;
 use crate::requirements::{RequirementsSource, RequirementsSpecification};
 
 /// Uninstall packages from the current environment.
 pub(crate) async fn pip_uninstall(
     sources: &[RequirementsSource],
+    dry_run: bool,
+    system: bool,
+    break_system_packages: bool,
     cache: Cache,
     mut printer: Printer,
 ) -> Result<ExitStatus> {
@@ -29,22 +31,29 @@ pub(crate) async fn pip_uninstall(
         constraints: _constraints,
         overrides: _overrides,
         editables,
+        hashes: _hashes,
         index_url: _index_url,
         extra_index_urls: _extra_index_urls,
         no_index: _no_index,
         find_links: _find_links,
         extras: _extras,
+        origins: _origins,
     } = RequirementsSpecification::from_simple_sources(sources)?;
 
     // Detect the current Python interpreter.
     let platform = Platform::current()?;
-    let venv = Virtualenv::from_env(platform, &cache)?;
+    let venv = detect_environment(system, platform, &cache)?;
     debug!(
         "Using Python {} environment at {}",
         venv.interpreter().python_version(),
         venv.python_executable().normalized_display().cyan(),
     );
 
+    // If we're modifying the system interpreter's environment, respect PEP 668.
+    if system {
+        check_externally_managed(&venv, break_system_packages)?;
+    }
+
     let _lock = venv.lock()?;
 
     // Index the current `site-packages` directory.
@@ -124,6 +133,36 @@ pub(crate) async fn pip_uninstall(
         return Ok(ExitStatus::Success);
     }
 
+    // If this is a dry run, report the packages that would be removed, without removing them.
+    if dry_run {
+        writeln!(
+            printer,
+            "{}",
+            format!(
+                "Would uninstall {}",
+                format!(
+                    "{} package{}",
+                    distributions.len(),
+                    if distributions.len() == 1 { "" } else { "s" }
+                )
+                .bold(),
+            )
+            .dimmed()
+        )?;
+
+        for distribution in distributions {
+            writeln!(
+                printer,
+                " {} {}{}",
+                "-".red(),
+                distribution.name().as_ref().bold(),
+                distribution.installed_version().to_string().dimmed()
+            )?;
+        }
+
+        return Ok(ExitStatus::Success);
+    }
+
     // Uninstall each package.
     for distribution in &distributions {
         let summary = uv_installer::uninstall(distribution).await?;
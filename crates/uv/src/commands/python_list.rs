@@ -0,0 +1,45 @@
+use anstream::println;
+use anyhow::Result;
+use owo_colors::OwoColorize;
+
+use platform_host::Platform;
+use uv_cache::Cache;
+use uv_fs::Normalized;
+use uv_interpreter::{find_all_pythons, find_default_python};
+
+use crate::commands::ExitStatus;
+
+/// List the Python interpreters that uv can discover.
+pub(crate) fn python_list(cache: &Cache) -> Result<ExitStatus> {
+    let platform = Platform::current()?;
+    let interpreters = find_all_pythons(&platform, cache)?;
+
+    // Determine the interpreter that would be used by default (e.g., by `uv venv`).
+    let default = find_default_python(&platform, cache)
+        .ok()
+        .map(|interpreter| interpreter.sys_executable().to_path_buf());
+
+    for (source, interpreter) in &interpreters {
+        let key = format!(
+            "{}-{}-{}",
+            interpreter.implementation_name(),
+            interpreter.markers().python_full_version,
+            interpreter.platform().arch()
+        );
+        let is_default = default
+            .as_deref()
+            .is_some_and(|default| default == interpreter.sys_executable());
+        println!(
+            "{key:<28} {} {}",
+            interpreter.sys_executable().normalized_display().cyan(),
+            if is_default {
+                format!("({source}, default)")
+            } else {
+                format!("({source})")
+            }
+            .dimmed()
+        );
+    }
+
+    Ok(ExitStatus::Success)
+}
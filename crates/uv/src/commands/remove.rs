@@ -0,0 +1,100 @@
+use std::fmt::Write;
+
+use anyhow::{anyhow, Context, Result};
+use owo_colors::OwoColorize;
+use url::Url;
+
+use distribution_types::IndexLocations;
+use uv_cache::Cache;
+use uv_client::{Connectivity, Timeouts, TlsSettings};
+use uv_fs::Normalized;
+use uv_installer::NoBinary;
+use uv_interpreter::PythonVersion;
+use uv_normalize::PackageName;
+use uv_resolver::{ExcludeNewer, PreReleaseMode, ResolutionMode};
+use uv_traits::{Concurrency, ConfigSettings, NoBuild, SetupPyStrategy};
+
+use crate::commands::{lock, ExitStatus, Upgrade};
+use crate::printer::Printer;
+use crate::pyproject::{DependencyType, PyProjectTomlMut};
+use crate::requirements::ExtrasSpecification;
+
+/// Remove one or more dependencies from the project's `pyproject.toml`, and update the lockfile.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn remove(
+    packages: Vec<PackageName>,
+    dependency_type: DependencyType,
+    prerelease_mode: PreReleaseMode,
+    index_locations: IndexLocations,
+    connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    concurrency: Concurrency,
+    python_version: Option<PythonVersion>,
+    exclude_newer: ExcludeNewer,
+    cache: Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let root = std::env::current_dir()?;
+    let path = root.join("pyproject.toml");
+    if !path.is_file() {
+        return Err(anyhow!(
+            "No `pyproject.toml` found in: {}",
+            root.normalized_display()
+        ));
+    }
+    let original = uv_fs::read_to_string(&path)?;
+    let mut pyproject = PyProjectTomlMut::from_toml(&original)
+        .with_context(|| format!("Failed to parse `{}`", path.normalized_display()))?;
+
+    for package in &packages {
+        if pyproject
+            .remove_dependency(package, &dependency_type)?
+            .is_empty()
+        {
+            return Err(anyhow!(
+                "`{package}` is not a dependency in {dependency_type}"
+            ));
+        }
+    }
+    fs_err::write(&path, pyproject.to_string())?;
+
+    // Update the lockfile, retaining the locked versions of the remaining packages. If the
+    // lockfile can't be updated, restore the original `pyproject.toml`.
+    let status = lock(
+        ExtrasSpecification::None,
+        ResolutionMode::default(),
+        prerelease_mode,
+        Upgrade::None,
+        index_locations,
+        SetupPyStrategy::default(),
+        ConfigSettings::default(),
+        connectivity,
+        tls,
+        retries,
+        proxy,
+        timeouts,
+        concurrency,
+        &NoBuild::None,
+        &NoBinary::None,
+        python_version,
+        exclude_newer,
+        cache,
+        printer,
+    )
+    .await;
+    if !matches!(status, Ok(ExitStatus::Success)) {
+        fs_err::write(&path, original)?;
+        return status;
+    }
+
+    writeln!(
+        printer,
+        "{}",
+        format!("Updated {}", "pyproject.toml".bold()).dimmed()
+    )?;
+
+    Ok(ExitStatus::Success)
+}
@@ -160,6 +160,24 @@ impl uv_installer::DownloadReporter for DownloadReporter {
             rev.dimmed()
         ));
     }
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize {
+        on_any_download_start(
+            &self.multi_progress,
+            &self.progress,
+            self.printer,
+            &self.bars,
+            name,
+            size,
+        )
+    }
+
+    fn on_download_progress(&self, id: usize, bytes: u64) {
+        self.bars.lock().unwrap()[id].inc(bytes);
+    }
+
+    fn on_download_complete(&self, _name: &PackageName, id: usize) {
+        self.bars.lock().unwrap()[id].finish_and_clear();
+    }
 }
 
 #[derive(Debug)]
@@ -239,6 +257,10 @@ impl uv_resolver::ResolverReporter for ResolverReporter {
         }
     }
 
+    fn on_backtrack(&self, name: &PackageName) {
+        self.progress.set_message(format!("{name} (backtracking)"));
+    }
+
     fn on_complete(&self) {
         self.progress.finish_and_clear();
     }
@@ -301,6 +323,60 @@ impl uv_resolver::ResolverReporter for ResolverReporter {
             rev.dimmed()
         ));
     }
+    fn on_download_start(&self, name: &PackageName, size: Option<u64>) -> usize {
+        on_any_download_start(
+            &self.multi_progress,
+            &self.progress,
+            self.printer,
+            &self.bars,
+            name,
+            size,
+        )
+    }
+
+    fn on_download_progress(&self, id: usize, bytes: u64) {
+        self.bars.lock().unwrap()[id].inc(bytes);
+    }
+
+    fn on_download_complete(&self, _name: &PackageName, id: usize) {
+        self.bars.lock().unwrap()[id].finish_and_clear();
+    }
+}
+
+/// Add a progress bar for a single download, above the given aggregate progress bar.
+///
+/// If the size of the download is known, the bar tracks the bytes received against it;
+/// otherwise, it only displays the bytes received so far.
+fn on_any_download_start(
+    multi_progress: &MultiProgress,
+    aggregate: &ProgressBar,
+    printer: Printer,
+    bars: &Mutex<Vec<ProgressBar>>,
+    name: &PackageName,
+    size: Option<u64>,
+) -> usize {
+    let progress = multi_progress.insert_before(
+        aggregate,
+        ProgressBar::with_draw_target(size, printer.target()),
+    );
+
+    if size.is_some() {
+        progress.set_style(
+            ProgressStyle::with_template(
+                "{msg:10.dim} {bar:30.green/dim} {decimal_bytes:>7}/{decimal_total_bytes:7}",
+            )
+            .unwrap()
+            .progress_chars("--"),
+        );
+    } else {
+        progress
+            .set_style(ProgressStyle::with_template("{msg:10.dim} {decimal_bytes:>7}").unwrap());
+    }
+    progress.set_message(name.to_string());
+
+    let mut bars = bars.lock().unwrap();
+    bars.push(progress);
+    bars.len() - 1
 }
 
 /// Like [`std::fmt::Display`], but with colors.
@@ -0,0 +1,405 @@
+use std::env;
+use std::ffi::OsString;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+use owo_colors::OwoColorize;
+use tempfile::tempdir_in;
+use tokio::process::Command;
+use tracing::debug;
+use url::Url;
+
+use distribution_types::IndexLocations;
+use install_wheel_rs::linker::LinkMode;
+use pep508_rs::Requirement;
+use platform_host::Platform;
+use uv_cache::{Cache, CacheBucket};
+use uv_client::{Connectivity, Timeouts, TlsSettings};
+use uv_fs::Normalized;
+use uv_installer::{NoBinary, Reinstall};
+use uv_interpreter::{find_default_python, find_requested_python, Virtualenv};
+use uv_resolver::{DependencyMode, ExcludeNewer, PreReleaseMode, ResolutionMode, YankedMode};
+use uv_traits::{Concurrency, ConfigSettings, NoBuild, PackageConfigSettings, SetupPyStrategy};
+
+use crate::commands::pip_install::install_requirements;
+use crate::commands::{lock, sync, ExitStatus, Upgrade};
+use crate::printer::Printer;
+use crate::requirements::ExtrasSpecification;
+use crate::script::ScriptMetadata;
+use crate::workspace::environment_root;
+
+/// Run a command in the project's virtual environment (`.venv`), after locking the project (if
+/// necessary) and syncing the environment with the lockfile.
+///
+/// If the command is a Python script with inline metadata (PEP 723), the script is instead run in
+/// a cached environment that satisfies its metadata, without requiring a project.
+#[allow(clippy::too_many_arguments)]
+pub(crate) async fn run(
+    command: Vec<OsString>,
+    with: Vec<Requirement>,
+    python: Option<&str>,
+    index_locations: IndexLocations,
+    connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    concurrency: Concurrency,
+    exclude_newer: ExcludeNewer,
+    cache: Cache,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    let Some((program, args)) = command.split_first() else {
+        return Err(anyhow!("No command provided"));
+    };
+
+    // If the command is a Python script with inline metadata, run it in an environment that
+    // satisfies its metadata, rather than in the project environment.
+    let script = Path::new(program);
+    if script
+        .extension()
+        .is_some_and(|extension| extension == "py")
+        && script.is_file()
+    {
+        if let Some(metadata) = ScriptMetadata::read(script)? {
+            return run_script(
+                script,
+                args,
+                metadata,
+                with,
+                python,
+                index_locations,
+                connectivity,
+                tls,
+                retries,
+                proxy,
+                timeouts,
+                concurrency,
+                exclude_newer,
+                cache,
+                start,
+                printer,
+            )
+            .await;
+        }
+    }
+
+    let project_root = env::current_dir()?;
+    if !project_root.join("pyproject.toml").is_file() {
+        return Err(anyhow!(
+            "No `pyproject.toml` found in: {}",
+            project_root.normalized_display()
+        ));
+    }
+    let root = environment_root(&project_root)?;
+
+    // Lock the project, if it hasn't been locked yet.
+    if !root.join("uv.lock").is_file() {
+        let status = lock(
+            ExtrasSpecification::None,
+            ResolutionMode::default(),
+            PreReleaseMode::default(),
+            Upgrade::None,
+            index_locations.clone(),
+            SetupPyStrategy::default(),
+            ConfigSettings::default(),
+            connectivity,
+            tls.clone(),
+            retries,
+            proxy.clone(),
+            timeouts,
+            concurrency,
+            &NoBuild::None,
+            &NoBinary::None,
+            None,
+            exclude_newer.clone(),
+            cache.clone(),
+            printer,
+        )
+        .await?;
+        if !matches!(status, ExitStatus::Success) {
+            return Ok(status);
+        }
+    }
+
+    // Bring the project environment in sync with the lockfile (including the development
+    // dependencies), creating it if necessary.
+    let status = sync(
+        ExtrasSpecification::None,
+        &["dev".to_string()],
+        python,
+        &Reinstall::None,
+        LinkMode::default(),
+        false,
+        index_locations.flat_index().cloned().collect(),
+        SetupPyStrategy::default(),
+        connectivity,
+        tls.clone(),
+        retries,
+        proxy.clone(),
+        timeouts,
+        concurrency,
+        &ConfigSettings::default(),
+        false,
+        &NoBuild::None,
+        &NoBinary::None,
+        false,
+        false,
+        cache.clone(),
+        printer,
+    )
+    .await?;
+    if !matches!(status, ExitStatus::Success) {
+        return Ok(status);
+    }
+
+    let venv = Virtualenv::from_root(&root.join(".venv"), Platform::current()?, &cache)?;
+
+    // Layer any additional requirements on top of the project environment, in a temporary
+    // environment whose site-packages extends that of the project. The temporary environment is
+    // removed once the command exits.
+    let ephemeral = if with.is_empty() {
+        None
+    } else {
+        let temp_dir = tempdir_in(cache.root())?;
+        let extra_cfg = vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())];
+        let ephemeral = gourgeist::create_venv(
+            temp_dir.path(),
+            venv.interpreter().clone(),
+            gourgeist::Prompt::None,
+            false,
+            gourgeist::OnExisting::default(),
+            gourgeist::InterpreterLink::default(),
+            extra_cfg,
+        )?;
+        fs_err::write(
+            ephemeral.site_packages().join("_uv_project.pth"),
+            format!("{}\n", venv.site_packages().display()),
+        )?;
+
+        let status = install_requirements(
+            with,
+            Vec::new(),
+            Vec::new(),
+            None,
+            Vec::new(),
+            &[],
+            &ephemeral,
+            ResolutionMode::default(),
+            PreReleaseMode::default(),
+            YankedMode::default(),
+            DependencyMode::default(),
+            Upgrade::None,
+            &index_locations,
+            &Reinstall::None,
+            LinkMode::default(),
+            false,
+            SetupPyStrategy::default(),
+            connectivity,
+            tls,
+            retries,
+            proxy,
+            timeouts,
+            concurrency,
+            &ConfigSettings::default(),
+            &PackageConfigSettings::default(),
+            false,
+            &NoBuild::None,
+            &NoBinary::None,
+            false,
+            exclude_newer,
+            None,
+            &cache,
+            start,
+            printer,
+        )
+        .await?;
+        if !matches!(status, ExitStatus::Success) {
+            return Ok(status);
+        }
+
+        Some((temp_dir, ephemeral))
+    };
+
+    match &ephemeral {
+        Some((_, ephemeral)) => run_command(program, args, &[ephemeral, &venv]).await,
+        None => run_command(program, args, &[&venv]).await,
+    }
+}
+
+/// Run a Python script in an environment that satisfies its inline metadata (PEP 723).
+///
+/// The environment is cached, keyed by the interpreter and the script's requirements, such that
+/// it's reused across invocations.
+#[allow(clippy::too_many_arguments)]
+async fn run_script(
+    script: &Path,
+    args: &[OsString],
+    metadata: ScriptMetadata,
+    with: Vec<Requirement>,
+    python: Option<&str>,
+    index_locations: IndexLocations,
+    connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    concurrency: Concurrency,
+    exclude_newer: ExcludeNewer,
+    cache: Cache,
+    start: std::time::Instant,
+    printer: Printer,
+) -> Result<ExitStatus> {
+    let platform = Platform::current()?;
+
+    // Find an interpreter that satisfies the script's `requires-python`.
+    let interpreter = if let Some(python) = python {
+        find_requested_python(python, &platform, &cache)?
+            .ok_or_else(|| uv_interpreter::Error::NoSuchPython(python.to_string()))?
+    } else {
+        find_default_python(&platform, &cache)?
+    };
+    if let Some(requires_python) = metadata.requires_python.as_ref() {
+        if !requires_python.contains(interpreter.python_version()) {
+            return Err(anyhow!(
+                "The script requires Python {requires_python}, but Python {} was found at {} (use `--python` to select a compatible interpreter)",
+                interpreter.python_version(),
+                interpreter.sys_executable().normalized_display()
+            ));
+        }
+    }
+
+    let requirements = metadata
+        .dependencies
+        .into_iter()
+        .chain(with)
+        .collect::<Vec<_>>();
+
+    // Reuse the cached environment for this interpreter and set of requirements, if any.
+    let key = {
+        let mut requirements = requirements
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>();
+        requirements.sort_unstable();
+        cache_key::digest(&(
+            interpreter.sys_executable(),
+            interpreter.python_version().to_string(),
+            requirements,
+        ))
+    };
+    let root = cache.bucket(CacheBucket::Environments).join(key);
+    let venv = if root.join("pyvenv.cfg").is_file() {
+        debug!(
+            "Using cached script environment at {}",
+            root.normalized_display().cyan()
+        );
+        Virtualenv::from_root(&root, platform, &cache)?
+    } else {
+        debug!(
+            "Creating script environment at {}",
+            root.normalized_display().cyan()
+        );
+        let extra_cfg = vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())];
+        gourgeist::create_venv(
+            &root,
+            interpreter,
+            gourgeist::Prompt::None,
+            false,
+            gourgeist::OnExisting::Clear,
+            gourgeist::InterpreterLink::default(),
+            extra_cfg,
+        )?
+    };
+
+    // Install the script's requirements. If the environment is cached, they're already
+    // satisfied, unless it was modified in the meantime.
+    if !requirements.is_empty() {
+        let status = install_requirements(
+            requirements,
+            Vec::new(),
+            Vec::new(),
+            None,
+            Vec::new(),
+            &[],
+            &venv,
+            ResolutionMode::default(),
+            PreReleaseMode::default(),
+            YankedMode::default(),
+            DependencyMode::default(),
+            Upgrade::None,
+            &index_locations,
+            &Reinstall::None,
+            LinkMode::default(),
+            false,
+            SetupPyStrategy::default(),
+            connectivity,
+            tls,
+            retries,
+            proxy,
+            timeouts,
+            concurrency,
+            &ConfigSettings::default(),
+            &PackageConfigSettings::default(),
+            false,
+            &NoBuild::None,
+            &NoBinary::None,
+            false,
+            exclude_newer,
+            None,
+            &cache,
+            start,
+            printer,
+        )
+        .await?;
+        if !matches!(status, ExitStatus::Success) {
+            return Ok(status);
+        }
+    }
+
+    // Run the script with the environment's interpreter.
+    let program = venv.python_executable().into_os_string();
+    let args = std::iter::once(script.as_os_str().to_os_string())
+        .chain(args.iter().cloned())
+        .collect::<Vec<_>>();
+    run_command(&program, &args, &[&venv]).await
+}
+
+/// Run a command with the given environments' executables at the front of the `PATH`, in order,
+/// and the first environment as the active `VIRTUAL_ENV`.
+async fn run_command(
+    program: &OsString,
+    args: &[OsString],
+    environments: &[&Virtualenv],
+) -> Result<ExitStatus> {
+    let path = env::join_paths(
+        environments
+            .iter()
+            .map(|venv| venv.bin_dir())
+            .chain(env::split_paths(&env::var_os("PATH").unwrap_or_default())),
+    )?;
+
+    let mut command = Command::new(program);
+    command.args(args).env("PATH", path);
+    if let Some(active) = environments.first() {
+        debug!(
+            "Running `{}` in {}",
+            program.to_string_lossy(),
+            active.root().normalized_display().cyan()
+        );
+        command.env("VIRTUAL_ENV", active.root());
+    }
+    let status = command
+        .status()
+        .await
+        .with_context(|| format!("Failed to spawn: `{}`", program.to_string_lossy()))?;
+
+    // Exit with the command's exit code; if it was terminated by a signal, report a failure.
+    Ok(match status.code() {
+        Some(0) => ExitStatus::Success,
+        Some(code) => ExitStatus::External(u8::try_from(code).unwrap_or(1)),
+        None => ExitStatus::Failure,
+    })
+}
@@ -0,0 +1,326 @@
+use std::fmt::Write;
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use owo_colors::OwoColorize;
+use rustc_hash::FxHashMap;
+use tracing::debug;
+use url::Url;
+
+use distribution_types::{FlatIndexLocation, IndexLocations, IndexUrl};
+use install_wheel_rs::linker::LinkMode;
+use pep440_rs::VersionSpecifiers;
+use pep508_rs::Requirement;
+use platform_host::Platform;
+use requirements_txt::EditableRequirement;
+use uv_cache::Cache;
+use uv_client::{Connectivity, Timeouts, TlsSettings};
+use uv_fs::Normalized;
+use uv_installer::{NoBinary, Reinstall};
+use uv_interpreter::{find_default_python, find_requested_python, Virtualenv};
+use uv_normalize::PackageName;
+use uv_resolver::{Lock, LockedDistribution, LockedSource};
+use uv_traits::{Concurrency, ConfigSettings, NoBuild, PackageConfigSettings, SetupPyStrategy};
+
+use crate::commands::export::reachable_distributions;
+use crate::commands::pip_sync::sync_requirements;
+use crate::commands::ExitStatus;
+use crate::printer::Printer;
+use crate::requirements::ExtrasSpecification;
+use crate::workspace::Workspace;
+
+/// Install the project's locked dependencies (`uv.lock`) into the project's virtual environment
+/// (`.venv`), removing any packages that aren't in the lockfile.
+///
+/// Only the distributions that are reachable from the project's requirements (along with the
+/// requested extras and dependency groups), and that apply to the environment, are installed. The
+/// distributions are installed from the registries recorded in the lockfile.
+#[allow(clippy::too_many_arguments, clippy::fn_params_excessive_bools)]
+pub(crate) async fn sync(
+    extras: ExtrasSpecification<'_>,
+    groups: &[String],
+    python: Option<&str>,
+    reinstall: &Reinstall,
+    link_mode: LinkMode,
+    compile: bool,
+    find_links: Vec<FlatIndexLocation>,
+    setup_py: SetupPyStrategy,
+    connectivity: Connectivity,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    concurrency: Concurrency,
+    config_settings: &ConfigSettings,
+    no_build_isolation: bool,
+    no_build: &NoBuild,
+    no_binary: &NoBinary,
+    require_hashes: bool,
+    dry_run: bool,
+    cache: Cache,
+    mut printer: Printer,
+) -> Result<ExitStatus> {
+    let start = std::time::Instant::now();
+
+    // Read the lockfile from the current directory, or from the workspace root if the project is
+    // part of a workspace.
+    let project_root = std::env::current_dir()?;
+    let pyproject = project_root.join("pyproject.toml");
+    let workspace = Workspace::discover(&project_root)?;
+    let root = workspace
+        .as_ref()
+        .map_or(project_root, |workspace| workspace.root().to_path_buf());
+    let lockfile = root.join("uv.lock");
+    if !lockfile.is_file() {
+        return Err(anyhow!(
+            "No `uv.lock` found in: {} (run `uv lock` to create one)",
+            root.normalized_display()
+        ));
+    }
+    let contents = fs_err::read_to_string(&lockfile)?;
+    let lock = Lock::from_toml(&contents)
+        .with_context(|| format!("Failed to parse: {}", lockfile.normalized_display()))?;
+    if !pyproject.is_file() {
+        return Err(anyhow!(
+            "No `pyproject.toml` found in: {}",
+            pyproject
+                .parent()
+                .unwrap_or(&pyproject)
+                .normalized_display()
+        ));
+    }
+
+    // Find (or create) the project's virtual environment.
+    let venv = project_venv(&root, python, lock.requires_python(), &cache, &mut printer)?;
+    debug!(
+        "Using Python {} environment at {}",
+        venv.interpreter().python_version(),
+        venv.python_executable().normalized_display().cyan()
+    );
+
+    // Select the locked distributions that are required by the project and apply to the current
+    // environment.
+    let distributions = reachable_distributions(
+        &lock,
+        pyproject,
+        workspace.as_ref(),
+        &extras,
+        groups,
+        Some(venv.interpreter().markers()),
+    )?;
+    let mut requirements = Vec::new();
+    let mut editables = Vec::new();
+    let mut hashes = FxHashMap::<PackageName, Vec<String>>::default();
+    for distribution in distributions {
+        if let LockedSource::Editable(path) = &distribution.source {
+            editables.push(
+                EditableRequirement::parse(path, &root)
+                    .with_context(|| format!("Invalid editable for `{}`", distribution.name))?,
+            );
+            continue;
+        }
+
+        requirements.push(to_requirement(distribution, &root)?);
+        let distribution_hashes = distribution.hashes();
+        if !distribution_hashes.is_empty() {
+            hashes.insert(distribution.name.clone(), distribution_hashes);
+        }
+    }
+
+    // If the project itself is buildable, install it in editable mode alongside its dependencies.
+    // (The members of a workspace are already included in the lockfile as editables.)
+    let pyproject = root.join("pyproject.toml");
+    if workspace.is_none() && pyproject.is_file() {
+        let contents = fs_err::read_to_string(&pyproject)?;
+        let pyproject_toml = toml::from_str::<pyproject_toml::PyProjectToml>(&contents)
+            .with_context(|| format!("Failed to parse: {}", pyproject.normalized_display()))?;
+        if pyproject_toml.build_system.is_some() {
+            editables.push(EditableRequirement::parse(".", &root)?);
+        }
+    }
+
+    if requirements.is_empty() && editables.is_empty() {
+        writeln!(printer, "No requirements found")?;
+        return Ok(ExitStatus::Success);
+    }
+
+    // Install from the registries that the distributions were locked against.
+    let index_locations = locked_index_locations(&lock, find_links)?;
+
+    sync_requirements(
+        &requirements,
+        editables,
+        &hashes,
+        require_hashes,
+        &[],
+        &venv,
+        reinstall,
+        link_mode,
+        compile,
+        &index_locations,
+        setup_py,
+        connectivity,
+        tls,
+        retries,
+        proxy,
+        timeouts,
+        concurrency,
+        config_settings,
+        &PackageConfigSettings::default(),
+        no_build_isolation,
+        no_build,
+        no_binary,
+        false,
+        dry_run,
+        None,
+        &cache,
+        start,
+        printer,
+    )
+    .await
+}
+
+/// Determine the index locations from the registries recorded in the lockfile (in order of first
+/// appearance), along with the given `--find-links` locations.
+fn locked_index_locations(
+    lock: &Lock,
+    find_links: Vec<FlatIndexLocation>,
+) -> Result<IndexLocations> {
+    let mut indexes = Vec::<IndexUrl>::new();
+    for distribution in lock.distributions() {
+        if let LockedSource::Registry(url) = &distribution.source {
+            let index = IndexUrl::from_str(url)
+                .with_context(|| format!("Invalid locked index for `{}`", distribution.name))?;
+            if !indexes.contains(&index) {
+                indexes.push(index);
+            }
+        }
+    }
+    let mut indexes = indexes.into_iter();
+    Ok(IndexLocations::new(
+        indexes.next(),
+        indexes.collect(),
+        find_links,
+        false,
+    ))
+}
+
+/// Convert a locked distribution into a requirement pinned to its locked version or source.
+fn to_requirement(distribution: &LockedDistribution, root: &Path) -> Result<Requirement> {
+    let name = &distribution.name;
+    let requirement = match &distribution.source {
+        LockedSource::Registry(_) => {
+            let Some(version) = distribution.version.as_ref() else {
+                return Err(anyhow!(
+                    "Locked registry distribution `{name}` is missing a version"
+                ));
+            };
+            format!("{name}=={version}")
+        }
+        LockedSource::Direct(url) => format!("{name} @ {url}"),
+        LockedSource::Git(url) => format!("{name} @ git+{url}"),
+        LockedSource::Path(path) => {
+            // Local paths are stored relative to the project root.
+            let url = Url::from_file_path(root.join(path))
+                .map_err(|()| anyhow!("Invalid locked path for `{name}`: {path}"))?;
+            format!("{name} @ {url}")
+        }
+        LockedSource::Editable(_) => unreachable!("editables are handled separately"),
+    };
+    Requirement::parse(&requirement, root)
+        .with_context(|| format!("Invalid locked source for `{name}`"))
+}
+
+/// Return the virtual environment at `.venv` in the project root, creating it if it doesn't
+/// exist.
+///
+/// If the existing environment doesn't use the requested interpreter (`--python`), or doesn't
+/// satisfy the lockfile's `requires-python`, it's recreated.
+fn project_venv(
+    root: &Path,
+    python: Option<&str>,
+    requires_python: Option<&VersionSpecifiers>,
+    cache: &Cache,
+    printer: &mut Printer,
+) -> Result<Virtualenv> {
+    let path = root.join(".venv");
+    let platform = Platform::current()?;
+
+    let requested = if let Some(python) = python {
+        Some(
+            find_requested_python(python, &platform, cache)?
+                .ok_or_else(|| uv_interpreter::Error::NoSuchPython(python.to_string()))?,
+        )
+    } else {
+        None
+    };
+
+    // Reuse the existing environment, unless it's incompatible with the request.
+    let exists = path.is_dir();
+    if exists {
+        let venv = Virtualenv::from_root(&path, platform.clone(), cache)?;
+        let interpreter = venv.interpreter();
+        let reason = if requested.as_ref().is_some_and(|requested| {
+            requested.base_prefix() != interpreter.base_prefix()
+                || requested.python_version() != interpreter.python_version()
+        }) {
+            Some(format!(
+                "it uses Python {}, not the requested interpreter",
+                interpreter.python_version()
+            ))
+        } else if requires_python
+            .is_some_and(|requires_python| !requires_python.contains(interpreter.python_version()))
+        {
+            Some(format!(
+                "Python {} doesn't satisfy `requires-python`",
+                interpreter.python_version()
+            ))
+        } else {
+            None
+        };
+        let Some(reason) = reason else {
+            return Ok(venv);
+        };
+        debug!("Recreating the project environment, as {reason}");
+    }
+
+    let interpreter = if let Some(interpreter) = requested {
+        interpreter
+    } else {
+        find_default_python(&platform, cache)?
+    };
+    if let Some(requires_python) = requires_python {
+        if !requires_python.contains(interpreter.python_version()) {
+            return Err(anyhow!(
+                "The project requires Python {requires_python}, but Python {} was found at {} (use `--python` to select a compatible interpreter)",
+                interpreter.python_version(),
+                interpreter.sys_executable().normalized_display()
+            ));
+        }
+    }
+
+    writeln!(
+        printer,
+        "{} virtualenv at: {}",
+        if exists { "Recreating" } else { "Creating" },
+        path.normalized_display().cyan()
+    )?;
+
+    // Extra cfg for pyvenv.cfg to specify uv version
+    let extra_cfg = vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())];
+
+    Ok(gourgeist::create_venv(
+        &path,
+        interpreter,
+        gourgeist::Prompt::None,
+        false,
+        if exists {
+            gourgeist::OnExisting::Clear
+        } else {
+            gourgeist::OnExisting::default()
+        },
+        gourgeist::InterpreterLink::default(),
+        extra_cfg,
+    )?)
+}
@@ -10,19 +10,22 @@ use itertools::Itertools;
 use miette::{Diagnostic, IntoDiagnostic};
 use owo_colors::OwoColorize;
 use thiserror::Error;
+use url::Url;
 
 use distribution_types::{DistributionMetadata, IndexLocations, Name};
-use gourgeist::Prompt;
+use gourgeist::{InterpreterLink, OnExisting, Prompt};
 use pep508_rs::Requirement;
 use platform_host::Platform;
 use uv_cache::Cache;
-use uv_client::{Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder};
+use uv_client::{
+    Connectivity, FlatIndex, FlatIndexClient, RegistryClientBuilder, Timeouts, TlsSettings,
+};
 use uv_dispatch::BuildDispatch;
 use uv_fs::Normalized;
 use uv_installer::NoBinary;
 use uv_interpreter::{find_default_python, find_requested_python, Error};
 use uv_resolver::{InMemoryIndex, OptionsBuilder};
-use uv_traits::{BuildContext, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
+use uv_traits::{BuildContext, Concurrency, ConfigSettings, InFlight, NoBuild, SetupPyStrategy};
 
 use crate::commands::ExitStatus;
 use crate::printer::Printer;
@@ -34,8 +37,15 @@ pub(crate) async fn venv(
     python_request: Option<&str>,
     index_locations: &IndexLocations,
     prompt: Prompt,
+    system_site_packages: bool,
+    on_existing: OnExisting,
+    interpreter_link: InterpreterLink,
     connectivity: Connectivity,
-    seed: bool,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    seed: Option<&[Requirement]>,
     exclude_newer: Option<DateTime<Utc>>,
     cache: &Cache,
     printer: Printer,
@@ -45,7 +55,14 @@ pub(crate) async fn venv(
         python_request,
         index_locations,
         prompt,
+        system_site_packages,
+        on_existing,
+        interpreter_link,
         connectivity,
+        tls,
+        retries,
+        proxy,
+        timeouts,
         seed,
         exclude_newer,
         cache,
@@ -78,6 +95,10 @@ enum VenvError {
     #[error("Failed to resolve `--find-links` entry")]
     #[diagnostic(code(uv::venv::flat_index))]
     FlatIndex(#[source] uv_client::FlatIndexError),
+
+    #[error("Failed to load TLS certificates")]
+    #[diagnostic(code(uv::venv::tls))]
+    Tls(#[source] uv_client::TlsError),
 }
 
 /// Create a virtual environment.
@@ -87,8 +108,15 @@ async fn venv_impl(
     python_request: Option<&str>,
     index_locations: &IndexLocations,
     prompt: Prompt,
+    system_site_packages: bool,
+    on_existing: OnExisting,
+    interpreter_link: InterpreterLink,
     connectivity: Connectivity,
-    seed: bool,
+    tls: TlsSettings,
+    retries: u32,
+    proxy: Option<Url>,
+    timeouts: Timeouts,
+    seed: Option<&[Requirement]>,
     exclude_newer: Option<DateTime<Utc>>,
     cache: &Cache,
     mut printer: Printer,
@@ -123,11 +151,19 @@ async fn venv_impl(
     let extra_cfg = vec![("uv".to_string(), env!("CARGO_PKG_VERSION").to_string())];
 
     // Create the virtual environment.
-    let venv = gourgeist::create_venv(path, interpreter, prompt, extra_cfg)
-        .map_err(VenvError::Creation)?;
+    let venv = gourgeist::create_venv(
+        path,
+        interpreter,
+        prompt,
+        system_site_packages,
+        on_existing,
+        interpreter_link,
+        extra_cfg,
+    )
+    .map_err(VenvError::Creation)?;
 
     // Install seed packages.
-    if seed {
+    if let Some(seed) = seed {
         // Extract the interpreter.
         let interpreter = venv.interpreter();
 
@@ -135,7 +171,12 @@ async fn venv_impl(
         let client = RegistryClientBuilder::new(cache.clone())
             .index_urls(index_locations.index_urls())
             .connectivity(connectivity)
-            .build();
+            .tls(tls)
+            .retries(retries)
+            .proxy(proxy)
+            .timeouts(timeouts)
+            .build()
+            .map_err(VenvError::Tls)?;
 
         // Resolve the flat indexes from `--find-links`.
         let flat_index = {
@@ -145,7 +186,7 @@ async fn venv_impl(
                 .fetch(index_locations.flat_index())
                 .await
                 .map_err(VenvError::FlatIndex)?;
-            FlatIndex::from_entries(entries, tags)
+            FlatIndex::from_entries(entries, tags, &NoBuild::All, &NoBinary::None)
         };
 
         // Create a shared in-memory index.
@@ -171,17 +212,24 @@ async fn venv_impl(
             &config_settings,
             &NoBuild::All,
             &NoBinary::None,
+            Concurrency::default(),
         )
         .with_options(OptionsBuilder::new().exclude_newer(exclude_newer).build());
 
-        // Resolve the seed packages.
-        let mut requirements = vec![Requirement::from_str("pip").unwrap()];
+        // Resolve the seed packages, defaulting to `pip` (and `setuptools` and `wheel`) if none were
+        // provided.
+        let requirements = if seed.is_empty() {
+            let mut requirements = vec![Requirement::from_str("pip").unwrap()];
 
-        // Only include `setuptools` and `wheel` on Python <3.12
-        if interpreter.python_tuple() < (3, 12) {
-            requirements.push(Requirement::from_str("setuptools").unwrap());
-            requirements.push(Requirement::from_str("wheel").unwrap());
-        }
+            // Only include `setuptools` and `wheel` on Python <3.12
+            if interpreter.python_tuple() < (3, 12) {
+                requirements.push(Requirement::from_str("setuptools").unwrap());
+                requirements.push(Requirement::from_str("wheel").unwrap());
+            }
+            requirements
+        } else {
+            seed.to_vec()
+        };
         let resolution = build_dispatch
             .resolve(&requirements)
             .await
@@ -208,22 +256,82 @@ async fn venv_impl(
         }
     }
 
-    if cfg!(windows) {
-        writeln!(
-            printer,
-            // This should work whether the user is on CMD or PowerShell:
-            "Activate with: {}\\Scripts\\activate",
-            path.normalized_display().cyan()
-        )
-        .into_diagnostic()?;
-    } else {
-        writeln!(
-            printer,
-            "Activate with: source {}/bin/activate",
-            path.normalized_display().cyan()
-        )
-        .into_diagnostic()?;
+    // Determine the appropriate activation command for the user's shell.
+    let activate = match (Shell::from_env(), cfg!(windows)) {
+        (Some(Shell::Nushell), false) => {
+            format!(
+                "overlay use {}/bin/activate.nu",
+                path.normalized_display().cyan()
+            )
+        }
+        (Some(Shell::Nushell), true) => {
+            format!(
+                "overlay use {}\\Scripts\\activate.nu",
+                path.normalized_display().cyan()
+            )
+        }
+        (Some(Shell::Fish), _) => {
+            format!(
+                "source {}/bin/activate.fish",
+                path.normalized_display().cyan()
+            )
+        }
+        (Some(Shell::Csh), _) => {
+            format!(
+                "source {}/bin/activate.csh",
+                path.normalized_display().cyan()
+            )
+        }
+        (Some(Shell::Powershell), false) => {
+            format!("{}/bin/activate.ps1", path.normalized_display().cyan())
+        }
+        // This should work whether the user is on CMD or PowerShell:
+        (_, true) => format!("{}\\Scripts\\activate", path.normalized_display().cyan()),
+        (_, false) => format!("source {}/bin/activate", path.normalized_display().cyan()),
     };
+    writeln!(printer, "Activate with: {activate}").into_diagnostic()?;
 
     Ok(ExitStatus::Success)
 }
+
+/// A shell in which a virtual environment can be activated.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Shell {
+    /// A POSIX-compatible shell (e.g., Bash or Zsh).
+    Posix,
+    /// The Fish shell.
+    Fish,
+    /// The C shell (or `tcsh`).
+    Csh,
+    /// Nushell.
+    Nushell,
+    /// PowerShell.
+    Powershell,
+}
+
+impl Shell {
+    /// Detect the user's shell from the environment.
+    ///
+    /// Returns `None` if the shell can't be determined.
+    fn from_env() -> Option<Self> {
+        // Nushell doesn't set `SHELL`, but does export `NU_VERSION`.
+        if std::env::var_os("NU_VERSION").is_some() {
+            return Some(Self::Nushell);
+        }
+
+        // On Windows, `SHELL` isn't set by either CMD or PowerShell.
+        if cfg!(windows) {
+            return None;
+        }
+
+        let shell = std::env::var_os("SHELL")?;
+        let name = Path::new(&shell).file_stem()?.to_str()?;
+        match name {
+            "fish" => Some(Self::Fish),
+            "csh" | "tcsh" => Some(Self::Csh),
+            "nu" => Some(Self::Nushell),
+            "pwsh" | "powershell" => Some(Self::Powershell),
+            _ => Some(Self::Posix),
+        }
+    }
+}
@@ -113,13 +113,13 @@ impl CompatArgs for PipCompileCompatArgs {
 
         if self.build_isolation {
             warn_user!(
-                "pip-compile's `--build-isolation` has no effect (uv always uses build isolation)."
+                "pip-compile's `--build-isolation` has no effect (uv uses build isolation by default)."
             );
         }
 
         if self.no_build_isolation {
             return Err(anyhow!(
-                "pip-compile's `--no-build-isolation` is unsupported (uv always uses build isolation)."
+                "pip-compile's `--no-build-isolation` is unsupported (uv always uses build isolation when resolving)."
             ));
         }
 
@@ -325,9 +325,6 @@ enum Resolver {
 #[derive(Args)]
 #[allow(clippy::struct_excessive_bools)]
 pub(crate) struct VenvCompatArgs {
-    #[clap(long, hide = true)]
-    clear: bool,
-
     #[clap(long, hide = true)]
     no_seed: bool,
 
@@ -348,12 +345,6 @@ impl CompatArgs for VenvCompatArgs {
     /// behavior. If an argument is passed that does _not_ match uv's behavior, this method will
     /// return an error.
     fn validate(&self) -> Result<()> {
-        if self.clear {
-            warn_user!(
-                "virtualenv's `--clear` has no effect (uv always clears the virtual environment)."
-            );
-        }
-
         if self.no_seed {
             warn_user!(
                 "virtualenv's `--no-seed` has no effect (uv omits seed packages by default)."
@@ -9,6 +9,8 @@ pub(crate) enum Printer {
     Quiet,
     /// A printer that prints all output, including debug messages.
     Verbose,
+    /// A printer that prints to standard streams, but hides progress bars.
+    NoProgress,
 }
 
 impl Printer {
@@ -20,6 +22,7 @@ impl Printer {
             // Confusingly, hide the progress bar when in verbose mode.
             // Otherwise, it gets interleaved with debug messages.
             Self::Verbose => ProgressDrawTarget::hidden(),
+            Self::NoProgress => ProgressDrawTarget::hidden(),
         }
     }
 }
@@ -27,7 +30,7 @@ impl Printer {
 impl std::fmt::Write for Printer {
     fn write_str(&mut self, s: &str) -> std::fmt::Result {
         match self {
-            Self::Default | Self::Verbose => {
+            Self::Default | Self::Verbose | Self::NoProgress => {
                 #[allow(clippy::print_stderr, clippy::ignored_unit_patterns)]
                 {
                     eprint!("{s}");
@@ -0,0 +1,411 @@
+//! Read and edit the dependencies declared in a project's `pyproject.toml`, preserving its
+//! formatting and comments.
+
+use std::fmt::{Display, Formatter};
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use toml_edit::{Array, Document, Item, Table, Value};
+
+use pep440_rs::VersionSpecifiers;
+use pep508_rs::{Requirement, VersionOrUrl};
+use uv_fs::Normalized;
+use uv_normalize::{ExtraName, PackageName};
+
+/// The kind of dependency being added to (or removed from) a project.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) enum DependencyType {
+    /// A dependency in `project.dependencies`.
+    Production,
+    /// A development dependency in `tool.uv.dev-dependencies`.
+    Dev,
+    /// An optional dependency in `project.optional-dependencies`, under the given extra.
+    Optional(ExtraName),
+}
+
+impl Display for DependencyType {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Production => write!(f, "`project.dependencies`"),
+            Self::Dev => write!(f, "`tool.uv.dev-dependencies`"),
+            Self::Optional(extra) => write!(f, "`project.optional-dependencies.{extra}`"),
+        }
+    }
+}
+
+/// A `pyproject.toml` that can be edited in place.
+#[derive(Debug, Clone)]
+pub(crate) struct PyProjectTomlMut {
+    document: Document,
+}
+
+impl PyProjectTomlMut {
+    /// Parse the contents of a `pyproject.toml`.
+    pub(crate) fn from_toml(contents: &str) -> Result<Self> {
+        let document = Document::from_str(contents)?;
+        if !document.get("project").is_some_and(Item::is_table) {
+            return Err(anyhow!("Missing `[project]` table"));
+        }
+        Ok(Self { document })
+    }
+
+    /// Add a dependency, replacing any existing requirement for the same package.
+    pub(crate) fn add_dependency(
+        &mut self,
+        requirement: &Requirement,
+        dependency_type: &DependencyType,
+    ) -> Result<()> {
+        let dependencies = self.dependencies_mut(dependency_type)?;
+        let formatted = format_requirement(requirement);
+
+        // Replace the existing requirement in place, to retain its position and formatting.
+        if let Some(index) = find_dependency(dependencies, &requirement.name).first() {
+            dependencies.replace(*index, formatted);
+            return Ok(());
+        }
+
+        // Otherwise, append the requirement, matching the layout of the existing entries (e.g.,
+        // one requirement per line). The last entry's prefix may include a comment trailing the
+        // preceding entry, so only its indentation is retained.
+        let indent = dependencies.iter().last().and_then(|value| {
+            let prefix = value.decor().prefix()?.as_str()?;
+            let (_, indent) = prefix.rsplit_once('\n')?;
+            Some(format!("\n{indent}"))
+        });
+        dependencies.push(formatted);
+        if let Some(indent) = indent {
+            if let Some(value) = dependencies.iter_mut().last() {
+                value.decor_mut().set_prefix(indent);
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Remove all requirements for the given package, returning the removed requirements.
+    pub(crate) fn remove_dependency(
+        &mut self,
+        name: &PackageName,
+        dependency_type: &DependencyType,
+    ) -> Result<Vec<String>> {
+        let Some(dependencies) = self.dependencies(dependency_type)? else {
+            return Ok(Vec::new());
+        };
+        let indices = find_dependency(dependencies, name);
+        if indices.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        let dependencies = self.dependencies_mut(dependency_type)?;
+        let removed = indices
+            .into_iter()
+            .rev()
+            .filter_map(|index| dependencies.remove(index).as_str().map(ToString::to_string))
+            .collect::<Vec<_>>();
+        Ok(removed.into_iter().rev().collect())
+    }
+
+    /// Return the array of requirements for the given dependency type, if it exists.
+    fn dependencies(&self, dependency_type: &DependencyType) -> Result<Option<&Array>> {
+        let item = match dependency_type {
+            DependencyType::Production => self
+                .document
+                .get("project")
+                .and_then(|project| project.get("dependencies")),
+            DependencyType::Dev => self
+                .document
+                .get("tool")
+                .and_then(|tool| tool.get("uv"))
+                .and_then(|uv| uv.get("dev-dependencies")),
+            DependencyType::Optional(extra) => self
+                .document
+                .get("project")
+                .and_then(|project| project.get("optional-dependencies"))
+                .and_then(|optional| optional.get(extra.as_ref())),
+        };
+        item.map(|item| {
+            item.as_array()
+                .ok_or_else(|| anyhow!("Expected {dependency_type} to be an array"))
+        })
+        .transpose()
+    }
+
+    /// Return the array of requirements for the given dependency type, creating it (and any
+    /// enclosing tables) if it doesn't exist.
+    fn dependencies_mut(&mut self, dependency_type: &DependencyType) -> Result<&mut Array> {
+        let table = match dependency_type {
+            DependencyType::Production => self.document["project"]
+                .as_table_mut()
+                .ok_or_else(|| anyhow!("Expected `project` to be a table"))?,
+            DependencyType::Dev => {
+                let tool = implicit_table(self.document.as_table_mut(), "tool")?;
+                implicit_table(tool, "uv")?
+            }
+            DependencyType::Optional(_) => {
+                let project = self.document["project"]
+                    .as_table_mut()
+                    .ok_or_else(|| anyhow!("Expected `project` to be a table"))?;
+                implicit_table(project, "optional-dependencies")?
+            }
+        };
+        let key = match dependency_type {
+            DependencyType::Production => "dependencies",
+            DependencyType::Dev => "dev-dependencies",
+            DependencyType::Optional(extra) => extra.as_ref(),
+        };
+        table
+            .entry(key)
+            .or_insert(Item::Value(Value::Array(Array::new())))
+            .as_array_mut()
+            .ok_or_else(|| anyhow!("Expected {dependency_type} to be an array"))
+    }
+}
+
+impl Display for PyProjectTomlMut {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.document)
+    }
+}
+
+/// Return the development dependencies (`tool.uv.dev-dependencies`) declared in the given
+/// `pyproject.toml`.
+pub(crate) fn dev_dependencies(pyproject: &Path) -> Result<Vec<Requirement>> {
+    let contents = uv_fs::read_to_string(pyproject)?;
+    let document = Document::from_str(&contents)
+        .with_context(|| format!("Failed to parse `{}`", pyproject.normalized_display()))?;
+    let Some(dependencies) = document
+        .get("tool")
+        .and_then(|tool| tool.get("uv"))
+        .and_then(|uv| uv.get("dev-dependencies"))
+    else {
+        return Ok(Vec::new());
+    };
+    let working_dir = pyproject.parent().unwrap_or_else(|| Path::new("."));
+    dependencies
+        .as_array()
+        .ok_or_else(|| anyhow!("Expected `tool.uv.dev-dependencies` to be an array"))?
+        .iter()
+        .map(|value| {
+            let requirement = value.as_str().ok_or_else(|| {
+                anyhow!("Expected `tool.uv.dev-dependencies` to contain only strings")
+            })?;
+            Requirement::parse(requirement, working_dir)
+                .with_context(|| format!("Failed to parse `{requirement}`"))
+        })
+        .collect()
+}
+
+/// Return the Python versions supported by the project (`project.requires-python`) declared in the
+/// given `pyproject.toml`, if any.
+pub(crate) fn requires_python(pyproject: &Path) -> Result<Option<VersionSpecifiers>> {
+    let contents = uv_fs::read_to_string(pyproject)?;
+    let document = Document::from_str(&contents)
+        .with_context(|| format!("Failed to parse `{}`", pyproject.normalized_display()))?;
+    let Some(requires_python) = document
+        .get("project")
+        .and_then(|project| project.get("requires-python"))
+    else {
+        return Ok(None);
+    };
+    let requires_python = requires_python
+        .as_str()
+        .ok_or_else(|| anyhow!("Expected `project.requires-python` to be a string"))?;
+    VersionSpecifiers::from_str(requires_python)
+        .map(Some)
+        .with_context(|| format!("Failed to parse `requires-python`: `{requires_python}`"))
+}
+
+/// Return the indices of any requirements for the given package in the array.
+fn find_dependency(dependencies: &Array, name: &PackageName) -> Vec<usize> {
+    dependencies
+        .iter()
+        .enumerate()
+        .filter_map(|(index, value)| {
+            let requirement = Requirement::from_str(value.as_str()?).ok()?;
+            (requirement.name == *name).then_some(index)
+        })
+        .collect()
+}
+
+/// Return the table at the given key, creating an implicit table if it doesn't exist.
+fn implicit_table<'a>(table: &'a mut Table, key: &str) -> Result<&'a mut Table> {
+    table
+        .entry(key)
+        .or_insert_with(|| {
+            let mut table = Table::new();
+            table.set_implicit(true);
+            Item::Table(table)
+        })
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("Expected `{key}` to be a table"))
+}
+
+/// Format a requirement as it's conventionally written in a `pyproject.toml` (e.g.,
+/// `anyio>=4.0.0`, rather than `anyio >=4.0.0`).
+fn format_requirement(requirement: &Requirement) -> String {
+    let mut formatted = requirement.name.to_string();
+    if !requirement.extras.is_empty() {
+        formatted.push('[');
+        formatted.push_str(
+            &requirement
+                .extras
+                .iter()
+                .map(ToString::to_string)
+                .collect::<Vec<_>>()
+                .join(","),
+        );
+        formatted.push(']');
+    }
+    match &requirement.version_or_url {
+        None => {}
+        Some(VersionOrUrl::VersionSpecifier(specifiers)) => {
+            formatted.push_str(
+                &specifiers
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(","),
+            );
+        }
+        Some(VersionOrUrl::Url(url)) => {
+            formatted.push_str(" @ ");
+            formatted.push_str(&url.to_string());
+        }
+    }
+    if let Some(marker) = &requirement.marker {
+        formatted.push_str("; ");
+        formatted.push_str(&marker.to_string());
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use indoc::indoc;
+
+    use pep508_rs::Requirement;
+    use uv_normalize::{ExtraName, PackageName};
+
+    use super::{DependencyType, PyProjectTomlMut};
+
+    #[test]
+    fn add_preserves_formatting() {
+        let mut pyproject = PyProjectTomlMut::from_toml(indoc! {r#"
+            [project]
+            name = "project"
+            version = "0.1.0"
+            # Keep these sorted.
+            dependencies = [
+                "anyio>=4.0.0", # async
+                "idna",
+            ]
+        "#})
+        .unwrap();
+
+        pyproject
+            .add_dependency(
+                &Requirement::from_str("sniffio >= 1.3.0").unwrap(),
+                &DependencyType::Production,
+            )
+            .unwrap();
+        pyproject
+            .add_dependency(
+                &Requirement::from_str("idna==3.4").unwrap(),
+                &DependencyType::Production,
+            )
+            .unwrap();
+
+        assert_eq!(
+            pyproject.to_string(),
+            indoc! {r#"
+            [project]
+            name = "project"
+            version = "0.1.0"
+            # Keep these sorted.
+            dependencies = [
+                "anyio>=4.0.0", # async
+                "idna==3.4",
+                "sniffio>=1.3.0",
+            ]
+        "#}
+        );
+    }
+
+    #[test]
+    fn add_creates_tables() {
+        let mut pyproject = PyProjectTomlMut::from_toml(indoc! {r#"
+            [project]
+            name = "project"
+            version = "0.1.0"
+        "#})
+        .unwrap();
+
+        pyproject
+            .add_dependency(
+                &Requirement::from_str("pytest").unwrap(),
+                &DependencyType::Dev,
+            )
+            .unwrap();
+        pyproject
+            .add_dependency(
+                &Requirement::from_str("trio; sys_platform == 'linux'").unwrap(),
+                &DependencyType::Optional(ExtraName::from_str("async").unwrap()),
+            )
+            .unwrap();
+
+        assert_eq!(
+            pyproject.to_string(),
+            indoc! {r#"
+            [project]
+            name = "project"
+            version = "0.1.0"
+
+            [project.optional-dependencies]
+            async = ["trio; sys_platform == 'linux'"]
+
+            [tool.uv]
+            dev-dependencies = ["pytest"]
+        "#}
+        );
+    }
+
+    #[test]
+    fn remove() {
+        let mut pyproject = PyProjectTomlMut::from_toml(indoc! {r#"
+            [project]
+            name = "project"
+            version = "0.1.0"
+            dependencies = ["anyio>=4.0.0", "Flask[async]", "idna"]
+        "#})
+        .unwrap();
+
+        let removed = pyproject
+            .remove_dependency(
+                &PackageName::from_str("flask").unwrap(),
+                &DependencyType::Production,
+            )
+            .unwrap();
+        assert_eq!(removed, vec!["Flask[async]".to_string()]);
+
+        let removed = pyproject
+            .remove_dependency(
+                &PackageName::from_str("pytest").unwrap(),
+                &DependencyType::Dev,
+            )
+            .unwrap();
+        assert!(removed.is_empty());
+
+        assert_eq!(
+            pyproject.to_string(),
+            indoc! {r#"
+            [project]
+            name = "project"
+            version = "0.1.0"
+            dependencies = ["anyio>=4.0.0", "idna"]
+        "#}
+        );
+    }
+}
@@ -3,11 +3,12 @@
 use std::path::{Path, PathBuf};
 use std::str::FromStr;
 
-use anyhow::{Context, Result};
+use anyhow::{anyhow, Context, Result};
 use console::Term;
 use indexmap::IndexMap;
-use rustc_hash::FxHashSet;
+use rustc_hash::{FxHashMap, FxHashSet};
 
+use distribution_filename::DistFilename;
 use distribution_types::{FlatIndexLocation, IndexUrl};
 use pep508_rs::Requirement;
 use requirements_txt::{EditableRequirement, FindLink, RequirementsTxt};
@@ -19,7 +20,7 @@ use crate::confirm;
 
 use uv_warnings::warn_user;
 
-#[derive(Debug)]
+#[derive(Debug, Clone)]
 pub(crate) enum RequirementsSource {
     /// A package was provided on the command line (e.g., `pip install flask`).
     Package(String),
@@ -29,6 +30,10 @@ pub(crate) enum RequirementsSource {
     RequirementsTxt(PathBuf),
     /// Dependencies were provided via a `pyproject.toml` file (e.g., `pip-compile pyproject.toml`).
     PyprojectToml(PathBuf),
+    /// Dependencies were provided via a `setup.py` file (e.g., `pip-compile setup.py`).
+    SetupPy(PathBuf),
+    /// Dependencies were provided via a `setup.cfg` file (e.g., `pip-compile setup.cfg`).
+    SetupCfg(PathBuf),
 }
 
 impl RequirementsSource {
@@ -36,6 +41,10 @@ impl RequirementsSource {
     pub(crate) fn from_path(path: PathBuf) -> Self {
         if path.ends_with("pyproject.toml") {
             Self::PyprojectToml(path)
+        } else if path.ends_with("setup.py") {
+            Self::SetupPy(path)
+        } else if path.ends_with("setup.cfg") {
+            Self::SetupCfg(path)
         } else {
             Self::RequirementsTxt(path)
         }
@@ -78,13 +87,28 @@ impl RequirementsSource {
 
         Self::Package(name)
     }
+
+    /// Return a label identifying the file from which requirements were read, for use in
+    /// annotations (e.g., `-r requirements.in`), if the source is a file.
+    pub(crate) fn origin(&self) -> Option<String> {
+        match self {
+            Self::Package(_) | Self::Editable(_) => None,
+            Self::RequirementsTxt(path) => Some(format!("-r {}", path.normalized_display())),
+            Self::PyprojectToml(path) | Self::SetupPy(path) | Self::SetupCfg(path) => {
+                Some(path.normalized_display().to_string())
+            }
+        }
+    }
 }
 
 impl std::fmt::Display for RequirementsSource {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         match self {
             Self::Editable(path) => write!(f, "-e {path}"),
-            Self::RequirementsTxt(path) | Self::PyprojectToml(path) => {
+            Self::RequirementsTxt(path)
+            | Self::PyprojectToml(path)
+            | Self::SetupPy(path)
+            | Self::SetupCfg(path) => {
                 write!(f, "{}", path.display())
             }
             Self::Package(package) => write!(f, "{package}"),
@@ -123,6 +147,8 @@ pub(crate) struct RequirementsSpecification {
     pub(crate) overrides: Vec<Requirement>,
     /// Package to install as editable installs
     pub(crate) editables: Vec<EditableRequirement>,
+    /// The `--hash` digests provided for each requirement, keyed by package name.
+    pub(crate) hashes: FxHashMap<PackageName, Vec<String>>,
     /// The extras used to collect requirements.
     pub(crate) extras: FxHashSet<ExtraName>,
     /// The index URL to use for fetching packages.
@@ -133,6 +159,8 @@ pub(crate) struct RequirementsSpecification {
     pub(crate) no_index: bool,
     /// The `--find-links` locations to use for fetching packages.
     pub(crate) find_links: Vec<FlatIndexLocation>,
+    /// The input sources that requested each package (e.g., `-r requirements.in`).
+    pub(crate) origins: FxHashMap<PackageName, Vec<String>>,
 }
 
 impl RequirementsSpecification {
@@ -144,19 +172,32 @@ impl RequirementsSpecification {
     ) -> Result<Self> {
         Ok(match source {
             RequirementsSource::Package(name) => {
-                let requirement = Requirement::parse(name, std::env::current_dir()?)
-                    .with_context(|| format!("Failed to parse `{name}`"))?;
+                let requirement = match Requirement::parse(name, std::env::current_dir()?) {
+                    Ok(requirement) => requirement,
+                    Err(err) => {
+                        // Like pip, accept a URL or path to a distribution without a package name
+                        // (e.g., `https://.../flask-3.0.0-py3-none-any.whl`), inferring the name
+                        // from the filename.
+                        let Some(named) = infer_name(name) else {
+                            return Err(err).with_context(|| format!("Failed to parse `{name}`"));
+                        };
+                        Requirement::parse(&named, std::env::current_dir()?)
+                            .with_context(|| format!("Failed to parse `{name}`"))?
+                    }
+                };
                 Self {
                     project: None,
                     requirements: vec![requirement],
                     constraints: vec![],
                     overrides: vec![],
                     editables: vec![],
+                    hashes: FxHashMap::default(),
                     extras: FxHashSet::default(),
                     index_url: None,
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    origins: FxHashMap::default(),
                 }
             }
             RequirementsSource::Editable(name) => {
@@ -168,24 +209,34 @@ impl RequirementsSpecification {
                     constraints: vec![],
                     overrides: vec![],
                     editables: vec![requirement],
+                    hashes: FxHashMap::default(),
                     extras: FxHashSet::default(),
                     index_url: None,
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    origins: FxHashMap::default(),
                 }
             }
             RequirementsSource::RequirementsTxt(path) => {
                 let requirements_txt = RequirementsTxt::parse(path, std::env::current_dir()?)?;
+                let mut hashes = FxHashMap::<PackageName, Vec<String>>::default();
+                let mut requirements = Vec::with_capacity(requirements_txt.requirements.len());
+                for entry in requirements_txt.requirements {
+                    if !entry.hashes.is_empty() {
+                        hashes
+                            .entry(entry.requirement.name.clone())
+                            .or_default()
+                            .extend(entry.hashes);
+                    }
+                    requirements.push(entry.requirement);
+                }
                 Self {
                     project: None,
-                    requirements: requirements_txt
-                        .requirements
-                        .into_iter()
-                        .map(|entry| entry.requirement)
-                        .collect(),
+                    requirements,
                     constraints: requirements_txt.constraints,
                     editables: requirements_txt.editables,
+                    hashes,
                     overrides: vec![],
                     extras: FxHashSet::default(),
                     index_url: requirements_txt.index_url.map(IndexUrl::from),
@@ -203,6 +254,7 @@ impl RequirementsSpecification {
                             FindLink::Path(path) => FlatIndexLocation::Path(path),
                         })
                         .collect(),
+                    origins: FxHashMap::default(),
                 }
             }
             RequirementsSource::PyprojectToml(path) => {
@@ -220,6 +272,16 @@ impl RequirementsSpecification {
                             format!("Invalid `project.name` in {}", path.normalized_display())
                         })?;
 
+                    // Dynamic dependencies can only be determined by building the project, which
+                    // isn't supported for `pyproject.toml` inputs.
+                    if project.dynamic.as_ref().is_some_and(|dynamic| {
+                        dynamic.iter().any(|field| {
+                            field == "dependencies" || field == "optional-dependencies"
+                        })
+                    }) {
+                        warn_user!("`{}` declares its dependencies as dynamic, which is not supported; only statically declared `project.dependencies` and `project.optional-dependencies` will be included", path.normalized_display());
+                    }
+
                     // Include the default dependencies.
                     requirements.extend(project.dependencies.unwrap_or_default());
 
@@ -262,16 +324,31 @@ impl RequirementsSpecification {
                     constraints: vec![],
                     overrides: vec![],
                     editables: vec![],
+                    hashes: FxHashMap::default(),
                     extras: used_extras,
                     index_url: None,
                     extra_index_urls: vec![],
                     no_index: false,
                     find_links: vec![],
+                    origins: FxHashMap::default(),
                 }
             }
+            RequirementsSource::SetupPy(path) | RequirementsSource::SetupCfg(path) => {
+                return Err(anyhow!(
+                    "`{}` requires a build to determine its dependencies, which is only supported by `pip compile`",
+                    path.normalized_display()
+                ));
+            }
         })
     }
 
+    /// Read the build constraints from a set of sources, treating _everything_ as a constraint.
+    pub(crate) fn from_build_constraints(
+        sources: &[RequirementsSource],
+    ) -> Result<Vec<Requirement>> {
+        Ok(Self::from_sources(&[], sources, &[], &ExtrasSpecification::None)?.constraints)
+    }
+
     /// Read the combined requirements and constraints from a set of sources.
     pub(crate) fn from_sources(
         requirements: &[RequirementsSource],
@@ -285,12 +362,24 @@ impl RequirementsSpecification {
         // A `requirements.txt` can contain a `-c constraints.txt` directive within it, so reading
         // a requirements file can also add constraints.
         for source in requirements {
+            let origin = source.origin();
             let source = Self::from_source(source, extras)?;
+            if let Some(origin) = origin {
+                for requirement in &source.requirements {
+                    let origins = spec.origins.entry(requirement.name.clone()).or_default();
+                    if !origins.contains(&origin) {
+                        origins.push(origin.clone());
+                    }
+                }
+            }
             spec.requirements.extend(source.requirements);
             spec.constraints.extend(source.constraints);
             spec.overrides.extend(source.overrides);
             spec.extras.extend(source.extras);
             spec.editables.extend(source.editables);
+            for (name, hashes) in source.hashes {
+                spec.hashes.entry(name).or_default().extend(hashes);
+            }
 
             // Use the first project name discovered.
             if spec.project.is_none() {
@@ -312,7 +401,13 @@ impl RequirementsSpecification {
 
         // Read all constraints, treating _everything_ as a constraint.
         for source in constraints {
+            let display = source.to_string();
             let source = Self::from_source(source, extras)?;
+            if !source.editables.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "Editable requirements are not supported as constraints (found in `{display}`)"
+                ));
+            }
             spec.constraints.extend(source.requirements);
             spec.constraints.extend(source.constraints);
             spec.constraints.extend(source.overrides);
@@ -426,3 +521,13 @@ fn flatten_extra(
         &mut FxHashSet::default(),
     )
 }
+
+/// Given a URL or path to a built or source distribution without a package name, return the
+/// equivalent named requirement (e.g., `flask @ https://.../flask-3.0.0-py3-none-any.whl`), using
+/// the package name encoded in the filename.
+fn infer_name(given: &str) -> Option<String> {
+    let path = given.split(['#', '?']).next()?;
+    let filename = path.rsplit(['/', '\\']).next()?;
+    let filename = DistFilename::try_from_normalized_filename(filename)?;
+    Some(format!("{} @ {given}", filename.name()))
+}
@@ -0,0 +1,173 @@
+//! Read the inline metadata of a standalone Python script, as specified in PEP 723.
+//!
+//! See: <https://peps.python.org/pep-0723/>
+
+use std::path::Path;
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+
+use pep440_rs::VersionSpecifiers;
+use pep508_rs::Requirement;
+use uv_fs::Normalized;
+
+/// The metadata declared in the `script` block of a Python script.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ScriptMetadata {
+    /// The requirements of the script (`dependencies`).
+    pub(crate) dependencies: Vec<Requirement>,
+    /// The Python versions supported by the script (`requires-python`).
+    pub(crate) requires_python: Option<VersionSpecifiers>,
+}
+
+/// The `script` block, as written in the script.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct RawScriptMetadata {
+    #[serde(default)]
+    dependencies: Vec<String>,
+    requires_python: Option<String>,
+}
+
+impl ScriptMetadata {
+    /// Read the metadata of the Python script at the given path, if it contains a `script` block.
+    pub(crate) fn read(path: &Path) -> Result<Option<Self>> {
+        let contents = uv_fs::read_to_string(path)?;
+        Self::parse(&contents)
+            .with_context(|| format!("Invalid script metadata in `{}`", path.normalized_display()))
+    }
+
+    /// Parse the metadata from the contents of a Python script, if it contains a `script` block.
+    pub(crate) fn parse(contents: &str) -> Result<Option<Self>> {
+        let Some(toml) = extract_block(contents, "script")? else {
+            return Ok(None);
+        };
+        let raw = toml::from_str::<RawScriptMetadata>(&toml)?;
+
+        let dependencies = raw
+            .dependencies
+            .iter()
+            .map(|dependency| {
+                Requirement::from_str(dependency)
+                    .with_context(|| format!("Failed to parse dependency: `{dependency}`"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let requires_python = raw
+            .requires_python
+            .as_deref()
+            .map(|requires_python| {
+                VersionSpecifiers::from_str(requires_python).with_context(|| {
+                    format!("Failed to parse `requires-python`: `{requires_python}`")
+                })
+            })
+            .transpose()?;
+
+        Ok(Some(Self {
+            dependencies,
+            requires_python,
+        }))
+    }
+}
+
+/// Extract the TOML content of the metadata block of the given type (e.g., `# /// script`).
+///
+/// The block consists of an opening `# /// <type>` line, followed by comment lines, and ends at
+/// the last `# ///` line before the first line that isn't a comment. Each comment line is either
+/// `#` alone, or `# ` followed by content.
+fn extract_block(contents: &str, block_type: &str) -> Result<Option<String>> {
+    let opening = format!("# /// {block_type}");
+    let mut lines = contents.lines().skip_while(|line| *line != opening);
+    if lines.next().is_none() {
+        return Ok(None);
+    }
+
+    let mut content = Vec::new();
+    let mut end = None;
+    for line in lines {
+        if line == "# ///" {
+            end = Some(content.len());
+        }
+        if line == "#" {
+            content.push("");
+        } else if let Some(line) = line.strip_prefix("# ") {
+            content.push(line);
+        } else {
+            break;
+        }
+    }
+
+    let Some(end) = end else {
+        return Err(anyhow!("Unclosed `{opening}` block (expected `# ///`)"));
+    };
+    content.truncate(end);
+
+    let mut toml = content.join("\n");
+    toml.push('\n');
+    Ok(Some(toml))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use indoc::indoc;
+
+    use pep440_rs::VersionSpecifiers;
+    use pep508_rs::Requirement;
+
+    use super::ScriptMetadata;
+
+    #[test]
+    fn parse() {
+        let contents = indoc! {r#"
+            #!/usr/bin/env python3
+            # /// script
+            # requires-python = ">=3.11"
+            # dependencies = [
+            #   "requests<3",
+            #   "rich",
+            # ]
+            #
+            # [tool.example]
+            # key = "value"
+            # ///
+
+            import requests
+        "#};
+
+        let metadata = ScriptMetadata::parse(contents).unwrap().unwrap();
+        assert_eq!(
+            metadata,
+            ScriptMetadata {
+                dependencies: vec![
+                    Requirement::from_str("requests<3").unwrap(),
+                    Requirement::from_str("rich").unwrap(),
+                ],
+                requires_python: Some(VersionSpecifiers::from_str(">=3.11").unwrap()),
+            }
+        );
+    }
+
+    #[test]
+    fn parse_missing() {
+        let contents = indoc! {r#"
+            # A script without any metadata.
+            import sys
+        "#};
+
+        assert!(ScriptMetadata::parse(contents).unwrap().is_none());
+    }
+
+    #[test]
+    fn parse_unclosed() {
+        let contents = indoc! {r#"
+            # /// script
+            # dependencies = ["rich"]
+
+            import rich
+        "#};
+
+        assert!(ScriptMetadata::parse(contents).is_err());
+    }
+}
@@ -0,0 +1,225 @@
+//! Read persistent configuration from `uv.toml` files.
+//!
+//! Settings are read from a user-level `uv.toml` (e.g., `~/.config/uv/uv.toml`) and a project-level
+//! `uv.toml` (discovered in the current directory or any of its ancestors). For any given setting,
+//! the command line takes precedence over environment variables, which take precedence over the
+//! project-level configuration, which takes precedence over the user-level configuration.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use tracing::debug;
+
+use distribution_types::{FlatIndexLocation, IndexLocations, IndexUrl};
+use uv_fs::Normalized;
+use uv_resolver::{PreReleaseMode, ResolutionMode};
+
+/// The name of the configuration file.
+const CONFIG_FILE: &str = "uv.toml";
+
+/// The settings read from one or more `uv.toml` files.
+#[derive(Debug, Clone, Default)]
+pub(crate) struct Settings {
+    /// The URL of the Python package index.
+    pub(crate) index_url: Option<IndexUrl>,
+    /// Extra URLs of package indexes to use, in addition to `index-url`.
+    pub(crate) extra_index_url: Option<Vec<IndexUrl>>,
+    /// Ignore the registry index, relying on `find-links` and direct URL dependencies.
+    pub(crate) no_index: Option<bool>,
+    /// Locations to search for candidate distributions, beyond those found in the indexes.
+    pub(crate) find_links: Option<Vec<FlatIndexLocation>>,
+    /// Load TLS certificates from the platform's native certificate store.
+    pub(crate) native_tls: Option<bool>,
+    /// The strategy to use when selecting between the different compatible versions for a given
+    /// package requirement.
+    pub(crate) resolution: Option<ResolutionMode>,
+    /// The strategy to use when considering pre-release versions.
+    pub(crate) prerelease: Option<PreReleaseMode>,
+}
+
+/// The contents of a `uv.toml` file, as written.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case", deny_unknown_fields)]
+struct RawSettings {
+    index_url: Option<String>,
+    extra_index_url: Option<Vec<String>>,
+    no_index: Option<bool>,
+    find_links: Option<Vec<String>>,
+    native_tls: Option<bool>,
+    resolution: Option<ResolutionMode>,
+    prerelease: Option<PreReleaseMode>,
+}
+
+impl Settings {
+    /// Read the settings for the current directory.
+    ///
+    /// If `config_file` is provided, only that file is read; if `no_config` is set, no files are
+    /// read. Otherwise, the project-level `uv.toml` (if any) is layered over the user-level
+    /// `uv.toml` (if any).
+    pub(crate) fn discover(config_file: Option<&Path>, no_config: bool) -> Result<Self> {
+        if no_config {
+            return Ok(Self::default());
+        }
+        if let Some(config_file) = config_file {
+            return Self::read(config_file);
+        }
+
+        let mut settings = Self::default();
+        let project = std::env::current_dir()?
+            .ancestors()
+            .map(|directory| directory.join(CONFIG_FILE))
+            .find(|path| path.is_file());
+        if let Some(project) = project {
+            settings = settings.combine(Self::read(&project)?);
+        }
+        if let Some(user) = user_config_dir()
+            .map(|directory| directory.join(CONFIG_FILE))
+            .filter(|path| path.is_file())
+        {
+            settings = settings.combine(Self::read(&user)?);
+        }
+        Ok(settings)
+    }
+
+    /// Read the settings from the `uv.toml` file at the given path.
+    ///
+    /// Relative `find-links` paths are resolved relative to the directory containing the file.
+    fn read(path: &Path) -> Result<Self> {
+        debug!("Reading configuration from: {}", path.normalized_display());
+        let contents = uv_fs::read_to_string(path)?;
+        let raw = toml::from_str::<RawSettings>(&contents)
+            .with_context(|| format!("Failed to parse: {}", path.normalized_display()))?;
+        let directory = path.parent().unwrap_or_else(|| Path::new("."));
+
+        let index_url = raw
+            .index_url
+            .as_deref()
+            .map(|url| parse_index_url(url, path))
+            .transpose()?;
+        let extra_index_url = raw
+            .extra_index_url
+            .map(|urls| {
+                urls.iter()
+                    .map(|url| parse_index_url(url, path))
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?;
+        let find_links = raw
+            .find_links
+            .map(|locations| {
+                locations
+                    .iter()
+                    .map(|location| {
+                        let location = FlatIndexLocation::from_str(location).map_err(|err| {
+                            anyhow!(
+                                "Invalid `find-links` entry `{location}` in {}: {err}",
+                                path.normalized_display()
+                            )
+                        })?;
+                        Ok(match location {
+                            FlatIndexLocation::Path(relative) if relative.is_relative() => {
+                                FlatIndexLocation::Path(directory.join(relative))
+                            }
+                            location => location,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()
+            })
+            .transpose()?;
+
+        Ok(Self {
+            index_url,
+            extra_index_url,
+            no_index: raw.no_index,
+            find_links,
+            native_tls: raw.native_tls,
+            resolution: raw.resolution,
+            prerelease: raw.prerelease,
+        })
+    }
+
+    /// Combine two sets of settings, preferring the values in `self`, and falling back to those
+    /// in `other`.
+    #[must_use]
+    fn combine(self, other: Self) -> Self {
+        Self {
+            index_url: self.index_url.or(other.index_url),
+            extra_index_url: self.extra_index_url.or(other.extra_index_url),
+            no_index: self.no_index.or(other.no_index),
+            find_links: self.find_links.or(other.find_links),
+            native_tls: self.native_tls.or(other.native_tls),
+            resolution: self.resolution.or(other.resolution),
+            prerelease: self.prerelease.or(other.prerelease),
+        }
+    }
+
+    /// Determine the [`IndexLocations`] from the command-line arguments (or environment
+    /// variables), falling back to the configured settings for any that weren't provided.
+    pub(crate) fn index_locations(
+        &self,
+        index_url: Option<IndexUrl>,
+        extra_index_url: Vec<IndexUrl>,
+        find_links: Vec<FlatIndexLocation>,
+        no_index: bool,
+    ) -> IndexLocations {
+        IndexLocations::new(
+            index_url.or_else(|| self.index_url.clone()),
+            if extra_index_url.is_empty() {
+                self.extra_index_url.clone().unwrap_or_default()
+            } else {
+                extra_index_url
+            },
+            if find_links.is_empty() {
+                self.find_links.clone().unwrap_or_default()
+            } else {
+                find_links
+            },
+            no_index || self.no_index.unwrap_or(false),
+        )
+    }
+
+    /// Determine whether to use the platform's native certificate store.
+    pub(crate) fn native_tls(&self, native_tls: bool) -> bool {
+        native_tls || self.native_tls.unwrap_or(false)
+    }
+
+    /// Determine the [`ResolutionMode`], falling back to the configured setting (and then the
+    /// default) if it wasn't provided.
+    pub(crate) fn resolution(&self, resolution: Option<ResolutionMode>) -> ResolutionMode {
+        resolution.or(self.resolution).unwrap_or_default()
+    }
+
+    /// Determine the [`PreReleaseMode`], falling back to the configured setting (and then the
+    /// default) if it wasn't provided.
+    pub(crate) fn prerelease(&self, prerelease: Option<PreReleaseMode>) -> PreReleaseMode {
+        prerelease.or(self.prerelease).unwrap_or_default()
+    }
+}
+
+/// Parse an index URL from a `uv.toml` file.
+fn parse_index_url(url: &str, path: &Path) -> Result<IndexUrl> {
+    IndexUrl::from_str(url).map_err(|err| {
+        anyhow!(
+            "Invalid index URL `{url}` in {}: {err}",
+            path.normalized_display()
+        )
+    })
+}
+
+/// Return the directory containing the user-level `uv.toml`.
+///
+/// On Windows, this is `%APPDATA%\uv`; elsewhere, it's `$XDG_CONFIG_HOME/uv`, or `~/.config/uv`
+/// if `XDG_CONFIG_HOME` isn't set.
+fn user_config_dir() -> Option<PathBuf> {
+    if cfg!(windows) {
+        directories::BaseDirs::new().map(|dirs| dirs.config_dir().join("uv"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|path| path.is_absolute())
+            .or_else(|| directories::BaseDirs::new().map(|dirs| dirs.home_dir().join(".config")))
+            .map(|path| path.join("uv"))
+    }
+}
@@ -0,0 +1,245 @@
+//! Discover the workspace (`[tool.uv.workspace]`) that a project belongs to.
+//!
+//! A workspace is a root `pyproject.toml` with a `[tool.uv.workspace]` table, whose `members`
+//! (a list of globs, relative to the workspace root) are projects that share a single lockfile and
+//! virtual environment at the workspace root.
+
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use anyhow::{anyhow, Context, Result};
+use serde::Deserialize;
+use tracing::debug;
+
+use pep440_rs::Version;
+use uv_fs::Normalized;
+use uv_normalize::PackageName;
+
+/// A workspace, along with its members.
+#[derive(Debug, Clone)]
+pub(crate) struct Workspace {
+    /// The directory containing the workspace's root `pyproject.toml`.
+    root: PathBuf,
+    /// The members of the workspace, sorted by name.
+    members: Vec<WorkspaceMember>,
+}
+
+/// A project in a [`Workspace`].
+#[derive(Debug, Clone)]
+pub(crate) struct WorkspaceMember {
+    /// The name of the project.
+    pub(crate) name: PackageName,
+    /// The version of the project, if it's declared statically.
+    pub(crate) version: Option<Version>,
+    /// The directory containing the project's `pyproject.toml`.
+    pub(crate) root: PathBuf,
+    /// Whether the project declares a build system, such that it can be installed (in editable
+    /// mode) into the workspace environment.
+    pub(crate) buildable: bool,
+}
+
+/// The subset of a `pyproject.toml` that's relevant to workspace discovery.
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+struct PyProjectToml {
+    project: Option<Project>,
+    build_system: Option<toml::Table>,
+    tool: Option<Tool>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Project {
+    name: String,
+    version: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Tool {
+    uv: Option<ToolUv>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ToolUv {
+    workspace: Option<ToolUvWorkspace>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ToolUvWorkspace {
+    #[serde(default)]
+    members: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+}
+
+impl Workspace {
+    /// Discover the workspace that contains the project at `project_root`, if any.
+    ///
+    /// Searches the project's directory and its ancestors for a `pyproject.toml` with a
+    /// `[tool.uv.workspace]` table. The project is only part of the workspace if it's the workspace
+    /// root itself, or matches one of the workspace's `members` (and none of its `exclude`s).
+    pub(crate) fn discover(project_root: &Path) -> Result<Option<Self>> {
+        let project_root = fs_err::canonicalize(project_root)?;
+        for root in project_root.ancestors() {
+            let path = root.join("pyproject.toml");
+            if !path.is_file() {
+                continue;
+            }
+            let pyproject_toml = read_pyproject_toml(&path)?;
+            let Some(workspace) = pyproject_toml
+                .tool
+                .and_then(|tool| tool.uv)
+                .and_then(|uv| uv.workspace)
+            else {
+                continue;
+            };
+
+            let workspace = Self::from_root(root, pyproject_toml.project.is_some(), &workspace)?;
+            if root == project_root
+                || workspace
+                    .members
+                    .iter()
+                    .any(|member| member.root == project_root)
+            {
+                debug!(
+                    "Found workspace at {} with {} member(s)",
+                    root.normalized_display(),
+                    workspace.members.len()
+                );
+                return Ok(Some(workspace));
+            }
+
+            debug!(
+                "Project at {} is not a member of the workspace at {}",
+                project_root.normalized_display(),
+                root.normalized_display()
+            );
+            return Ok(None);
+        }
+        Ok(None)
+    }
+
+    /// Read the workspace with the given (canonicalized) root and `[tool.uv.workspace]` table.
+    fn from_root(root: &Path, is_project: bool, workspace: &ToolUvWorkspace) -> Result<Self> {
+        // The workspace root is itself a member, unless it's only a container for other members.
+        let mut roots = Vec::new();
+        if is_project {
+            roots.push(root.to_path_buf());
+        }
+
+        let exclude = workspace
+            .exclude
+            .iter()
+            .map(|pattern| {
+                glob::Pattern::new(&root.join(pattern).to_string_lossy())
+                    .with_context(|| format!("Invalid workspace `exclude` glob: `{pattern}`"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        for pattern in &workspace.members {
+            let paths = glob::glob(&root.join(pattern).to_string_lossy())
+                .with_context(|| format!("Invalid workspace `members` glob: `{pattern}`"))?;
+            for path in paths {
+                let path = path?;
+                if !path.is_dir() || exclude.iter().any(|exclude| exclude.matches_path(&path)) {
+                    continue;
+                }
+                let path = fs_err::canonicalize(path)?;
+                if !roots.contains(&path) {
+                    roots.push(path);
+                }
+            }
+        }
+
+        let mut members = roots
+            .into_iter()
+            .map(WorkspaceMember::from_root)
+            .collect::<Result<Vec<_>>>()?;
+        members.sort_unstable_by(|a, b| a.name.cmp(&b.name));
+        if let Some(duplicate) = members.windows(2).find(|pair| pair[0].name == pair[1].name) {
+            return Err(anyhow!(
+                "Multiple workspace members are named `{}`: {} and {}",
+                duplicate[0].name,
+                duplicate[0].root.normalized_display(),
+                duplicate[1].root.normalized_display()
+            ));
+        }
+
+        Ok(Self {
+            root: root.to_path_buf(),
+            members,
+        })
+    }
+
+    /// Return the workspace root, which contains the shared lockfile and virtual environment.
+    pub(crate) fn root(&self) -> &Path {
+        &self.root
+    }
+
+    /// Return the members of the workspace, sorted by name.
+    pub(crate) fn members(&self) -> &[WorkspaceMember] {
+        &self.members
+    }
+
+    /// Returns `true` if the given package is a member of the workspace.
+    pub(crate) fn contains(&self, name: &PackageName) -> bool {
+        self.members.iter().any(|member| &member.name == name)
+    }
+}
+
+impl WorkspaceMember {
+    /// Return the path to the project's `pyproject.toml`.
+    pub(crate) fn pyproject(&self) -> PathBuf {
+        self.root.join("pyproject.toml")
+    }
+
+    /// Read the workspace member in the given directory.
+    fn from_root(root: PathBuf) -> Result<Self> {
+        let path = root.join("pyproject.toml");
+        if !path.is_file() {
+            return Err(anyhow!(
+                "Workspace member is missing a `pyproject.toml`: {}",
+                root.normalized_display()
+            ));
+        }
+        let pyproject_toml = read_pyproject_toml(&path)?;
+        let Some(project) = pyproject_toml.project else {
+            return Err(anyhow!(
+                "Workspace member is missing a `[project]` table: {}",
+                path.normalized_display()
+            ));
+        };
+        let name = PackageName::new(project.name)
+            .with_context(|| format!("Invalid `project.name` in {}", path.normalized_display()))?;
+        let version = project
+            .version
+            .as_deref()
+            .map(Version::from_str)
+            .transpose()
+            .map_err(|err| anyhow!(err))
+            .with_context(|| {
+                format!("Invalid `project.version` in {}", path.normalized_display())
+            })?;
+        Ok(Self {
+            name,
+            version,
+            root,
+            buildable: pyproject_toml.build_system.is_some(),
+        })
+    }
+}
+
+/// Read the `pyproject.toml` at the given path.
+fn read_pyproject_toml(path: &Path) -> Result<PyProjectToml> {
+    let contents = uv_fs::read_to_string(path)?;
+    toml::from_str(&contents)
+        .with_context(|| format!("Failed to parse `{}`", path.normalized_display()))
+}
+
+/// Return the directory containing the lockfile and virtual environment for the project at
+/// `project_root`: the workspace root, if the project is part of a workspace, or otherwise the
+/// project root itself.
+pub(crate) fn environment_root(project_root: &Path) -> Result<PathBuf> {
+    Ok(match Workspace::discover(project_root)? {
+        Some(workspace) => workspace.root().to_path_buf(),
+        None => project_root.to_path_buf(),
+    })
+}
@@ -0,0 +1,177 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{get_bin, uv_snapshot, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create an `add` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("add")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Add a registry dependency, which should be constrained to at least the locked version.
+#[test]
+fn add_registry() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        # Direct dependencies of the project.
+        dependencies = [
+            "iniconfig==2.0.0",
+        ]
+        "#
+    })?;
+
+    uv_snapshot!(command(&context).arg("anyio"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    Updated pyproject.toml
+    "###
+    );
+
+    insta::assert_snapshot!(fs_err::read_to_string(&pyproject_toml)?, @r###"
+    [project]
+    name = "project"
+    version = "0.1.0"
+    # Direct dependencies of the project.
+    dependencies = [
+        "iniconfig==2.0.0",
+        "anyio>=4.0.0",
+    ]
+    "###
+    );
+
+    let lock = fs_err::read_to_string(context.temp_dir.child("uv.lock"))?;
+    assert!(lock.contains("name = \"anyio\""));
+
+    // Adding an existing dependency should replace its requirement in place.
+    uv_snapshot!(command(&context).arg("iniconfig<2"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    Updated pyproject.toml
+    "###
+    );
+
+    insta::assert_snapshot!(fs_err::read_to_string(&pyproject_toml)?, @r###"
+    [project]
+    name = "project"
+    version = "0.1.0"
+    # Direct dependencies of the project.
+    dependencies = [
+        "iniconfig<2",
+        "anyio>=4.0.0",
+    ]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Add development and optional dependencies.
+#[test]
+fn add_dev_optional() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        "#
+    })?;
+
+    uv_snapshot!(command(&context).arg("iniconfig").arg("--dev"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Updated pyproject.toml
+    "###
+    );
+
+    uv_snapshot!(command(&context).arg("sniffio==1.3.0").arg("--optional").arg("async"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Updated pyproject.toml
+    "###
+    );
+
+    insta::assert_snapshot!(fs_err::read_to_string(&pyproject_toml)?, @r###"
+    [project]
+    name = "project"
+    version = "0.1.0"
+
+    [project.optional-dependencies]
+    async = ["sniffio==1.3.0"]
+
+    [tool.uv]
+    dev-dependencies = ["iniconfig>=2.0.0"]
+    "###
+    );
+
+    Ok(())
+}
+
+/// If the added requirement can't be resolved, the `pyproject.toml` should be left unchanged.
+#[test]
+fn add_unsatisfiable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let contents = indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["anyio==4.0.0"]
+        "#
+    };
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(contents)?;
+
+    uv_snapshot!(command(&context).arg("anyio==3.7.0"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because you require anyio==3.7.0 and you require anyio==4.0.0, we can conclude that the requirements are unsatisfiable.
+    "###
+    );
+
+    pyproject_toml.assert(contents);
+
+    Ok(())
+}
@@ -26,6 +26,11 @@ pub static EXCLUDE_NEWER: &str = "2023-11-18T12:00:00Z";
 
 pub const INSTA_FILTERS: &[(&str, &str)] = &[
     (r"--cache-dir [^\s]+", "--cache-dir [CACHE_DIR]"),
+    // The uv version in the header of compiled output
+    (
+        r"autogenerated by uv v[^\s]+ via",
+        "autogenerated by uv v[VERSION] via",
+    ),
     // Operation times
     (r"(\s|\()(\d+m )?(\d+\.)?\d+(ms|s)", "$1[TIME]"),
     // File sizes
@@ -99,6 +104,22 @@ impl TestContext {
         cmd
     }
 
+    /// Sync the given requirements (in `requirements.txt` format) into the virtual environment.
+    pub fn pip_sync(&self, requirements: &str) {
+        let requirements_txt = self.temp_dir.child("requirements.txt");
+        fs_err::write(&requirements_txt, requirements).expect("Failed to write requirements.txt");
+
+        std::process::Command::new(get_bin())
+            .arg("pip")
+            .arg("sync")
+            .arg(requirements_txt.path())
+            .arg("--cache-dir")
+            .arg(self.cache_dir.path())
+            .env("VIRTUAL_ENV", self.venv.as_os_str())
+            .assert()
+            .success();
+    }
+
     /// Run the given python code and check whether it succeeds.
     pub fn assert_command(&self, command: &str) -> Assert {
         std::process::Command::new(venv_to_interpreter(&self.venv))
@@ -250,6 +271,7 @@ pub fn create_venv(
     Command::new(get_bin())
         .arg("venv")
         .arg(venv.as_os_str())
+        .arg("--clear")
         .arg("--cache-dir")
         .arg(cache_dir.path())
         .arg("--python")
@@ -0,0 +1,142 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use crate::common::{uv_snapshot, TestContext};
+
+mod common;
+
+/// Create a `pip compile` command that reads `requirements.in`, with the user-level configuration
+/// directory isolated to the test's temporary directory.
+fn command(context: &TestContext) -> Command {
+    let mut command = context.compile();
+    command
+        .arg("requirements.in")
+        .arg("--no-header")
+        .env("XDG_CONFIG_HOME", context.temp_dir.child("config").path());
+    command
+}
+
+/// Read the resolution strategy from the project-level `uv.toml`, unless it's overridden on the
+/// command line.
+#[test]
+fn config_project() -> Result<()> {
+    let context = TestContext::new("3.12");
+    context
+        .temp_dir
+        .child("requirements.in")
+        .write_str("iniconfig>=1.1.1")?;
+    context
+        .temp_dir
+        .child("uv.toml")
+        .write_str(r#"resolution = "lowest-direct""#)?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    iniconfig==1.1.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // The command line takes precedence over the configuration file.
+    uv_snapshot!(command(&context).arg("--resolution").arg("highest"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    iniconfig==2.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // With `--no-config`, the configuration file is ignored.
+    uv_snapshot!(command(&context).arg("--no-config"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    iniconfig==2.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Read the resolution strategy from the user-level `uv.toml`, unless it's overridden by the
+/// project-level `uv.toml`.
+#[test]
+fn config_user() -> Result<()> {
+    let context = TestContext::new("3.12");
+    context
+        .temp_dir
+        .child("requirements.in")
+        .write_str("iniconfig>=1.1.1")?;
+    context
+        .temp_dir
+        .child("config")
+        .child("uv")
+        .child("uv.toml")
+        .write_str(r#"resolution = "lowest-direct""#)?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    iniconfig==1.1.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // The project-level configuration takes precedence over the user-level configuration.
+    context
+        .temp_dir
+        .child("uv.toml")
+        .write_str(r#"resolution = "highest""#)?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    iniconfig==2.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject unknown settings, rather than silently ignoring them.
+#[test]
+fn config_unknown_field() -> Result<()> {
+    let context = TestContext::new("3.12");
+    context
+        .temp_dir
+        .child("requirements.in")
+        .write_str("iniconfig")?;
+    context
+        .temp_dir
+        .child("uv.toml")
+        .write_str(r#"index-urls = ["https://test.pypi.org/simple"]"#)?;
+
+    command(&context)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("unknown field `index-urls`"));
+
+    Ok(())
+}
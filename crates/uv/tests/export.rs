@@ -0,0 +1,280 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// Create an `export` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("export")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Write a `pyproject.toml` (without a build system) and a `uv.lock` to the project directory.
+fn write_project(context: &TestContext, pyproject_toml: &str, lock: &str) -> Result<()> {
+    context
+        .temp_dir
+        .child("pyproject.toml")
+        .write_str(pyproject_toml)?;
+    context.temp_dir.child("uv.lock").write_str(lock)?;
+    Ok(())
+}
+
+/// Export the locked dependencies as a `requirements.txt`, including optional and development
+/// dependencies only when requested.
+#[test]
+fn export() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    write_project(
+        &context,
+        indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["anyio==4.0.0"]
+
+        [project.optional-dependencies]
+        test = ["iniconfig"]
+
+        [tool.uv]
+        dev-dependencies = ["sniffio"]
+        "#
+        },
+        indoc! {r#"
+        version = 1
+
+        [[distribution]]
+        name = "anyio"
+        version = "4.0.0"
+        source = "registry+https://pypi.org/simple"
+
+        [[distribution.file]]
+        filename = "anyio-4.0.0-py3-none-any.whl"
+        hash = "sha256:1111"
+
+        [[distribution.file]]
+        filename = "anyio-4.0.0.tar.gz"
+        hash = "sha256:2222"
+
+        [[distribution.dependencies]]
+        name = "idna"
+
+        [[distribution.dependencies]]
+        name = "sniffio"
+
+        [[distribution]]
+        name = "idna"
+        version = "3.4"
+        source = "registry+https://pypi.org/simple"
+
+        [[distribution.file]]
+        filename = "idna-3.4-py3-none-any.whl"
+        hash = "sha256:3333"
+
+        [[distribution]]
+        name = "iniconfig"
+        version = "2.0.0"
+        source = "registry+https://pypi.org/simple"
+
+        [[distribution.file]]
+        filename = "iniconfig-2.0.0-py3-none-any.whl"
+        hash = "sha256:4444"
+
+        [[distribution]]
+        name = "sniffio"
+        version = "1.3.0"
+        source = "registry+https://pypi.org/simple"
+
+        [[distribution.file]]
+        filename = "sniffio-1.3.0-py3-none-any.whl"
+        hash = "sha256:5555"
+        "#
+        },
+    )?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv export --cache-dir [CACHE_DIR]
+    anyio==4.0.0
+    idna==3.4
+    sniffio==1.3.0
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(command(&context)
+        .arg("--hashes")
+        .arg("--extra")
+        .arg("test")
+        .arg("--group")
+        .arg("dev"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv export --cache-dir [CACHE_DIR] --hashes --extra test --group dev
+    anyio==4.0.0 \
+        --hash=sha256:1111 \
+        --hash=sha256:2222
+    idna==3.4 \
+        --hash=sha256:3333
+    iniconfig==2.0.0 \
+        --hash=sha256:4444
+    sniffio==1.3.0 \
+        --hash=sha256:5555
+
+    ----- stderr -----
+    "###
+    );
+
+    // Writing to a file should produce the same output.
+    command(&context)
+        .arg("--output-file")
+        .arg("requirements.txt")
+        .arg("--quiet")
+        .assert()
+        .success();
+    context
+        .temp_dir
+        .child("requirements.txt")
+        .assert(predicates::str::ends_with(indoc! {r"
+            anyio==4.0.0
+            idna==3.4
+            sniffio==1.3.0
+        "}));
+
+    Ok(())
+}
+
+/// Retain the lockfile's markers in a universal export, or evaluate them against the target
+/// platform, if one is provided.
+#[test]
+fn export_marker() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    write_project(
+        &context,
+        indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["click==8.1.7"]
+        "#
+        },
+        indoc! {r#"
+        version = 1
+
+        [[distribution]]
+        name = "click"
+        version = "8.1.7"
+        source = "registry+https://pypi.org/simple"
+
+        [[distribution.dependencies]]
+        name = "colorama"
+        marker = "platform_system == 'Windows'"
+
+        [[distribution]]
+        name = "colorama"
+        version = "0.4.6"
+        source = "registry+https://pypi.org/simple"
+        marker = "platform_system == 'Windows'"
+        "#
+        },
+    )?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv export --cache-dir [CACHE_DIR]
+    click==8.1.7
+    colorama==0.4.6 ; platform_system == 'Windows'
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(command(&context)
+        .arg("--python-platform")
+        .arg("windows"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv export --cache-dir [CACHE_DIR] --python-platform windows
+    click==8.1.7
+    colorama==0.4.6
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(command(&context)
+        .arg("--python-platform")
+        .arg("linux"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv export --cache-dir [CACHE_DIR] --python-platform linux
+    click==8.1.7
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject dependency groups other than `dev`.
+#[test]
+fn export_unknown_group() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    write_project(
+        &context,
+        indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        "#
+        },
+        indoc! {r"
+        version = 1
+        "
+        },
+    )?;
+
+    uv_snapshot!(command(&context)
+        .arg("--group")
+        .arg("docs"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Unknown dependency group `docs` (expected: `dev`)
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,364 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{get_bin, uv_snapshot, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `lock` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("lock")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Read the `uv.lock` in the project directory, redacting the file hashes and URL paths.
+fn read_lock(context: &TestContext) -> Result<String> {
+    let lock = fs_err::read_to_string(context.temp_dir.child("uv.lock"))?;
+    let hash = regex::Regex::new(r"sha256:[0-9a-f]{64}")?;
+    let lock = hash.replace_all(&lock, "sha256:[HASH]");
+    let path = regex::Regex::new(
+        r"https://files\.pythonhosted\.org/packages/[0-9a-f]{2}/[0-9a-f]{2}/[0-9a-f]{60}/",
+    )?;
+    Ok(path
+        .replace_all(&lock, "https://files.pythonhosted.org/packages/[PATH]/")
+        .into_owned())
+}
+
+/// Lock a project with registry dependencies.
+#[test]
+fn lock_registry() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["anyio==4.0.0"]
+        "#
+    })?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    insta::assert_snapshot!(read_lock(&context)?, @r###"
+    # This file was autogenerated by uv; it is not intended to be edited manually.
+    version = 1
+
+    [[distribution]]
+    name = "anyio"
+    version = "4.0.0"
+    source = "registry+https://pypi.org/simple"
+
+    [[distribution.file]]
+    filename = "anyio-4.0.0-py3-none-any.whl"
+    url = "https://files.pythonhosted.org/packages/[PATH]/anyio-4.0.0-py3-none-any.whl"
+    hash = "sha256:[HASH]"
+
+    [[distribution.file]]
+    filename = "anyio-4.0.0.tar.gz"
+    url = "https://files.pythonhosted.org/packages/[PATH]/anyio-4.0.0.tar.gz"
+    hash = "sha256:[HASH]"
+
+    [[distribution.dependencies]]
+    name = "idna"
+    version = "3.4"
+
+    [[distribution.dependencies]]
+    name = "sniffio"
+    version = "1.3.0"
+
+    [[distribution]]
+    name = "idna"
+    version = "3.4"
+    source = "registry+https://pypi.org/simple"
+
+    [[distribution.file]]
+    filename = "idna-3.4-py3-none-any.whl"
+    url = "https://files.pythonhosted.org/packages/[PATH]/idna-3.4-py3-none-any.whl"
+    hash = "sha256:[HASH]"
+
+    [[distribution.file]]
+    filename = "idna-3.4.tar.gz"
+    url = "https://files.pythonhosted.org/packages/[PATH]/idna-3.4.tar.gz"
+    hash = "sha256:[HASH]"
+
+    [[distribution]]
+    name = "sniffio"
+    version = "1.3.0"
+    source = "registry+https://pypi.org/simple"
+
+    [[distribution.file]]
+    filename = "sniffio-1.3.0-py3-none-any.whl"
+    url = "https://files.pythonhosted.org/packages/[PATH]/sniffio-1.3.0-py3-none-any.whl"
+    hash = "sha256:[HASH]"
+
+    [[distribution.file]]
+    filename = "sniffio-1.3.0.tar.gz"
+    url = "https://files.pythonhosted.org/packages/[PATH]/sniffio-1.3.0.tar.gz"
+    hash = "sha256:[HASH]"
+    "###
+    );
+
+    Ok(())
+}
+
+/// Lock a project with a platform-conditional dependency, which should be included in the
+/// lockfile along with its marker, regardless of the current platform.
+#[test]
+fn lock_marker() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["click==8.1.7"]
+        "#
+    })?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 2 packages in [TIME]
+    "###
+    );
+
+    insta::assert_snapshot!(read_lock(&context)?, @r###"
+    # This file was autogenerated by uv; it is not intended to be edited manually.
+    version = 1
+
+    [[distribution]]
+    name = "click"
+    version = "8.1.7"
+    source = "registry+https://pypi.org/simple"
+
+    [[distribution.file]]
+    filename = "click-8.1.7-py3-none-any.whl"
+    url = "https://files.pythonhosted.org/packages/[PATH]/click-8.1.7-py3-none-any.whl"
+    hash = "sha256:[HASH]"
+
+    [[distribution.file]]
+    filename = "click-8.1.7.tar.gz"
+    url = "https://files.pythonhosted.org/packages/[PATH]/click-8.1.7.tar.gz"
+    hash = "sha256:[HASH]"
+
+    [[distribution.dependencies]]
+    name = "colorama"
+    version = "0.4.6"
+    marker = "platform_system == 'Windows'"
+
+    [[distribution]]
+    name = "colorama"
+    version = "0.4.6"
+    source = "registry+https://pypi.org/simple"
+    marker = "platform_system == 'Windows'"
+
+    [[distribution.file]]
+    filename = "colorama-0.4.6-py2.py3-none-any.whl"
+    url = "https://files.pythonhosted.org/packages/[PATH]/colorama-0.4.6-py2.py3-none-any.whl"
+    hash = "sha256:[HASH]"
+
+    [[distribution.file]]
+    filename = "colorama-0.4.6.tar.gz"
+    url = "https://files.pythonhosted.org/packages/[PATH]/colorama-0.4.6.tar.gz"
+    hash = "sha256:[HASH]"
+    "###
+    );
+
+    Ok(())
+}
+
+/// Lock a project that declares a `requires-python`, which should be recorded in the lockfile. A
+/// dependency that's only required on older Python versions should be included, even though it
+/// doesn't apply to the current interpreter.
+#[test]
+fn lock_requires_python() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.8"
+        dependencies = ["iniconfig==2.0.0 ; python_version < '3.10'"]
+        "#
+    })?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    insta::assert_snapshot!(read_lock(&context)?, @r###"
+    # This file was autogenerated by uv; it is not intended to be edited manually.
+    version = 1
+    requires-python = ">=3.8"
+
+    [[distribution]]
+    name = "iniconfig"
+    version = "2.0.0"
+    source = "registry+https://pypi.org/simple"
+    marker = "python_version < '3.10'"
+
+    [[distribution.file]]
+    filename = "iniconfig-2.0.0-py3-none-any.whl"
+    url = "https://files.pythonhosted.org/packages/[PATH]/iniconfig-2.0.0-py3-none-any.whl"
+    hash = "sha256:[HASH]"
+
+    [[distribution.file]]
+    filename = "iniconfig-2.0.0.tar.gz"
+    url = "https://files.pythonhosted.org/packages/[PATH]/iniconfig-2.0.0.tar.gz"
+    hash = "sha256:[HASH]"
+    "###
+    );
+
+    Ok(())
+}
+
+/// Lock a project that only supports Python versions newer than any that have been released. A
+/// dependency that's only required on those versions should still be included.
+#[test]
+fn lock_requires_python_unreleased() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.14"
+        dependencies = ["iniconfig==2.0.0 ; python_version >= '3.14'"]
+        "#
+    })?;
+    command(&context).assert().success();
+
+    let lock = read_lock(&context)?;
+    assert!(lock.contains("requires-python = \">=3.14\""));
+    assert!(lock.contains("name = \"iniconfig\""));
+
+    Ok(())
+}
+
+/// Lock a project that requires different versions of a package on different Python versions,
+/// which should fork the resolution and lock each version with its marker.
+#[test]
+fn lock_fork() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.8"
+        dependencies = [
+            "anyio==4.0.0 ; python_version >= '3.11'",
+            "anyio==3.7.1 ; python_version < '3.11'",
+        ]
+        "#
+    })?;
+    command(&context).assert().success();
+
+    let lock = read_lock(&context)?;
+    assert!(lock.contains(indoc! {r#"
+        name = "anyio"
+        version = "3.7.1"
+        source = "registry+https://pypi.org/simple"
+        marker = "python_version < '3.11'"
+    "#}));
+    assert!(lock.contains(indoc! {r#"
+        name = "anyio"
+        version = "4.0.0"
+        source = "registry+https://pypi.org/simple"
+        marker = "python_version >= '3.11'"
+    "#}));
+
+    Ok(())
+}
+
+/// Prefer the versions pinned in an existing lockfile, unless `--upgrade` is provided.
+#[test]
+fn lock_preferences() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["iniconfig<2"]
+        "#
+    })?;
+    command(&context).assert().success();
+    assert!(read_lock(&context)?.contains("version = \"1.1.1\""));
+
+    // Loosening the requirement should retain the locked version.
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["iniconfig"]
+        "#
+    })?;
+    command(&context).assert().success();
+    assert!(read_lock(&context)?.contains("version = \"1.1.1\""));
+
+    // Unless the user requests an upgrade.
+    command(&context).arg("--upgrade").assert().success();
+    assert!(read_lock(&context)?.contains("version = \"2.0.0\""));
+
+    Ok(())
+}
+
+/// Locking requires a `pyproject.toml` in the current directory.
+#[test]
+fn lock_missing_pyproject() {
+    let context = TestContext::new("3.12");
+
+    let filters: Vec<_> = [(r"found in: .*", "found in: [TEMP_DIR]")]
+        .into_iter()
+        .chain(context.filters())
+        .collect();
+
+    uv_snapshot!(filters, command(&context), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No `pyproject.toml` found in: [TEMP_DIR]
+    "###
+    );
+}
@@ -0,0 +1,80 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use crate::common::{get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// Create a `pip check` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("check")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Check an environment in which all requirements are satisfied.
+#[test]
+fn check_compatible() {
+    let context = TestContext::new("3.12");
+
+    context.pip_sync("Jinja2==3.1.2\nMarkupSafe==2.1.3");
+
+    uv_snapshot!(context.filters(), command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Checked 2 packages in [TIME]
+    All installed packages are compatible
+    "###
+    );
+}
+
+/// Check an environment in which a dependency is missing.
+#[test]
+fn check_missing_dependency() {
+    let context = TestContext::new("3.12");
+
+    // `pip sync` installs exactly what it's given, so `MarkupSafe` is left out.
+    context.pip_sync("Jinja2==3.1.2");
+
+    uv_snapshot!(context.filters(), command(&context), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Checked 1 package in [TIME]
+    Found 1 incompatibility
+    error: The package `jinja2` requires `MarkupSafe >=2.0`, but it's not installed.
+    "###
+    );
+}
+
+/// Check an environment in which an installed dependency has an incompatible version.
+#[test]
+fn check_incompatible_dependency() {
+    let context = TestContext::new("3.12");
+
+    context.pip_sync("Jinja2==3.1.2\nMarkupSafe==1.1.1");
+
+    uv_snapshot!(context.filters(), command(&context), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    Checked 2 packages in [TIME]
+    Found 1 incompatibility
+    error: The package `jinja2` requires `MarkupSafe >=2.0`, but `1.1.1` is installed.
+    "###
+    );
+}
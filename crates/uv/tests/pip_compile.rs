@@ -6,6 +6,7 @@ use std::path::PathBuf;
 use std::process::Command;
 
 use anyhow::{bail, Context, Result};
+use assert_cmd::prelude::*;
 use assert_fs::prelude::*;
 use assert_fs::TempDir;
 use indoc::indoc;
@@ -31,7 +32,7 @@ fn compile_requirements_in() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     asgiref==3.7.2
         # via django
@@ -60,7 +61,7 @@ fn compile_requirements_in_annotation_line() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z --annotation-style=line requirements.in
     asgiref==3.7.2            # via django
     django==5.0b1
@@ -88,7 +89,7 @@ fn compile_requirements_in_stdin() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z -
     asgiref==3.7.2
         # via django
@@ -103,6 +104,97 @@ fn compile_requirements_in_stdin() -> Result<()> {
     Ok(())
 }
 
+/// Read requirements from stdin and write the resolution to stdout, as in a shell pipeline.
+#[test]
+fn compile_requirements_in_stdin_output_file_stdout() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("django==5.0b1")?;
+
+    uv_snapshot!(context
+        .compile()
+        .stdin(fs::File::open(requirements_in)?)
+        .arg("-")
+        .arg("--output-file")
+        .arg("-"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z - --output-file -
+    asgiref==3.7.2
+        # via django
+    django==5.0b1
+    sqlparse==0.4.4
+        # via django
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###);
+
+    // No file named `-` should be created.
+    context
+        .temp_dir
+        .child("-")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}
+
+/// Stdin can only be read once.
+#[test]
+fn compile_requirements_in_stdin_twice() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(context
+        .compile()
+        .arg("-")
+        .arg("--constraint")
+        .arg("-"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Requirements can only be read from stdin (`-`) once, but `-` was provided multiple times
+    "###);
+}
+
+/// Resolve multiple input files together, annotating each package with the file(s) that
+/// requested it.
+#[test]
+fn compile_multiple_requirements_files() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("django==5.0b1")?;
+    let dev_in = context.temp_dir.child("dev.in");
+    dev_in.write_str("sqlparse")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("dev.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in dev.in
+    asgiref==3.7.2
+        # via django
+    django==5.0b1
+        # via -r requirements.in
+    sqlparse==0.4.4
+        # via
+        #   -r dev.in
+        #   django
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 #[test]
 fn missing_requirements_in() {
     let context = TestContext::new("3.12");
@@ -174,7 +266,7 @@ dependencies = [
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z pyproject.toml
     asgiref==3.7.2
         # via django
@@ -207,7 +299,7 @@ fn compile_constraints_txt() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
     asgiref==3.7.2
         # via django
@@ -228,8 +320,7 @@ fn compile_constraints_txt() -> Result<()> {
 fn compile_constraints_inline() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("django==5.0b1")?;
-    requirements_in.write_str("-c constraints.txt")?;
+    requirements_in.write_str("django==5.0b1\n-c constraints.txt")?;
 
     let constraints_txt = context.temp_dir.child("constraints.txt");
     constraints_txt.write_str("sqlparse<0.4.4")?;
@@ -239,11 +330,78 @@ fn compile_constraints_inline() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    asgiref==3.7.2
+        # via django
+    django==5.0b1
+    sqlparse==0.4.3
+        # via django
 
     ----- stderr -----
-    Resolved 0 packages in [TIME]
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a package from a `requirements.in` file that includes another requirements file,
+/// which in turn references a constraints file. Constrained packages that aren't otherwise
+/// required should not be included in the resolution.
+#[test]
+fn compile_constraints_nested() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("-r base.in")?;
+
+    let base_in = context.temp_dir.child("base.in");
+    base_in.write_str("django==5.0b1\n-c constraints.txt")?;
+
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("sqlparse<0.4.4\nflask<3")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    asgiref==3.7.2
+        # via django
+    django==5.0b1
+    sqlparse==0.4.3
+        # via django
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Editable requirements can't be used as constraints.
+#[test]
+fn compile_constraints_editable() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("django==5.0b1")?;
+
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("-e ./django")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--constraint")
+            .arg("constraints.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Editable requirements are not supported as constraints (found in `constraints.txt`)
     "###
     );
 
@@ -271,7 +429,7 @@ fn compile_constraints_markers() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
     anyio==4.0.0
     idna==3.4
@@ -312,7 +470,7 @@ optional-dependencies.foo = [
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z pyproject.toml --extra foo
     asgiref==3.7.2
         # via django
@@ -353,7 +511,7 @@ optional-dependencies."FrIeNdLy-._.-bArD" = [
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z pyproject.toml --extra FRiENDlY-...-_-BARd
     asgiref==3.7.2
         # via django
@@ -403,6 +561,186 @@ optional-dependencies.foo = [
     Ok(())
 }
 
+/// Show a warning if the user tries to compile a `pyproject.toml` file with dynamic dependencies.
+#[test]
+fn compile_pyproject_toml_dynamic_dependencies() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(
+        r#"[build-system]
+requires = ["setuptools>=42", "wheel"]
+
+[project]
+name = "project"
+version = "0.1.0"
+dynamic = ["dependencies"]
+"#,
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("pyproject.toml"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z pyproject.toml
+
+    ----- stderr -----
+    warning: `pyproject.toml` declares its dependencies as dynamic, which is not supported; only statically declared `project.dependencies` and `project.optional-dependencies` will be included
+    Resolved 0 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve the requirements declared by a `setup.py` file, including an extra.
+#[test]
+fn compile_setup_py() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let setup_py = context.temp_dir.child("setup.py");
+    setup_py.write_str(
+        r#"from setuptools import setup
+
+setup(
+    name="project",
+    version="0.1.0",
+    install_requires=["anyio==4.0.0"],
+    extras_require={"dev": ["iniconfig==2.0.0"]},
+)
+"#,
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("setup.py")
+            .arg("--extra")
+            .arg("dev"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z setup.py --extra dev
+    anyio==4.0.0
+    idna==3.4
+        # via anyio
+    iniconfig==2.0.0
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 4 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve the requirements declared by a `setup.cfg` file.
+#[test]
+fn compile_setup_cfg() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let setup_cfg = context.temp_dir.child("setup.cfg");
+    setup_cfg.write_str(indoc! {r"
+        [metadata]
+        name = project
+        version = 0.1.0
+
+        [options]
+        install_requires =
+            anyio==4.0.0
+    "})?;
+    let setup_py = context.temp_dir.child("setup.py");
+    setup_py.write_str("from setuptools import setup\n\nsetup()\n")?;
+
+    uv_snapshot!(context.compile()
+            .arg("setup.cfg"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z setup.cfg
+    anyio==4.0.0
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Reject a `setup.cfg` file without a `setup.py` or `pyproject.toml` alongside it, since there's
+/// nothing to invoke `setuptools` with.
+#[test]
+fn compile_setup_cfg_without_setup_py() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let setup_cfg = context.temp_dir.child("setup.cfg");
+    setup_cfg.write_str(indoc! {r"
+        [metadata]
+        name = project
+        version = 0.1.0
+
+        [options]
+        install_requires =
+            anyio==4.0.0
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("setup.cfg"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to determine the requirements of `setup.cfg`: expected a `setup.py` or `pyproject.toml` file in the same directory
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve the requirements declared by a `setup.py` file, with build constraints that conflict
+/// with the default build requirements.
+#[test]
+fn compile_setup_py_build_constraints() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let setup_py = context.temp_dir.child("setup.py");
+    setup_py.write_str(
+        r#"from setuptools import setup
+
+setup(
+    name="project",
+    version="0.1.0",
+    install_requires=["anyio==4.0.0"],
+)
+"#,
+    )?;
+
+    // Without build constraints, the build succeeds.
+    context.compile().arg("setup.py").assert().success();
+
+    // Constrain `setuptools` to a version that doesn't satisfy `setuptools>=40.8.0`.
+    let build_constraints_txt = context.temp_dir.child("build_constraints.txt");
+    build_constraints_txt.write_str("setuptools==40.0.0")?;
+
+    context
+        .compile()
+        .arg("setup.py")
+        .arg("--build-constraint")
+        .arg("build_constraints.txt")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Failed to install requirements from setup.py build (resolve)",
+        ));
+
+    Ok(())
+}
+
 /// Show a dedicated warning if the user tries to compile a `pyproject.toml` file with a `poetry`
 /// section.
 #[test]
@@ -431,7 +769,7 @@ build-backend = "poetry.core.masonry.api"
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z pyproject.toml
 
     ----- stderr -----
@@ -497,7 +835,7 @@ fn compile_requirements_file_extra() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    error: Requesting extras requires a pyproject.toml input file.
+    error: Requesting extras requires a pyproject.toml, setup.py, or setup.cfg input file.
     "###
     );
 
@@ -554,7 +892,7 @@ fn compile_python_312() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --python-version 3.12
     black==23.10.1
     click==8.1.7
@@ -576,54 +914,203 @@ fn compile_python_312() -> Result<()> {
     Ok(())
 }
 
-/// Resolve a specific version of Black at Python 3.12 with `--annotation-style=line`.
+/// Resolve a specific version of Black at Python 3.12 in universal mode, preserving the
+/// `platform_system` marker on `colorama` and the `python_version` markers on `tomli` and
+/// `typing-extensions`.
 #[test]
-fn compile_python_312_annotation_line() -> Result<()> {
+fn compile_python_312_universal() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
     requirements_in.write_str("black==23.10.1")?;
 
     uv_snapshot!(context.compile()
-            .arg("--annotation-style=line")
             .arg("requirements.in")
             .arg("--python-version")
-            .arg("3.12"), @r###"
+            .arg("3.12")
+            .arg("--universal"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z --annotation-style=line requirements.in --python-version 3.12
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --python-version 3.12 --universal
     black==23.10.1
-    click==8.1.7              # via black
-    mypy-extensions==1.0.0    # via black
-    packaging==23.2           # via black
-    pathspec==0.11.2          # via black
-    platformdirs==4.0.0       # via black
+    click==8.1.7
+        # via black
+    colorama==0.4.6 ; platform_system == 'Windows'
+        # via click
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+    tomli==2.0.1 ; python_version < '3.11'
+        # via black
+    typing-extensions==4.8.0 ; python_version < '3.11'
+        # via black
 
     ----- stderr -----
-    Resolved 6 packages in [TIME]
+    Resolved 9 packages in [TIME]
     "###
     );
 
     Ok(())
 }
 
-/// Resolve a specific version of Black at Python 3.12 without deps.
+/// Resolve conflicting requirements on `anyio` in universal mode, by forking the resolution on
+/// the disjoint `python_version` markers, such that each version is pinned with its marker.
 #[test]
-fn compile_python_312_no_deps() -> Result<()> {
+fn compile_universal_fork() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("black==23.10.1")?;
+    requirements_in.write_str(indoc! {r"
+        anyio==4.0.0 ; python_version >= '3.11'
+        anyio==3.7.1 ; python_version < '3.11'
+    "})?;
 
     uv_snapshot!(context.compile()
             .arg("requirements.in")
-            .arg("--no-deps")
-            .arg("--python-version")
-            .arg("3.12"), @r###"
+            .arg("--universal"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --universal
+    anyio==3.7.1 ; python_version < '3.11'
+    anyio==4.0.0 ; python_version >= '3.11'
+    exceptiongroup==1.1.3 ; python_version < '3.11'
+        # via anyio
+    idna==3.4
+        # via anyio
+    sniffio==1.3.0
+        # via anyio
+    typing-extensions==4.8.0 ; python_version < '3.8'
+        # via anyio
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a specific version of Black for Windows, from any platform, such that `colorama` is
+/// included via its `platform_system` marker.
+#[test]
+fn compile_python_platform_windows() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--python-platform")
+            .arg("windows"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --python-platform windows
+    black==23.10.1
+    click==8.1.7
+        # via black
+    colorama==0.4.6
+        # via click
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    // Resolving for Linux should omit `colorama`.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--python-platform")
+            .arg("x86_64-manylinux2014"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --python-platform x86_64-manylinux2014
+    black==23.10.1
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a specific version of Black at Python 3.12 with `--annotation-style=line`.
+#[test]
+fn compile_python_312_annotation_line() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("--annotation-style=line")
+            .arg("requirements.in")
+            .arg("--python-version")
+            .arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z --annotation-style=line requirements.in --python-version 3.12
+    black==23.10.1
+    click==8.1.7              # via black
+    mypy-extensions==1.0.0    # via black
+    packaging==23.2           # via black
+    pathspec==0.11.2          # via black
+    platformdirs==4.0.0       # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a specific version of Black at Python 3.12 without deps.
+#[test]
+fn compile_python_312_no_deps() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-deps")
+            .arg("--python-version")
+            .arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-deps --python-version 3.12
     black==23.10.1
 
@@ -689,7 +1176,7 @@ fn compile_sdist_resolution_lowest() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --resolution=lowest-direct --python-version 3.12
     anyio @ https://files.pythonhosted.org/packages/2d/b8/7333d87d5f03247215d86a86362fd3e324111788c6cdd8d2e6196a6ba833/anyio-4.2.0.tar.gz
     idna==3.4
@@ -770,7 +1257,7 @@ fn compile_numpy_py38() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-build
     numpy==1.24.4
 
@@ -782,6 +1269,34 @@ fn compile_numpy_py38() -> Result<()> {
     Ok(())
 }
 
+/// Reject versions that only provide a source distribution when builds are disabled for the
+/// package, rather than failing to build them after resolution.
+#[test]
+fn compile_only_binary_package() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // `sgmllib3k` is only published as a source distribution.
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("sgmllib3k")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--only-binary")
+            .arg("sgmllib3k"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because only sgmllib3k==1.0.0 is available and sgmllib3k==1.0.0 is unusable because no usable wheels are available and building from source is disabled, we can conclude that all versions of sgmllib3k cannot be used.
+          And because you require sgmllib3k, we can conclude that the requirements are unsatisfiable.
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a specific Flask wheel via a URL dependency.
 #[test]
 fn compile_wheel_url_dependency() -> Result<()> {
@@ -794,7 +1309,7 @@ fn compile_wheel_url_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -834,7 +1349,7 @@ fn compile_sdist_url_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -879,7 +1394,7 @@ fn compile_git_https_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -917,7 +1432,7 @@ fn compile_git_branch_https_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     click==8.1.7
         # via flask
@@ -954,7 +1469,7 @@ fn compile_git_tag_https_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -995,7 +1510,7 @@ fn compile_git_long_commit_https_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     click==8.1.7
         # via flask
@@ -1032,7 +1547,7 @@ fn compile_git_short_commit_https_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     click==8.1.7
         # via flask
@@ -1070,7 +1585,7 @@ fn compile_git_refs_https_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -1109,7 +1624,7 @@ fn compile_git_subdirectory_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     example-pkg-a @ git+https://github.com/pypa/sample-namespace-packages.git@df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45#subdirectory=pkg_resources/pkg_a
 
@@ -1135,7 +1650,7 @@ fn compile_git_concurrent_access() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     example-pkg-a @ git+https://github.com/pypa/sample-namespace-packages.git@df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45#subdirectory=pkg_resources/pkg_a
     example-pkg-b @ git+https://github.com/pypa/sample-namespace-packages.git@df7530eeb8fa0cb7dbb8ecb28363e8e36bfa2f45#subdirectory=pkg_resources/pkg_b
@@ -1185,7 +1700,7 @@ fn mixed_url_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -1230,6 +1745,9 @@ fn conflicting_direct_url_dependency() -> Result<()> {
       ╰─▶ Because there is no version of werkzeug==3.0.0 and you require
           werkzeug==3.0.0, we can conclude that the requirements are
           unsatisfiable.
+
+          hint: werkzeug was requested via a direct URL, which provides
+          werkzeug==2.0.0, but werkzeug==3.0.0 was also required
     "###
     );
 
@@ -1249,7 +1767,7 @@ fn compatible_direct_url_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     werkzeug @ https://files.pythonhosted.org/packages/ff/1d/960bb4017c68674a1cb099534840f18d3def3ce44aed12b5ed8b78e0153e/Werkzeug-2.0.0-py3-none-any.whl
 
@@ -1300,7 +1818,7 @@ fn conflicting_repeated_url_dependency_markers() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     werkzeug @ https://files.pythonhosted.org/packages/bd/24/11c3ea5a7e866bf2d97f0501d0b4b1c9bbeade102bb4b588f0d2919a5212/Werkzeug-2.0.1-py3-none-any.whl
 
@@ -1374,7 +1892,7 @@ fn compatible_repeated_url_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     werkzeug @ git+https://github.com/pallets/werkzeug@af160e0b6b7ddd81c22f1652c728ff5ac72d5c74
 
@@ -1428,7 +1946,7 @@ fn allowed_transitive_url_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
     transitive-url-dependency @ https://github.com/astral-sh/ruff/files/14078476/transitive_url_dependency.zip
     werkzeug @ git+https://github.com/pallets/werkzeug@af160e0b6b7ddd81c22f1652c728ff5ac72d5c74
@@ -1462,7 +1980,7 @@ fn allowed_transitive_canonical_url_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
     transitive-url-dependency @ https://github.com/astral-sh/ruff/files/14078476/transitive_url_dependency.zip
     werkzeug @ git+https://github.com/pallets/werkzeug.git@af160e0b6b7ddd81c22f1652c728ff5ac72d5c74
@@ -1503,7 +2021,7 @@ optional-dependencies.bar = [
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z pyproject.toml --all-extras
     anyio==4.0.0
         # via httpcore
@@ -1559,7 +2077,7 @@ optional-dependencies.bar = [
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z --annotation-style=line pyproject.toml --all-extras
     anyio==4.0.0              # via httpcore
     asgiref==3.7.2            # via django
@@ -1708,7 +2226,7 @@ fn compile_exclude_newer() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile requirements.in --exclude-newer 2022-04-04T12:00:00Z --cache-dir [CACHE_DIR]
     tqdm==4.64.0
 
@@ -1732,7 +2250,7 @@ fn compile_exclude_newer() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile requirements.in --exclude-newer 2022-04-04 --cache-dir [CACHE_DIR]
     tqdm==4.64.0
 
@@ -1766,6 +2284,64 @@ fn compile_exclude_newer() -> Result<()> {
     Ok(())
 }
 
+/// Override the global `--exclude-newer` cutoff for a single package.
+#[test]
+fn compile_exclude_newer_package() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(Command::new(get_bin())
+            .arg("pip")
+            .arg("compile")
+            .arg("requirements.in")
+            .arg("--exclude-newer")
+            // 4.64.0: 2022-04-04T01:48:46.194635Z1
+            // 4.64.1: 2022-09-03T11:10:27.148080Z
+            .arg("2022-09-04T12:00:00Z")
+            .arg("--exclude-newer-package")
+            .arg("tqdm=2022-04-04T12:00:00Z")
+            .arg("--cache-dir")
+            .arg(context.cache_dir.path())
+            .env("VIRTUAL_ENV", context.venv.as_os_str())
+            .current_dir(context.temp_dir.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile requirements.in --exclude-newer 2022-09-04T12:00:00Z --exclude-newer-package tqdm=2022-04-04T12:00:00Z --cache-dir [CACHE_DIR]
+    tqdm==4.64.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    // Check the error message for a missing package name.
+    uv_snapshot!(Command::new(get_bin())
+            .arg("pip")
+            .arg("compile")
+            .arg("requirements.in")
+            .arg("--exclude-newer-package")
+            .arg("2022-04-04")
+            .arg("--cache-dir")
+            .arg(context.cache_dir.path())
+            .env("VIRTUAL_ENV", context.venv.as_os_str())
+            .current_dir(context.temp_dir.path()), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: invalid value '2022-04-04' for '--exclude-newer-package <EXCLUDE_NEWER_PACKAGE>': Expected `PACKAGE=DATE`, found: `2022-04-04`
+
+    For more information, try '--help'.
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a local path dependency on a specific wheel.
 #[test]
 fn compile_wheel_path_dependency() -> Result<()> {
@@ -1794,7 +2370,7 @@ fn compile_wheel_path_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -1825,7 +2401,7 @@ fn compile_wheel_path_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -1857,7 +2433,7 @@ fn compile_wheel_path_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -1889,7 +2465,7 @@ fn compile_wheel_path_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -1928,7 +2504,7 @@ fn compile_wheel_path_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -1981,7 +2557,7 @@ fn compile_source_distribution_path_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -2006,6 +2582,91 @@ fn compile_source_distribution_path_dependency() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a local path dependency, writing it relative to the output file with
+/// `--relative-paths`.
+#[test]
+fn compile_relative_paths() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Download a wheel into a `libs` directory.
+    let response = reqwest::blocking::get("https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl")?;
+    let flask_wheel = context
+        .temp_dir
+        .child("libs")
+        .child("flask-3.0.0-py3-none-any.whl");
+    flask_wheel.touch()?;
+    let mut flask_wheel_file = fs::File::create(&flask_wheel)?;
+    std::io::copy(&mut response.bytes()?.as_ref(), &mut flask_wheel_file)?;
+
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask @ ./libs/flask-3.0.0-py3-none-any.whl")?;
+
+    // Write the output file to a sibling directory; the path should be relative to it.
+    context.temp_dir.child("requirements").create_dir_all()?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements/requirements.txt")
+            .arg("--relative-paths")
+            .arg("--no-header"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask @ ../libs/flask-3.0.0-py3-none-any.whl
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.2
+        # via flask
+    markupsafe==2.1.3
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    let requirements_txt = context
+        .temp_dir
+        .child("requirements")
+        .child("requirements.txt");
+    requirements_txt.assert(predicates::str::contains(
+        "flask @ ../libs/flask-3.0.0-py3-none-any.whl",
+    ));
+
+    // When writing to stdout, the path should be relative to the current directory.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--relative-paths")
+            .arg("--no-header")
+            .arg("--no-annotate"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    blinker==1.7.0
+    click==8.1.7
+    flask @ ./libs/flask-3.0.0-py3-none-any.whl
+    itsdangerous==2.1.2
+    jinja2==3.1.2
+    markupsafe==2.1.3
+    werkzeug==3.0.1
+
+    ----- stderr -----
+    Resolved 7 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a local path dependency to a non-existent file.
 #[test]
 fn compile_wheel_path_dependency_missing() -> Result<()> {
@@ -2044,12 +2705,13 @@ fn compile_yanked_version_direct() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     attrs==21.1.0
 
     ----- stderr -----
     Resolved 1 package in [TIME]
+    warning: `attrs==21.1.0` is yanked (reason: "Installable but not importable on Python 3.4").
     "###
     );
 
@@ -2086,31 +2748,114 @@ fn compile_yanked_version_indirect() -> Result<()> {
     Ok(())
 }
 
-/// Flask==3.0.0 depends on Werkzeug>=3.0.0. Demonstrate that we can override this
-/// requirement with an incompatible version.
+/// Resolve `attrs` to a yanked version (`21.1.0`) indirectly, by allowing all yanked versions.
 #[test]
-fn override_dependency() -> Result<()> {
+fn compile_yanked_version_indirect_allow_yanked() -> Result<()> {
     let context = TestContext::new("3.12");
     let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("flask==3.0.0")?;
-
-    let overrides_txt = context.temp_dir.child("overrides.txt");
-    overrides_txt.write_str("werkzeug==2.3.0")?;
+    requirements_in.write_str("attrs>20.3.0,<21.2.0")?;
 
     uv_snapshot!(context.compile()
             .arg("requirements.in")
-            .arg("--override")
-            .arg("overrides.txt"), @r###"
+            .arg("--allow-yanked"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --override overrides.txt
-    blinker==1.7.0
-        # via flask
-    click==8.1.7
-        # via flask
-    flask==3.0.0
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --allow-yanked
+    attrs==21.1.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    warning: `attrs==21.1.0` is yanked (reason: "Installable but not importable on Python 3.4").
+    "###
+    );
+
+    Ok(())
+}
+
+/// Fail to resolve a yanked version of `attrs`, even when specified directly, if yanked versions
+/// are denied.
+#[test]
+fn compile_yanked_version_direct_deny_yanked() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("attrs==21.1.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--deny-yanked"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+      × No solution found when resolving dependencies:
+      ╰─▶ Because attrs==21.1.0 is unusable because it was yanked (reason:
+          Installable but not importable on Python 3.4) and you require
+          attrs==21.1.0, we can conclude that the requirements are
+          unsatisfiable.
+    "###
+    );
+
+    Ok(())
+}
+
+/// Resolve a yanked version of `attrs` by pinning the version in an overrides file.
+#[test]
+fn override_yanked_version() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("attrs")?;
+
+    let overrides_txt = context.temp_dir.child("overrides.txt");
+    overrides_txt.write_str("attrs==21.1.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--override")
+            .arg("overrides.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --override overrides.txt
+    attrs==21.1.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    warning: `attrs==21.1.0` is yanked (reason: "Installable but not importable on Python 3.4").
+    "###
+    );
+
+    Ok(())
+}
+
+/// Flask==3.0.0 depends on Werkzeug>=3.0.0. Demonstrate that we can override this
+/// requirement with an incompatible version.
+#[test]
+fn override_dependency() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask==3.0.0")?;
+
+    let overrides_txt = context.temp_dir.child("overrides.txt");
+    overrides_txt.write_str("werkzeug==2.3.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--override")
+            .arg("overrides.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --override overrides.txt
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask==3.0.0
     itsdangerous==2.1.2
         # via flask
     jinja2==3.1.2
@@ -2150,7 +2895,7 @@ fn override_multi_dependency() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --override overrides.txt
     black==23.10.1
     click==8.1.7
@@ -2186,7 +2931,7 @@ fn missing_registry_extra() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     black==23.10.1
     click==8.1.7
@@ -2221,7 +2966,7 @@ fn missing_url_extra() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -2261,7 +3006,7 @@ fn preserve_url() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -2306,7 +3051,7 @@ fn preserve_env_var() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     blinker==1.7.0
         # via flask
@@ -2363,7 +3108,7 @@ fn compile_editable() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile requirements.in --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z
     -e ${PROJECT_ROOT}/../../scripts/editable-installs/maturin_editable
     -e ../../scripts/editable-installs/poetry_editable
@@ -2424,7 +3169,7 @@ fn recursive_extras_direct_url() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile requirements.in --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z
     aiohttp==3.9.0
         # via black
@@ -2480,7 +3225,7 @@ fn compile_editable_url_requirement() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile requirements.in --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z
     -e ../../scripts/editable-installs/hatchling_editable
     iniconfig @ https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl
@@ -2514,7 +3259,7 @@ fn cache_errors_are_non_fatal() -> Result<()> {
     let interpreter_cache = context
         .cache_dir
         .path()
-        .join("interpreter-v0")
+        .join("interpreter-v1")
         .read_dir()?
         .next()
         .context("Expected a python interpreter cache file")??
@@ -2606,7 +3351,7 @@ fn compile_html() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile requirements.in --cache-dir [CACHE_DIR]
     jinja2==3.1.2
     markupsafe==2.1.3
@@ -2634,7 +3379,7 @@ fn trailing_slash() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     jinja2==3.1.2
     markupsafe==2.1.3
@@ -2652,7 +3397,7 @@ fn trailing_slash() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     jinja2==3.1.2
     markupsafe==2.1.3
@@ -2678,7 +3423,7 @@ fn compile_legacy_sdist_pep_517() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     flake8 @ https://files.pythonhosted.org/packages/66/53/3ad4a3b74d609b3b9008a10075c40e7c8909eae60af53623c3888f7a529a/flake8-6.0.0.tar.gz
     mccabe==0.7.0
@@ -2709,7 +3454,7 @@ fn compile_legacy_sdist_setuptools() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --legacy-setup-py
     flake8 @ https://files.pythonhosted.org/packages/66/53/3ad4a3b74d609b3b9008a10075c40e7c8909eae60af53623c3888f7a529a/flake8-6.0.0.tar.gz
     mccabe==0.7.0
@@ -2759,7 +3504,7 @@ fn generate_hashes() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --generate-hashes
     blinker==1.7.0 \
         --hash=sha256:c3f865d4d54db7abc53758a01601cf343fe55b84c1de4e3fa910e420b438d5b9 \
@@ -2857,6 +3602,127 @@ fn generate_hashes() -> Result<()> {
     Ok(())
 }
 
+/// Include a hash for a direct URL requirement that doesn't include one in the URL itself, by
+/// hashing the archive locally.
+#[test]
+fn generate_hashes_url_without_fragment() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str(&format!(
+        "tqdm @ {}",
+        Url::from_file_path(
+            project_root
+                .join("scripts")
+                .join("wheels")
+                .join("tqdm-1000.0.0-py3-none-any.whl")
+        )
+        .unwrap()
+    ))?;
+
+    let project_root_string = regex::escape(&project_root.normalized_display().to_string());
+    let filters: Vec<_> = [(project_root_string.as_str(), "[PROJECT_ROOT]")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, context.compile()
+        .arg("requirements.in")
+        .arg("--generate-hashes"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --generate-hashes
+    tqdm @ file://[PROJECT_ROOT]/scripts/wheels/tqdm-1000.0.0-py3-none-any.whl \
+        --hash=sha256:a34996d4bd5abb2336e14ff0a2d22b92cfd0f0ed344e6883041ce01953276a13
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile with `--generate-hashes --hash-algorithm sha512`, which requires computing the digests
+/// locally, as PyPI only publishes SHA 256 digests.
+#[test]
+fn generate_hashes_sha512() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==4.0.0")?;
+
+    let filters: Vec<_> = [(r"sha512:[0-9a-f]{128}", "sha512:[HASH]")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect();
+
+    uv_snapshot!(filters, context.compile()
+        .arg("requirements.in")
+        .arg("--generate-hashes")
+        .arg("--hash-algorithm")
+        .arg("sha512"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --generate-hashes --hash-algorithm sha512
+    anyio==4.0.0 \
+        --hash=sha512:[HASH] \
+        --hash=sha512:[HASH]
+    idna==3.4 \
+        --hash=sha512:[HASH] \
+        --hash=sha512:[HASH]
+        # via anyio
+    sniffio==1.3.0 \
+        --hash=sha512:[HASH] \
+        --hash=sha512:[HASH]
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Compile with `--generate-hashes --hash-algorithm sha512` for a package that's only available
+/// via `--find-links`, which requires hashing the pinned file, as there's no index to query.
+#[test]
+fn generate_hashes_sha512_find_links() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm==1000.0.0")?;
+
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--no-index")
+        .arg("--find-links")
+        .arg(project_root.join("scripts").join("wheels"))
+        .arg("--generate-hashes")
+        .arg("--hash-algorithm")
+        .arg("sha512"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --generate-hashes --hash-algorithm sha512
+    tqdm==1000.0.0 \
+        --hash=sha512:9cf96ddf15bd07581e184c1d825f30e3dffe8c4a640946f9168bc566b20288a4ba26061137f6341982c4a294b9c3cc1174dc394a82a416cb829e1155377e1c9a
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Compile using `--find-links` with a local directory.
 #[test]
 fn find_links_directory() -> Result<()> {
@@ -2889,7 +3755,7 @@ fn find_links_directory() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     markupsafe==2.1.3
         # via werkzeug
@@ -2904,6 +3770,83 @@ fn find_links_directory() -> Result<()> {
     Ok(())
 }
 
+/// Compile using `--find-links` with a directory whose wheels are nested in subdirectories.
+#[test]
+fn find_links_directory_nested() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let wheels = context.temp_dir.child("wheels");
+    wheels.child("tqdm").child("1000.0.0").create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        wheels
+            .child("tqdm")
+            .child("1000.0.0")
+            .child("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--find-links")
+            .arg(wheels.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    tqdm==1000.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Compile using `--index-url` with a `file://` URL pointing to a simple index laid out on disk.
+#[test]
+fn index_url_local_directory() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    let project_root = fs_err::canonicalize(std::env::current_dir()?.join("..").join(".."))?;
+    let simple = context.temp_dir.child("simple");
+    simple.child("tqdm").create_dir_all()?;
+    fs_err::copy(
+        project_root
+            .join("scripts")
+            .join("wheels")
+            .join("tqdm-1000.0.0-py3-none-any.whl"),
+        simple.child("tqdm").child("tqdm-1000.0.0-py3-none-any.whl"),
+    )?;
+
+    let index_url = Url::from_directory_path(simple.path()).unwrap();
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--index-url")
+            .arg(index_url.as_str()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    tqdm==1000.0.0
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###);
+
+    Ok(())
+}
+
 /// Compile using `--find-links` with a URL by resolving `tqdm` from the `PyTorch` wheels index.
 #[test]
 fn find_links_url() -> Result<()> {
@@ -2919,7 +3862,7 @@ fn find_links_url() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index
     tqdm==4.64.1
 
@@ -2946,7 +3889,7 @@ fn find_links_requirements_txt() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --emit-find-links
     --find-links https://download.pytorch.org/whl/torch_stable.html
 
@@ -2990,7 +3933,7 @@ fn upgrade_none() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt
     black==23.10.1
     click==8.1.2
@@ -3012,6 +3955,180 @@ fn upgrade_none() -> Result<()> {
     Ok(())
 }
 
+/// Use an existing resolution for `black==23.10.1` in which `click` has been edited to a lower
+/// bound. Only exact pins are preserved, so `click` should be resolved to the latest version,
+/// while `pathspec` should remain pinned.
+#[test]
+fn upgrade_none_lower_bound() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        black==23.10.1
+        click>=8.1.2
+            # via black
+        mypy-extensions==1.0.0
+            # via black
+        packaging==23.2
+            # via black
+        pathspec==0.11.0
+            # via black
+        platformdirs==4.0.0
+            # via black
+    "})?;
+
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt
+    black==23.10.1
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.0
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Use an existing resolution in the JSON format, with a stale version of `sniffio`. Nothing
+/// should change.
+#[test]
+fn upgrade_none_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==4.0.0")?;
+
+    let requirements_json = context.temp_dir.child("requirements.json");
+    requirements_json.write_str(indoc! {r#"
+        {
+          "dependencies": [],
+          "packages": [
+            { "name": "anyio", "version": "4.0.0" },
+            { "name": "idna", "version": "3.4" },
+            { "name": "sniffio", "version": "1.2.0" }
+          ]
+        }
+    "#})?;
+
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--format")
+        .arg("json")
+        .arg("--output-file")
+        .arg("requirements.json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "dependencies": [
+        {
+          "from": "anyio",
+          "range": ">=2.8",
+          "to": "idna"
+        },
+        {
+          "from": "anyio",
+          "range": ">=1.1",
+          "to": "sniffio"
+        }
+      ],
+      "packages": [
+        {
+          "editable": false,
+          "hashes": [],
+          "marker": null,
+          "name": "anyio",
+          "url": null,
+          "version": "4.0.0"
+        },
+        {
+          "editable": false,
+          "hashes": [],
+          "marker": null,
+          "name": "idna",
+          "url": null,
+          "version": "3.4"
+        },
+        {
+          "editable": false,
+          "hashes": [],
+          "marker": null,
+          "name": "sniffio",
+          "url": null,
+          "version": "1.2.0"
+        }
+      ]
+    }
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Use an existing resolution in the JSON format, with a stale version of `sniffio`, when writing
+/// the same output file in the `requirements.txt` format. The existing pins should be respected.
+#[test]
+fn upgrade_none_json_to_requirements_txt() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==4.0.0")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r#"
+        {
+          "dependencies": [],
+          "packages": [
+            { "name": "anyio", "version": "4.0.0" },
+            { "name": "idna", "version": "3.4" },
+            { "name": "sniffio", "version": "1.2.0" }
+          ]
+        }
+    "#})?;
+
+    uv_snapshot!(context.compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt
+    anyio==4.0.0
+    idna==3.4
+        # via anyio
+    sniffio==1.2.0
+        # via anyio
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Use an existing resolution for `black==23.10.1`, with stale versions of `click` and `pathspec`.
 /// Both packages should be upgraded.
 #[test]
@@ -3022,7 +4139,7 @@ fn upgrade_all() -> Result<()> {
 
     let requirements_txt = context.temp_dir.child("requirements.txt");
     requirements_txt.write_str(indoc! {r"
-        # This file was autogenerated by uv via the following command:
+        # This file was autogenerated by uv v[VERSION] via the following command:
         #    uv pip compile requirements.in --python-version 3.12 --cache-dir [CACHE_DIR]
         black==23.10.1
         click==8.1.2
@@ -3045,7 +4162,7 @@ fn upgrade_all() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt
     black==23.10.1
     click==8.1.7
@@ -3077,7 +4194,7 @@ fn upgrade_package() -> Result<()> {
 
     let requirements_txt = context.temp_dir.child("requirements.txt");
     requirements_txt.write_str(indoc! {r"
-        # This file was autogenerated by uv via the following command:
+        # This file was autogenerated by uv v[VERSION] via the following command:
         #    uv pip compile requirements.in --python-version 3.12 --cache-dir [CACHE_DIR]
         black==23.10.1
         click==8.1.2
@@ -3101,8 +4218,121 @@ fn upgrade_package() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt --upgrade-package click
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt
+    black==23.10.1
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.0
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Upgrade multiple packages using the short form of `--upgrade-package`. Only `click` and
+/// `pathspec` should be upgraded.
+#[test]
+fn upgrade_package_multiple() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        # This file was autogenerated by uv v[VERSION] via the following command:
+        #    uv pip compile requirements.in --python-version 3.12 --cache-dir [CACHE_DIR]
+        black==23.10.1
+        click==8.1.2
+            # via black
+        mypy-extensions==0.4.3
+            # via black
+        packaging==23.2
+            # via black
+        pathspec==0.11.0
+            # via black
+        platformdirs==4.0.0
+            # via black
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("-P")
+            .arg("click")
+            .arg("-P")
+            .arg("pathspec"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt
+    black==23.10.1
+    click==8.1.7
+        # via black
+    mypy-extensions==0.4.3
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Upgrade a package using the short form of `--upgrade-package` with the value attached (e.g.,
+/// `-Pclick`). The flag should be omitted from the output header.
+#[test]
+fn upgrade_package_attached() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(indoc! {r"
+        # This file was autogenerated by uv v[VERSION] via the following command:
+        #    uv pip compile requirements.in --python-version 3.12 --cache-dir [CACHE_DIR]
+        black==23.10.1
+        click==8.1.2
+            # via black
+        mypy-extensions==1.0.0
+            # via black
+        packaging==23.2
+            # via black
+        pathspec==0.11.0
+            # via black
+        platformdirs==4.0.0
+            # via black
+    "})?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--output-file")
+            .arg("requirements.txt")
+            .arg("-Pclick"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --output-file requirements.txt
     black==23.10.1
     click==8.1.7
         # via black
@@ -3198,37 +4428,159 @@ fn missing_package_name() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    error: Unsupported requirement in requirements.in at position 0
-      Caused by: URL requirement must be preceded by a package name. Add the name of the package before the URL (e.g., `package_name @ https://...`).
-    https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl
-    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    error: Unsupported requirement in requirements.in at position 0
+      Caused by: URL requirement must be preceded by a package name. Add the name of the package before the URL (e.g., `package_name @ https://...`).
+    https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl
+    ^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^^
+    "###
+    );
+
+    Ok(())
+}
+
+/// Exclude annotations from the output.
+#[test]
+fn no_annotate() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-annotate"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-annotate
+    black==23.10.1
+    click==8.1.7
+    mypy-extensions==1.0.0
+    packaging==23.2
+    pathspec==0.11.2
+    platformdirs==4.0.0
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Include the requested version ranges in the annotations.
+#[test]
+fn annotate_specifiers() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--annotate-specifiers"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --annotate-specifiers
+    black==23.10.1
+    click==8.1.7
+        # via black (>=8.0.0)
+    mypy-extensions==1.0.0
+        # via black (>=0.4.3)
+    packaging==23.2
+        # via black (>=22.0)
+    pathspec==0.11.2
+        # via black (>=0.9.0)
+    platformdirs==4.0.0
+        # via black (>=2)
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Override the command shown in the header of the output.
+#[test]
+fn custom_compile_command() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("black==23.10.1")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--custom-compile-command")
+            .arg("make requirements.txt"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    make requirements.txt
+    black==23.10.1
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    // The command can also be provided via an environment variable.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .env("UV_CUSTOM_COMPILE_COMMAND", "./scripts/lock.sh"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    ./scripts/lock.sh
+    black==23.10.1
+    click==8.1.7
+        # via black
+    mypy-extensions==1.0.0
+        # via black
+    packaging==23.2
+        # via black
+    pathspec==0.11.2
+        # via black
+    platformdirs==4.0.0
+        # via black
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
     "###
     );
 
-    Ok(())
-}
-
-/// Exclude annotations from the output.
-#[test]
-fn no_annotate() -> Result<()> {
-    let context = TestContext::new("3.12");
-    let requirements_in = context.temp_dir.child("requirements.in");
-    requirements_in.write_str("black==23.10.1")?;
-
+    // With `--no-header`, the environment variable is ignored.
     uv_snapshot!(context.compile()
             .arg("requirements.in")
-            .arg("--no-annotate"), @r###"
+            .arg("--no-header")
+            .env("UV_CUSTOM_COMPILE_COMMAND", "./scripts/lock.sh"), @r###"
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
-    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-annotate
     black==23.10.1
     click==8.1.7
+        # via black
     mypy-extensions==1.0.0
+        # via black
     packaging==23.2
+        # via black
     pathspec==0.11.2
+        # via black
     platformdirs==4.0.0
+        # via black
 
     ----- stderr -----
     Resolved 6 packages in [TIME]
@@ -3271,6 +4623,69 @@ fn no_header() -> Result<()> {
     Ok(())
 }
 
+/// Write the resolution as JSON.
+#[test]
+fn format_json() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==4.0.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--format")
+            .arg("json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "dependencies": [
+        {
+          "from": "anyio",
+          "range": ">=2.8",
+          "to": "idna"
+        },
+        {
+          "from": "anyio",
+          "range": ">=1.1",
+          "to": "sniffio"
+        }
+      ],
+      "packages": [
+        {
+          "editable": false,
+          "hashes": [],
+          "marker": null,
+          "name": "anyio",
+          "url": null,
+          "version": "4.0.0"
+        },
+        {
+          "editable": false,
+          "hashes": [],
+          "marker": null,
+          "name": "idna",
+          "url": null,
+          "version": "3.4"
+        },
+        {
+          "editable": false,
+          "hashes": [],
+          "marker": null,
+          "name": "sniffio",
+          "url": null,
+          "version": "1.3.0"
+        }
+      ]
+    }
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Emit warnings when users pass redundant options from `pip-compile`.
 #[test]
 fn allow_unsafe() -> Result<()> {
@@ -3284,7 +4699,7 @@ fn allow_unsafe() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --allow-unsafe
     markupsafe==2.1.3
         # via werkzeug
@@ -3337,7 +4752,7 @@ fn emit_index_urls() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --emit-index-url --extra-index-url https://test.pypi.org/simple/
     --index-url https://pypi.org/simple
     --extra-index-url https://test.pypi.org/simple/
@@ -3362,6 +4777,38 @@ fn emit_index_urls() -> Result<()> {
     Ok(())
 }
 
+/// Emit `--no-index` in place of the index URLs when index lookups are disabled.
+#[test]
+fn emit_index_urls_no_index() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("tqdm")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--no-index")
+            .arg("--emit-index-url")
+            .arg("--emit-find-links")
+            .arg("--find-links")
+            .arg("https://download.pytorch.org/whl/torch_stable.html"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-index --emit-index-url --emit-find-links --find-links https://download.pytorch.org/whl/torch_stable.html
+    --no-index
+    --find-links https://download.pytorch.org/whl/torch_stable.html
+
+    tqdm==4.64.1
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Emit the `--find-links` locations.
 #[test]
 fn emit_find_links() -> Result<()> {
@@ -3377,7 +4824,7 @@ fn emit_find_links() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --emit-find-links --find-links ./
     --find-links ./
 
@@ -3444,7 +4891,7 @@ fn index_url_requirements_txt() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     tqdm==4.66.1
 
@@ -3512,7 +4959,7 @@ fn offline() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     black==23.10.1
     click==8.1.7
@@ -3538,7 +4985,7 @@ fn offline() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --offline
     black==23.10.1
     click==8.1.7
@@ -3612,6 +5059,43 @@ fn offline_find_links() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a Git dependency without network access via the `--offline` flag.
+#[test]
+#[cfg(feature = "git")]
+fn offline_git() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask @ git+https://github.com/pallets/flask.git@3.0.0")?;
+
+    // Resolve with `--offline` with an empty cache.
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--offline"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to download and build: flask @ git+https://github.com/pallets/flask.git@3.0.0
+      Caused by: Git operation failed
+      Caused by: Network connectivity is disabled, but the requested Git repository wasn't found in the cache for: `https://github.com/pallets/flask.git`
+    "###
+    );
+
+    // Populate the cache.
+    context.compile().arg("requirements.in").assert().success();
+
+    // Resolve with `--offline` with a populated cache.
+    context
+        .compile()
+        .arg("requirements.in")
+        .arg("--offline")
+        .assert()
+        .success();
+
+    Ok(())
+}
+
 /// Resolve nested `-r` requirements files with relative paths.
 #[test]
 fn compile_relative_subfile() -> Result<()> {
@@ -3632,7 +5116,7 @@ fn compile_relative_subfile() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     anyio==4.0.0
     idna==3.4
@@ -3660,7 +5144,7 @@ fn compile_none_extra() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     entrypoints==0.3
 
@@ -3691,7 +5175,7 @@ fn compile_types_pytz() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in -o requirements.txt
     types-pytz==2021.1.0
 
@@ -3720,7 +5204,7 @@ fn compile_constraints_compatible_url() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
     anyio @ https://files.pythonhosted.org/packages/bf/cd/d6d9bb1dadf73e7af02d18225cbd2c93f8552e13130484f1c8dcfece292b/anyio-4.2.0-py3-none-any.whl
     idna==3.4
@@ -3754,7 +5238,7 @@ fn compile_constraints_compatible_url_version() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
     anyio @ https://files.pythonhosted.org/packages/bf/cd/d6d9bb1dadf73e7af02d18225cbd2c93f8552e13130484f1c8dcfece292b/anyio-4.2.0-py3-none-any.whl
     idna==3.4
@@ -3838,7 +5322,7 @@ fn index_url_from_command_line() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
     anyio==3.7.1
     idna==3.4
@@ -3889,7 +5373,7 @@ fn no_deps_valid_extra() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-deps
     flask==3.0.0
 
@@ -3901,6 +5385,49 @@ fn no_deps_valid_extra() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a package with an extra using `--strip-extras`, which has no effect, as uv always omits
+/// the extra from the output while retaining its dependencies.
+#[test]
+fn compile_strip_extras() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask[dotenv]==3.0.0")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--strip-extras"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --strip-extras
+    blinker==1.7.0
+        # via flask
+    click==8.1.7
+        # via flask
+    flask==3.0.0
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.2
+        # via flask
+    markupsafe==2.1.3
+        # via
+        #   jinja2
+        #   werkzeug
+    python-dotenv==1.0.0
+        # via flask
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    warning: pip-compile's `--strip-extras` has no effect (uv always strips extras).
+    Resolved 8 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve a package with `--no-deps`, including an invalid extra.
 #[test]
 fn no_deps_invalid_extra() -> Result<()> {
@@ -3914,7 +5441,7 @@ fn no_deps_invalid_extra() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --no-deps
     flask==3.0.0
 
@@ -3955,7 +5482,7 @@ fn editable_invalid_extra() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile requirements.in --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z
     -e ../../scripts/editable-installs/black_editable
 
@@ -3986,7 +5513,7 @@ fn compile_constraints_compatible_version() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt
     distlib==0.3.7
         # via virtualenv
@@ -4084,7 +5611,7 @@ fn editable_override() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --override overrides.txt
     black==23.11.0
     click==8.1.7
@@ -4142,7 +5669,7 @@ fn override_editable() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile requirements.in --override overrides.txt --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z
     -e ../../scripts/editable-installs/black_editable
 
@@ -4177,7 +5704,7 @@ fn override_with_compatible_constraint() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt --override overrides.txt
     anyio==3.0.0
     idna==3.4
@@ -4243,7 +5770,7 @@ fn unsafe_package() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --unsafe-package jinja2 --unsafe-package pydantic
     blinker==1.7.0
         # via flask
@@ -4259,8 +5786,8 @@ fn unsafe_package() -> Result<()> {
     werkzeug==3.0.1
         # via flask
 
-    # The following packages were excluded from the output:
-    # jinja2
+    # The following packages are considered to be unsafe in a requirements file:
+    # jinja2==3.1.2
 
     ----- stderr -----
     Resolved 7 packages in [TIME]
@@ -4284,7 +5811,7 @@ fn pre_release_upper_bound_exclude() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --prerelease=allow
     click==7.1.2
         # via flask
@@ -4320,7 +5847,7 @@ fn pre_release_upper_bound_include() -> Result<()> {
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --prerelease=allow
     click==8.1.7
         # via flask
@@ -4344,6 +5871,48 @@ fn pre_release_upper_bound_include() -> Result<()> {
     Ok(())
 }
 
+/// Resolve a package with an explicit pre-release specifier in a constraints file. With
+/// `--prerelease=explicit`, pre-releases should be allowed for the constrained package.
+#[test]
+fn pre_release_explicit_constraint() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("flask")?;
+    let constraints_txt = context.temp_dir.child("constraints.txt");
+    constraints_txt.write_str("flask<2.0.0rc4")?;
+
+    uv_snapshot!(context.compile()
+            .arg("requirements.in")
+            .arg("--constraint")
+            .arg("constraints.txt")
+            .arg("--prerelease=explicit"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in --constraint constraints.txt --prerelease=explicit
+    click==8.1.7
+        # via flask
+    flask==2.0.0rc2
+    itsdangerous==2.1.2
+        # via flask
+    jinja2==3.1.2
+        # via flask
+    markupsafe==2.1.3
+        # via
+        #   jinja2
+        #   werkzeug
+    werkzeug==3.0.1
+        # via flask
+
+    ----- stderr -----
+    Resolved 6 packages in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
 /// Resolve from a `pyproject.toml` file with a recursive extra.
 #[test]
 fn compile_pyproject_toml_recursive_extra() -> Result<()> {
@@ -4376,7 +5945,7 @@ dev = [
     success: true
     exit_code: 0
     ----- stdout -----
-    # This file was autogenerated by uv via the following command:
+    # This file was autogenerated by uv v[VERSION] via the following command:
     #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z pyproject.toml --extra dev
     pep517==0.13.1
     tomli==2.0.1
@@ -4388,3 +5957,112 @@ dev = [
 
     Ok(())
 }
+
+/// The output file should never contain ANSI escape codes, even if color is forced.
+#[test]
+fn compile_output_file_no_color() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    let output = context
+        .compile()
+        .arg("requirements.in")
+        .arg("--output-file")
+        .arg("requirements.txt")
+        .arg("--color")
+        .arg("always")
+        .output()?;
+    assert!(output.status.success());
+
+    let requirements_txt = fs::read_to_string(context.temp_dir.child("requirements.txt"))?;
+    assert!(
+        !requirements_txt.contains('\x1b'),
+        "Output file contains ANSI escape codes:\n{requirements_txt}"
+    );
+    assert!(!requirements_txt.contains('\r'));
+    assert!(requirements_txt.contains("anyio==3.7.0\n"));
+
+    Ok(())
+}
+
+/// Resolve with a single concurrent download and build.
+#[test]
+fn compile_sequential() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("django==5.0b1")?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in")
+        .env("UV_CONCURRENT_DOWNLOADS", "1")
+        .env("UV_CONCURRENT_BUILDS", "1"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    # This file was autogenerated by uv v[VERSION] via the following command:
+    #    uv pip compile --cache-dir [CACHE_DIR] --exclude-newer 2023-11-18T12:00:00Z requirements.in
+    asgiref==3.7.2
+        # via django
+    django==5.0b1
+    sqlparse==0.4.4
+        # via django
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    "###);
+
+    Ok(())
+}
+
+/// Fail if the requested CA certificate bundle can't be loaded, rather than falling back to the
+/// bundled root certificates.
+#[test]
+fn compile_missing_cert() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in")
+        .arg("--cert")
+        .arg("missing.pem"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to read certificates from: missing.pem
+      Caused by: failed to open file `missing.pem`
+      Caused by: No such file or directory (os error 2)
+    "###);
+
+    Ok(())
+}
+
+/// Fail if the requested client certificate can't be loaded, rather than continuing without it.
+#[test]
+fn compile_missing_client_cert() -> Result<()> {
+    let context = TestContext::new("3.12");
+    let requirements_in = context.temp_dir.child("requirements.in");
+    requirements_in.write_str("anyio==3.7.0")?;
+
+    uv_snapshot!(context
+        .compile()
+        .arg("requirements.in")
+        .arg("--client-cert")
+        .arg("missing.pem"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to read client certificate from: missing.pem
+      Caused by: failed to open file `missing.pem`
+      Caused by: No such file or directory (os error 2)
+    "###);
+
+    Ok(())
+}
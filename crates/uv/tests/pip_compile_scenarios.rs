@@ -82,7 +82,7 @@ fn requires_incompatible_python_version_compatible_override() -> Result<()> {
                  success: true
                  exit_code: 0
                  ----- stdout -----
-                 # This file was autogenerated by uv via the following command:
+                 # This file was autogenerated by uv v[VERSION] via the following command:
                  #    uv pip compile requirements.in --cache-dir [CACHE_DIR] --python-version=3.11
                  albatross==1.0.0
 
@@ -243,7 +243,7 @@ fn requires_incompatible_python_version_compatible_override_no_wheels_available_
                  success: true
                  exit_code: 0
                  ----- stdout -----
-                 # This file was autogenerated by uv via the following command:
+                 # This file was autogenerated by uv v[VERSION] via the following command:
                  #    uv pip compile requirements.in --cache-dir [CACHE_DIR] --python-version=3.11
                  albatross==1.0.0
 
@@ -463,7 +463,7 @@ fn requires_python_patch_version_override_patch_compatible() -> Result<()> {
                  success: true
                  exit_code: 0
                  ----- stdout -----
-                 # This file was autogenerated by uv via the following command:
+                 # This file was autogenerated by uv v[VERSION] via the following command:
                  #    uv pip compile requirements.in --cache-dir [CACHE_DIR] --python-version=3.8.0
                  albatross==1.0.0
 
@@ -0,0 +1,152 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+
+use crate::common::{get_bin, uv_snapshot, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `pip download` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("download")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Download a package and its dependencies into a wheelhouse.
+#[test]
+fn download_wheels() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("anyio==4.0.0")
+        .arg("--dest")
+        .arg("wheelhouse"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Saved 3 files to wheelhouse in [TIME]
+     + anyio-4.0.0-py3-none-any.whl
+     + idna-3.4-py3-none-any.whl
+     + sniffio-1.3.0-py3-none-any.whl
+    "###
+    );
+
+    let wheelhouse = context.temp_dir.child("wheelhouse");
+    wheelhouse
+        .child("anyio-4.0.0-py3-none-any.whl")
+        .assert(predicates::path::is_file());
+    wheelhouse
+        .child("idna-3.4-py3-none-any.whl")
+        .assert(predicates::path::is_file());
+    wheelhouse
+        .child("sniffio-1.3.0-py3-none-any.whl")
+        .assert(predicates::path::is_file());
+
+    // Nothing should be installed into the environment.
+    context.assert_command("import anyio").failure();
+
+    Ok(())
+}
+
+/// Download the source distributions, rather than the wheels, with `--no-binary`.
+#[test]
+fn download_no_binary() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("iniconfig==2.0.0")
+        .arg("--no-binary")
+        .arg(":all:")
+        .arg("--dest")
+        .arg("wheelhouse"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Saved 1 file to wheelhouse in [TIME]
+     + iniconfig-2.0.0.tar.gz
+    "###
+    );
+
+    context
+        .temp_dir
+        .child("wheelhouse")
+        .child("iniconfig-2.0.0.tar.gz")
+        .assert(predicates::path::is_file());
+
+    Ok(())
+}
+
+/// Download wheels for a Python version other than that of the current interpreter.
+#[test]
+fn download_python_version() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    command(&context)
+        .arg("markupsafe==2.1.3")
+        .arg("--only-binary")
+        .arg(":all:")
+        .arg("--python-version")
+        .arg("3.8")
+        .arg("--dest")
+        .arg("wheelhouse")
+        .assert()
+        .success();
+
+    let filenames = std::fs::read_dir(context.temp_dir.child("wheelhouse").path())?
+        .map(|entry| Ok(entry?.file_name().to_string_lossy().to_lowercase()))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(filenames.len(), 1, "{filenames:?}");
+    assert!(
+        filenames[0].starts_with("markupsafe-2.1.3-cp38-cp38-"),
+        "{filenames:?}"
+    );
+
+    Ok(())
+}
+
+/// Download wheels for a platform other than the current platform.
+#[test]
+fn download_python_platform() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    command(&context)
+        .arg("markupsafe==2.1.3")
+        .arg("--only-binary")
+        .arg(":all:")
+        .arg("--python-platform")
+        .arg("windows")
+        .arg("--dest")
+        .arg("wheelhouse")
+        .assert()
+        .success();
+
+    let filenames = std::fs::read_dir(context.temp_dir.child("wheelhouse").path())?
+        .map(|entry| Ok(entry?.file_name().to_string_lossy().to_lowercase()))
+        .collect::<Result<Vec<_>>>()?;
+    assert_eq!(filenames.len(), 1, "{filenames:?}");
+    assert!(
+        filenames[0].starts_with("markupsafe-2.1.3-cp312-cp312-win_amd64"),
+        "{filenames:?}"
+    );
+
+    Ok(())
+}
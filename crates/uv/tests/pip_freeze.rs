@@ -5,8 +5,9 @@ use std::process::Command;
 use anyhow::Result;
 use assert_cmd::prelude::*;
 use assert_fs::prelude::*;
+use url::Url;
 
-use crate::common::{get_bin, uv_snapshot, TestContext};
+use crate::common::{get_bin, uv_snapshot, TestContext, INSTA_FILTERS};
 
 mod common;
 
@@ -175,3 +176,205 @@ fn freeze_url() -> Result<()> {
 
     Ok(())
 }
+
+/// List a Git package in a virtual environment, pinned to the commit it was installed from.
+#[test]
+#[cfg(feature = "git")]
+fn freeze_git() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(
+        "uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage",
+    )?;
+
+    // Run `pip sync`.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg(requirements_txt.path())
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .assert()
+        .success();
+
+    // Run `pip freeze`.
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage@0dacfd662c64cb4ceb16e6cf65a157a8b715b979
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// List a local wheel in a virtual environment.
+#[test]
+fn freeze_path_wheel() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Download a wheel.
+    let response = reqwest::blocking::get("https://files.pythonhosted.org/packages/ef/a6/62565a6e1cf69e10f5727360368e451d4b7f58beeac6173dc9db836a5b46/iniconfig-2.0.0-py3-none-any.whl")?;
+    let archive = context.temp_dir.child("iniconfig-2.0.0-py3-none-any.whl");
+    let mut archive_file = fs_err::File::create(archive.path())?;
+    std::io::copy(&mut response.bytes()?.as_ref(), &mut archive_file)?;
+
+    // Run `pip install`.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("install")
+        .arg(format!(
+            "iniconfig @ {}",
+            Url::from_file_path(archive.path()).unwrap()
+        ))
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .assert()
+        .success();
+
+    // The wheel should be recorded as an archive, rather than a directory.
+    let site_packages = if cfg!(windows) {
+        context.venv.join("Lib").join("site-packages")
+    } else {
+        context
+            .venv
+            .join("lib")
+            .join("python3.12")
+            .join("site-packages")
+    };
+    let direct_url = fs_err::read_to_string(
+        site_packages
+            .join("iniconfig-2.0.0.dist-info")
+            .join("direct_url.json"),
+    )?;
+    assert!(direct_url.contains("\"archive_info\""), "{direct_url}");
+
+    let filters = [(r"file://.*/iniconfig", "file://[TEMP_DIR]/iniconfig")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    // Run `pip freeze`.
+    uv_snapshot!(filters, command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    iniconfig @ file://[TEMP_DIR]/iniconfig-2.0.0-py3-none-any.whl
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// Exclude editable packages from the output.
+#[test]
+fn freeze_exclude_editable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create an editable package.
+    let editable_dir = context.temp_dir.child("example");
+    editable_dir.child("pyproject.toml").write_str(
+        r#"[project]
+name = "example"
+version = "0.0.0"
+requires-python = ">=3.8"
+"#,
+    )?;
+
+    // Run `pip install`.
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("install")
+        .arg("MarkupSafe==2.1.3")
+        .arg("--editable")
+        .arg(editable_dir.path())
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .assert()
+        .success();
+
+    let filters = [(r"file://.*/example", "file://[TEMP_DIR]/example")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    // Run `pip freeze`.
+    uv_snapshot!(filters, command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    -e file://[TEMP_DIR]/example
+    markupsafe==2.1.3
+
+    ----- stderr -----
+    "###
+    );
+
+    // Run `pip freeze --exclude-editable`.
+    uv_snapshot!(filters, command(&context)
+        .arg("--exclude-editable"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    markupsafe==2.1.3
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
+
+/// List packages installed in the legacy `.egg-info` format.
+#[test]
+fn freeze_egg_info() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let site_packages = if cfg!(windows) {
+        context.venv.join("Lib").join("site-packages")
+    } else {
+        context
+            .venv
+            .join("lib")
+            .join("python3.12")
+            .join("site-packages")
+    };
+
+    // An `.egg-info` directory, as written by `setup.py install`.
+    let egg_info = site_packages.join("zstandard-0.22.0-py3.12.egg-info");
+    fs_err::create_dir_all(&egg_info)?;
+    fs_err::write(
+        egg_info.join("PKG-INFO"),
+        "Metadata-Version: 2.1\nName: zstandard\nVersion: 0.22.0\n",
+    )?;
+
+    // An `.egg-info` file, as written by `distutils`.
+    fs_err::write(
+        site_packages.join("simple_launcher-0.1.0-py3.12.egg-info"),
+        "Metadata-Version: 1.0\nName: simple-launcher\nVersion: 0.1.0\n",
+    )?;
+
+    // Run `pip freeze`.
+    uv_snapshot!(command(&context)
+        .arg("--strict"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    simple-launcher==0.1.0
+    zstandard==0.22.0
+
+    ----- stderr -----
+    "###
+    );
+
+    Ok(())
+}
@@ -341,6 +341,7 @@ fn respect_installed_and_reinstall() -> Result<()> {
 
     ----- stderr -----
     Resolved 7 packages in [TIME]
+    Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
      - flask==3.0.0
      + flask==3.0.0
@@ -832,6 +833,38 @@ fn install_git_public_https() {
     context.assert_installed("uv_public_pypackage", "0.1.0");
 }
 
+/// Install a package from a public GitHub repository, fetching only the requested commit rather
+/// than the repository's full history.
+#[test]
+#[cfg(feature = "git")]
+fn install_git_public_https_shallow() {
+    let context = TestContext::new("3.8");
+
+    let mut command = command(&context);
+    command
+        .arg("uv-public-pypackage @ git+https://github.com/astral-test/uv-public-pypackage@main");
+    if cfg!(all(windows, debug_assertions)) {
+        // TODO(konstin): Reduce stack usage in debug mode enough that the tests pass with the
+        // default windows stack of 1MB
+        command.env("UV_STACK_SIZE", (2 * 1024 * 1024).to_string());
+    }
+    command.assert().success();
+
+    context.assert_installed("uv_public_pypackage", "0.1.0");
+
+    // The Git database should be a shallow clone.
+    let databases = context.cache_dir.path().join("git-v0").join("db");
+    let shallow = fs_err::read_dir(&databases)
+        .unwrap()
+        .filter_map(Result::ok)
+        .any(|entry| entry.path().join("shallow").is_file());
+    assert!(
+        shallow,
+        "Expected a shallow clone in: {}",
+        databases.display()
+    );
+}
+
 /// Install a package from a public GitHub repository at a ref that does not exist
 #[test]
 #[cfg(feature = "git")]
@@ -1034,6 +1067,76 @@ fn install_git_private_https_pat_not_authorized() {
     "###);
 }
 
+/// Reinstalling a package from a local source tree should rebuild it, rather than reusing the
+/// cached wheel, even if the project metadata is unchanged.
+#[test]
+fn reinstall_source_tree() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a local package.
+    let project = context.temp_dir.child("example");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.0.0"
+        "#
+    })?;
+    let module = project.child("example.py");
+    module.write_str("MESSAGE = 'original'")?;
+
+    let requirement = format!(
+        "example @ {}",
+        Url::from_directory_path(project.path()).unwrap()
+    );
+
+    let filters = [(r"\(from file://.*\)", "(from [PROJECT_DIR])")]
+        .into_iter()
+        .chain(INSTA_FILTERS.to_vec())
+        .collect::<Vec<_>>();
+
+    uv_snapshot!(filters, command(&context)
+        .arg(&requirement), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.0.0 (from [PROJECT_DIR])
+    "###
+    );
+
+    // Modify a source file, without touching the project metadata.
+    module.write_str("MESSAGE = 'modified'")?;
+
+    // Reinstalling should rebuild the package.
+    uv_snapshot!(filters, command(&context)
+        .arg(&requirement)
+        .arg("--reinstall-package")
+        .arg("example"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     - example==0.0.0 (from [PROJECT_DIR])
+     + example==0.0.0 (from [PROJECT_DIR])
+    "###
+    );
+
+    context
+        .assert_command("import example; print(example.MESSAGE, end='')")
+        .success()
+        .stdout("modified");
+
+    Ok(())
+}
+
 /// Install a package without using pre-built wheels.
 #[test]
 fn reinstall_no_binary() {
@@ -1120,6 +1223,7 @@ fn reinstall_no_binary() {
 
     ----- stderr -----
     Resolved 3 packages in [TIME]
+    Downloaded 1 package in [TIME]
     Installed 1 package in [TIME]
      - anyio==4.0.0
      + anyio==4.0.0
@@ -1267,6 +1371,27 @@ fn no_deps() {
     context.assert_command("import flask").failure();
 }
 
+/// Install a package from a URL to a wheel, without specifying the package name.
+#[test]
+fn install_unnamed_url() {
+    let context = TestContext::new("3.12");
+
+    uv_snapshot!(command(&context)
+        .arg("https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl")
+        .arg("--no-deps"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + flask==3.0.0 (from https://files.pythonhosted.org/packages/36/42/015c23096649b908c809c69388a805a571a3bea44362fe87e33fc3afa01f/flask-3.0.0-py3-none-any.whl)
+    "###
+    );
+}
+
 /// Upgrade a package.
 #[test]
 fn install_upgrade() {
@@ -1936,3 +2061,312 @@ requires-python = ">=3.8"
 
     Ok(())
 }
+
+/// Emit a JSON report of the installed distributions.
+#[test]
+fn install_report() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a local package.
+    let project = context.temp_dir.child("example");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.0.0"
+        "#
+    })?;
+    project.child("example.py").touch()?;
+
+    let requirement = format!(
+        "example @ {}",
+        Url::from_directory_path(project.path()).unwrap()
+    );
+
+    // The package is built from source.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg(&requirement)
+        .arg("--json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "version": "1",
+      "install": [
+        {
+          "name": "example",
+          "version": "0.0.0",
+          "url": "file://[TEMP_DIR]/example/",
+          "hashes": [],
+          "cached": false,
+          "built": true,
+          "editable": false
+        }
+      ]
+    }
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.0.0 (from file://[TEMP_DIR]/example/)
+    "###
+    );
+
+    // Nothing is installed if the requirement is already satisfied.
+    uv_snapshot!(context.filters(), command(&context)
+        .arg(&requirement)
+        .arg("--json"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    {
+      "version": "1",
+      "install": []
+    }
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    // After uninstalling, the package is linked from the cache.
+    uninstall_command(&context)
+        .arg("example")
+        .assert()
+        .success();
+
+    let report = context.temp_dir.child("report.json");
+    command(&context)
+        .arg(&requirement)
+        .arg("--report")
+        .arg(report.path())
+        .assert()
+        .success();
+
+    let temp_dir = Url::from_directory_path(context.temp_dir.path()).unwrap();
+    let report =
+        fs_err::read_to_string(report.path())?.replace(temp_dir.as_str(), "file://[TEMP_DIR]/");
+    insta::assert_snapshot!(report, @r###"
+    {
+      "version": "1",
+      "install": [
+        {
+          "name": "example",
+          "version": "0.0.0",
+          "url": "file://[TEMP_DIR]/example/",
+          "hashes": [],
+          "cached": true,
+          "built": true,
+          "editable": false
+        }
+      ]
+    }
+    "###
+    );
+
+    Ok(())
+}
+
+/// Rebuild a local source tree when any of its files change, not just its `pyproject.toml`.
+#[test]
+fn install_local_source_tree_rebuild_on_change() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a local package.
+    let project = context.temp_dir.child("example");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.0.0"
+        "#
+    })?;
+    project.child("example.py").write_str("VERSION = 1\n")?;
+
+    let requirement = format!(
+        "example @ {}",
+        Url::from_directory_path(project.path()).unwrap()
+    );
+
+    let site_packages = if cfg!(windows) {
+        context.venv.join("Lib").join("site-packages")
+    } else {
+        context
+            .venv
+            .join("lib")
+            .join("python3.12")
+            .join("site-packages")
+    };
+
+    command(&context).arg(&requirement).assert().success();
+    assert_eq!(
+        fs_err::read_to_string(site_packages.join("example.py"))?,
+        "VERSION = 1\n"
+    );
+
+    // Modify the source, without touching the `pyproject.toml`.
+    project.child("example.py").write_str("VERSION = 2\n")?;
+
+    // After uninstalling, the package is rebuilt rather than linked from the stale cache entry.
+    uninstall_command(&context)
+        .arg("example")
+        .assert()
+        .success();
+    command(&context).arg(&requirement).assert().success();
+    assert_eq!(
+        fs_err::read_to_string(site_packages.join("example.py"))?,
+        "VERSION = 2\n"
+    );
+
+    Ok(())
+}
+
+/// Build a source tree against the packages installed in the target environment, rather than in
+/// an isolated build environment.
+#[test]
+fn install_no_build_isolation() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a local package that requires `setuptools` to build.
+    let project = context.temp_dir.child("example");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.0.0"
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+        "#
+    })?;
+    project.child("example.py").touch()?;
+
+    let requirement = format!(
+        "example @ {}",
+        Url::from_directory_path(project.path()).unwrap()
+    );
+
+    // Without build isolation, the build fails, since `setuptools` isn't installed.
+    command(&context)
+        .arg(&requirement)
+        .arg("--no-build-isolation")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains("No module named 'setuptools'"));
+
+    // Once `setuptools` is installed, the build succeeds.
+    command(&context).arg("setuptools").assert().success();
+
+    uv_snapshot!(context.filters(), command(&context)
+        .arg(&requirement)
+        .arg("--no-build-isolation"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + example==0.0.0 (from file://[TEMP_DIR]/example/)
+    "###
+    );
+
+    Ok(())
+}
+
+/// Pass config settings to the build backend of a specific package, overriding the global
+/// settings.
+#[test]
+fn install_config_settings_package() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a local package with an in-tree build backend that echoes its config settings.
+    let project = context.temp_dir.child("example");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.0.0"
+
+        [build-system]
+        requires = []
+        build-backend = "backend"
+        backend-path = ["."]
+        "#
+    })?;
+    project.child("backend.py").write_str(indoc! {r#"
+        def build_wheel(wheel_directory, config_settings=None, metadata_directory=None):
+            raise ValueError(f"config_settings={config_settings!r}")
+        "#
+    })?;
+
+    let requirement = format!(
+        "example @ {}",
+        Url::from_directory_path(project.path()).unwrap()
+    );
+
+    // Settings for other packages are ignored.
+    command(&context)
+        .arg(&requirement)
+        .arg("--config-setting")
+        .arg("key=global")
+        .arg("--config-setting-package")
+        .arg("other:key=other")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "config_settings={'key': 'global'}",
+        ));
+
+    // Settings for the package take precedence over the global settings.
+    command(&context)
+        .arg(&requirement)
+        .arg("--config-setting")
+        .arg("key=global")
+        .arg("--config-setting")
+        .arg("other=global")
+        .arg("--config-setting-package")
+        .arg("example:key=package")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "config_settings={'key': 'package', 'other': 'global'}",
+        ));
+
+    Ok(())
+}
+
+/// Installing a package as editable requires a build backend that implements PEP 660.
+#[test]
+fn install_editable_unsupported_backend() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    // Create a local package with an in-tree build backend that only supports regular wheels.
+    let project = context.temp_dir.child("example");
+    project.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "example"
+        version = "0.0.0"
+
+        [build-system]
+        requires = []
+        build-backend = "backend"
+        backend-path = ["."]
+        "#
+    })?;
+    project.child("backend.py").write_str(indoc! {r#"
+        def build_wheel(wheel_directory, config_settings=None, metadata_directory=None):
+            raise NotImplementedError
+        "#
+    })?;
+
+    command(&context)
+        .arg("-e")
+        .arg(project.path())
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "Build backend `backend` does not define a `build_editable` hook",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,139 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use crate::common::{get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// Create a `pip show` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("pip")
+        .arg("show")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Show a single installed package.
+#[test]
+#[cfg(unix)]
+fn show_requires_required_by() {
+    let context = TestContext::new("3.12");
+
+    context.pip_sync("Jinja2==3.1.2\nMarkupSafe==2.1.3");
+
+    uv_snapshot!(context.filters(), command(&context).arg("jinja2"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Name: jinja2
+    Version: 3.1.2
+    Location: [VENV]/lib/python3.12/site-packages
+    Requires: markupsafe
+    Required-by: 
+
+    ----- stderr -----
+    "###
+    );
+
+    uv_snapshot!(context.filters(), command(&context).arg("markupsafe"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Name: markupsafe
+    Version: 2.1.3
+    Location: [VENV]/lib/python3.12/site-packages
+    Requires: 
+    Required-by: jinja2
+
+    ----- stderr -----
+    "###
+    );
+}
+
+/// Show multiple packages, separated by a divider.
+#[test]
+#[cfg(unix)]
+fn show_many() {
+    let context = TestContext::new("3.12");
+
+    context.pip_sync("MarkupSafe==2.1.3\ntomli==2.0.1");
+
+    uv_snapshot!(context.filters(), command(&context).arg("tomli").arg("markupsafe"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Name: tomli
+    Version: 2.0.1
+    Location: [VENV]/lib/python3.12/site-packages
+    Requires: 
+    Required-by: 
+    ---
+    Name: markupsafe
+    Version: 2.1.3
+    Location: [VENV]/lib/python3.12/site-packages
+    Requires: 
+    Required-by: 
+
+    ----- stderr -----
+    "###
+    );
+}
+
+/// Warn about packages that aren't installed, and fail if none of them are.
+#[test]
+fn show_missing() {
+    let context = TestContext::new("3.12");
+
+    context.pip_sync("tomli==2.0.1");
+
+    uv_snapshot!(context.filters(), command(&context).arg("flask"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    warning: Package(s) not found for: flask
+    "###
+    );
+}
+
+/// List the files installed by a package.
+#[test]
+#[cfg(unix)]
+fn show_files() {
+    let context = TestContext::new("3.12");
+
+    context.pip_sync("tomli==2.0.1");
+
+    uv_snapshot!(context.filters(), command(&context).arg("tomli").arg("--files"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    Name: tomli
+    Version: 2.0.1
+    Location: [VENV]/lib/python3.12/site-packages
+    Requires: 
+    Required-by: 
+    Files:
+      tomli-2.0.1.dist-info/INSTALLER
+      tomli-2.0.1.dist-info/LICENSE
+      tomli-2.0.1.dist-info/METADATA
+      tomli-2.0.1.dist-info/RECORD
+      tomli-2.0.1.dist-info/REQUESTED
+      tomli-2.0.1.dist-info/WHEEL
+      tomli/__init__.py
+      tomli/_parser.py
+      tomli/_re.py
+      tomli/_types.py
+      tomli/py.typed
+
+    ----- stderr -----
+    "###
+    );
+}
@@ -487,6 +487,192 @@ fn upgrade() -> Result<()> {
     Ok(())
 }
 
+/// Upgrade a package with `--dry-run`, which should report the changes without applying them.
+#[test]
+fn dry_run() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("tomli==2.0.0")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--dry-run"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Would uninstall 1 package
+    Would install 1 package
+     - tomli==2.0.0
+     + tomli==2.0.1
+    "###
+    );
+
+    // The environment should be unchanged.
+    context
+        .assert_command(
+            "import importlib.metadata; assert importlib.metadata.version('tomli') == '2.0.0'",
+        )
+        .success();
+
+    // If the environment is already in sync, the dry run should succeed.
+    requirements_txt.write_str("tomli==2.0.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--dry-run"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Audit the environment against the requirements with `--check`.
+#[test]
+fn check() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("tomli==2.0.0")?;
+
+    command(&context)
+        .arg("requirements.txt")
+        .arg("--strict")
+        .assert()
+        .success();
+
+    // If the environment is in sync, the check should succeed.
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--check"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    // Modify an installed file, and require an additional package.
+    let site_packages = if cfg!(windows) {
+        context.venv.join("Lib").join("site-packages")
+    } else {
+        context
+            .venv
+            .join("lib")
+            .join("python3.12")
+            .join("site-packages")
+    };
+    fs_err::write(site_packages.join("tomli").join("__init__.py"), "")?;
+    requirements_txt.write_str("tomli==2.0.0\niniconfig==2.0.0")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--check"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Found 2 differences between the environment and the requirements
+     + iniconfig==2.0.0
+     ~ tomli==2.0.0 (modified: tomli/__init__.py)
+    "###
+    );
+
+    // Require a different version.
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--check"), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Found 2 differences between the environment and the requirements
+     - tomli==2.0.0
+     + tomli==2.0.1
+    "###
+    );
+
+    // The environment should be unchanged.
+    context
+        .assert_command(
+            "import importlib.metadata; assert importlib.metadata.version('tomli') == '2.0.0'",
+        )
+        .success();
+
+    Ok(())
+}
+
+/// Install a package under a `--prefix`, rather than into the virtual environment.
+#[test]
+fn prefix() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    let prefix = context.temp_dir.child("prefix");
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--prefix")
+        .arg(prefix.path()), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + markupsafe==2.1.3
+    "###
+    );
+
+    // The package should be installed under the prefix, and not in the virtual environment.
+    let site_packages = if cfg!(windows) {
+        prefix.child("Lib").child("site-packages")
+    } else {
+        prefix
+            .child("lib")
+            .child("python3.12")
+            .child("site-packages")
+    };
+    site_packages
+        .child("markupsafe")
+        .assert(predicates::path::is_dir());
+    context.assert_command("import markupsafe").failure();
+
+    Ok(())
+}
+
 /// Install a package into a virtual environment from a URL.
 #[test]
 fn install_url() -> Result<()> {
@@ -1926,6 +2112,8 @@ fn reinstall() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
+    Resolved 2 packages in [TIME]
+    Downloaded 2 packages in [TIME]
     Uninstalled 2 packages in [TIME]
     Installed 2 packages in [TIME]
      - markupsafe==2.1.3
@@ -1980,6 +2168,8 @@ fn reinstall_package() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
     Uninstalled 1 package in [TIME]
     Installed 1 package in [TIME]
      - tomli==2.0.1
@@ -2031,6 +2221,8 @@ fn reinstall_git() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
     Uninstalled 1 package in [TIME]
     Installed 1 package in [TIME]
      - werkzeug==2.0.0 (from git+https://github.com/pallets/werkzeug.git@af160e0b6b7ddd81c22f1652c728ff5ac72d5c74)
@@ -2882,3 +3074,77 @@ requires-python = ">=3.8"
 
     Ok(())
 }
+
+/// Require hashes for all requirements, but omit a hash for one of them.
+#[test]
+fn require_hashes_missing_hash() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str("tomli==2.0.1")?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt")
+        .arg("--require-hashes"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: In `--require-hashes` mode, all requirements must have a hash, but none were provided for: tomli==2.0.1
+    "###
+    );
+
+    Ok(())
+}
+
+/// Provide a hash for a requirement that isn't pinned to an exact version, which implicitly
+/// enables hash-checking mode.
+#[test]
+fn require_hashes_unpinned() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(
+        "tomli>=2.0.0 --hash=sha256:0000000000000000000000000000000000000000000000000000000000000000",
+    )?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: In `--require-hashes` mode, all requirements must be pinned to an exact version (with `==`) or to a distribution URL, but found: tomli>=2.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Provide a hash that doesn't match any of the distributions for the pinned version.
+#[test]
+fn require_hashes_mismatch() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.write_str(
+        "tomli==2.0.1 --hash=sha256:0000000000000000000000000000000000000000000000000000000000000000",
+    )?;
+
+    uv_snapshot!(command(&context)
+        .arg("requirements.txt"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: Failed to find a distribution of tomli==2.0.1 that matches the provided hashes
+    "###
+    );
+
+    context.assert_command("import tomli").failure();
+
+    Ok(())
+}
@@ -284,6 +284,55 @@ fn uninstall() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn uninstall_dry_run() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let requirements_txt = context.temp_dir.child("requirements.txt");
+    requirements_txt.touch()?;
+    requirements_txt.write_str("MarkupSafe==2.1.3")?;
+
+    Command::new(get_bin())
+        .arg("pip")
+        .arg("sync")
+        .arg("requirements.txt")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("uninstall")
+        .arg("MarkupSafe")
+        .arg("--dry-run")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Would uninstall 1 package
+     - markupsafe==2.1.3
+    "###
+    );
+
+    // The package should still be installed.
+    Command::new(venv_to_interpreter(&context.venv))
+        .arg("-c")
+        .arg("import markupsafe")
+        .current_dir(&context.temp_dir)
+        .assert()
+        .success();
+
+    Ok(())
+}
+
 #[test]
 fn missing_record() -> Result<()> {
     let context = TestContext::new("3.12");
@@ -626,3 +675,123 @@ fn uninstall_duplicate() -> Result<()> {
 
     Ok(())
 }
+
+/// Uninstall a package installed in the legacy `.egg-info` format.
+#[test]
+fn uninstall_egg_info() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let site_packages = if cfg!(windows) {
+        context.venv.join("Lib").join("site-packages")
+    } else {
+        context
+            .venv
+            .join("lib")
+            .join("python3.12")
+            .join("site-packages")
+    };
+
+    // An `.egg-info` directory, as written by `setup.py install`.
+    let egg_info = site_packages.join("zstandard-0.22.0-py3.12.egg-info");
+    fs_err::create_dir_all(&egg_info)?;
+    fs_err::write(
+        egg_info.join("PKG-INFO"),
+        "Metadata-Version: 2.1\nName: zstandard\nVersion: 0.22.0\n",
+    )?;
+    fs_err::write(egg_info.join("top_level.txt"), "zstandard\n")?;
+
+    // The top-level package.
+    fs_err::create_dir_all(site_packages.join("zstandard"))?;
+    fs_err::write(site_packages.join("zstandard").join("__init__.py"), "")?;
+
+    uv_snapshot!(Command::new(get_bin())
+        .arg("pip")
+        .arg("uninstall")
+        .arg("zstandard")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 1 package in [TIME]
+     - zstandard==0.22.0
+    "###
+    );
+
+    assert!(!egg_info.exists());
+    assert!(!site_packages.join("zstandard").exists());
+
+    Ok(())
+}
+
+/// Uninstall a legacy editable package, as installed by `setup.py develop`.
+#[test]
+fn uninstall_legacy_editable() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let site_packages = if cfg!(windows) {
+        context.venv.join("Lib").join("site-packages")
+    } else {
+        context
+            .venv
+            .join("lib")
+            .join("python3.12")
+            .join("site-packages")
+    };
+
+    // The project, with its `.egg-info` directory.
+    let target = context.temp_dir.child("legacy_editable");
+    target
+        .child("legacy_editable")
+        .child("__init__.py")
+        .touch()?;
+    target
+        .child("legacy_editable.egg-info")
+        .child("PKG-INFO")
+        .write_str("Metadata-Version: 2.1\nName: legacy-editable\nVersion: 0.1.0\n")?;
+
+    // The `.egg-link` file, and the corresponding entry in `easy-install.pth`.
+    let egg_link = site_packages.join("legacy_editable.egg-link");
+    fs_err::write(&egg_link, format!("{}\n.", target.path().display()))?;
+    let easy_install = site_packages.join("easy-install.pth");
+    fs_err::write(
+        &easy_install,
+        format!("/path/to/another/project\n{}\n", target.path().display()),
+    )?;
+
+    uv_snapshot!(context.filters(), Command::new(get_bin())
+        .arg("pip")
+        .arg("uninstall")
+        .arg("legacy-editable")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Uninstalled 1 package in [TIME]
+     - legacy-editable==0.1.0 (from file://[TEMP_DIR]/legacy_editable)
+    "###
+    );
+
+    // The `.egg-link` file and `easy-install.pth` entry should be removed, but the project should
+    // be left in place.
+    assert!(!egg_link.exists());
+    assert_eq!(
+        fs_err::read_to_string(&easy_install)?,
+        "/path/to/another/project\n"
+    );
+    assert!(target
+        .child("legacy_editable")
+        .child("__init__.py")
+        .exists());
+
+    Ok(())
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "python")]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+
+use crate::common::{create_bin_with_executables, get_bin};
+
+mod common;
+
+#[test]
+fn python_list() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+
+    let output = Command::new(get_bin())
+        .arg("python")
+        .arg("list")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .env_remove("VIRTUAL_ENV")
+        .env_remove("CONDA_PREFIX")
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    // The interpreter on the `PATH` should be listed exactly once, and marked as the default.
+    let stdout = String::from_utf8(output.get_output().stdout.clone())?;
+    let lines = stdout.lines().collect::<Vec<_>>();
+    assert_eq!(lines.len(), 1, "Unexpected output:\n{stdout}");
+    assert!(lines[0].starts_with("cpython-3.12."), "{stdout}");
+    assert!(lines[0].ends_with("(PATH, default)"), "{stdout}");
+
+    Ok(())
+}
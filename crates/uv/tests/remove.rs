@@ -0,0 +1,96 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{get_bin, uv_snapshot, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `remove` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("remove")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Remove a dependency, which should also be removed from the lockfile.
+#[test]
+fn remove() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = [
+            "anyio==4.0.0",
+            "iniconfig==2.0.0", # for testing
+        ]
+        "#
+    })?;
+
+    uv_snapshot!(command(&context).arg("anyio"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Updated pyproject.toml
+    "###
+    );
+
+    insta::assert_snapshot!(fs_err::read_to_string(&pyproject_toml)?, @r###"
+    [project]
+    name = "project"
+    version = "0.1.0"
+    dependencies = [
+        "iniconfig==2.0.0", # for testing
+    ]
+    "###
+    );
+
+    let lock = fs_err::read_to_string(context.temp_dir.child("uv.lock"))?;
+    assert!(!lock.contains("name = \"anyio\""));
+
+    Ok(())
+}
+
+/// Removing a package that isn't a dependency is an error.
+#[test]
+fn remove_missing() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    let pyproject_toml = context.temp_dir.child("pyproject.toml");
+    pyproject_toml.write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["anyio==4.0.0"]
+        "#
+    })?;
+
+    uv_snapshot!(command(&context).arg("iniconfig").arg("--dev"), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: `iniconfig` is not a dependency in `tool.uv.dev-dependencies`
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,222 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{get_bin, uv_snapshot, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `run` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("run")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Write a `pyproject.toml` (without a build system) to the project directory.
+fn write_project(context: &TestContext) -> Result<()> {
+    context
+        .temp_dir
+        .child("pyproject.toml")
+        .write_str(indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["iniconfig==2.0.0"]
+        "#
+        })?;
+    Ok(())
+}
+
+/// Lock and sync the project before running the command in its environment.
+#[test]
+fn run() -> Result<()> {
+    let context = TestContext::new("3.12");
+    write_project(&context)?;
+
+    uv_snapshot!(command(&context).arg("python").arg("-c").arg("import iniconfig; print('ok')"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ok
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    context
+        .temp_dir
+        .child("uv.lock")
+        .assert(predicates::path::is_file());
+
+    // The second invocation should reuse the lockfile and the environment.
+    uv_snapshot!(command(&context).arg("python").arg("-c").arg("import iniconfig; print('ok')"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ok
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    Ok(())
+}
+
+/// Layer additional requirements on top of the project environment, without installing them into
+/// the project environment itself.
+#[test]
+fn run_with() -> Result<()> {
+    let context = TestContext::new("3.12");
+    write_project(&context)?;
+
+    uv_snapshot!(command(&context)
+        .arg("--with")
+        .arg("sniffio==1.3.0")
+        .arg("python")
+        .arg("-c")
+        .arg("import iniconfig, sniffio; print('ok')"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ok
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + sniffio==1.3.0
+    "###
+    );
+
+    context.assert_command("import sniffio").failure();
+
+    Ok(())
+}
+
+/// The command's exit code should be propagated.
+#[test]
+fn run_exit_code() -> Result<()> {
+    let context = TestContext::new("3.12");
+    write_project(&context)?;
+
+    uv_snapshot!(command(&context).arg("python").arg("-c").arg("import sys; sys.exit(42)"), @r###"
+    success: false
+    exit_code: 42
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    Ok(())
+}
+
+/// Run a script with inline metadata in a cached environment that satisfies its dependencies,
+/// without a project.
+#[test]
+fn run_script() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    context.temp_dir.child("script.py").write_str(indoc! {r#"
+        # /// script
+        # requires-python = ">=3.11"
+        # dependencies = [
+        #   "iniconfig==2.0.0",
+        # ]
+        # ///
+
+        import sys
+
+        import iniconfig
+
+        print(sys.argv[1])
+        "#
+    })?;
+
+    uv_snapshot!(command(&context).arg("--python").arg("3.12").arg("script.py").arg("ok"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ok
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    // The environment should be reused on subsequent runs.
+    uv_snapshot!(command(&context).arg("--python").arg("3.12").arg("script.py").arg("ok"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+    ok
+
+    ----- stderr -----
+    Audited 1 package in [TIME]
+    "###
+    );
+
+    // The script's dependencies shouldn't be installed into the active environment.
+    context.assert_command("import iniconfig").failure();
+
+    Ok(())
+}
+
+/// Running a script requires an interpreter that satisfies its `requires-python`.
+#[test]
+fn run_script_requires_python() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    context.temp_dir.child("script.py").write_str(indoc! {r#"
+        # /// script
+        # requires-python = ">=4"
+        # ///
+
+        print("unreachable")
+        "#
+    })?;
+
+    command(&context)
+        .arg("--python")
+        .arg("3.12")
+        .arg("script.py")
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "error: The script requires Python >=4",
+        ));
+
+    Ok(())
+}
@@ -0,0 +1,375 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{get_bin, uv_snapshot, TestContext};
+
+mod common;
+
+/// Create a `sync` command with options shared across scenarios.
+fn command(context: &TestContext) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg("sync")
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(&context.temp_dir);
+    command
+}
+
+/// Write a `pyproject.toml` (without a build system) and a `uv.lock` to the project directory.
+fn write_project(context: &TestContext, pyproject_toml: &str, lock: &str) -> Result<()> {
+    context
+        .temp_dir
+        .child("pyproject.toml")
+        .write_str(pyproject_toml)?;
+    context.temp_dir.child("uv.lock").write_str(lock)?;
+    Ok(())
+}
+
+/// Install the locked distributions into the project environment.
+#[test]
+fn sync() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    write_project(
+        &context,
+        indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["anyio==4.0.0"]
+        "#
+        },
+        indoc! {r#"
+        version = 1
+
+        [[distribution]]
+        name = "anyio"
+        version = "4.0.0"
+        source = "registry+https://pypi.org/simple"
+
+        [[distribution.dependencies]]
+        name = "idna"
+
+        [[distribution.dependencies]]
+        name = "sniffio"
+
+        [[distribution]]
+        name = "idna"
+        version = "3.4"
+        source = "registry+https://pypi.org/simple"
+
+        [[distribution]]
+        name = "sniffio"
+        version = "1.3.0"
+        source = "registry+https://pypi.org/simple"
+        "#
+        },
+    )?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 3 packages in [TIME]
+    Installed 3 packages in [TIME]
+     + anyio==4.0.0
+     + idna==3.4
+     + sniffio==1.3.0
+    "###
+    );
+
+    context.assert_installed("anyio", "4.0.0");
+
+    // Syncing against a different lockfile should remove any packages that are no longer locked.
+    write_project(
+        &context,
+        indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["iniconfig==2.0.0"]
+        "#
+        },
+        indoc! {r#"
+        version = 1
+
+        [[distribution]]
+        name = "iniconfig"
+        version = "2.0.0"
+        source = "registry+https://pypi.org/simple"
+        "#
+        },
+    )?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Uninstalled 3 packages in [TIME]
+    Installed 1 package in [TIME]
+     - anyio==4.0.0
+     - idna==3.4
+     + iniconfig==2.0.0
+     - sniffio==1.3.0
+    "###
+    );
+
+    context.assert_installed("iniconfig", "2.0.0");
+    context.assert_command("import anyio").failure();
+
+    Ok(())
+}
+
+/// Skip locked distributions whose markers don't apply to the current platform.
+#[test]
+#[cfg(not(windows))]
+fn sync_marker() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    write_project(
+        &context,
+        indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["click==8.1.7"]
+        "#
+        },
+        indoc! {r#"
+        version = 1
+
+        [[distribution]]
+        name = "click"
+        version = "8.1.7"
+        source = "registry+https://pypi.org/simple"
+
+        [[distribution.dependencies]]
+        name = "colorama"
+        marker = "platform_system == 'Windows'"
+
+        [[distribution]]
+        name = "colorama"
+        version = "0.4.6"
+        source = "registry+https://pypi.org/simple"
+        marker = "platform_system == 'Windows'"
+        "#
+        },
+    )?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + click==8.1.7
+    "###
+    );
+
+    context.assert_command("import colorama").failure();
+
+    Ok(())
+}
+
+/// Install optional and development dependencies only when requested.
+#[test]
+fn sync_extra_and_group() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    write_project(
+        &context,
+        indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["iniconfig==2.0.0"]
+
+        [project.optional-dependencies]
+        test = ["sniffio==1.3.0"]
+
+        [tool.uv]
+        dev-dependencies = ["idna==3.4"]
+        "#
+        },
+        indoc! {r#"
+        version = 1
+
+        [[distribution]]
+        name = "idna"
+        version = "3.4"
+        source = "registry+https://pypi.org/simple"
+
+        [[distribution]]
+        name = "iniconfig"
+        version = "2.0.0"
+        source = "registry+https://pypi.org/simple"
+
+        [[distribution]]
+        name = "sniffio"
+        version = "1.3.0"
+        source = "registry+https://pypi.org/simple"
+        "#
+        },
+    )?;
+
+    uv_snapshot!(command(&context), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    context.assert_command("import sniffio").failure();
+
+    uv_snapshot!(command(&context).arg("--extra").arg("test").arg("--group").arg("dev"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 3 packages in [TIME]
+    Downloaded 2 packages in [TIME]
+    Installed 2 packages in [TIME]
+     + idna==3.4
+     + sniffio==1.3.0
+    "###
+    );
+
+    context.assert_installed("idna", "3.4");
+    context.assert_installed("sniffio", "1.3.0");
+
+    Ok(())
+}
+
+/// Create the project environment if it doesn't exist.
+#[test]
+fn sync_create_venv() -> Result<()> {
+    let context = TestContext::new("3.12");
+    fs_err::remove_dir_all(&context.venv)?;
+
+    write_project(
+        &context,
+        indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        dependencies = ["iniconfig==2.0.0"]
+        "#
+        },
+        indoc! {r#"
+        version = 1
+
+        [[distribution]]
+        name = "iniconfig"
+        version = "2.0.0"
+        source = "registry+https://pypi.org/simple"
+        "#
+        },
+    )?;
+
+    uv_snapshot!(context.filters(), command(&context).arg("--python").arg("3.12"), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Creating virtualenv at: [VENV]/
+    Resolved 1 package in [TIME]
+    Downloaded 1 package in [TIME]
+    Installed 1 package in [TIME]
+     + iniconfig==2.0.0
+    "###
+    );
+
+    context
+        .temp_dir
+        .child(".venv")
+        .child("pyvenv.cfg")
+        .assert(predicates::path::is_file());
+    context.assert_installed("iniconfig", "2.0.0");
+
+    Ok(())
+}
+
+/// Reject an existing project environment that doesn't satisfy the lockfile's `requires-python`,
+/// if no compatible interpreter can be found to recreate it.
+#[test]
+fn sync_incompatible_venv() -> Result<()> {
+    let context = TestContext::new("3.12");
+
+    write_project(
+        &context,
+        indoc! {r#"
+        [project]
+        name = "project"
+        version = "0.1.0"
+        requires-python = ">=3.99"
+        dependencies = ["iniconfig==2.0.0"]
+        "#
+        },
+        indoc! {r#"
+        version = 1
+        requires-python = ">=3.99"
+
+        [[distribution]]
+        name = "iniconfig"
+        version = "2.0.0"
+        source = "registry+https://pypi.org/simple"
+        "#
+        },
+    )?;
+
+    command(&context)
+        .assert()
+        .failure()
+        .stderr(predicates::str::contains(
+            "The project requires Python >=3.99, but Python 3.12",
+        ));
+
+    Ok(())
+}
+
+/// Syncing requires a `uv.lock` in the current directory.
+#[test]
+fn sync_missing_lock() {
+    let context = TestContext::new("3.12");
+
+    let filters: Vec<_> = [(r"found in: .* \(", "found in: [TEMP_DIR] (")]
+        .into_iter()
+        .chain(context.filters())
+        .collect();
+
+    uv_snapshot!(filters, command(&context), @r###"
+    success: false
+    exit_code: 2
+    ----- stdout -----
+
+    ----- stderr -----
+    error: No `uv.lock` found in: [TEMP_DIR] (run `uv lock` to create one)
+    "###
+    );
+}
@@ -3,6 +3,7 @@
 use std::process::Command;
 
 use anyhow::Result;
+use assert_cmd::prelude::*;
 use assert_fs::prelude::*;
 
 use uv_fs::Normalized;
@@ -58,7 +59,42 @@ fn create_venv() -> Result<()> {
 
     venv.assert(predicates::path::is_dir());
 
-    // Create a virtual environment at the same location, which should replace it.
+    // Create a virtual environment at the same location, which should fail without `--clear`.
+    let filter_venv = regex::escape(&venv.normalized_display().to_string());
+    let filters = &[
+        (
+            r"Using Python 3\.\d+\.\d+ interpreter at .+",
+            "Using Python [VERSION] interpreter at [PATH]",
+        ),
+        (&filter_venv, "/home/ferris/project/.venv"),
+    ];
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_NO_WRAP", "1")
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python [VERSION] interpreter at [PATH]
+    Creating virtualenv at: /home/ferris/project/.venv
+    uv::venv::creation
+
+      × Failed to create virtualenv
+      ╰─▶ A virtualenv already exists at `/home/ferris/project/.venv` (use `--clear` to replace it, or `--allow-existing` to update it in place)
+    "###
+    );
+
+    // With `--clear`, the existing virtual environment should be replaced.
     let filter_venv = regex::escape(&venv.normalized_display().to_string());
     let filter_prompt = r"Activate with: (?:.*)\\Scripts\\activate";
     let filters = &[
@@ -75,6 +111,7 @@ fn create_venv() -> Result<()> {
     uv_snapshot!(filters, Command::new(get_bin())
         .arg("venv")
         .arg(venv.as_os_str())
+        .arg("--clear")
         .arg("--python")
         .arg("3.12")
         .arg("--cache-dir")
@@ -194,6 +231,57 @@ fn seed() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn seed_packages() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    let filter_venv = regex::escape(&venv.normalized_display().to_string());
+    let filter_prompt = r"Activate with: (?:.*)\\Scripts\\activate";
+    let filters = &[
+        (
+            r"Using Python 3\.\d+\.\d+ interpreter at .+",
+            "Using Python [VERSION] interpreter at [PATH]",
+        ),
+        (&filter_venv, "/home/ferris/project/.venv"),
+        (
+            filter_prompt,
+            "Activate with: source /home/ferris/project/.venv/bin/activate",
+        ),
+    ];
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--seed=pip==23.3.1,setuptools")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_NO_WRAP", "1")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python [VERSION] interpreter at [PATH]
+    Creating virtualenv at: /home/ferris/project/.venv
+     + pip==23.3.1
+     + setuptools==68.2.2
+    Activate with: source /home/ferris/project/.venv/bin/activate
+    "###
+    );
+
+    venv.assert(predicates::path::is_dir());
+
+    Ok(())
+}
+
 #[test]
 fn seed_older_python_version() -> Result<()> {
     let temp_dir = assert_fs::TempDir::new()?;
@@ -556,7 +644,7 @@ fn windows_shims() -> Result<()> {
         format!("@echo off\r\n{}/python.exe %*", py38.display()),
     )?;
 
-    // Create a virtual environment at `.venv`, passing the redundant `--clear` flag.
+    // Create a virtual environment at `.venv`, replacing any existing one.
     let filter_venv = regex::escape(&venv.normalized_display().to_string());
     let filter_prompt = r"Activate with: (?:.*)\\Scripts\\activate";
     let filters = &[
@@ -585,7 +673,6 @@ fn windows_shims() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    warning: virtualenv's `--clear` has no effect (uv always clears the virtual environment).
     Using Python 3.8.x interpreter at [PATH]
     Creating virtualenv at: /home/ferris/project/.venv
     Activate with: source /home/ferris/project/.venv/bin/activate
@@ -604,7 +691,7 @@ fn virtualenv_compatibility() -> Result<()> {
     let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
     let venv = temp_dir.child(".venv");
 
-    // Create a virtual environment at `.venv`, passing the redundant `--clear` flag.
+    // Create a virtual environment at `.venv`, passing the redundant `--no-seed` flag.
     let filter_venv = regex::escape(&venv.normalized_display().to_string());
     let filter_prompt = r"Activate with: (?:.*)\\Scripts\\activate";
     let filters = &[
@@ -621,7 +708,7 @@ fn virtualenv_compatibility() -> Result<()> {
     uv_snapshot!(filters, Command::new(get_bin())
         .arg("venv")
         .arg(venv.as_os_str())
-        .arg("--clear")
+        .arg("--no-seed")
         .arg("--python")
         .arg("3.12")
         .arg("--cache-dir")
@@ -635,7 +722,7 @@ fn virtualenv_compatibility() -> Result<()> {
     ----- stdout -----
 
     ----- stderr -----
-    warning: virtualenv's `--clear` has no effect (uv always clears the virtual environment).
+    warning: virtualenv's `--no-seed` has no effect (uv omits seed packages by default).
     Using Python [VERSION] interpreter at [PATH]
     Creating virtualenv at: /home/ferris/project/.venv
     Activate with: source /home/ferris/project/.venv/bin/activate
@@ -663,3 +750,390 @@ fn verify_pyvenv_cfg() {
     let search_string = format!("uv = {version}");
     pyvenv_cfg.assert(predicates::str::contains(search_string));
 }
+
+#[test]
+fn system_site_packages() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+    let pyvenv_cfg = venv.child("pyvenv.cfg");
+
+    // By default, the system site packages are excluded.
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+    pyvenv_cfg.assert(predicates::str::contains(
+        "include-system-site-packages = false",
+    ));
+
+    // With `--system-site-packages`, they're included.
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--clear")
+        .arg("--system-site-packages")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+    pyvenv_cfg.assert(predicates::str::contains(
+        "include-system-site-packages = true",
+    ));
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn activate_hint_fish() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    // The activation hint should match the user's shell.
+    let filter_venv = regex::escape(&venv.normalized_display().to_string());
+    let filters = &[
+        (
+            r"Using Python 3\.\d+\.\d+ interpreter at .+",
+            "Using Python [VERSION] interpreter at [PATH]",
+        ),
+        (&filter_venv, "/home/ferris/project/.venv"),
+    ];
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("SHELL", "/usr/bin/fish")
+        .env_remove("NU_VERSION")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python [VERSION] interpreter at [PATH]
+    Creating virtualenv at: /home/ferris/project/.venv
+    Activate with: source /home/ferris/project/.venv/bin/activate.fish
+    "###
+    );
+
+    venv.child("bin")
+        .child("activate.fish")
+        .assert(predicates::path::is_file());
+
+    Ok(())
+}
+
+#[test]
+fn allow_existing() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    // Add a file to the virtual environment.
+    let marker = venv.child("marker.txt");
+    marker.touch()?;
+
+    // With `--allow-existing`, the virtual environment is updated in place.
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--allow-existing")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+    marker.assert(predicates::path::is_file());
+
+    // With `--clear`, the virtual environment is replaced.
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--clear")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+    marker.assert(predicates::path::missing());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn upgrade() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    // Upgrading a virtual environment that doesn't exist should fail.
+    let filter_venv = regex::escape(&venv.normalized_display().to_string());
+    let filters = &[
+        (
+            r"Using Python 3\.\d+\.\d+ interpreter at .+",
+            "Using Python [VERSION] interpreter at [PATH]",
+        ),
+        (&filter_venv, "/home/ferris/project/.venv"),
+    ];
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--upgrade")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_NO_WRAP", "1")
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir), @r###"
+    success: false
+    exit_code: 1
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python [VERSION] interpreter at [PATH]
+    Creating virtualenv at: /home/ferris/project/.venv
+    uv::venv::creation
+
+      × Failed to create virtualenv
+      ╰─▶ No virtualenv exists at `/home/ferris/project/.venv` to upgrade
+    "###
+    );
+
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    // Add a package to the virtual environment.
+    let site_packages = venv.child("lib").child("python3.12").child("site-packages");
+    let package = site_packages.child("example.py");
+    package.touch()?;
+
+    // Upgrading should preserve the installed packages.
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--upgrade")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+    package.assert(predicates::path::is_file());
+
+    Ok(())
+}
+
+#[test]
+#[cfg(unix)]
+fn copies() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12"]).expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--copies")
+        .arg("--python")
+        .arg("3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir)
+        .assert()
+        .success();
+
+    // The interpreter should be a copy, rather than a symlink.
+    for name in ["python", "python3", "python3.12"] {
+        let python = venv.child("bin").child(name);
+        assert!(fs_err::symlink_metadata(&python)?.is_file());
+    }
+
+    // The copied interpreter should be usable.
+    Command::new(venv.child("bin").child("python").path())
+        .arg("-c")
+        .arg("import sys; assert sys.prefix != sys.base_prefix")
+        .assert()
+        .success();
+
+    Ok(())
+}
+
+#[test]
+fn python_version_file() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12", "3.10"])
+        .expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    // Without `--python`, the version in `.python-version` should be used.
+    temp_dir.child(".python-version").write_str("3.10\n")?;
+
+    let filter_venv = regex::escape(&venv.normalized_display().to_string());
+    let filter_prompt = r"Activate with: (?:.*)\\Scripts\\activate";
+    let filters = &[
+        (
+            r"Using Python 3\.10\.\d+ interpreter at .+",
+            "Using Python 3.10.[X] interpreter at [PATH]",
+        ),
+        (&filter_venv, "/home/ferris/project/.venv"),
+        (
+            filter_prompt,
+            "Activate with: source /home/ferris/project/.venv/bin/activate",
+        ),
+    ];
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_NO_WRAP", "1")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.10.[X] interpreter at [PATH]
+    Creating virtualenv at: /home/ferris/project/.venv
+    Activate with: source /home/ferris/project/.venv/bin/activate
+    "###
+    );
+
+    Ok(())
+}
+
+#[test]
+fn python_specifiers() -> Result<()> {
+    let temp_dir = assert_fs::TempDir::new()?;
+    let cache_dir = assert_fs::TempDir::new()?;
+    let bin = create_bin_with_executables(&temp_dir, &["3.12", "3.10"])
+        .expect("Failed to create bin dir");
+    let venv = temp_dir.child(".venv");
+
+    let filter_venv = regex::escape(&venv.normalized_display().to_string());
+    let filter_prompt = r"Activate with: (?:.*)\\Scripts\\activate";
+    let filters = &[
+        (
+            r"Using Python 3\.10\.\d+ interpreter at .+",
+            "Using Python 3.10.[X] interpreter at [PATH]",
+        ),
+        (&filter_venv, "/home/ferris/project/.venv"),
+        (
+            filter_prompt,
+            "Activate with: source /home/ferris/project/.venv/bin/activate",
+        ),
+    ];
+
+    // Only Python 3.10 satisfies the range.
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--python")
+        .arg(">=3.10,<3.12")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_NO_WRAP", "1")
+        .env("UV_TEST_PYTHON_PATH", bin.clone())
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.10.[X] interpreter at [PATH]
+    Creating virtualenv at: /home/ferris/project/.venv
+    Activate with: source /home/ferris/project/.venv/bin/activate
+    "###
+    );
+
+    // A bare wildcard is shorthand for `==`.
+    uv_snapshot!(filters, Command::new(get_bin())
+        .arg("venv")
+        .arg(venv.as_os_str())
+        .arg("--clear")
+        .arg("--python")
+        .arg("3.10.*")
+        .arg("--cache-dir")
+        .arg(cache_dir.path())
+        .arg("--exclude-newer")
+        .arg(EXCLUDE_NEWER)
+        .env("UV_NO_WRAP", "1")
+        .env("UV_TEST_PYTHON_PATH", bin)
+        .current_dir(&temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Using Python 3.10.[X] interpreter at [PATH]
+    Creating virtualenv at: /home/ferris/project/.venv
+    Activate with: source /home/ferris/project/.venv/bin/activate
+    "###
+    );
+
+    Ok(())
+}
@@ -0,0 +1,179 @@
+#![cfg(all(feature = "python", feature = "pypi"))]
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::Result;
+use assert_cmd::prelude::*;
+use assert_fs::prelude::*;
+use indoc::indoc;
+
+use crate::common::{get_bin, uv_snapshot, TestContext, EXCLUDE_NEWER};
+
+mod common;
+
+/// Create a `uv` command for the given subcommand, run from the given directory, with options
+/// shared across scenarios.
+fn command(context: &TestContext, subcommand: &str, current_dir: &Path) -> Command {
+    let mut command = Command::new(get_bin());
+    command
+        .arg(subcommand)
+        .arg("--cache-dir")
+        .arg(context.cache_dir.path())
+        .env("VIRTUAL_ENV", context.venv.as_os_str())
+        .current_dir(current_dir);
+    if subcommand == "lock" {
+        command.arg("--exclude-newer").arg(EXCLUDE_NEWER);
+    }
+    command
+}
+
+/// Read the `uv.lock` in the given directory, redacting the file hashes and URL paths.
+fn read_lock(dir: &Path) -> Result<String> {
+    let lock = fs_err::read_to_string(dir.join("uv.lock"))?;
+    let hash = regex::Regex::new(r"sha256:[0-9a-f]{64}")?;
+    let lock = hash.replace_all(&lock, "sha256:[HASH]");
+    let path = regex::Regex::new(
+        r"https://files\.pythonhosted\.org/packages/[0-9a-f]{2}/[0-9a-f]{2}/[0-9a-f]{60}/",
+    )?;
+    Ok(path
+        .replace_all(&lock, "https://files.pythonhosted.org/packages/[PATH]/")
+        .into_owned())
+}
+
+/// Write a workspace with a (non-buildable) root project that depends on a buildable member in
+/// `packages/child`.
+fn write_workspace(context: &TestContext) -> Result<()> {
+    context
+        .temp_dir
+        .child("pyproject.toml")
+        .write_str(indoc! {r#"
+        [project]
+        name = "root"
+        version = "0.1.0"
+        dependencies = ["child"]
+
+        [tool.uv.workspace]
+        members = ["packages/*"]
+        exclude = ["packages/excluded"]
+        "#
+        })?;
+
+    let child = context.temp_dir.child("packages").child("child");
+    child.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "child"
+        version = "0.1.0"
+        dependencies = ["iniconfig==2.0.0"]
+
+        [build-system]
+        requires = ["setuptools>=42"]
+        build-backend = "setuptools.build_meta"
+        "#
+    })?;
+    child.child("child").child("__init__.py").touch()?;
+
+    Ok(())
+}
+
+/// Lock all workspace members together at the workspace root, with the members as editables.
+#[test]
+fn workspace_lock() -> Result<()> {
+    let context = TestContext::new("3.12");
+    write_workspace(&context)?;
+
+    uv_snapshot!(command(&context, "lock", &context.temp_dir), @r###"
+    success: true
+    exit_code: 0
+    ----- stdout -----
+
+    ----- stderr -----
+    Resolved 1 package in [TIME]
+    "###
+    );
+
+    insta::assert_snapshot!(read_lock(&context.temp_dir)?, @r###"
+    # This file was autogenerated by uv; it is not intended to be edited manually.
+    version = 1
+
+    [[distribution]]
+    name = "child"
+    version = "0.1.0"
+    source = "editable+./packages/child"
+
+    [[distribution.dependencies]]
+    name = "iniconfig"
+    version = "2.0.0"
+
+    [[distribution]]
+    name = "iniconfig"
+    version = "2.0.0"
+    source = "registry+https://pypi.org/simple"
+
+    [[distribution.file]]
+    filename = "iniconfig-2.0.0-py3-none-any.whl"
+    url = "https://files.pythonhosted.org/packages/[PATH]/iniconfig-2.0.0-py3-none-any.whl"
+    hash = "sha256:[HASH]"
+
+    [[distribution.file]]
+    filename = "iniconfig-2.0.0.tar.gz"
+    url = "https://files.pythonhosted.org/packages/[PATH]/iniconfig-2.0.0.tar.gz"
+    hash = "sha256:[HASH]"
+    "###
+    );
+
+    // Locking from a member should update the lockfile at the workspace root.
+    let member = context.temp_dir.child("packages").child("child");
+    fs_err::remove_file(context.temp_dir.child("uv.lock"))?;
+    command(&context, "lock", &member).assert().success();
+    context
+        .temp_dir
+        .child("uv.lock")
+        .assert(predicates::path::is_file());
+    member.child("uv.lock").assert(predicates::path::missing());
+
+    Ok(())
+}
+
+/// Sync the shared workspace environment from a member, installing the members as editables.
+#[test]
+fn workspace_sync() -> Result<()> {
+    let context = TestContext::new("3.12");
+    write_workspace(&context)?;
+
+    let member = context.temp_dir.child("packages").child("child");
+    command(&context, "lock", &member).assert().success();
+    command(&context, "sync", &member).assert().success();
+
+    context.assert_installed("iniconfig", "2.0.0");
+    context.assert_command("import child").success();
+
+    Ok(())
+}
+
+/// Projects that are excluded from the workspace are locked on their own.
+#[test]
+fn workspace_excluded() -> Result<()> {
+    let context = TestContext::new("3.12");
+    write_workspace(&context)?;
+
+    let excluded = context.temp_dir.child("packages").child("excluded");
+    excluded.child("pyproject.toml").write_str(indoc! {r#"
+        [project]
+        name = "excluded"
+        version = "0.1.0"
+        dependencies = ["iniconfig==2.0.0"]
+        "#
+    })?;
+
+    command(&context, "lock", &excluded).assert().success();
+    excluded
+        .child("uv.lock")
+        .assert(predicates::path::is_file());
+    context
+        .temp_dir
+        .child("uv.lock")
+        .assert(predicates::path::missing());
+
+    Ok(())
+}